@@ -0,0 +1,108 @@
+//! Benchmarks for the statistics core and plot rendering.
+//!
+//! Run with `cargo bench`. Pass a substring to run only the matching
+//! benchmarks, e.g. `cargo bench -- percentile`.
+
+extern crate dent;
+
+use std::env;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use dent::distribution::{Distribution, Normal};
+use dent::special::inc_beta;
+use dent::plot::{self, PlotWidth};
+use dent::rng::Rng;
+use dent::summary::{Summarizer, Summary};
+use dent::t_test::welch_t_test;
+
+
+const SIZES: [usize; 3] = [100, 10_000, 1_000_000];
+
+/// Minimum total time to spend measuring each benchmark.
+const TARGET: Duration = Duration::from_millis(500);
+
+struct Bencher {
+    filter: Option<String>,
+}
+
+impl Bencher {
+    /// Time `f`, doubling the iteration count until a batch takes at least
+    /// `TARGET`, then report the mean time per iteration.
+    fn run<F, T>(&self, name: &str, mut f: F)
+        where F: FnMut() -> T {
+        if let Some(ref filter) = self.filter {
+            if !name.contains(filter.as_str()) {
+                return;
+            }
+        }
+
+        let mut iters: u32 = 1;
+
+        loop {
+            let start = Instant::now();
+
+            for _ in 0..iters {
+                black_box(f());
+            }
+
+            let elapsed = start.elapsed();
+
+            if elapsed >= TARGET || iters >= 1 << 24 {
+                let per_iter = elapsed / iters;
+                println!("{:<40} {:>14?}/iter ({} iters)", name, per_iter, iters);
+                return;
+            }
+
+            iters *= 2;
+        }
+    }
+}
+
+fn sample(rng: &mut Rng, mean: f64, size: usize) -> Vec<f64> {
+    Normal::new(mean, 1.0).unwrap().sample_n(rng, size)
+}
+
+fn main() {
+    // Cargo passes `--bench`, which we ignore along with any other flags.
+    let filter = env::args().skip(1).find(|a| !a.starts_with('-'));
+    let b = Bencher { filter };
+
+    let mut rng = Rng::new(0);
+
+    for &n in &SIZES {
+        let data = sample(&mut rng, 0.0, n);
+        let other = sample(&mut rng, 0.1, n);
+
+        b.run(&format!("summarizer_new/{}", n), || Summarizer::new(&data).unwrap());
+        b.run(&format!("summarizer_from_vec/{}", n), || Summarizer::from_vec(data.clone()).unwrap());
+        b.run(&format!("summary_new/{}", n), || Summary::new(&data).unwrap());
+
+        let s = Summarizer::new(&data).unwrap();
+        b.run(&format!("percentile/{}", n), || s.percentile(black_box(0.99)).unwrap());
+
+        let s1 = Summary::new(&data).unwrap();
+        let s2 = Summary::new(&other).unwrap();
+        b.run(&format!("welch_t_test/{}", n), || welch_t_test(&s1, &s2).unwrap());
+    }
+
+    for &(x, a, b_) in &[(0.3, 2.0, 3.0), (0.9, 50.0, 0.5), (0.5, 500.0, 500.0)] {
+        b.run(&format!("inc_beta/{}_{}_{}", x, a, b_), || inc_beta(black_box(x), a, b_).unwrap());
+    }
+
+    let summaries: Vec<Summary> = (0..5)
+        .map(|i| Summary::new(&sample(&mut rng, i as f64, 1000)).unwrap())
+        .collect();
+    let refs: Vec<&Summary> = summaries.iter().collect();
+
+    for &cols in &[80, 200] {
+        let width = PlotWidth::new(cols).unwrap();
+
+        b.run(&format!("comparison_plot/{}", cols), || {
+            plot::comparison_plot(&refs, width, false, true, false).unwrap()
+        });
+        b.run(&format!("comparison_plot_subcell/{}", cols), || {
+            plot::comparison_plot_subcell(&refs, width, true, false, None).unwrap()
+        });
+    }
+}
@@ -0,0 +1,183 @@
+use error::Error;
+use special::normal_quantile;
+use summary::{self, Summarizer};
+use t_test::t_quantile;
+
+
+/// Multiple of the standard deviation of the differences between the bias
+/// and each limit of agreement, so that 95% of differences fall within them.
+const LIMITS_Z: f64 = 1.96;
+
+/// The Bland-Altman analysis [1] of the agreement of two methods which each
+/// measured the same subjects, from the differences of each pair of
+/// measurements, second minus first.
+///
+/// [1]: J. M. Bland and D. G. Altman, "Statistical methods for assessing
+///      agreement between two methods of clinical measurement", Lancet
+///      327(8476), 1986.
+pub struct BlandAltman {
+    pub size: usize,
+
+    /// Mean difference, and its confidence interval.
+    pub bias: f64,
+    pub bias_interval: (f64, f64),
+
+    pub standard_deviation: f64,
+
+    /// Limits of agreement, `LIMITS_Z` standard deviations either side of the
+    /// bias, within which most differences fall.
+    pub limits: (f64, f64),
+
+    /// The mean and difference of each pair, in order: the points of the
+    /// difference plot.
+    pub points: Vec<(f64, f64)>,
+}
+
+impl BlandAltman {
+    /// Compare paired measurements by the methods `first` and `second`, which
+    /// must be of equal length, at least two, with a confidence interval of
+    /// the bias at the given level.
+    pub fn new(first: &[f64], second: &[f64], confidence: f64) -> Result<Self, Error> {
+        if first.len() != second.len() {
+            return Err(Error::UnpairedSamples);
+        }
+
+        if !(0.0 < confidence && confidence < 1.0) {
+            return Err(Error::BadParameter);
+        }
+
+        let points: Vec<(f64, f64)> = first
+            .iter()
+            .zip(second)
+            .map(|(a, b)| (0.5 * (a + b), b - a))
+            .collect();
+
+        let differences: Vec<f64> = points.iter().map(|p| p.1).collect();
+        let summ = Summarizer::new(&differences)?;
+        let size = differences.len();
+
+        if size < 2 {
+            return Err(Error::EmptySample);
+        }
+
+        let bias = summ.mean();
+        let standard_deviation = summ.standard_deviation();
+
+        let t = t_quantile(0.5 * (1.0 + confidence), (size - 1) as f64)?;
+        let margin = t * standard_deviation / (size as f64).sqrt();
+
+        Ok(BlandAltman {
+            size,
+            bias,
+            bias_interval: (bias - margin, bias + margin),
+            standard_deviation,
+            limits: (bias - LIMITS_Z * standard_deviation, bias + LIMITS_Z * standard_deviation),
+            points,
+        })
+    }
+}
+
+/// Passing-Bablok regression [1] of measurements by one method on those by
+/// another. It is robust to outliers, and like Deming regression, allows for
+/// error in both methods, but assumes nothing of its distribution.
+///
+/// The slope is a shifted median of the slopes between each pair of points,
+/// and the intercept the median of the intercepts of lines of that slope
+/// through each point. The methods agree if the interval of the slope holds
+/// 1 and that of the intercept holds 0.
+///
+/// [1]: H. Passing and W. Bablok, "A new biometrical procedure for testing
+///      the equality of measurements from two different analytical methods",
+///      J. Clin. Chem. Clin. Biochem. 21(11), 1983.
+pub struct PassingBablok {
+    pub slope: f64,
+    pub intercept: f64,
+    pub slope_interval: (f64, f64),
+    pub intercept_interval: (f64, f64),
+}
+
+impl PassingBablok {
+    /// Fit `(x, y)` points, of which there must be at least three, with
+    /// intervals at the given confidence level. The intervals are undefined,
+    /// and the fit fails, if there are too few points for the level.
+    pub fn new(data: &[(f64, f64)], confidence: f64) -> Result<Self, Error> {
+        if !(0.0 < confidence && confidence < 1.0) {
+            return Err(Error::BadParameter);
+        }
+
+        if data.len() < 3 {
+            return Err(Error::EmptySample);
+        }
+
+        if data.iter().any(|&(x, y)| !x.is_finite() || !y.is_finite()) {
+            return Err(Error::BadSample);
+        }
+
+        let mut slopes = vec![];
+
+        for (i, &(x1, y1)) in data.iter().enumerate() {
+            for &(x2, y2) in &data[i + 1..] {
+                let (dx, dy) = (x2 - x1, y2 - y1);
+
+                // Identical points give no slope, and a slope of -1 would
+                // bias the estimate, so both are left out.
+                if dx == 0.0 && dy == 0.0 {
+                    continue;
+                }
+
+                let s = if dx == 0.0 { dy.signum() * f64::INFINITY } else { dy / dx };
+
+                if s != -1.0 {
+                    slopes.push(s);
+                }
+            }
+        }
+
+        slopes.sort_by(|a, b| a.total_cmp(b));
+
+        // Slopes below -1 are offset, so that the estimate does not depend on
+        // which method is taken as `x`.
+        let offset = slopes.iter().filter(|&&s| s < -1.0).count();
+        let m = slopes.len();
+
+        // The slope of 1-based rank `r`, offset.
+        let ranked = |r: usize| slopes.get(r + offset - 1).cloned().ok_or(Error::Undefined);
+
+        let slope = if m % 2 == 1 {
+            ranked(m.div_ceil(2))?
+        } else {
+            0.5 * (ranked(m / 2)? + ranked(m / 2 + 1)?)
+        };
+
+        let n = data.len() as f64;
+        let z = normal_quantile(0.5 * (1.0 + confidence))?;
+        let spread = z * (n * (n - 1.0) * (2.0 * n + 5.0) / 18.0).sqrt();
+        let lo_rank = ((m as f64 - spread) / 2.0).round();
+
+        if lo_rank < 1.0 {
+            return Err(Error::Undefined);
+        }
+
+        let lo_rank = lo_rank as usize;
+        let slope_interval = (ranked(lo_rank)?, ranked(m - lo_rank + 1)?);
+
+        Ok(PassingBablok {
+            slope,
+            intercept: median_intercept(data, slope)?,
+            slope_interval,
+
+            // The steeper line crosses the y-axis lower, as `x` is positive.
+            intercept_interval: (
+                median_intercept(data, slope_interval.1)?,
+                median_intercept(data, slope_interval.0)?,
+            ),
+        })
+    }
+}
+
+/// The median intercept of lines of the given `slope` through each point.
+fn median_intercept(data: &[(f64, f64)], slope: f64) -> Result<f64, Error> {
+    let mut intercepts: Vec<f64> = data.iter().map(|&(x, y)| y - slope * x).collect();
+
+    summary::percentile_of_unsorted(&mut intercepts, 0.5)
+}
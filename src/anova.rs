@@ -0,0 +1,482 @@
+use error::Error;
+use special::{inc_beta, inc_gamma};
+
+
+/// A one-way analysis of variance of repeated measures: the same subjects,
+/// such as test cases, each measured under `k` conditions.
+///
+/// Unlike a one-way ANOVA of independent groups, variation between subjects
+/// is removed from the error term, so that a large spread of subjects does
+/// not mask a consistent effect of condition.
+#[derive(Clone, Copy, Debug)]
+pub struct RepeatedMeasuresAnova {
+    pub f: f64,
+    pub df_conditions: f64,
+    pub df_error: f64,
+    pub p: f64,
+
+    /// The Greenhouse-Geisser estimate `ε` of departure from sphericity [1],
+    /// between `1 / (k - 1)` and 1, and the p-value with both degrees of
+    /// freedom scaled by it. Prefer the corrected p-value unless the
+    /// differences between each pair of conditions have equal variances.
+    ///
+    /// [1]: S. Greenhouse and S. Geisser, "On methods in the analysis of
+    ///      profile data", Psychometrika 24(2), 1959.
+    pub epsilon: f64,
+    pub p_corrected: f64,
+
+    /// Partial `η²`, the proportion of the variation within subjects which is
+    /// due to condition.
+    pub partial_eta_squared: f64,
+}
+
+impl RepeatedMeasuresAnova {
+    /// Analyze `conditions`, each of which holds the measures of every
+    /// subject under that condition, in the same order. There must be at
+    /// least two conditions and two subjects.
+    pub fn new(conditions: &[&[f64]]) -> Result<Self, Error> {
+        let (k, n) = check_design(conditions)?;
+        let (kf, nf) = (k as f64, n as f64);
+
+        let condition_means: Vec<f64> = conditions.iter().map(|c| mean(c)).collect();
+        let subject_means: Vec<f64> = (0..n)
+            .map(|i| conditions.iter().map(|c| c[i]).sum::<f64>() / kf)
+            .collect();
+        let grand_mean = mean(&condition_means);
+
+        let ss_total: f64 = conditions
+            .iter()
+            .flat_map(|c| c.iter())
+            .map(|x| (x - grand_mean).powi(2))
+            .sum();
+        let sum_sq_dev = |ms: &[f64]| ms.iter().map(|m| (m - grand_mean).powi(2)).sum::<f64>();
+        let ss_conditions = nf * sum_sq_dev(&condition_means);
+        let ss_subjects = kf * sum_sq_dev(&subject_means);
+        let ss_error = ss_total - ss_conditions - ss_subjects;
+
+        if ss_error <= 0.0 {
+            return Err(Error::Undefined);
+        }
+
+        let df_conditions = kf - 1.0;
+        let df_error = (kf - 1.0) * (nf - 1.0);
+
+        let f = (ss_conditions / df_conditions) / (ss_error / df_error);
+        let p = f_upper_tail(f, df_conditions, df_error)?;
+
+        let epsilon = greenhouse_geisser_epsilon(conditions, &condition_means);
+        let p_corrected = f_upper_tail(f, epsilon * df_conditions, epsilon * df_error)?;
+
+        Ok(RepeatedMeasuresAnova {
+            f,
+            df_conditions,
+            df_error,
+            p,
+            epsilon,
+            p_corrected,
+            partial_eta_squared: ss_conditions / (ss_conditions + ss_error),
+        })
+    }
+}
+
+/// The Friedman test [1], the nonparametric analog of repeated-measures
+/// ANOVA, of whether the ranks of conditions within subjects differ.
+///
+/// [1]: M. Friedman, "The use of ranks to avoid the assumption of normality
+///      implicit in the analysis of variance", J. Am. Stat. Assoc. 32(200),
+///      1937.
+#[derive(Clone, Debug)]
+pub struct Friedman {
+    /// The statistic, corrected for ties, which is asymptotically chi-square
+    /// with `df` degrees of freedom.
+    pub statistic: f64,
+    pub df: f64,
+    pub p: f64,
+
+    /// Mean rank of each condition within subjects, from 1 to `k`.
+    pub mean_ranks: Vec<f64>,
+
+    /// Kendall's `W`, the agreement of subjects on the order of conditions,
+    /// from 0 for none to 1 for complete.
+    pub kendall_w: f64,
+}
+
+impl Friedman {
+    /// Test `conditions`, laid out as for `RepeatedMeasuresAnova::new`.
+    pub fn new(conditions: &[&[f64]]) -> Result<Self, Error> {
+        let (k, n) = check_design(conditions)?;
+        let (kf, nf) = (k as f64, n as f64);
+
+        let mut rank_sums = vec![0.0; k];
+        let mut ties = 0.0;
+
+        for i in 0..n {
+            let mut row: Vec<(f64, usize)> = conditions.iter().map(|c| c[i]).zip(0..k).collect();
+            row.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            // Assign tied values the mean of the ranks they span.
+            let mut start = 0;
+
+            while start < k {
+                let end = start + row[start..].iter().take_while(|r| r.0 == row[start].0).count();
+                let rank = 0.5 * (start + end + 1) as f64;
+
+                for r in &row[start..end] {
+                    rank_sums[r.1] += rank;
+                }
+
+                let t = (end - start) as f64;
+                ties += t.powi(3) - t;
+                start = end;
+            }
+        }
+
+        let correction = 1.0 - ties / (nf * kf * (kf * kf - 1.0));
+
+        if correction <= 0.0 {
+            return Err(Error::Undefined);
+        }
+
+        let sum_sq: f64 = rank_sums.iter().map(|r| r * r).sum();
+        let statistic =
+            (12.0 / (nf * kf * (kf + 1.0)) * sum_sq - 3.0 * nf * (kf + 1.0)) / correction;
+
+        let df = kf - 1.0;
+        let p = 1.0 - inc_gamma(0.5 * statistic, 0.5 * df)?;
+
+        Ok(Friedman {
+            statistic,
+            df,
+            p,
+            mean_ranks: rank_sums.iter().map(|r| r / nf).collect(),
+            kendall_w: statistic / (nf * df),
+        })
+    }
+}
+
+/// Check that `conditions` is a complete design of at least two conditions
+/// and subjects, returning their numbers.
+fn check_design(conditions: &[&[f64]]) -> Result<(usize, usize), Error> {
+    if conditions.len() < 2 {
+        return Err(Error::BadParameter);
+    }
+
+    let n = conditions[0].len();
+
+    if conditions.iter().any(|c| c.len() != n) {
+        return Err(Error::UnpairedSamples);
+    }
+
+    if n < 2 {
+        return Err(Error::Undefined);
+    }
+
+    if conditions.iter().any(|c| c.iter().any(|x| !x.is_finite())) {
+        return Err(Error::BadSample);
+    }
+
+    Ok((conditions.len(), n))
+}
+
+fn mean(xs: &[f64]) -> f64 {
+    xs.iter().sum::<f64>() / xs.len() as f64
+}
+
+/// The probability that an F-distributed variate with `d1` and `d2` degrees
+/// of freedom exceeds `f`.
+fn f_upper_tail(f: f64, d1: f64, d2: f64) -> Result<f64, Error> {
+    inc_beta(d2 / (d2 + d1 * f), 0.5 * d2, 0.5 * d1)
+}
+
+/// The Greenhouse-Geisser `ε`, from the double-centered covariance matrix of
+/// the conditions.
+fn greenhouse_geisser_epsilon(conditions: &[&[f64]], means: &[f64]) -> f64 {
+    let k = conditions.len();
+    let n = conditions[0].len() as f64;
+
+    let mut cov = vec![vec![0.0; k]; k];
+
+    for i in 0..k {
+        for j in 0..k {
+            cov[i][j] = conditions[i]
+                .iter()
+                .zip(conditions[j])
+                .map(|(x, y)| (x - means[i]) * (y - means[j]))
+                .sum::<f64>() / (n - 1.0);
+        }
+    }
+
+    let row_means: Vec<f64> = cov.iter().map(|r| mean(r)).collect();
+    let grand_mean = mean(&row_means);
+
+    let mut trace = 0.0;
+    let mut sum_sq = 0.0;
+
+    for i in 0..k {
+        for j in 0..k {
+            let c = cov[i][j] - row_means[i] - row_means[j] + grand_mean;
+
+            if i == j {
+                trace += c;
+            }
+
+            sum_sq += c * c;
+        }
+    }
+
+    trace * trace / ((k - 1) as f64 * sum_sq)
+}
+
+/// Observations of a response under two crossed factors, in long format.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LongFormat {
+    /// Names of the two factors, from a header line, or `A` and `B`.
+    pub factors: (String, String),
+
+    /// The level of each factor, and the response, of each observation.
+    pub observations: Vec<(String, String, f64)>,
+}
+
+impl LongFormat {
+    /// Parse lines of three whitespace-separated fields: a level of each
+    /// factor, then the response. An optional header line, recognized by a
+    /// non-numeric last field, names the factors. Blank lines are ignored.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let mut factors = ("A".to_string(), "B".to_string());
+        let mut observations = vec![];
+
+        for (i, line) in text.lines().enumerate() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+
+            match fields[..] {
+                [] => continue,
+                [a, b, y] => match y.parse::<f64>() {
+                    Ok(y) => observations.push((a.to_string(), b.to_string(), y)),
+                    Err(_) if i == 0 => factors = (a.to_string(), b.to_string()),
+                    Err(_) => return Err(Error::BadRecord),
+                },
+                _ => return Err(Error::BadRecord),
+            }
+        }
+
+        Ok(LongFormat { factors, observations })
+    }
+}
+
+/// The test of one term of a `TwoWayAnova`.
+#[derive(Clone, Copy, Debug)]
+pub struct Effect {
+    pub sum_sq: f64,
+    pub df: f64,
+    pub f: f64,
+    pub p: f64,
+
+    /// Partial `η²`, the proportion of the variation not explained by other
+    /// terms which is due to this one.
+    pub partial_eta_squared: f64,
+}
+
+/// A two-way analysis of variance, of the main effects of two crossed
+/// factors and their interaction, such as compiler version and optimization
+/// level.
+///
+/// Sums of squares are of Type III, from sum-to-zero coding of each factor,
+/// so that each term is tested as if added last. In a balanced design, with
+/// equally many observations in each cell, these are the classical sums of
+/// squares; in an unbalanced one, they do not depend on the order of terms.
+#[derive(Clone, Debug)]
+pub struct TwoWayAnova {
+    /// Levels of each factor, in order of first appearance.
+    pub levels: (Vec<String>, Vec<String>),
+
+    /// Mean and size of each cell, indexed by the level of the first factor,
+    /// then the second.
+    pub cell_means: Vec<Vec<f64>>,
+    pub cell_sizes: Vec<Vec<usize>>,
+
+    pub a: Effect,
+    pub b: Effect,
+    pub interaction: Effect,
+
+    pub residual_sum_sq: f64,
+    pub df_residual: f64,
+}
+
+impl TwoWayAnova {
+    /// Analyze `observations` of the levels of each factor and a response.
+    /// Each factor must have at least two levels, every combination of levels
+    /// must be observed, and some more than once.
+    pub fn new(observations: &[(&str, &str, f64)]) -> Result<Self, Error> {
+        if observations.is_empty() {
+            return Err(Error::EmptySample);
+        }
+
+        if observations.iter().any(|o| !o.2.is_finite()) {
+            return Err(Error::BadSample);
+        }
+
+        let index = |levels: &mut Vec<String>, level: &str| -> usize {
+            levels.iter().position(|l| l == level).unwrap_or_else(|| {
+                levels.push(level.to_string());
+                levels.len() - 1
+            })
+        };
+
+        let mut levels = (vec![], vec![]);
+        let cells: Vec<(usize, usize, f64)> = observations
+            .iter()
+            .map(|&(a, b, y)| (index(&mut levels.0, a), index(&mut levels.1, b), y))
+            .collect();
+
+        let (ka, kb) = (levels.0.len(), levels.1.len());
+
+        if ka < 2 || kb < 2 {
+            return Err(Error::BadParameter);
+        }
+
+        let mut sums = vec![vec![0.0; kb]; ka];
+        let mut cell_sizes = vec![vec![0; kb]; ka];
+
+        for &(i, j, y) in &cells {
+            sums[i][j] += y;
+            cell_sizes[i][j] += 1;
+        }
+
+        if cell_sizes.iter().flatten().any(|&n| n == 0) {
+            return Err(Error::Undefined);
+        }
+
+        let cell_means: Vec<Vec<f64>> = sums
+            .iter()
+            .zip(&cell_sizes)
+            .map(|(s, n)| s.iter().zip(n).map(|(s, &n)| s / n as f64).collect())
+            .collect();
+
+        let residual_sum_sq: f64 = cells
+            .iter()
+            .map(|&(i, j, y)| (y - cell_means[i][j]).powi(2))
+            .sum();
+        let df_residual = (cells.len() - ka * kb) as f64;
+
+        if df_residual == 0.0 || residual_sum_sq == 0.0 {
+            return Err(Error::Undefined);
+        }
+
+        // Columns of the full model: the intercept, then the codes of each
+        // factor, then their products.
+        let codes = |level: usize, k: usize| -> Vec<f64> {
+            (0..k - 1)
+                .map(|c| if level == k - 1 { -1.0 } else if level == c { 1.0 } else { 0.0 })
+                .collect()
+        };
+
+        let rows: Vec<Vec<f64>> = cells
+            .iter()
+            .map(|&(i, j, _)| {
+                let (a, b) = (codes(i, ka), codes(j, kb));
+                let mut row = vec![1.0];
+                row.extend(&a);
+                row.extend(&b);
+                row.extend(a.iter().flat_map(|x| b.iter().map(move |y| x * y)));
+                row
+            })
+            .collect();
+        let ys: Vec<f64> = cells.iter().map(|c| c.2).collect();
+
+        let a_terms = 1..ka;
+        let b_terms = ka..(ka + kb - 1);
+        let ab_terms = (ka + kb - 1)..(ka * kb);
+
+        let effect = |dropped: ::std::ops::Range<usize>| -> Result<Effect, Error> {
+            let df = dropped.len() as f64;
+            let kept = |r: &Vec<f64>| -> Vec<f64> {
+                r.iter()
+                    .enumerate()
+                    .filter(|&(c, _)| !dropped.contains(&c))
+                    .map(|(_, &x)| x)
+                    .collect()
+            };
+            let reduced: Vec<Vec<f64>> = rows.iter().map(kept).collect();
+
+            let sum_sq = (residual_sum_sq_of(&reduced, &ys)? - residual_sum_sq).max(0.0);
+            let f = (sum_sq / df) / (residual_sum_sq / df_residual);
+
+            Ok(Effect {
+                sum_sq,
+                df,
+                f,
+                p: f_upper_tail(f, df, df_residual)?,
+                partial_eta_squared: sum_sq / (sum_sq + residual_sum_sq),
+            })
+        };
+
+        Ok(TwoWayAnova {
+            a: effect(a_terms)?,
+            b: effect(b_terms)?,
+            interaction: effect(ab_terms)?,
+            levels,
+            cell_means,
+            cell_sizes,
+            residual_sum_sq,
+            df_residual,
+        })
+    }
+}
+
+/// The residual sum of squares of the least-squares fit of `ys` to the
+/// design matrix with the given `rows`, from the normal equations.
+fn residual_sum_sq_of(rows: &[Vec<f64>], ys: &[f64]) -> Result<f64, Error> {
+    let p = rows[0].len();
+
+    // The augmented matrix `[XᵀX | Xᵀy]`.
+    let mut m = vec![vec![0.0; p + 1]; p];
+
+    for (r, y) in rows.iter().zip(ys) {
+        for i in 0..p {
+            for j in 0..p {
+                m[i][j] += r[i] * r[j];
+            }
+
+            m[i][p] += r[i] * y;
+        }
+    }
+
+    // Gaussian elimination with partial pivoting.
+    for c in 0..p {
+        let pivot = (c..p)
+            .max_by(|&i, &j| m[i][c].abs().total_cmp(&m[j][c].abs()))
+            .unwrap_or(c);
+
+        if m[pivot][c].abs() < SINGULAR_PIVOT {
+            return Err(Error::Undefined);
+        }
+
+        m.swap(c, pivot);
+
+        let (done, rest) = m.split_at_mut(c + 1);
+        let pivot_row = &done[c];
+
+        for row in rest {
+            let factor = row[c] / pivot_row[c];
+
+            for (x, y) in row.iter_mut().zip(pivot_row).skip(c) {
+                *x -= factor * y;
+            }
+        }
+    }
+
+    let mut beta = vec![0.0; p];
+
+    for i in (0..p).rev() {
+        let sum: f64 = ((i + 1)..p).map(|j| m[i][j] * beta[j]).sum();
+        beta[i] = (m[i][p] - sum) / m[i][i];
+    }
+
+    Ok(rows
+        .iter()
+        .zip(ys)
+        .map(|(r, y)| (y - r.iter().zip(&beta).map(|(x, b)| x * b).sum::<f64>()).powi(2))
+        .sum())
+}
+
+const SINGULAR_PIVOT: f64 = 1e-12;
@@ -0,0 +1,174 @@
+use std::fmt;
+
+use error::Error;
+use special;
+use summary::{Summarizer, Summary};
+use t_test::SigLevel;
+
+
+/// Sample size above which we rely on the central limit theorem, and treat
+/// the t-test as valid whatever the shape of the data.
+const LARGE_SAMPLE: f64 = 30.0;
+
+/// The outcome of a test of one assumption of a hypothesis test.
+#[derive(Debug)]
+pub struct AssumptionCheck {
+    /// What was assumed, e.g. "sample 1 is normal".
+    pub assumption: String,
+
+    /// Name of the test used to check the assumption.
+    pub test: &'static str,
+    pub statistic: f64,
+    pub p: f64,
+
+    /// False if the assumption was rejected at the chosen significance level.
+    pub plausible: bool,
+}
+
+/// A two-sample hypothesis test which `select_test` may choose.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TestChoice {
+    Student,
+    Welch,
+    MannWhitney,
+}
+
+impl fmt::Display for TestChoice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            TestChoice::Student => write!(f, "Student's t-test"),
+            TestChoice::Welch => write!(f, "Welch's t-test"),
+            TestChoice::MannWhitney => write!(f, "Mann–Whitney U test"),
+        }
+    }
+}
+
+/// The checks made by `select_test`, the test chosen, and why.
+#[derive(Debug)]
+pub struct Selection {
+    pub checks: Vec<AssumptionCheck>,
+    pub choice: TestChoice,
+    pub reason: &'static str,
+}
+
+/// Check the assumptions of the two-sample t-tests, and choose a test for
+/// comparing `x` and `y` accordingly. Each check rejects its assumption if
+/// its p-value is below `level`.
+///
+///   - If either sample is small and rejects normality, choose the
+///     Mann–Whitney U test, which makes no assumption about shape. Larger
+///     samples are left to the central limit theorem.
+///   - Otherwise, if the variances are plausibly equal, choose Student's
+///     t-test, which pools them.
+///   - Otherwise, choose Welch's t-test.
+pub fn select_test(x: &Summarizer, y: &Summarizer, level: SigLevel) -> Result<Selection, Error> {
+    let alpha = level.alpha();
+    let mut checks = vec![];
+
+    for (i, s) in [x, y].iter().enumerate() {
+        let (jb, p) = jarque_bera(s)?;
+
+        checks.push(AssumptionCheck {
+            assumption: format!("sample {} is normal", i + 1),
+            test: "Jarque–Bera",
+            statistic: jb,
+            p,
+            plausible: p >= alpha,
+        });
+    }
+
+    let (f, p) = brown_forsythe(x, y)?;
+    let equal_variances = p >= alpha;
+
+    checks.push(AssumptionCheck {
+        assumption: String::from("variances are equal"),
+        test: "Brown–Forsythe",
+        statistic: f,
+        p,
+        plausible: equal_variances,
+    });
+
+    let normal = checks[0].plausible && checks[1].plausible;
+    let small_and_not_normal = [x, y]
+        .iter()
+        .zip(&checks)
+        .any(|(s, c)| !c.plausible && s.size() < LARGE_SAMPLE);
+
+    let (choice, reason) = match (small_and_not_normal, normal, equal_variances) {
+        (true, _, _) =>
+            (TestChoice::MannWhitney,
+             "a sample of fewer than 30 points is not plausibly normal, so a rank test is safer"),
+        (false, true, true) =>
+            (TestChoice::Student,
+             "both samples are plausibly normal with equal variances, which can be pooled"),
+        (false, false, true) =>
+            (TestChoice::Student,
+             "the samples are large enough for a t-test despite non-normality, and equal \
+              variances are plausible, so they can be pooled"),
+        (false, true, false) =>
+            (TestChoice::Welch,
+             "both samples are plausibly normal, but the variances differ and must not be pooled"),
+        (false, false, false) =>
+            (TestChoice::Welch,
+             "the samples are large enough for a t-test despite non-normality, but the \
+              variances differ and must not be pooled"),
+    };
+
+    Ok(Selection { checks, choice, reason })
+}
+
+/// The Jarque–Bera test of normality, from the sample skewness and kurtosis.
+/// Returns the statistic and its p-value under the asymptotic χ² distribution
+/// with 2 degrees of freedom, which is conservative for small samples.
+pub fn jarque_bera(s: &Summarizer) -> Result<(f64, f64), Error> {
+    let n = s.size();
+    let m = s.mean();
+
+    let moment = |k: i32| s.as_slice().iter().map(|x| (x - m).powi(k)).sum::<f64>() / n;
+    let m2 = moment(2);
+
+    if m2 == 0.0 {
+        return Err(Error::Undefined);
+    }
+
+    let skewness = moment(3) / m2.powf(1.5);
+    let kurtosis = moment(4) / m2.powi(2);
+
+    let jb = n / 6.0 * (skewness.powi(2) + (kurtosis - 3.0).powi(2) / 4.0);
+
+    // The survival function of χ² with 2 degrees of freedom.
+    Ok((jb, (-jb / 2.0).exp()))
+}
+
+/// The Brown–Forsythe test of equal variances: an analysis of variance of
+/// the absolute deviations of each sample from its median. Returns the F
+/// statistic and its p-value.
+pub fn brown_forsythe(x: &Summarizer, y: &Summarizer) -> Result<(f64, f64), Error> {
+    let deviations = |s: &Summarizer| -> Vec<f64> {
+        let m = s.median();
+
+        s.as_slice().iter().map(|v| (v - m).abs()).collect()
+    };
+
+    let zx = Summary::new(&deviations(x))?;
+    let zy = Summary::new(&deviations(y))?;
+
+    let (n1, n2) = (zx.size(), zy.size());
+    let n = n1 + n2;
+    let grand = (n1 * zx.mean() + n2 * zy.mean()) / n;
+
+    let between = n1 * (zx.mean() - grand).powi(2) + n2 * (zy.mean() - grand).powi(2);
+    let within = (n1 - 1.0) * zx.unbiased_variance() + (n2 - 1.0) * zy.unbiased_variance();
+
+    let df1 = 1.0;
+    let df2 = n - 2.0;
+
+    if df2 < 1.0 || within.is_nan() || within == 0.0 {
+        return Err(Error::Undefined);
+    }
+
+    let f = (between / df1) / (within / df2);
+    let p = special::inc_beta(df2 / (df2 + df1 * f), df2 / 2.0, df1 / 2.0)?;
+
+    Ok((f, p))
+}
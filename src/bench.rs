@@ -0,0 +1,463 @@
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::Instant;
+
+use dent::summary::Summary;
+
+use log::json_string;
+
+
+/// A measure of a run of a benchmarked command, with its name and unit.
+pub struct Metric {
+    pub name: &'static str,
+    pub unit: &'static str,
+    pub value: fn(&Measurement) -> Option<f64>,
+}
+
+/// The metrics of each run, of which the first, wall-clock time, is always
+/// known, and the others only where the system reports the resource usage
+/// of child processes.
+pub const METRICS: [Metric; 4] = [
+    Metric { name: "Wall time", unit: "ms", value: |m| Some(m.wall) },
+    Metric { name: "User CPU time", unit: "ms", value: |m| m.resources.map(|r| r.user) },
+    Metric { name: "System CPU time", unit: "ms", value: |m| m.resources.map(|r| r.system) },
+    Metric { name: "Max RSS", unit: "KiB", value: |m| m.resources.map(|r| r.max_rss) },
+];
+
+/// Resources used by a run of a command: its CPU time in user and kernel
+/// mode, in ms, and its maximum resident set size, in KiB.
+#[derive(Clone, Copy, Debug)]
+pub struct Resources {
+    pub user: f64,
+    pub system: f64,
+    pub max_rss: f64,
+}
+
+/// One run of a benchmarked command.
+#[derive(Clone, Copy, Debug)]
+pub struct Measurement {
+    /// The 0-based index of the run among all runs of the benchmark, in the
+    /// order they were made.
+    pub sequence: usize,
+
+    /// Wall-clock time of the run, in ms.
+    pub wall: f64,
+
+    pub resources: Option<Resources>,
+    pub status: ExitStatus,
+}
+
+/// A benchmarked command which could not be run, or which failed.
+#[derive(Debug)]
+pub struct BenchError {
+    pub command: String,
+
+    message: String,
+}
+
+impl fmt::Display for BenchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "Command {:?} {}", self.command, self.message)
+    }
+}
+
+impl error::Error for BenchError {}
+
+/// How to run benchmarked commands.
+#[derive(Clone, Copy, Debug)]
+pub struct Plan<'a> {
+    /// Number of runs of each command to start with.
+    pub runs: usize,
+
+    /// Whether to run the commands in turn, once each per round, so that
+    /// drift in the state of the machine, as it warms up or as background
+    /// load comes and goes, affects each alike. Otherwise, every run of each
+    /// command is made before those of the next.
+    pub interleave: bool,
+
+    /// Whether to keep runs which exit with a failing status, rather than
+    /// stopping with an error.
+    pub allow_failure: bool,
+
+    /// Whether to run again, once, each of the first runs of a command whose
+    /// wall time is an outlier among them, by Tukey's fences. The outlying
+    /// runs are kept, and their reruns recorded apart from them.
+    pub rerun_outliers: bool,
+
+    /// Relative width of the interval of the mean wall time of a command at
+    /// which to stop adding runs of it, at the `confidence` level, if any.
+    pub target_precision: Option<f64>,
+    pub confidence: f64,
+
+    /// Number of runs of each command after which to stop adding runs.
+    pub max_runs: usize,
+
+    /// Commands to run, untimed, before and after runs: once before the
+    /// first and after the last, for runs with warm caches, or around each
+    /// if `cold`, to measure each run from the same state, as after clearing
+    /// caches.
+    pub prepare: Option<&'a str>,
+    pub cleanup: Option<&'a str>,
+    pub cold: bool,
+}
+
+/// A run of a command made again, since the run it repeats was an outlier.
+#[derive(Clone, Copy, Debug)]
+pub struct Rerun {
+    /// The 0-based index of the outlying run among those of its command.
+    pub of: usize,
+
+    pub measurement: Measurement,
+}
+
+/// The runs of each of a set of benchmarked commands.
+#[derive(Debug)]
+pub struct Bench {
+    pub measurements: Vec<Vec<Measurement>>,
+
+    /// Reruns of the outlying runs of each command, in the order they were
+    /// made, which are not among its `measurements`.
+    pub reruns: Vec<Vec<Rerun>>,
+}
+
+impl Bench {
+    /// Relative width of the interval of the mean wall time of each command,
+    /// at the given confidence level.
+    pub fn precision(&self, confidence: f64) -> Vec<f64> {
+        self.measurements.iter().map(|ms| relative_width(ms, confidence)).collect()
+    }
+}
+
+/// Measure runs of each of `commands` by `plan`, run by the shell with their
+/// output discarded.
+pub fn measure_commands(commands: &[&str], plan: &Plan) -> Result<Bench, BenchError> {
+    let hook = |cmd: Option<&str>, name: &str| match cmd {
+        Some(cmd) => run_hook(cmd, name),
+        None => Ok(()),
+    };
+
+    let mut sequence = 0;
+
+    let mut measure = |c: usize| -> Result<Measurement, BenchError> {
+        if plan.cold {
+            hook(plan.prepare, "prepare")?;
+        }
+
+        let m = Measurement { sequence, ..measure_command(commands[c])? };
+        sequence += 1;
+
+        if plan.cold {
+            hook(plan.cleanup, "cleanup")?;
+        }
+
+        if !(plan.allow_failure || m.status.success()) {
+            return Err(BenchError {
+                command: commands[c].to_string(),
+                message: format!("failed: {}", m.status),
+            });
+        }
+
+        Ok(m)
+    };
+
+    if !plan.cold {
+        hook(plan.prepare, "prepare")?;
+    }
+
+    let mut measurements = vec![Vec::with_capacity(plan.runs); commands.len()];
+
+    let order: Vec<usize> = if plan.interleave {
+        (0..plan.runs).flat_map(|_| 0..commands.len()).collect()
+    } else {
+        (0..commands.len()).flat_map(|c| (0..plan.runs).map(move |_| c)).collect()
+    };
+
+    for c in order {
+        measurements[c].push(measure(c)?);
+    }
+
+    let mut reruns = vec![vec![]; commands.len()];
+
+    if plan.rerun_outliers {
+        let outlying: Vec<Vec<usize>> = measurements.iter().map(|ms| outliers(ms)).collect();
+
+        // Rerun in the same order as the runs: in turn, if interleaved.
+        let order: Vec<(usize, usize)> = if plan.interleave {
+            let most = outlying.iter().map(|o| o.len()).max().unwrap_or(0);
+
+            (0..most)
+                .flat_map(|k| outlying.iter().enumerate().filter_map(move |(c, o)| {
+                    o.get(k).map(|&i| (c, i))
+                }))
+                .collect()
+        } else {
+            outlying
+                .iter()
+                .enumerate()
+                .flat_map(|(c, o)| o.iter().map(move |&i| (c, i)))
+                .collect()
+        };
+
+        for (c, i) in order {
+            reruns[c].push(Rerun { of: i, measurement: measure(c)? });
+        }
+    }
+
+    if let Some(target) = plan.target_precision {
+        let imprecise = |ms: &[Measurement]| {
+            ms.len() < plan.max_runs && relative_width(ms, plan.confidence) > target
+        };
+
+        if plan.interleave {
+            loop {
+                let pending: Vec<usize> = (0..commands.len())
+                    .filter(|&c| imprecise(&measurements[c]))
+                    .collect();
+
+                if pending.is_empty() {
+                    break;
+                }
+
+                for c in pending {
+                    measurements[c].push(measure(c)?);
+                }
+            }
+        } else {
+            for (c, ms) in measurements.iter_mut().enumerate() {
+                while imprecise(ms) {
+                    ms.push(measure(c)?);
+                }
+            }
+        }
+    }
+
+    if !plan.cold {
+        hook(plan.cleanup, "cleanup")?;
+    }
+
+    Ok(Bench { measurements, reruns })
+}
+
+/// Columns of the raw measurements of a benchmark, as written by `save_raw`.
+const RAW_COLUMNS: [&str; 9] = [
+    "sequence", "command", "run", "status", "wall_ms", "user_ms", "system_ms", "max_rss_kib",
+    "rerun_of",
+];
+
+/// Units of the raw measurements, by column, or empty for columns which are
+/// not measurements.
+const RAW_UNITS: [&str; 9] = ["", "", "", "", "ms", "ms", "ms", "KiB", ""];
+
+/// Write every run of `bench`, in the order they were made, to the file at
+/// `path`: as a JSON array of objects if its name ends in `.json`, and as CSV
+/// with a header otherwise. Kept runs are numbered from 1 among those of
+/// their command, and each rerun of an outlier instead gives the number of
+/// the run it repeats, under `rerun_of`. An exit status which is not a code,
+/// as when the command was killed by a signal, unknown resources, and the
+/// missing number of each run, are empty or `null`. In JSON,
+/// each object also maps the measurements to their units under `units`.
+pub fn save_raw(path: &str, commands: &[&str], bench: &Bench) -> io::Result<()> {
+    let json = Path::new(path).extension().is_some_and(|e| e == "json");
+
+    // The command, run number, number of the run repeated, and measurement.
+    let kept = bench
+        .measurements
+        .iter()
+        .enumerate()
+        .flat_map(|(c, ms)| ms.iter().enumerate().map(move |(i, m)| (c, Some(i + 1), None, m)));
+    let reruns = bench
+        .reruns
+        .iter()
+        .enumerate()
+        .flat_map(|(c, rs)| rs.iter().map(move |r| (c, None, Some(r.of + 1), &r.measurement)));
+
+    let mut rows: Vec<(usize, Option<usize>, Option<usize>, &Measurement)> =
+        kept.chain(reruns).collect();
+    rows.sort_by_key(|&(_, _, _, m)| m.sequence);
+
+    let units: Vec<String> = RAW_COLUMNS
+        .iter()
+        .zip(&RAW_UNITS)
+        .filter(|(_, u)| !u.is_empty())
+        .map(|(k, u)| format!("{}:{}", json_string(k), json_string(u)))
+        .collect();
+    let units = format!("{}:{{{}}}", json_string("units"), units.join(","));
+
+    let mut text = String::new();
+
+    if json {
+        text.push('[');
+    } else {
+        text += &RAW_COLUMNS.join(",");
+        text.push('\n');
+    }
+
+    for (i, &(c, run, rerun_of, m)) in rows.iter().enumerate() {
+        let null = if json { "null" } else { "" };
+        let number = |x: Option<f64>| x.map_or(null.to_string(), |x| x.to_string());
+        let index = |x: Option<usize>| x.map_or(null.to_string(), |x| x.to_string());
+
+        let fields = [
+            m.sequence.to_string(),
+            if json { json_string(commands[c]) } else { csv_field(commands[c]) },
+            index(run),
+            m.status.code().map_or(null.to_string(), |s| s.to_string()),
+            m.wall.to_string(),
+            number(m.resources.map(|r| r.user)),
+            number(m.resources.map(|r| r.system)),
+            number(m.resources.map(|r| r.max_rss)),
+            index(rerun_of),
+        ];
+
+        if json {
+            let members: Vec<String> = RAW_COLUMNS
+                .iter()
+                .zip(&fields)
+                .map(|(k, v)| format!("{}:{}", json_string(k), v))
+                .chain(Some(units.clone()))
+                .collect();
+            let sep = if i == 0 { "\n" } else { ",\n" };
+
+            text += &format!("{}{{{}}}", sep, members.join(","));
+        } else {
+            text += &fields.join(",");
+            text.push('\n');
+        }
+    }
+
+    if json {
+        text += "\n]\n";
+    }
+
+    fs::write(path, text)
+        .map_err(|e| io::Error::new(e.kind(), format!("Could not write {:?}: {}", path, e)))
+}
+
+/// A CSV field of `s`, quoted if it holds a comma, quote, or line break.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Run the `name` hook `command`, untimed.
+fn run_hook(command: &str, name: &str) -> Result<(), BenchError> {
+    let error = |message: String| BenchError { command: command.to_string(), message };
+
+    let status = shell(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| error(format!("could not be run as the {} command: {}", name, e)))?;
+
+    if !status.success() {
+        return Err(error(format!("failed as the {} command: {}", name, status)));
+    }
+
+    Ok(())
+}
+
+/// Indices of the runs whose wall times lie outside Tukey's fences.
+fn outliers(ms: &[Measurement]) -> Vec<usize> {
+    let walls: Vec<f64> = ms.iter().map(|m| m.wall).collect();
+
+    match Summary::new(&walls) {
+        Ok(s) => (0..walls.len())
+            .filter(|&i| walls[i] < s.min_adjacent() || s.max_adjacent() < walls[i])
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+/// Width of the interval of the mean wall time of runs, relative to the
+/// mean, or infinite if it is undefined.
+fn relative_width(ms: &[Measurement], confidence: f64) -> f64 {
+    let walls: Vec<f64> = ms.iter().map(|m| m.wall).collect();
+
+    Summary::new(&walls)
+        .and_then(|s| s.mean_confidence_interval(confidence).map(|(lo, hi)| (hi - lo) / s.mean()))
+        .ok()
+        .filter(|w| w.is_finite())
+        .unwrap_or(f64::INFINITY)
+}
+
+/// Measure one run of `command`.
+fn measure_command(command: &str) -> Result<Measurement, BenchError> {
+    let error = |e: io::Error| BenchError {
+        command: command.to_string(),
+        message: format!("could not be run: {}", e),
+    };
+
+    let mut cmd = shell(command);
+    cmd.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+
+    let start = Instant::now();
+    let child = cmd.spawn().map_err(error)?;
+    let (status, resources) = wait(child).map_err(error)?;
+    let elapsed = start.elapsed();
+
+    Ok(Measurement { sequence: 0, wall: 1e3 * elapsed.as_secs_f64(), resources, status })
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(not(unix))]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+/// Wait for `child` to exit, and get the resources it and its waited-for
+/// descendants used, by `wait4(2)`.
+#[cfg(unix)]
+fn wait(child: Child) -> io::Result<(ExitStatus, Option<Resources>)> {
+    use std::mem;
+    use std::os::unix::process::ExitStatusExt;
+
+    let pid = child.id() as libc::pid_t;
+    let mut status = 0;
+
+    // Safe, since `rusage` is plain data, and both pointers are valid for
+    // the call. We reap the child ourselves, and so must not wait on it
+    // through `child` after.
+    let mut usage: libc::rusage = unsafe { mem::zeroed() };
+
+    while unsafe { libc::wait4(pid, &mut status, 0, &mut usage) } != pid {
+        let e = io::Error::last_os_error();
+
+        if e.kind() != io::ErrorKind::Interrupted {
+            return Err(e);
+        }
+    }
+
+    let ms = |t: libc::timeval| 1e3 * t.tv_sec as f64 + 1e-3 * t.tv_usec as f64;
+
+    // Reported in bytes on macOS, and in KiB elsewhere.
+    let max_rss = if cfg!(target_os = "macos") {
+        usage.ru_maxrss as f64 / 1024.0
+    } else {
+        usage.ru_maxrss as f64
+    };
+
+    let resources = Resources { user: ms(usage.ru_utime), system: ms(usage.ru_stime), max_rss };
+
+    Ok((ExitStatus::from_raw(status), Some(resources)))
+}
+
+#[cfg(not(unix))]
+fn wait(mut child: Child) -> io::Result<(ExitStatus, Option<Resources>)> {
+    Ok((child.wait()?, None))
+}
@@ -0,0 +1,159 @@
+use std::fmt;
+
+use error::Error;
+use special::inc_gamma;
+
+
+/// The count of one leading digit, with its observed proportion and that
+/// expected under Benford's law, `log₁₀(1 + 1/d)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Digit {
+    pub digit: u8,
+    pub count: u64,
+    pub observed: f64,
+    pub expected: f64,
+}
+
+/// Conformity of first digits to Benford's law, by the mean absolute
+/// deviation of their proportions, with the thresholds of Nigrini [1].
+///
+/// [1]: M. Nigrini, "Benford's Law: Applications for Forensic Accounting,
+///      Auditing, and Fraud Detection", Wiley, 2012, p. 160.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Conformity {
+    Close,
+    Acceptable,
+    Marginal,
+    Nonconformity,
+}
+
+impl fmt::Display for Conformity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Conformity::Close => write!(f, "close conformity"),
+            Conformity::Acceptable => write!(f, "acceptable conformity"),
+            Conformity::Marginal => write!(f, "marginally acceptable conformity"),
+            Conformity::Nonconformity => write!(f, "nonconformity"),
+        }
+    }
+}
+
+/// Upper bounds of the mean absolute deviation for each `Conformity` but the
+/// last, for first digits.
+const CONFORMITY_BOUNDS: [(f64, Conformity); 3] = [
+    (0.006, Conformity::Close),
+    (0.012, Conformity::Acceptable),
+    (0.015, Conformity::Marginal),
+];
+
+/// An analysis of the first significant digits of a sample by Benford's law,
+/// which holds for many figures which span several orders of magnitude, such
+/// as amounts in accounts. Departures from it in reported figures can be a
+/// sign of invention or manipulation.
+#[derive(Clone, Debug)]
+pub struct Benford {
+    /// Counts of each digit, from 1 to 9.
+    pub digits: Vec<Digit>,
+
+    /// Number of values with a first digit, and of zeros, which have none.
+    pub size: u64,
+    pub zeros: u64,
+
+    /// Pearson's chi-square statistic against the proportions of Benford's
+    /// law, with 8 degrees of freedom, and its p-value.
+    pub chi_square: f64,
+    pub p: f64,
+
+    /// Mean absolute deviation of observed from expected proportions.
+    pub mad: f64,
+
+    /// Orders of magnitude between the smallest and largest nonzero absolute
+    /// values. Benford's law is not expected to hold unless this is at least
+    /// a few.
+    pub orders_of_magnitude: f64,
+}
+
+impl Benford {
+    /// Count the first digits of the absolute values of `data`, which must
+    /// be finite, and include some nonzero value.
+    pub fn new(data: &[f64]) -> Result<Self, Error> {
+        if data.is_empty() {
+            return Err(Error::EmptySample);
+        }
+
+        if data.iter().any(|x| !x.is_finite()) {
+            return Err(Error::BadSample);
+        }
+
+        let mut counts = [0u64; 9];
+
+        for d in data.iter().filter_map(|&x| first_digit(x)) {
+            counts[d as usize - 1] += 1;
+        }
+
+        let size: u64 = counts.iter().sum();
+        let zeros = data.len() as u64 - size;
+
+        if size == 0 {
+            return Err(Error::Undefined);
+        }
+
+        let digits: Vec<Digit> = (1..10u8)
+            .zip(counts.iter())
+            .map(|(digit, &count)| Digit {
+                digit,
+                count,
+                observed: count as f64 / size as f64,
+                expected: (1.0 + 1.0 / digit as f64).log10(),
+            })
+            .collect();
+
+        let n = size as f64;
+        let chi_square: f64 = digits
+            .iter()
+            .map(|d| (d.count as f64 - n * d.expected).powi(2) / (n * d.expected))
+            .sum();
+        let p = 1.0 - inc_gamma(0.5 * chi_square, 4.0)?;
+
+        let mad = digits.iter().map(|d| (d.observed - d.expected).abs()).sum::<f64>() / 9.0;
+
+        let (lo, hi) = data
+            .iter()
+            .map(|x| x.abs())
+            .filter(|&x| x > 0.0)
+            .fold((f64::INFINITY, 0.0f64), |(lo, hi), x| (lo.min(x), hi.max(x)));
+
+        Ok(Benford {
+            digits,
+            size,
+            zeros,
+            chi_square,
+            p,
+            mad,
+            orders_of_magnitude: (hi / lo).log10(),
+        })
+    }
+
+    /// The conformity of the first digits, by their mean absolute deviation.
+    pub fn conformity(&self) -> Conformity {
+        CONFORMITY_BOUNDS
+            .iter()
+            .find(|&&(bound, _)| self.mad <= bound)
+            .map_or(Conformity::Nonconformity, |&(_, c)| c)
+    }
+}
+
+/// The first significant digit of `x`, ignoring its sign, or `None` if it is
+/// zero or not finite.
+pub fn first_digit(x: f64) -> Option<u8> {
+    if x == 0.0 || !x.is_finite() {
+        return None;
+    }
+
+    // Exact, unlike `x / 10^⌊log₁₀ x⌋`, which may round across a power of 10.
+    format!("{:e}", x.abs())
+        .chars()
+        .next()
+        .and_then(|c| c.to_digit(10))
+        .map(|d| d as u8)
+}
@@ -1,334 +1,4869 @@
 #[macro_use] extern crate clap;
 extern crate dent;
+#[cfg(unix)] extern crate libc;
+#[cfg(feature = "sqlite")] extern crate rusqlite;
 extern crate term;
 extern crate term_size;
+#[cfg(feature = "net")] extern crate ureq;
 
-use clap::{App, Arg};
-use dent::plot;
-use dent::summary::Summary;
-use dent::t_test::{TTest, welch_t_test};
+use clap::{App, AppSettings, Arg, ArgMatches, Shell, SubCommand};
+use dent::distribution;
+use dent::drift::{self, Drift};
+use dent::layout::{self, Layout, Table};
+use dent::plot::{self, BoxplotChars, PlotHeight, PlotWidth, RenderedPlot};
+use dent::quantize::Quantizer;
+use dent::ratio::RatioOfMeans;
+use dent::rng::Rng;
+use dent::robust::{self, MannWhitney};
+use dent::series::{self, Excursion, Series, Statistic, Streak, TimeFormat};
+use dent::shift::{self, ShiftFunction};
+use dent::smooth;
+use dent::stratify::StratifiedComparison;
+use dent::agreement::{BlandAltman, PassingBablok};
+use dent::anova::{Friedman, LongFormat, RepeatedMeasuresAnova, TwoWayAnova};
+use dent::assumptions::{self, TestChoice};
+use dent::benford::Benford;
+use dent::categorical::{CategoricalSummary, DiversityIndex};
+use dent::contingency::{self, TwoByTwo};
+use dent::dedup::{Dedup, DEDUP_NAMES};
+use dent::diagnostics;
+use dent::effect::{self, Magnitude};
+use dent::error::Error;
+use dent::estimate::{self, Estimate, Method};
+use dent::exceedance::{Exceedance, ExceedanceChange};
+use dent::expr::Expr;
+use dent::fit::{self, Criterion, DistributionFit, Family, RankedFit};
+use dent::frequency::FrequencySummarizer;
+use dent::histogram::{self, Binning, Histogram};
+use dent::lr::{
+    self, Coefficient, DemingRegression, Influence, LinearRegression, Model, ModelFit,
+    RegressionComparison, Segment, SegmentedRegression,
+};
+use dent::meta::{self, Combined, MetaAnalysis};
+use dent::paired::{self, SignTest};
+use dent::summary::{
+    self, NonFinitePolicy, NonFiniteReport, StreamingSummarizer, Summarizer, Summary, ValueCounts,
+};
+use dent::weighted::WeightedSummarizer;
+use dent::throughput::Throughput;
+use dent::tails;
+use dent::t_test::{
+    SigLevel, TTest, Verdict, holm_adjust, student_t_test, welch_t_test,
+    welch_t_test_from_stats,
+};
 
-use std::error;
-use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use config::Config;
+use input::STDIN_PATH;
 
+use std::env;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+mod bench;
+mod config;
+#[cfg(feature = "sqlite")] mod db;
 mod fmt;
+mod input;
 mod log;
+mod man;
+mod output;
+
 
+/// Like `println!`, but through the output layer, so that it may be paged.
+macro_rules! outln {
+    () => {
+        output::write("\n")
+    };
+    ($($arg: tt)*) => {
+        output::write(&format!("{}\n", format_args!($($arg)*)))
+    }
+}
 
 macro_rules! ok {
     ($r: expr) => {
         match $r {
             Ok(t) => t,
             Err(e) => {
-                log::error(&format!("{}", e));
-                std::process::exit(1);
+                log::fail(&e);
+                output::exit(1);
             }
         }
     }
 }
 
-fn print_summary(s: &Summary, outliers: bool) {
-    let width = 10;
-    let size_width = 6;
+/// Width of the numeric columns of summary tables.
+const SUMMARY_WIDTH: usize = 10;
 
-    if outliers {
-        println!(
-            "{n:>nw$}  {min:>w$}  {q1:>w$}  {med:>w$}  {q3:>w$}  {max:>w$}  {mean:>w$}  {std:>w$}",
-            w = width,
-            nw = size_width,
-            n = "Size",
-            min = "Min",
-            q1 = "Q1",
-            med = "Median",
-            q3 = "Q3",
-            max = "Max",
-            mean = "Mean",
-            std = "Std Dev",
-        );
-        println!(
-            "{n:>nw$}  {min:>w$}  {q1:>w$}  {med:>w$}  {q3:>w$}  {max:>w$}  {mean:>w$}  {std:>w$}",
-            w = width,
-            nw = size_width,
-            n = fmt::f(s.size(), width),
-            min = fmt::f(s.min(), width),
-            q1 = fmt::f(s.lower_quartile(), width),
-            med = fmt::f(s.median(), width),
-            q3 = fmt::f(s.upper_quartile(), width),
-            max = fmt::f(s.max(), width),
-            mean = fmt::f(s.mean(), width),
-            std = fmt::f(s.standard_deviation(), width),
-        );
+/// Width of the size column of summary tables.
+const SUMMARY_SIZE_WIDTH: usize = 6;
+
+/// Significant digits of the statistics printed by `--brief`, unless
+/// `--precision` is given.
+const BRIEF_PRECISION: usize = 3;
+
+/// A cell of `x` formatted as by `fmt::f`, marked with a leading `~` if it
+/// is approximate.
+fn approximate_cell(x: f64, width: usize, approximate: bool) -> String {
+    if approximate {
+        format!("~{}", fmt::f(x, width))
     } else {
-        println!(
-            "{n:>nw$}  {min:>w$}  {q1:>w$}  {med:>w$}  {q3:>w$}  {max:>w$}  {mean:>w$}  {std:>w$}",
-            w = width,
-            nw = size_width,
-            n = "Size",
-            min = "Min Adj",
-            q1 = "Q1",
-            med = "Median",
-            q3 = "Q3",
-            max = "Max Adj",
-            mean = "Mean",
-            std = "Std Dev",
-        );
-        println!(
-            "{n:>nw$}  {min:>w$}  {q1:>w$}  {med:>w$}  {q3:>w$}  {max:>w$}  {mean:>w$}  {std:>w$}",
-            w = width,
-            nw = size_width,
-            n = fmt::f(s.size(), width),
-            min = fmt::f(s.min_adjacent(), width),
-            q1 = fmt::f(s.lower_quartile(), width),
-            med = fmt::f(s.median(), width),
-            q3 = fmt::f(s.upper_quartile(), width),
-            max = fmt::f(s.max_adjacent(), width),
-            mean = fmt::f(s.mean(), width),
-            std = fmt::f(s.standard_deviation(), width),
-        );
+        fmt::f(x, width)
     }
 }
 
-fn print_t_test(t_test: &TTest, s1: &Summary, s2: &Summary) {
-    let width = 12;
+/// Notes on the summaries with approximate values, marked `~` in tables, of
+/// how each was estimated. Each is labeled by its input if there are several.
+fn approximation_notes(summaries: &[&Summary]) -> Vec<String> {
+    summaries
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.is_approximate())
+        .map(|(i, s)| match summaries.len() {
+            1 => format!("~ approximate: {}", s.method()),
+            _ => format!("~ {} approximate: {}", input_label(i + 1), s.method()),
+        })
+        .collect()
+}
 
-    let m1 = s1.mean();
-    let m2 = s2.mean();
-    let se1 = s1.standard_error();
-    let se2 = s1.standard_error();
+/// Add a table of summaries to `layout`, arranged as requested, followed by
+/// notes on any approximate values. A table with a column per input, by
+/// `--vertical`, is preceded by the source of each, labeled as its column.
+fn add_summary_table(
+    layout: &mut Layout,
+    table: Table,
+    summaries: &[&Summary],
+    sources: &[&str],
+    report: &ReportOptions,
+) {
+    if report.vertical && summaries.len() > 1 {
+        layout.text(input_legend(sources), LABEL_WIDTH + 3);
+    }
 
-    let del = m2 - m1;
-    let se_del = (se1.powi(2) + se1.powi(2)).sqrt();
+    layout.table(report.arrange(table, summaries.len()));
+
+    let notes = approximation_notes(summaries);
+
+    if !notes.is_empty() {
+        layout.text(notes, 2);
+    }
+}
+
+/// One table of the summaries of all inputs, one row each, so that their
+/// columns align. Approximate values are marked with a leading `~`.
+fn summary_table(summaries: &[&Summary], outliers: bool) -> Table {
+    let width = SUMMARY_WIDTH;
+
+    let (min_label, max_label) = if outliers { ("Min", "Max") } else { ("Min Adj", "Max Adj") };
 
-    println!("{l:>w$} = {v} ± {se}", w = width, l = "m₁ ± SE", v = m1, se = se1);
-    println!("{l:>w$} = {v} ± {se}", w = width, l = "m₂ ± SE", v = m2, se = se2);
-    println!("{l:>w$} = {v} ± {se}", w = width, l = "m₂ - m₁ ± SE", v = del, se = se_del);
-    println!("{l:>w$} = {v}", w = width, l = "p", v = t_test.p);
-    println!("{l:>w$} = {v}", w = width, l = "t", v = t_test.t);
-    println!("{l:>w$} = {v}", w = width, l = "DF", v = t_test.df);
+    let mut columns = vec![
+        ("Size", SUMMARY_SIZE_WIDTH),
+        (min_label, width),
+        ("Q1", width),
+        ("Median", width),
+        ("Q3", width),
+        (max_label, width),
+        ("Mean", width),
+        ("Std Dev", width),
+    ];
+
+    // Only add a column of units if there are any.
+    let units = summaries.iter().any(|s| s.unit().is_some());
+
+    if units {
+        columns.push(("Unit", 0));
+    }
+
+    let mut table = Table::new(&columns);
+
+    for s in summaries {
+        let (min, max, extremes) = if outliers {
+            (s.min(), s.max(), s.moments_are_approximate())
+        } else {
+            (s.min_adjacent(), s.max_adjacent(), s.quantiles_are_approximate())
+        };
+
+        let quantiles = s.quantiles_are_approximate();
+        let moments = s.moments_are_approximate();
+        let cell = |x: f64, approximate: bool| approximate_cell(x, width, approximate);
+
+        let mut row = vec![
+            fmt::f(s.size(), width),
+            cell(min, extremes),
+            cell(s.lower_quartile(), quantiles),
+            cell(s.median(), quantiles),
+            cell(s.upper_quartile(), quantiles),
+            cell(max, extremes),
+            cell(s.mean(), moments),
+            cell(s.standard_deviation(), moments),
+        ];
+
+        if units {
+            row.push(s.unit().unwrap_or("").to_string());
+        }
+
+        table.row(&row);
+    }
+
+    table
 }
 
-fn summarize_file(path: &str, lax_parsing: bool) -> Result<Summary, Box<error::Error>> {
-    let f = File::open(path).or_else(|e| {
-        log::error(&format!("Could not open file: {:?}", path));
-        Err(e)
-    })?;
-    let reader = BufReader::new(f);
+/// A table of the structural counts of each input, under `--verbose`.
+fn value_counts_table(counts: &[ValueCounts], sources: &[&str]) -> Table {
+    let width = SUMMARY_SIZE_WIDTH;
+
+    let mut table = Table::new(&[
+        ("Input", 0),
+        ("Zeros", width),
+        ("Negatives", width),
+        ("Duplicates", width),
+        ("Distinct", width),
+    ]);
 
-    let data = read_data(reader, lax_parsing)?;
+    for (c, src) in counts.iter().zip(sources) {
+        table.row(&[
+            src.to_string(),
+            c.zeros.to_string(),
+            c.negatives.to_string(),
+            c.duplicates.to_string(),
+            c.distinct.to_string(),
+        ]);
+    }
 
-    Ok(Summary::new(&data)?)
+    table
 }
 
-fn read_data<R>(reader: R, lax_parsing: bool) -> Result<Vec<f64>, Box<error::Error>>
-    where R: BufRead {
-    let mut data: Vec<f64> = vec![];
+/// A table of the `k` smallest and largest values of `parsed`, by rank from
+/// the most extreme, with the line of each.
+fn extremes_table(parsed: &input::Parsed, k: usize) -> Table {
+    let width = SUMMARY_WIDTH;
+    let line_width = SUMMARY_SIZE_WIDTH;
 
-    for l in reader.lines() {
-        let s = l?.trim().to_string();
+    let mut table = Table::new(&[
+        ("Rank", line_width),
+        ("Smallest", width),
+        ("Line", line_width),
+        ("Largest", width),
+        ("Line", line_width),
+    ]);
 
-        if s.is_empty() {
-            continue;
+    let (smallest, largest) = parsed.extremes(k);
+
+    for (i, (&(min, min_line), &(max, max_line))) in smallest.iter().zip(&largest).enumerate() {
+        table.row(&[
+            (i + 1).to_string(),
+            fmt::f(min, width),
+            min_line.to_string(),
+            fmt::f(max, width),
+            max_line.to_string(),
+        ]);
+    }
+
+    table
+}
+
+/// Print the percentile at which each of `values` falls in each input, and
+/// its rank.
+fn print_ranks(summarizers: &[Summarizer], sources: &[&str], values: &[f64]) {
+    let width = SUMMARY_WIDTH;
+
+    let mut table = Table::new(&[
+        ("Input", 0),
+        ("Value", width),
+        ("Percentile", width),
+        ("Rank", width),
+        ("Size", SUMMARY_SIZE_WIDTH),
+    ]);
+
+    for (s, src) in summarizers.iter().zip(sources) {
+        for &x in values {
+            table.row(&[
+                src.to_string(),
+                fmt::f(x, width),
+                fmt::f(100.0 * ok!(s.cdf_at(x)), width),
+                fmt::f(1.0 + ok!(s.rank_of(x)), width),
+                s.size().to_string(),
+            ]);
         }
+    }
 
-        match s.parse() {
-            Ok(d) => data.push(d),
-            err => if !lax_parsing { err?; }
+    let mut layout = Layout::new(layout_width());
+    layout.table(table);
+
+    outln!("{}", layout.render());
+}
+
+/// Histograms of the inputs over one set of bins, with edges from all the
+/// inputs pooled.
+fn shared_histograms(
+    samples: &[Summarizer],
+    sources: &[&str],
+    binning: Binning,
+) -> Vec<Histogram> {
+    if let Binning::Log(_) = binning {
+        for (s, src) in samples.iter().zip(sources) {
+            if s.as_slice().iter().any(|&x| x <= 0.0) {
+                log::error(&format!("Log-spaced bins require positive values: {}", src));
+                output::exit(1);
+            }
         }
     }
 
-    Ok(data)
+    let samples: Vec<&[f64]> = samples.iter().map(Summarizer::as_slice).collect();
+
+    ok!(Histogram::shared(&samples, binning))
 }
 
-fn summarize_stdin(lax_parsing: bool) -> Result<Summary, Box<error::Error>> {
-    let stdin = io::stdin();
-    let data = read_data(stdin.lock(), lax_parsing)?;
+/// Label a bin by its edges, in `unit`, if given. Only the last bin holds its
+/// upper edge.
+fn bin_label(lower: f64, upper: f64, last: bool, unit: Option<&str>) -> String {
+    let edge = |x: f64| fmt::value(x, Some(3));
+    let unit = unit.map(|u| format!(" {}", u)).unwrap_or_default();
 
-    Ok(Summary::new(&data)?)
+    format!("[{}, {}{}{}", edge(lower), edge(upper), if last { "]" } else { ")" }, unit)
 }
 
-fn display_t_test(
-    summary1: &Summary,
-    summary2: &Summary,
-    draw_plot: bool,
-    width: usize,
+/// Print the histogram of each input, with bins labeled by their edges. The
+/// histograms share their bins, and, for several inputs, bars scaled by the
+/// proportion of each input in a bin, so that they can be compared. With
+/// `overlay`, the histograms of two inputs are instead drawn on one plot, as
+/// proportions of each. Values are labeled in `unit`, if given.
+fn print_histograms(
+    histograms: &[Histogram],
+    sources: &[&str],
+    overlay: bool,
+    width: PlotWidth,
     ascii: bool,
-    outliers: bool,
+    unit: Option<&str>,
 ) {
-    let t_test = ok!(welch_t_test(&summary1, &summary2));
+    if overlay && histograms.len() != 2 {
+        log::error("Overlaid histograms require exactly two inputs");
+        output::exit(1);
+    }
+
+    if overlay {
+        let proportions: Vec<Vec<f64>> = histograms
+            .iter()
+            .map(|h| h.frequencies().iter().map(|f| f.proportion).collect())
+            .collect();
+
+        let edges = &histograms[0].edges;
+        let range = (edges[0], edges[edges.len() - 1]);
+        let height = ok!(PlotHeight::new(SCATTER_HEIGHT));
+        let (one, two, both) = plot::overlay_glyphs(ascii);
+
+        let mut layout = Layout::new(layout_width());
+        layout.plot(ok!(plot::overlay_histogram(
+            &proportions[0],
+            &proportions[1],
+            range,
+            width,
+            height,
+            ascii,
+            unit,
+        )));
+        layout.text(
+            vec![
+                format!("{} {}", one, sources[0]),
+                format!("{} {}", two, sources[1]),
+                format!("{} both", both),
+            ],
+            RESULT_INDENT,
+        );
 
-    if draw_plot {
-        let p = ok!(plot::comparison_plot(&[summary1, summary2], width, ascii, true, outliers));
-        println!("{}\n", p);
+        outln!("{}", layout.render());
+        return;
     }
 
-    print_summary(&summary1, outliers);
-    println!();
-    print_summary(&summary2, outliers);
-    println!();
-    print_t_test(&t_test, &summary1, &summary2);
-}
+    let last = histograms[0].counts.len() - 1;
+    let labels: Vec<String> = histograms[0]
+        .bins()
+        .enumerate()
+        .map(|(j, (lo, hi))| bin_label(lo, hi, j == last, unit))
+        .collect();
 
-fn display_summaries(
-    summaries: &[Summary],
-    draw_plot: bool,
-    width: usize,
-    ascii: bool,
-    outliers: bool,
-) {
-    if draw_plot {
-        let summary_refs: Vec<&Summary> = summaries
+    // Inputs may differ in size, so bars are scaled by the proportion of each
+    // input in their bins, the largest spanning the plot.
+    let totals: Vec<u64> = histograms.iter().map(|h| h.counts.iter().sum()).collect();
+    let max = histograms
+        .iter()
+        .zip(&totals)
+        .flat_map(|(h, &t)| h.counts.iter().map(move |&c| c as f64 / t as f64))
+        .fold(0.0, f64::max);
+
+    for (i, ((histogram, src), &total)) in histograms.iter().zip(sources).zip(&totals).enumerate() {
+        if i > 0 {
+            outln!();
+        }
+
+        if histograms.len() > 1 {
+            outln!("{}", src);
+        }
+
+        let bars: Vec<(&str, u64)> = labels
             .iter()
+            .zip(&histogram.counts)
+            .map(|(l, &c)| (l.as_str(), c))
             .collect();
 
-        let plot = ok!(plot::comparison_plot(&summary_refs, width, ascii, true, outliers));
-        println!("{}\n", plot);
+        let chart = if histograms.len() > 1 {
+            plot::proportion_chart(&bars, total, max, width, ascii)
+        } else {
+            plot::bar_chart(&bars, width, ascii)
+        };
+
+        let mut layout = Layout::new(layout_width());
+        layout.plot(ok!(chart));
+
+        outln!("{}", layout.render());
     }
+}
+
+/// Plot the histogram of each input with vertical bars, labeled by source if
+/// there are several, and with values in `unit`, if given.
+fn print_vertical_histograms(
+    histograms: &[Histogram],
+    sources: &[&str],
+    width: PlotWidth,
+    ascii: bool,
+    unit: Option<&str>,
+) {
+    let height = ok!(PlotHeight::new(HISTOGRAM_HEIGHT));
 
-    for i in 0..summaries.len() {
+    for (i, (h, src)) in histograms.iter().zip(sources).enumerate() {
         if i > 0 {
-            println!();
+            outln!();
+        }
+
+        if histograms.len() > 1 {
+            outln!("{}", src);
         }
-        print_summary(&summaries[i], outliers);
+
+        let mut layout = Layout::new(layout_width());
+        layout.plot(ok!(plot::histogram::render(h, width, height, ascii, unit)));
+
+        outln!("{}", layout.render());
     }
 }
 
-fn display_summaries_tsv(summaries: &[Summary], sources: &[&str]) {
-    let parts = vec![
-        "Source",
-        "Size",
-        "Mean",
-        "Median",
-        "StandardDeviation",
-        "Variance",
-        "StandardError",
-        "Min",
-        "Max",
-        "Range",
-        "LowerQuartile",
-        "UpperQuartile",
-        "IQR",
-        "MinAdjacent",
-        "MaxAdjacent",
-    ];
-    let header = parts.join("\t");
-    println!("{}", header);
-
-    for (summ, src) in summaries.iter().zip(sources) {
-        print_summary_tsv(summ, src);
-    }
-}
-
-fn print_summary_tsv(summary: &Summary, source: &str) {
-    let values = vec![
-        summary.size(),
-        summary.mean(),
-        summary.median(),
-        summary.standard_deviation(),
-        summary.unbiased_variance(),
-        summary.standard_error(),
-        summary.min(),
-        summary.max(),
-        summary.range(),
-        summary.lower_quartile(),
-        summary.upper_quartile(),
-        summary.iqr(),
-        summary.min_adjacent(),
-        summary.max_adjacent(),
+/// Print the frequency table of each histogram: the count of each bin, its
+/// proportion, and the cumulative percentage through it.
+fn print_frequency_tables(histograms: &[Histogram], sources: &[&str]) {
+    let width = SUMMARY_WIDTH;
+
+    let mut table = Table::new(&[
+        ("Input", 0),
+        ("Bin", 0),
+        ("Count", SUMMARY_SIZE_WIDTH),
+        ("Proportion", width),
+        ("Cumulative %", width),
+    ]);
+
+    for (h, src) in histograms.iter().zip(sources) {
+        let frequencies = h.frequencies();
+        let last = frequencies.len() - 1;
+
+        for (j, f) in frequencies.iter().enumerate() {
+            table.row(&[
+                src.to_string(),
+                bin_label(f.lower, f.upper, j == last, None),
+                f.count.to_string(),
+                fmt::f(f.proportion, width),
+                fmt::f(f.cumulative_percent, width),
+            ]);
+        }
+    }
+
+    let mut layout = Layout::new(layout_width());
+    layout.table(table);
+
+    outln!("{}", layout.render());
+}
+
+/// The values of each input, with any that are not finite dropped or
+/// rejected by `policy`.
+fn finite_samples(parsed: &[input::Parsed], policy: NonFinitePolicy) -> Vec<Summarizer> {
+    parsed
+        .iter()
+        .map(|p| ok!(Summarizer::new_with_policy(&p.data, policy)))
+        .collect()
+}
+
+/// Print the count and proportion of the values of each input above and at
+/// or below each of `thresholds`, with the mean of those on each side.
+fn print_exceedances(
+    samples: &[Summarizer],
+    sources: &[&str],
+    thresholds: &[f64],
+    confidence: f64,
+) {
+    let width = SUMMARY_WIDTH;
+    let ci = format!("{}% CI", 100.0 * confidence);
+
+    let mut table = Table::new(&[
+        ("Input", 0),
+        ("Values", width),
+        ("Count", SUMMARY_SIZE_WIDTH),
+        ("Proportion", width),
+        (&ci, 0),
+        ("Mean", width),
+    ]);
+
+    for (s, src) in samples.iter().zip(sources) {
+        for &t in thresholds {
+            let e = ok!(Exceedance::new(s.as_slice(), t, confidence));
+
+            for (op, side) in [(">", &e.above), ("≤", &e.below)] {
+                let (lo, hi) = side.interval;
+
+                table.row(&[
+                    src.to_string(),
+                    format!("{} {}", op, fmt::value(t, None)),
+                    side.count.to_string(),
+                    fmt::f(side.proportion, width),
+                    format!("[{}, {}]", fmt::f(lo, width), fmt::f(hi, width)),
+                    side.mean.map_or("-".to_string(), |m| fmt::f(m, width)),
+                ]);
+            }
+        }
+    }
+
+    let mut layout = Layout::new(layout_width());
+    layout.table(table);
+
+    outln!("{}", layout.render());
+}
+
+/// Print the change in the proportion of values above each of `thresholds`
+/// from the first input to the second, with its interval and z-test.
+fn print_exceedance_changes(
+    samples: &[Summarizer],
+    sources: &[&str],
+    thresholds: &[f64],
+    confidence: f64,
+    report: &ReportOptions,
+) {
+    if samples.len() != 2 {
+        log::error("Comparing exceedances requires exactly two inputs");
+        output::exit(1);
+    }
+
+    let show = |x: f64| fmt::value(x, report.precision);
+    let line = |l: &str, v: f64| format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = l, v = show(v));
+
+    let mut layout = Layout::new(layout_width());
+
+    for &t in thresholds {
+        let first = ok!(Exceedance::new(samples[0].as_slice(), t, confidence));
+        let second = ok!(Exceedance::new(samples[1].as_slice(), t, confidence));
+        let change = ok!(ExceedanceChange::new(&first, &second));
+
+        let mut lines = vec![
+            format!("Proportion > {}: {} - {}", fmt::value(t, None), sources[1], sources[0]),
+            format!(
+                "{l:>w$} = {v}, {c}% CI [{lo}, {hi}]",
+                w = LABEL_WIDTH,
+                l = "Δ",
+                v = show(change.difference),
+                c = 100.0 * confidence,
+                lo = show(change.interval.0),
+                hi = show(change.interval.1),
+            ),
+            line("z", change.z),
+            line("p", change.p),
+        ];
+
+        if let Some(level) = report.alpha {
+            lines.extend(p_verdict_lines(change.p, level));
+        }
+
+        layout.text(lines, RESULT_INDENT);
+    }
+
+    outln!("{}", layout.render());
+}
+
+/// Like `summary_table`, but with the median absolute deviation of each
+/// summary, given with it, in place of the mean and standard deviation.
+fn robust_summary_table(summaries: &[(&Summary, f64)], outliers: bool) -> Table {
+    let width = SUMMARY_WIDTH;
+
+    let (min_label, max_label) = if outliers { ("Min", "Max") } else { ("Min Adj", "Max Adj") };
+
+    let mut columns = vec![
+        ("Size", SUMMARY_SIZE_WIDTH),
+        (min_label, width),
+        ("Q1", width),
+        ("Median", width),
+        ("Q3", width),
+        (max_label, width),
+        ("MAD", width),
     ];
-    let fields: Vec<String> = values.iter().map(|x| format!("{}", x)).collect();
-    println!("{}\t{}", source, fields.join("\t"));
+
+    let units = summaries.iter().any(|(s, _)| s.unit().is_some());
+
+    if units {
+        columns.push(("Unit", 0));
+    }
+
+    let mut table = Table::new(&columns);
+
+    for &(s, mad) in summaries {
+        let (min, max, extremes) = if outliers {
+            (s.min(), s.max(), s.moments_are_approximate())
+        } else {
+            (s.min_adjacent(), s.max_adjacent(), s.quantiles_are_approximate())
+        };
+
+        let quantiles = s.quantiles_are_approximate();
+        let cell = |x: f64, approximate: bool| approximate_cell(x, width, approximate);
+
+        let mut row = vec![
+            fmt::f(s.size(), width),
+            cell(min, extremes),
+            cell(s.lower_quartile(), quantiles),
+            cell(s.median(), quantiles),
+            cell(s.upper_quartile(), quantiles),
+            cell(max, extremes),
+            cell(mad, quantiles),
+        ];
+
+        if units {
+            row.push(s.unit().unwrap_or("").to_string());
+        }
+
+        table.row(&row);
+    }
+
+    table
 }
 
-fn main() {
-    let matches = App::new("dent")
-        .version(crate_version!())
-        .author("Joe Ranweiler <joe@lemma.co>")
-        .about("A tiny tool for t-tests &c.")
-        .arg(Arg::with_name("stdin")
-             .short("s")
-             .long("stdin")
-             .help("Read and summarize data from stdin"))
-        .arg(Arg::with_name("files")
-             .multiple(true)
-             .value_name("FILES")
-             .takes_value(true)
-             .required_unless("stdin")
-             .help("Path to one or more files of sample data"))
-        .arg(Arg::with_name("lax")
-             .long("lax")
-             .help("Ignore non-numeric input lines"))
-        .arg(Arg::with_name("tsv")
-             .long("tsv")
-             .help("Print summary data to stdout in TSV format"))
-        .arg(Arg::with_name("plot_outliers")
-             .long("outliers")
-             .help("Include outliers and use min/max for outer fences of boxplot"))
-        .arg(Arg::with_name("plot")
-             .short("p")
-             .long("plot")
-             .help("Print standard boxplots"))
-        .arg(Arg::with_name("ascii")
-             .long("ascii")
-             .help("Use only ASCII characters in boxplots"))
-        .arg(Arg::with_name("width")
-             .short("w")
-             .long("width")
-             .value_name("WIDTH")
-             .takes_value(true)
-             .help("Width of boxplot"))
-        .get_matches();
+/// Width of the labels of `label = value` result lines.
+const LABEL_WIDTH: usize = 12;
 
-    let ascii = matches.is_present("ascii");
-    let lax_parsing = matches.is_present("lax");
-    let draw_plot = matches.is_present("plot");
-    let use_stdin = matches.is_present("stdin");
-    let outliers = matches.is_present("plot_outliers");
-    let tsv = matches.is_present("tsv");
+/// Indent of the wrapped continuation of a result line, past its label.
+const RESULT_INDENT: usize = LABEL_WIDTH + 3;
 
-    let width = matches
-        .value_of("width")
-        .and_then(|w| w.parse::<usize>().ok())
-        .or(term_size::dimensions().map(|(w, _)| w))
-        .unwrap_or(80);
+fn mann_whitney_lines(
+    test: &MannWhitney,
+    x: &Summarizer,
+    y: &Summarizer,
+    precision: Option<usize>,
+) -> Vec<String> {
+    let width = LABEL_WIDTH;
+    let show = |x: f64| fmt::value(x, precision);
 
-    let (sources, summaries) = if use_stdin {
-        (vec!["stdin"], vec![ok!(summarize_stdin(lax_parsing))])
-    } else {
-        // Required if `stdin` is not present, so we can unwrap.
-        let files = matches
-            .values_of("files")
-            .unwrap_or_else(|| unreachable!());
+    let med1 = x.median();
+    let med2 = y.median();
+    let mad1 = x.median_absolute_deviation();
+    let mad2 = y.median_absolute_deviation();
 
-        let summaries = files.clone().map(|f| ok!(summarize_file(f, lax_parsing))).collect();
-        (files.collect(), summaries)
-    };
+    vec![
+        format!("{l:>w$} = {v} ± {mad}", w = width, l = "med₁ ± MAD", v = show(med1), mad = show(mad1)),
+        format!("{l:>w$} = {v} ± {mad}", w = width, l = "med₂ ± MAD", v = show(med2), mad = show(mad2)),
+        format!("{l:>w$} = {v}", w = width, l = "HL shift", v = show(robust::hodges_lehmann_shift(x, y))),
+        format!("{l:>w$} = {v}", w = width, l = "p", v = show(test.p)),
+        format!("{l:>w$} = {v}", w = width, l = "U", v = show(test.u)),
+        format!("{l:>w$} = {v}", w = width, l = "z", v = show(test.z)),
+    ]
+}
+
+fn t_test_lines(t_test: &TTest, s1: &Summary, s2: &Summary, precision: Option<usize>) -> Vec<String> {
+    let width = LABEL_WIDTH;
+    let show = |x: f64| fmt::value(x, precision);
+
+    let m1 = s1.mean();
+    let m2 = s2.mean();
+    let se1 = s1.standard_error();
+    let se2 = s1.standard_error();
+
+    let del = m2 - m1;
+    let se_del = (se1.powi(2) + se1.powi(2)).sqrt();
+
+    vec![
+        format!("{l:>w$} = {v} ± {se}", w = width, l = "m₁ ± SE", v = show(m1), se = show(se1)),
+        format!("{l:>w$} = {v} ± {se}", w = width, l = "m₂ ± SE", v = show(m2), se = show(se2)),
+        format!("{l:>w$} = {v} ± {se}", w = width, l = "m₂ - m₁ ± SE", v = show(del), se = show(se_del)),
+        format!("{l:>w$} = {v}", w = width, l = "p", v = show(t_test.p)),
+        format!("{l:>w$} = {v}", w = width, l = "t", v = show(t_test.t)),
+        format!("{l:>w$} = {v}", w = width, l = "DF", v = show(t_test.df)),
+    ]
+}
+
+fn verdict_lines(t_test: &TTest, level: SigLevel, precision: Option<usize>) -> Vec<String> {
+    let width = LABEL_WIDTH;
 
-    if tsv {
-        return display_summaries_tsv(&summaries, &sources);
+    let verdict = t_test.verdict(level);
+    let critical = ok!(t_test.critical_value(level));
+
+    vec![
+        format!("{l:>w$} = {v}", w = width, l = "α", v = level.alpha()),
+        format!("{l:>w$} = {v}", w = width, l = "|t| critical", v = fmt::value(critical, precision)),
+        format!("{l:>w$} = {v}", w = width, l = "Verdict", v = verdict),
+    ]
+}
+
+/// Like `verdict_lines`, for a test without a critical value.
+fn p_verdict_lines(p: f64, level: SigLevel) -> Vec<String> {
+    vec![
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "α", v = level.alpha()),
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "Verdict", v = level.verdict(p)),
+    ]
+}
+
+/// A mark of the verdict on a test with p-value `p` at `level`: ✓ if it
+/// rejects the null hypothesis, ✗ if not, and `-` if `p` is undefined.
+fn verdict_mark(p: f64, level: SigLevel) -> &'static str {
+    match level.verdict(p) {
+        _ if p.is_nan() => "-",
+        Verdict::Reject => "✓",
+        Verdict::FailToReject => "✗",
     }
+}
 
-    match summaries.len() {
-        0 => unreachable!(),
-        // We want match 1 with the case `len()` > 2.
-        2 => {
-            display_t_test(
-                &summaries[0],
-                &summaries[1],
-                draw_plot,
-                width,
-                ascii,
-                outliers,
-            );
+/// Lines naming the source of each input by its label, `xᵢ`.
+fn input_legend(sources: &[&str]) -> Vec<String> {
+    sources
+        .iter()
+        .enumerate()
+        .map(|(i, src)| format!("{:>w$} = {}", input_label(i + 1), src, w = LABEL_WIDTH))
+        .collect()
+}
+
+/// Aggregate statistics of a sample given by `--stats`.
+struct Stats {
+    n: f64,
+    mean: f64,
+    sd: f64,
+}
+
+impl Stats {
+    /// Parse a comma-separated triple `n,mean,sd`.
+    fn parse(s: &str) -> Result<Self, String> {
+        let fields: Vec<f64> = s
+            .split(',')
+            .map(|f| f.trim().parse::<f64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("Expected n,mean,sd but got {:?}", s))?;
+
+        match fields[..] {
+            [n, mean, sd] => Ok(Stats { n, mean, sd }),
+            _ => Err(format!("Expected n,mean,sd but got {:?}", s)),
         }
-        _ => {
-            display_summaries(
-                &summaries,
-                draw_plot,
-                width,
-                ascii,
-                outliers,
-            );
-        },
+    }
+
+    fn standard_error(&self) -> f64 {
+        self.sd / self.n.sqrt()
+    }
+}
+
+fn display_stats_t_test(s1: &Stats, s2: &Stats, report: &ReportOptions) {
+    let t_test = ok!(welch_t_test_from_stats(s1.n, s1.mean, s1.sd, s2.n, s2.mean, s2.sd));
+
+    let width = LABEL_WIDTH;
+    let show = |x: f64| fmt::value(x, report.precision);
+
+    let se1 = s1.standard_error();
+    let se2 = s2.standard_error();
+
+    let del = s2.mean - s1.mean;
+    let se_del = (se1.powi(2) + se2.powi(2)).sqrt();
+
+    let mut lines = vec![
+        format!("{l:>w$} = {v} ± {se}", w = width, l = "m₁ ± SE", v = show(s1.mean), se = show(se1)),
+        format!("{l:>w$} = {v} ± {se}", w = width, l = "m₂ ± SE", v = show(s2.mean), se = show(se2)),
+        format!("{l:>w$} = {v} ± {se}", w = width, l = "m₂ - m₁ ± SE", v = show(del), se = show(se_del)),
+        format!("{l:>w$} = {v}", w = width, l = "p", v = show(t_test.p)),
+        format!("{l:>w$} = {v}", w = width, l = "t", v = show(t_test.t)),
+        format!("{l:>w$} = {v}", w = width, l = "DF", v = show(t_test.df)),
+    ];
+
+    if let Some(level) = report.alpha {
+        lines.extend(verdict_lines(&t_test, level, report.precision));
+    }
+
+    let mut layout = Layout::new(layout_width());
+    layout.text(lines, RESULT_INDENT);
+
+    outln!("{}", layout.render());
+}
+
+/// Quantiles compared by `--quantiles`, with their labels.
+const COMPARISON_QUANTILES: [(f64, &str); 6] = [
+    (0.10, "p10"),
+    (0.25, "p25"),
+    (0.50, "p50"),
+    (0.75, "p75"),
+    (0.90, "p90"),
+    (0.99, "p99"),
+];
+
+fn print_quantile_comparison(q1: &[f64], q2: &[f64]) {
+    let width = 10;
+    let label_width = 8;
+
+    outln!(
+        "{l:>lw$}  {x1:>w$}  {x2:>w$}  {del:>w$}  {pct:>w$}",
+        w = width,
+        lw = label_width,
+        l = "Quantile",
+        x1 = "x₁",
+        x2 = "x₂",
+        del = "x₂ - x₁",
+        pct = "Change",
+    );
+
+    for ((&(_, label), &x1), &x2) in COMPARISON_QUANTILES.iter().zip(q1).zip(q2) {
+        let del = x2 - x1;
+
+        // Relative change is undefined against a zero baseline.
+        let pct = if x1 == 0.0 {
+            "-".to_string()
+        } else {
+            let pct = 100.0 * del / x1.abs();
+            let s = format!("{:+.1}%", pct);
+
+            if s.len() <= width { s } else { format!("{}%", fmt::f(pct, width - 1)) }
+        };
+
+        outln!(
+            "{l:>lw$}  {x1:>w$}  {x2:>w$}  {del:>w$}  {pct:>w$}",
+            w = width,
+            lw = label_width,
+            l = label,
+            x1 = fmt::f(x1, width),
+            x2 = fmt::f(x2, width),
+            del = fmt::f(del, width),
+            pct = pct,
+        );
+    }
+}
+
+fn print_shift_function(f: &ShiftFunction, unit: Option<&str>) {
+    let width = 10;
+    let label_width = 8;
+    let ci = format!("{}% CI", 100.0 * f.confidence);
+
+    let del = match unit {
+        Some(unit) => format!("x₂ - x₁ ({})", unit),
+        None => "x₂ - x₁".to_string(),
     };
+    let del_width = width.max(del.chars().count());
+
+    outln!(
+        "{l:>lw$}  {del:>dw$}  {ci:>cw$}",
+        dw = del_width,
+        lw = label_width,
+        cw = 2 * width + 2,
+        l = "Quantile",
+        del = del,
+        ci = ci,
+    );
+
+    for p in &f.points {
+        outln!(
+            "{l:>lw$}  {del:>dw$}  {lo:>w$}  {hi:>w$}",
+            w = width,
+            dw = del_width,
+            lw = label_width,
+            l = format!("p{}", (100.0 * p.quantile).round()),
+            del = fmt::f(p.difference, width),
+            lo = fmt::f(p.interval.value.0, width),
+            hi = fmt::f(p.interval.value.1, width),
+        );
+    }
+
+    // Every interval is resampled alike, so note the largest error bound.
+    let widest = f.points
+        .iter()
+        .map(|p| p.interval)
+        .max_by(|a, b| a.error_bound.unwrap_or(0.0).total_cmp(&b.error_bound.unwrap_or(0.0)));
+
+    if let Some(note) = widest.as_ref().and_then(approximation_note) {
+        outln!("({})", note);
+    }
+}
+
+/// Options controlling how boxplots are drawn.
+struct PlotOptions<'a> {
+    width: PlotWidth,
+    style: &'a BoxplotChars,
+    subcell: bool,
+    outliers: bool,
+    mean_ci: Option<f64>,
+}
+
+/// Options controlling how summaries and test results are reported.
+struct ReportOptions {
+    outliers: bool,
+    alpha: Option<SigLevel>,
+
+    /// Significant digits of test results, or all if `None`.
+    precision: Option<usize>,
+
+    /// Unit of the sample values, given by `--unit`.
+    unit: Option<String>,
+
+    /// The input to compare each other against, by `--reference`: its path,
+    /// or its 1-based index.
+    reference: Option<String>,
+
+    /// Whether to print summary tables with a row per statistic, by
+    /// `--vertical`.
+    vertical: bool,
+}
+
+impl ReportOptions {
+    /// Annotate `s` with the `--unit`, if given, replacing any it has.
+    fn annotate(&self, s: Summary) -> Summary {
+        match self.unit {
+            Some(ref unit) => s.with_unit(unit),
+            None => s,
+        }
+    }
+
+    /// Arrange a table of summaries, with a row per input, as requested: as
+    /// is, or transposed, with a column per input headed by its label.
+    fn arrange(&self, table: Table, inputs: usize) -> Table {
+        if !self.vertical {
+            return table;
+        }
+
+        let labels: Vec<String> = match inputs {
+            1 => vec![],
+            n => (1..=n).map(input_label).collect(),
+        };
+
+        table.transpose(&labels)
+    }
+}
+
+fn draw_plot(summaries: &[&Summary], opts: &PlotOptions) -> RenderedPlot {
+    if opts.subcell {
+        ok!(plot::comparison_plot_subcell(summaries, opts.width, true, opts.outliers, opts.mean_ci))
+    } else {
+        ok!(plot::comparison_plot_with_style(
+            summaries,
+            opts.width,
+            opts.style,
+            true,
+            opts.outliers,
+            opts.mean_ci,
+        ))
+    }
+}
+
+fn display_t_test(
+    summary1: &Summary,
+    summary2: &Summary,
+    sources: &[&str],
+    plot_opts: Option<&PlotOptions>,
+    report: &ReportOptions,
+    ratio: Option<&RatioOfMeans>,
+) {
+    let problems = diagnostics::check_t_test(summary1, summary2);
+    for d in &problems {
+        log::warning(&format!("{}", d));
+    }
+    let t_test = if problems.iter().any(|d| d.blocks_t_test()) {
+        None
+    } else {
+        Some(ok!(welch_t_test(summary1, summary2)))
+    };
+
+    let mut layout = Layout::new(layout_width());
+
+    if let Some(opts) = plot_opts {
+        layout.plot(draw_plot(&[summary1, summary2], opts));
+    }
+
+    let summaries = [summary1, summary2];
+    let table = summary_table(&summaries, report.outliers);
+    add_summary_table(&mut layout, table, &summaries, sources, report);
+
+    if let Some(t_test) = t_test {
+        let mut lines = t_test_lines(&t_test, summary1, summary2, report.precision);
+
+        if let Some(level) = report.alpha {
+            lines.extend(verdict_lines(&t_test, level, report.precision));
+        }
+
+        layout.text(lines, RESULT_INDENT);
+    }
+
+    if let Some(r) = ratio {
+        layout.text(vec![speedup_line(r)], RESULT_INDENT);
+    }
+
+    outln!("{}", layout.render());
+}
+
+fn speedup_line(r: &RatioOfMeans) -> String {
+    format!(
+        "{l:>w$} = {v}× [{lo}, {hi}] ({c}% CI, {m})",
+        w = LABEL_WIDTH,
+        l = "speedup",
+        v = fmt::f(r.ratio, 6),
+        lo = fmt::f(r.interval.value.0, 6),
+        hi = fmt::f(r.interval.value.1, 6),
+        c = 100.0 * r.confidence,
+        m = approximation_note(&r.interval).unwrap_or_else(|| r.method.name().to_string()),
+    )
+}
+
+/// Warn that the summary of `src` was estimated from a random subsample of
+/// `size` of `population` values, with the error bound of its mean, if known.
+fn report_subsample(src: &str, size: usize, population: usize, error_bound: Option<f64>) {
+    let mut message = format!(
+        "{}: estimated from a random {}",
+        src,
+        Method::Subsample { size, population },
+    );
+
+    if let Some(b) = error_bound {
+        message.push_str(&format!(
+            ", so the mean is within ± {} of that of all values ({}% confidence)",
+            fmt::f(b, 6),
+            100.0 * estimate::ERROR_BOUND_CONFIDENCE,
+        ));
+    }
+
+    log::warning(&message);
+}
+
+/// How an approximate `Estimate` was computed, with its error bound, such as
+/// `approximate: bootstrap of 1000 resamples, ± 0.0125`, or `None` if it is
+/// exact.
+fn approximation_note<T>(e: &Estimate<T>) -> Option<String> {
+    if e.is_exact() {
+        return None;
+    }
+
+    let note = match e.error_bound {
+        Some(b) => format!("approximate: {}, ± {}", e.method, fmt::f(b, 6)),
+        None => format!("approximate: {}", e.method),
+    };
+
+    Some(note)
+}
+
+/// Exit with an error unless there are exactly two inputs to compare.
+fn check_speedup_inputs(count: usize) {
+    if count != 2 {
+        log::error("Speedup requires exactly two inputs");
+        output::exit(1);
+    }
+}
+
+/// Exit with an error unless there is a reference input and another.
+fn check_reference_inputs(count: usize) {
+    if count < 2 {
+        log::error("Comparison against a reference requires at least two inputs");
+        output::exit(1);
+    }
+}
+
+/// The 0-based index among `sources` of the `--reference` input, given by its
+/// path, or else by its 1-based index. Exits with an error if there is none.
+fn reference_index(reference: &str, sources: &[&str]) -> usize {
+    let by_path = sources.iter().position(|&s| s == input::source_name(reference));
+    let by_index = || match reference.parse::<usize>() {
+        Ok(i) if (1..=sources.len()).contains(&i) => Some(i - 1),
+        _ => None,
+    };
+
+    match by_path.or_else(by_index) {
+        Some(i) => i,
+        None => {
+            log::error(&format!("No input to compare against: {:?}", reference));
+            output::exit(1);
+        },
+    }
+}
+
+/// Exit with an error unless there are exactly two inputs to explain.
+fn check_explain_inputs(count: usize) {
+    if count != 2 {
+        log::error("Explanation requires exactly two inputs");
+        output::exit(1);
+    }
+}
+
+/// The quantization of values by `--round` or `--bucket`, if either is given.
+fn parse_quantizer(matches: &ArgMatches) -> Option<Quantizer> {
+    let (name, value) = match (matches.value_of("round"), matches.value_of("bucket")) {
+        (Some(step), _) => ("round", step),
+        (None, Some(width)) => ("bucket", width),
+        (None, None) => return None,
+    };
+
+    // Rejected as a spacing, if it is not a number.
+    let spacing = value.parse().unwrap_or(f64::NAN);
+
+    let quantizer = match name {
+        "round" => Quantizer::round(spacing),
+        _ => Quantizer::bucket(spacing),
+    };
+
+    match quantizer {
+        Ok(q) => Some(q),
+        Err(_) => {
+            log::error(&format!("Invalid --{} spacing: {:?}", name, value));
+            output::exit(1);
+        },
+    }
+}
+
+/// Which duplicates to drop by `--dedup`, if any: all, if no mode is given.
+fn parse_dedup(matches: &ArgMatches) -> Option<Dedup> {
+    if !matches.is_present("dedup") {
+        return None;
+    }
+
+    // Validated by clap, so we can unwrap.
+    let mode = matches.value_of("dedup").unwrap_or("all");
+
+    Some(Dedup::from_name(mode).unwrap_or_else(|| unreachable!()))
+}
+
+/// The fraction of values at each end of a sample given by the option `name`,
+/// as `flag`, if any, exiting with an error if it is not in [0, 0.5).
+fn tail_fraction(matches: &ArgMatches, name: &str, flag: &str) -> Option<f64> {
+    matches.value_of(name).map(|v| match v.parse::<f64>() {
+        Ok(p) if (0.0..0.5).contains(&p) => p,
+        _ => {
+            log::error(&format!(
+                "Invalid {} fraction: {:?}, which must be at least 0 and below 0.5",
+                flag,
+                v,
+            ));
+            output::exit(1);
+        },
+    })
+}
+
+/// The value of the argument `name`, parsed as a `T`. Exits with an error if
+/// it is invalid, showing any output held until then.
+fn arg_value<T: FromStr>(matches: &ArgMatches, name: &str) -> T {
+    let value = matches.value_of(name).unwrap_or_default();
+
+    match value.parse() {
+        Ok(v) => v,
+        Err(_) => {
+            log::error(&format!("Invalid value for --{}: {:?}", name.replace('_', "-"), value));
+            output::exit(1);
+        },
+    }
+}
+
+/// The number of `--resamples` for bootstrap intervals.
+fn bootstrap_resamples(matches: &ArgMatches) -> usize {
+    match matches.value_of("resamples") {
+        Some(_) => arg_value::<usize>(matches, "resamples"),
+        None => 1000,
+    }
+}
+
+/// The `--quick` subsample size, if given. Exits with an error if it is zero.
+fn quick_size(matches: &ArgMatches) -> Option<usize> {
+    matches.value_of("quick")?;
+
+    match arg_value::<usize>(matches, "quick") {
+        0 => {
+            log::error("Subsample size must be positive");
+            output::exit(1);
+        },
+        n => Some(n),
+    }
+}
+
+/// Seed of random numbers under `--stable-output`, unless `--seed` is given.
+const STABLE_SEED: u64 = 0;
+
+/// A generator seeded by `--seed` if given, or else by `STABLE_SEED` under
+/// `--stable-output`, or else by the clock.
+fn seeded_rng(matches: &ArgMatches) -> Rng {
+    match matches.value_of("seed") {
+        Some(_) => Rng::new(arg_value::<u64>(matches, "seed")),
+        None if matches.is_present("stable_output") => Rng::new(STABLE_SEED),
+        None => Rng::from_time(),
+    }
+}
+
+fn display_summaries(
+    summaries: &[Summary],
+    sources: &[&str],
+    plot_opts: Option<&PlotOptions>,
+    report: &ReportOptions,
+) {
+    let mut layout = Layout::new(layout_width());
+    let summary_refs: Vec<&Summary> = summaries.iter().collect();
+
+    if let Some(opts) = plot_opts {
+        layout.plot(draw_plot(&summary_refs, opts));
+    }
+
+    let table = summary_table(&summary_refs, report.outliers);
+    add_summary_table(&mut layout, table, &summary_refs, sources, report);
+
+    outln!("{}", layout.render());
+}
+
+/// The label `xᵢ` of the `i`th input, counting from 1.
+fn input_label(i: usize) -> String {
+    let digits: String = i
+        .to_string()
+        .chars()
+        .filter_map(|d| char::from_u32(0x2080 + d.to_digit(10)?))
+        .collect();
+
+    format!("x{}", digits)
+}
+
+/// Print a table of Welch's t-tests of each pair of several samples, with, for
+/// each sample and each later one, the difference of their means, later minus
+/// earlier, the p-value of the test, adjusted for the number of pairs by
+/// Holm's method, and whether it is significant at `level`.
+fn display_pairwise(
+    summaries: &[Summary],
+    sources: &[&str],
+    level: SigLevel,
+    precision: Option<usize>,
+) {
+    let width = SUMMARY_WIDTH;
+    let n = summaries.len();
+    let pairs: Vec<(usize, usize)> = (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j))).collect();
+
+    // An undefined test, as of a sample of constant values, has no p-value.
+    let p: Vec<f64> = pairs
+        .iter()
+        .map(|&(i, j)| welch_t_test(&summaries[i], &summaries[j]).map_or(f64::NAN, |t| t.p))
+        .collect();
+    let adjusted = holm_adjust(&p);
+
+    let mut table = Table::new(&[("Pair", 0), ("Difference", width), ("p", width), ("p < α", 0)]);
+
+    for (&(i, j), &p) in pairs.iter().zip(&adjusted) {
+        let p_cell = if p.is_nan() { "-".to_string() } else { fmt::value(p, precision) };
+
+        table.row(&[
+            format!("{} - {}", input_label(j + 1), input_label(i + 1)),
+            fmt::f(summaries[j].mean() - summaries[i].mean(), width),
+            p_cell,
+            verdict_mark(p, level).to_string(),
+        ]);
+    }
+
+    let mut lines = vec![format!(
+        "Pairwise t-tests (difference of means, Holm-adjusted p, α = {}):",
+        level.alpha(),
+    )];
+    lines.extend(input_legend(sources));
+
+    let mut layout = Layout::new(layout_width());
+    layout.text(lines, LABEL_WIDTH + 3);
+    layout.table(table);
+
+    outln!();
+    outln!("{}", layout.render());
+}
+
+/// Print a table comparing each of several samples against that at `base`, as
+/// a baseline, by Welch's t-test, with the difference and relative change of
+/// each mean from that of the baseline, the p-value of each test, adjusted
+/// for the number of comparisons by Holm's method, and whether it is
+/// significant at `level`.
+fn display_reference(
+    summaries: &[Summary],
+    sources: &[&str],
+    base: usize,
+    level: SigLevel,
+    precision: Option<usize>,
+) {
+    let width = SUMMARY_WIDTH;
+    let others: Vec<usize> = (0..summaries.len()).filter(|&i| i != base).collect();
+    let base_mean = summaries[base].mean();
+
+    // An undefined test, as of samples of constant values, has no p-value.
+    let p: Vec<f64> = others
+        .iter()
+        .map(|&i| welch_t_test(&summaries[base], &summaries[i]).map_or(f64::NAN, |t| t.p))
+        .collect();
+    let adjusted = holm_adjust(&p);
+
+    let base_label = input_label(base + 1);
+    let difference = format!("m - m{}", &base_label[1..]);
+
+    let mut table = Table::new(&[
+        ("", 0),
+        ("Mean", width),
+        (&difference, width),
+        ("Change", width),
+        ("p", width),
+        ("p < α", 0),
+    ]);
+    table.row(&[base_label.clone(), fmt::f(base_mean, width)]);
+
+    for (&i, &p) in others.iter().zip(&adjusted) {
+        let del = summaries[i].mean() - base_mean;
+
+        // Relative change is undefined against a zero baseline.
+        let pct = if base_mean == 0.0 {
+            "-".to_string()
+        } else {
+            format!("{:+.1}%", 100.0 * del / base_mean.abs())
+        };
+
+        table.row(&[
+            input_label(i + 1),
+            fmt::f(summaries[i].mean(), width),
+            fmt::f(del, width),
+            pct,
+            if p.is_nan() { "-".to_string() } else { fmt::value(p, precision) },
+            verdict_mark(p, level).to_string(),
+        ]);
+    }
+
+    let mut lines = vec![format!(
+        "Compared against {} (Holm-adjusted p, α = {}):",
+        base_label,
+        level.alpha(),
+    )];
+    lines.extend(input_legend(sources));
+
+    let mut layout = Layout::new(layout_width());
+    layout.text(lines, LABEL_WIDTH + 3);
+    layout.table(table);
+
+    outln!();
+    outln!("{}", layout.render());
+}
+
+/// Print the count and proportion of each label of each input, with a bar
+/// chart of the counts. If `diversity` is given, also print indices of
+/// diversity, with intervals from its number of resamples, drawn by its `Rng`.
+fn display_categorical(
+    inputs: &[&str],
+    confidence: f64,
+    width: PlotWidth,
+    ascii: bool,
+    mut diversity: Option<(usize, Rng)>,
+) {
+    let col_width = SUMMARY_WIDTH;
+    let (lower, upper) = (format!("{}% Lower", 100.0 * confidence), "Upper");
+
+    let texts = ok!(input::read_texts(inputs));
+
+    for (i, (path, text)) in inputs.iter().zip(&texts).enumerate() {
+        let labels = text.lines().map(|l| l.trim()).filter(|l| !l.is_empty());
+        let summary = ok!(CategoricalSummary::new(labels, confidence));
+
+        if i > 0 {
+            outln!();
+        }
+
+        if inputs.len() > 1 {
+            outln!("{}", input::source_name(path));
+        }
+
+        let bars: Vec<(&str, u64)> = summary.categories
+            .iter()
+            .map(|c| (c.label.as_str(), c.count))
+            .collect();
+
+        let mut table = Table::new(&[
+            ("Label", 0),
+            ("Count", SUMMARY_SIZE_WIDTH),
+            ("Proportion", col_width),
+            (&lower, col_width),
+            (upper, col_width),
+        ]);
+
+        for c in &summary.categories {
+            table.row(&[
+                c.label.clone(),
+                c.count.to_string(),
+                fmt::f(c.proportion, col_width),
+                fmt::f(c.interval.0, col_width),
+                fmt::f(c.interval.1, col_width),
+            ]);
+        }
+
+        let mut layout = Layout::new(layout_width());
+        layout.plot(ok!(plot::bar_chart(&bars, width, ascii)));
+        layout.table(table);
+
+        if let Some((resamples, ref mut rng)) = diversity {
+            let d = ok!(summary.diversity(resamples, rng));
+            let line = |label: &str, index: &DiversityIndex| {
+                let mut line = format!(
+                    "{l:>w$} = {v}, {c}% CI [{lo}, {hi}]",
+                    w = LABEL_WIDTH,
+                    l = label,
+                    v = index.value,
+                    c = 100.0 * confidence,
+                    lo = index.interval.value.0,
+                    hi = index.interval.value.1,
+                );
+
+                if let Some(note) = approximation_note(&index.interval) {
+                    line.push_str(&format!(" ({})", note));
+                }
+
+                line
+            };
+
+            layout.text(vec![
+                line("H (bits)", &d.entropy),
+                line("Gini", &d.gini_impurity),
+                line("Simpson's D", &d.simpson),
+            ], RESULT_INDENT);
+        }
+
+        outln!("{}", layout.render());
+    }
+}
+
+/// Fewest orders of magnitude spanned by values for Benford's law to be
+/// expected to hold, below which a check of it warns.
+const BENFORD_MIN_ORDERS: f64 = 2.0;
+
+/// Check the first digits of each input against Benford's law.
+fn display_benford(
+    inputs: &[&str],
+    lax_parsing: bool,
+    width: PlotWidth,
+    ascii: bool,
+    report: &ReportOptions,
+) {
+    let col_width = SUMMARY_WIDTH;
+    let level = report.alpha.unwrap_or(SigLevel::Alpha005);
+
+    let parsed = ok!(input::read_inputs(inputs, input::Format::Values, lax_parsing));
+
+    for (i, (path, parsed)) in inputs.iter().zip(&parsed).enumerate() {
+        let benford = ok!(Benford::new(&parsed.data));
+
+        if i > 0 {
+            outln!();
+        }
+
+        if inputs.len() > 1 {
+            outln!("{}", input::source_name(path));
+        }
+
+        let labels: Vec<String> = benford.digits.iter().map(|d| d.digit.to_string()).collect();
+        let bars: Vec<(&str, u64)> = labels
+            .iter()
+            .zip(&benford.digits)
+            .map(|(l, d)| (l.as_str(), d.count))
+            .collect();
+
+        let mut table = Table::new(&[
+            ("Digit", 0),
+            ("Count", SUMMARY_SIZE_WIDTH),
+            ("Observed", col_width),
+            ("Expected", col_width),
+        ]);
+
+        for d in &benford.digits {
+            table.row(&[
+                d.digit.to_string(),
+                d.count.to_string(),
+                fmt::f(d.observed, col_width),
+                fmt::f(d.expected, col_width),
+            ]);
+        }
+
+        let mut lines = vec![
+            format!(
+                "{l:>w$} = {v}",
+                w = LABEL_WIDTH,
+                l = "χ²",
+                v = fmt::value(benford.chi_square, report.precision),
+            ),
+            format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "df", v = 8),
+            format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "p", v = benford.p),
+        ];
+        lines.extend(p_verdict_lines(benford.p, level));
+        lines.push(format!(
+            "{l:>w$} = {v}",
+            w = LABEL_WIDTH,
+            l = "MAD",
+            v = fmt::value(benford.mad, report.precision),
+        ));
+        lines.push(format!(
+            "{l:>w$} = {v}",
+            w = LABEL_WIDTH,
+            l = "Conformity",
+            v = benford.conformity(),
+        ));
+
+        let mut layout = Layout::new(layout_width());
+        layout.plot(ok!(plot::bar_chart(&bars, width, ascii)));
+        layout.table(table);
+        layout.text(lines, RESULT_INDENT);
+
+        outln!("{}", layout.render());
+
+        if benford.zeros > 0 {
+            log::warning(&format!(
+                "{}: Ignored {} zeros, which have no first digit",
+                input::source_name(path),
+                benford.zeros,
+            ));
+        }
+
+        if benford.orders_of_magnitude < BENFORD_MIN_ORDERS {
+            log::warning(&format!(
+                "{}: Values span only {:.1} orders of magnitude, so Benford's law may not apply",
+                input::source_name(path),
+                benford.orders_of_magnitude,
+            ));
+        }
+    }
+}
+
+/// Which analyses of the agreement of two methods of measurement to report.
+struct Agreement {
+    bland_altman: bool,
+    passing_bablok: bool,
+}
+
+/// Compare two inputs, each measured by a different method of the same
+/// subjects, in the same order, by a Bland-Altman analysis of their
+/// differences, and by Passing-Bablok regression of the second on the first.
+/// The Bland-Altman difference plot is drawn if `plot` gives its width.
+fn display_agreement(
+    parsed: &[input::Parsed],
+    sources: &[&str],
+    agreement: &Agreement,
+    confidence: f64,
+    plot: Option<(PlotWidth, bool)>,
+    report: &ReportOptions,
+) {
+    let show = |x: f64| fmt::value(x, report.precision);
+    let (first, second) = (&parsed[0].data, &parsed[1].data);
+
+    if first.len() != second.len() {
+        log::error("Method comparison requires inputs of equal length");
+        output::exit(1);
+    }
+
+    let interval_line = |l: &str, v: f64, (lo, hi): (f64, f64)| {
+        format!(
+            "{l:>w$} = {v}, {c}% CI [{lo}, {hi}]",
+            w = LABEL_WIDTH,
+            l = l,
+            v = show(v),
+            c = 100.0 * confidence,
+            lo = show(lo),
+            hi = show(hi),
+        )
+    };
+
+    let mut layout = Layout::new(layout_width());
+
+    if agreement.bland_altman {
+        let ba = ok!(BlandAltman::new(first, second, confidence));
+
+        if let Some((width, ascii)) = plot {
+            let height = ok!(PlotHeight::new(SCATTER_HEIGHT));
+            let levels = [ba.limits.0, ba.bias, ba.limits.1];
+
+            let unit = report.unit.as_deref();
+            let plot = plot::difference_plot(&ba.points, &levels, width, height, ascii, unit);
+
+            layout.plot(ok!(plot));
+        }
+
+        layout.text(
+            vec![
+                format!("Bland-Altman: {} - {}", sources[1], sources[0]),
+                format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "Pairs", v = ba.size),
+                interval_line("Bias", ba.bias, ba.bias_interval),
+                format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "SD",
+                        v = show(ba.standard_deviation)),
+                format!(
+                    "{l:>w$} = [{lo}, {hi}]",
+                    w = LABEL_WIDTH,
+                    l = "95% LoA",
+                    lo = show(ba.limits.0),
+                    hi = show(ba.limits.1),
+                ),
+            ],
+            RESULT_INDENT,
+        );
+    }
+
+    if agreement.passing_bablok {
+        let data: Vec<(f64, f64)> = first.iter().cloned().zip(second.iter().cloned()).collect();
+        let pb = ok!(PassingBablok::new(&data, confidence));
+
+        layout.text(
+            vec![
+                format!("Passing-Bablok: {} on {}", sources[1], sources[0]),
+                interval_line("Slope", pb.slope, pb.slope_interval),
+                interval_line("Intercept", pb.intercept, pb.intercept_interval),
+            ],
+            RESULT_INDENT,
+        );
+    }
+
+    outln!("{}", layout.render());
+}
+
+/// Compare inputs whose lines measure the same subjects under the condition
+/// of each input.
+fn display_repeated(parsed: &[input::Parsed], sources: &[&str], report: &ReportOptions) {
+    let width = SUMMARY_WIDTH;
+    let show = |x: f64| fmt::value(x, report.precision);
+
+    let conditions: Vec<&[f64]> = parsed.iter().map(|p| p.data.as_slice()).collect();
+
+    let anova = ok!(RepeatedMeasuresAnova::new(&conditions));
+    let friedman = ok!(Friedman::new(&conditions));
+
+    let mut table = Table::new(&[
+        ("Input", 0),
+        ("N", SUMMARY_SIZE_WIDTH),
+        ("Mean", width),
+        ("Mean rank", width),
+    ]);
+
+    for ((src, c), rank) in sources.iter().zip(&conditions).zip(&friedman.mean_ranks) {
+        table.row(&[
+            src.to_string(),
+            c.len().to_string(),
+            fmt::f(c.iter().sum::<f64>() / c.len() as f64, width),
+            fmt::f(*rank, width),
+        ]);
+    }
+
+    let line = |l: &str, v: f64| format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = l, v = show(v));
+
+    let mut anova_lines = vec![
+        line("F", anova.f),
+        format!(
+            "{l:>w$} = {d1}, {d2}",
+            w = LABEL_WIDTH,
+            l = "DF",
+            d1 = show(anova.df_conditions),
+            d2 = show(anova.df_error),
+        ),
+        line("p", anova.p),
+        line("ε (GG)", anova.epsilon),
+        line("p (GG)", anova.p_corrected),
+        line("partial η²", anova.partial_eta_squared),
+    ];
+
+    let mut friedman_lines = vec![
+        line("Friedman χ²", friedman.statistic),
+        line("DF", friedman.df),
+        line("p", friedman.p),
+        line("Kendall's W", friedman.kendall_w),
+    ];
+
+    if let Some(level) = report.alpha {
+        anova_lines.extend(p_verdict_lines(anova.p_corrected, level));
+        friedman_lines.extend(p_verdict_lines(friedman.p, level));
+    }
+
+    let mut layout = Layout::new(layout_width());
+    layout.table(table);
+    layout.text(anova_lines, RESULT_INDENT);
+    layout.text(friedman_lines, RESULT_INDENT);
+
+    outln!("{}", layout.render());
+}
+
+/// Compare two stratified inputs within each stratum, and pool the
+/// differences across strata.
+fn display_stratified(parsed: &[input::Parsed], confidence: f64, report: &ReportOptions) {
+    let width = SUMMARY_WIDTH;
+    let show = |x: f64| fmt::value(x, report.precision);
+
+    // Read in the `Stratified` format, so we can unwrap.
+    let x = parsed[0].stratified().unwrap_or_else(|| unreachable!());
+    let y = parsed[1].stratified().unwrap_or_else(|| unreachable!());
+
+    let comparison = ok!(StratifiedComparison::new(&x, &y, confidence));
+
+    let mut table = Table::new(&[
+        ("Stratum", 0),
+        ("N₁", SUMMARY_SIZE_WIDTH),
+        ("N₂", SUMMARY_SIZE_WIDTH),
+        ("m₂ - m₁", width),
+        ("SE", width),
+        ("p", width),
+    ]);
+
+    for s in &comparison.strata {
+        table.row(&[
+            s.label.clone(),
+            s.summaries.0.size().to_string(),
+            s.summaries.1.size().to_string(),
+            fmt::f(s.difference, width),
+            fmt::f(s.standard_error, width),
+            fmt::f(s.t_test.p, width),
+        ]);
+    }
+
+    let pooled = &comparison.pooled;
+
+    let mut lines = vec![
+        format!(
+            "{l:>w$} = {v} ± {se}",
+            w = LABEL_WIDTH,
+            l = "Pooled ± SE",
+            v = show(pooled.estimate),
+            se = show(pooled.standard_error),
+        ),
+        format!(
+            "{l:>w$} = [{lo}, {hi}]",
+            w = LABEL_WIDTH,
+            l = format!("{}% CI", 100.0 * confidence),
+            lo = show(pooled.interval.0),
+            hi = show(pooled.interval.1),
+        ),
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "p", v = show(pooled.p)),
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "I²", v = show(pooled.heterogeneity)),
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "Aggregate", v = show(comparison.aggregate)),
+    ];
+
+    if let Some(level) = report.alpha {
+        lines.extend(p_verdict_lines(pooled.p, level));
+    }
+
+    let mut layout = Layout::new(layout_width());
+    layout.table(table);
+    layout.text(lines, RESULT_INDENT);
+
+    outln!("{}", layout.render());
+
+    if !comparison.skipped.is_empty() {
+        log::warning(&format!(
+            "Skipped strata without two varying values in each input: {}",
+            comparison.skipped.join(", "),
+        ));
+    }
+
+    if comparison.is_reversed() {
+        log::warning(
+            "The aggregate difference has the opposite sign of the pooled difference \
+             within strata (Simpson's paradox)",
+        );
+    }
+}
+
+/// Print summaries with robust estimators of location and spread, and compare
+/// two samples by the Mann–Whitney U test and Hodges–Lehmann shift. The
+/// `summaries` are those of the `summarizers`.
+fn display_robust(
+    summarizers: &[Summarizer],
+    summaries: &[Summary],
+    sign_test: Option<&SignTest>,
+    sources: &[&str],
+    plot_opts: Option<&PlotOptions>,
+    report: &ReportOptions,
+) {
+    let mut layout = Layout::new(layout_width());
+
+    if let Some(opts) = plot_opts {
+        let summary_refs: Vec<&Summary> = summaries.iter().collect();
+
+        layout.plot(draw_plot(&summary_refs, opts));
+    }
+
+    let rows: Vec<(&Summary, f64)> = summaries
+        .iter()
+        .zip(summarizers)
+        .map(|(summ, s)| (summ, s.median_absolute_deviation()))
+        .collect();
+    let summary_refs: Vec<&Summary> = summaries.iter().collect();
+    let table = robust_summary_table(&rows, report.outliers);
+    add_summary_table(&mut layout, table, &summary_refs, sources, report);
+
+    if let [x, y] = summarizers {
+        let test = ok!(robust::mann_whitney_u_test(x, y));
+        let mut lines = mann_whitney_lines(&test, x, y, report.precision);
+
+        if let Some(level) = report.alpha {
+            lines.extend(p_verdict_lines(test.p, level));
+        }
+
+        layout.text(lines, RESULT_INDENT);
+    }
+
+    if let Some(test) = sign_test {
+        let mut lines = vec![
+            format!(
+                "{l:>w$} = {a} / {b} / {t}",
+                w = LABEL_WIDTH,
+                l = "+ / − / ties",
+                a = test.above,
+                b = test.below,
+                t = test.ties,
+            ),
+            format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "sign test p", v = fmt::value(test.p, report.precision)),
+        ];
+
+        if let Some(level) = report.alpha {
+            lines.extend(p_verdict_lines(test.p, level));
+        }
+
+        layout.text(lines, RESULT_INDENT);
+    }
+
+    outln!("{}", layout.render());
+}
+
+/// Check the assumptions of the t-tests on two samples, report the checks,
+/// and run whichever test they favor.
+fn display_auto(
+    x: &Summarizer,
+    y: &Summarizer,
+    summaries: &[Summary],
+    sources: &[&str],
+    plot_opts: Option<&PlotOptions>,
+    report: &ReportOptions,
+) {
+    // Assumptions are checked at the requested level, or else at 5%.
+    let level = report.alpha.unwrap_or(SigLevel::Alpha005);
+    let selection = ok!(assumptions::select_test(x, y, level));
+
+    let (s1, s2) = (&summaries[0], &summaries[1]);
+
+    let mut layout = Layout::new(layout_width());
+
+    if let Some(opts) = plot_opts {
+        layout.plot(draw_plot(&[s1, s2], opts));
+    }
+
+    let summary_refs = [s1, s2];
+    let table = summary_table(&summary_refs, report.outliers);
+    add_summary_table(&mut layout, table, &summary_refs, sources, report);
+
+    let mut checks = vec![format!("Assumption checks (α = {}):", level.alpha())];
+
+    for c in &selection.checks {
+        checks.push(format!(
+            "  {a}: {t} = {v}, p = {p} ({verdict})",
+            a = c.assumption,
+            t = c.test,
+            v = fmt::f(c.statistic, 8),
+            p = fmt::f(c.p, 8),
+            verdict = if c.plausible { "plausible" } else { "rejected" },
+        ));
+    }
+
+    layout.text(checks, 4);
+    layout.text(vec![format!("Chose {}: {}.", selection.choice, selection.reason)], 2);
+
+    let (mut lines, p) = match selection.choice {
+        TestChoice::MannWhitney => {
+            let test = ok!(robust::mann_whitney_u_test(x, y));
+
+            (mann_whitney_lines(&test, x, y, report.precision), test.p)
+        },
+        TestChoice::Student | TestChoice::Welch => {
+            let t_test = if selection.choice == TestChoice::Student {
+                ok!(student_t_test(s1, s2))
+            } else {
+                ok!(welch_t_test(s1, s2))
+            };
+
+            (t_test_lines(&t_test, s1, s2, report.precision), t_test.p)
+        },
+    };
+
+    if let Some(level) = report.alpha {
+        lines.extend(p_verdict_lines(p, level));
+    }
+
+    layout.text(lines, RESULT_INDENT);
+
+    outln!("{}", layout.render());
+}
+
+fn display_summaries_tsv(summaries: &[Summary], sources: &[&str]) {
+    // Only add a column of methods if any summary is approximate, and of
+    // units if there are any.
+    if summaries.iter().any(Summary::is_approximate) {
+        outln!(
+            "{}\t{}\t{}",
+            summary::TSV_COLUMNS.join("\t"),
+            summary::TSV_UNIT_COLUMN,
+            summary::TSV_METHOD_COLUMN,
+        );
+
+        for (summ, src) in summaries.iter().zip(sources) {
+            outln!("{}", summ.to_tsv_record_with_method(src));
+        }
+    } else if summaries.iter().any(|s| s.unit().is_some()) {
+        outln!("{}\t{}", summary::TSV_COLUMNS.join("\t"), summary::TSV_UNIT_COLUMN);
+
+        for (summ, src) in summaries.iter().zip(sources) {
+            outln!("{}", summ.to_tsv_record_with_unit(src));
+        }
+    } else {
+        outln!("{}", summary::TSV_COLUMNS.join("\t"));
+
+        for (summ, src) in summaries.iter().zip(sources) {
+            outln!("{}", summ.to_tsv_record(src));
+        }
+    }
+}
+
+/// Summarize each input as it is read, without holding its values, and
+/// display the summaries as `display` would.
+fn display_streaming(
+    matches: &ArgMatches,
+    inputs: &[&str],
+    policy: NonFinitePolicy,
+    format: SummaryFormat,
+    plot_opts: Option<&PlotOptions>,
+    report: &ReportOptions,
+) {
+    let lax_parsing = matches.is_present("lax");
+    let sources: Vec<&str> = inputs.iter().map(|&i| input::source_name(i)).collect();
+
+    let summaries: Vec<Summary> = inputs
+        .iter()
+        .zip(&sources)
+        .map(|(path, src)| {
+            let mut summarizer = StreamingSummarizer::with_policy(policy);
+            let skipped = ok!(input::stream_input(path, lax_parsing, |x| summarizer.push(x)));
+
+            if matches.is_present("report_skipped") {
+                input::report_skipped(src, &skipped);
+            }
+
+            if let Some(nonfinite) = summarizer.nonfinite_report() {
+                report_nonfinite(src, &nonfinite);
+            }
+
+            report.annotate(ok!(summarizer.summary()))
+        })
+        .collect();
+
+    display(&summaries, &sources, format, plot_opts, report, None);
+    record_results(matches, &summaries, &sources);
+
+    if matches.is_present("explain") {
+        check_explain_inputs(summaries.len());
+        print_explanation(&summaries, &sources, report.alpha);
+    }
+}
+
+/// Print each summary on one line, as `n=100 min=1.2 q1=3.4 med=4.4 q3=5.0
+/// max=9.1 mean=4.5±0.2`, with the standard error of the mean, and then its
+/// sparkline, if given. Lines are labeled with their source if there are
+/// several. Approximate values are given with `~` in place of `=`.
+fn display_summaries_brief(
+    summaries: &[Summary],
+    sources: &[&str],
+    sparklines: Option<&[String]>,
+    report: &ReportOptions,
+) {
+    let show = |x: f64| fmt::value(x, Some(report.precision.unwrap_or(BRIEF_PRECISION)));
+
+    let eq = |approximate: bool| if approximate { "~" } else { "=" };
+
+    for (i, (s, src)) in summaries.iter().zip(sources).enumerate() {
+        let mut line = format!(
+            "n={} min{m}{} q1{q}{} med{q}{} q3{q}{} max{m}{} mean{m}{}±{}",
+            s.size(),
+            show(s.min()),
+            show(s.lower_quartile()),
+            show(s.median()),
+            show(s.upper_quartile()),
+            show(s.max()),
+            show(s.mean()),
+            show(s.standard_error()),
+            q = eq(s.quantiles_are_approximate()),
+            m = eq(s.moments_are_approximate()),
+        );
+
+        if let Some(sparklines) = sparklines {
+            line = format!("{} {}", line, sparklines[i]);
+        }
+
+        if summaries.len() > 1 {
+            outln!("{}: {}", src, line);
+        } else {
+            outln!("{}", line);
+        }
+    }
+}
+
+/// Record summaries, and their t-test if there are two, in the results
+/// database given by `--db`, if any.
+#[cfg(feature = "sqlite")]
+fn record_results(matches: &ArgMatches, summaries: &[Summary], sources: &[&str]) {
+    let path = match matches.value_of("db") {
+        Some(path) => path,
+        None => return,
+    };
+
+    // Validated by clap, so we can unwrap.
+    let meta: Vec<(String, String)> = matches
+        .values_of("meta")
+        .into_iter()
+        .flatten()
+        .map(|m| db::parse_meta(m).unwrap())
+        .collect();
+
+    let t_test = match summaries {
+        [s1, s2] => welch_t_test(s1, s2).ok(),
+        _ => None,
+    };
+
+    ok!(db::record(path, &meta, summaries, sources, t_test.as_ref()));
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn record_results(_matches: &ArgMatches, _summaries: &[Summary], _sources: &[&str]) {}
+
+/// Print results recorded in a results database as TSV, one row per summary
+/// or t-test, with the time and metadata of its run.
+#[cfg(feature = "sqlite")]
+fn query_results(matches: &ArgMatches) {
+    // Required by clap, so we can unwrap.
+    let path = matches.value_of("db").unwrap();
+    let source = matches.value_of("source");
+
+    // Validated by clap, so we can unwrap.
+    let query = db::Query {
+        meta: matches
+            .values_of("meta")
+            .into_iter()
+            .flatten()
+            .map(|m| db::parse_meta(m).unwrap())
+            .collect(),
+        last: matches.value_of("last").map(|n| n.parse().unwrap()),
+    };
+
+    let runs = ok!(db::runs(path, &query));
+
+    let value = |x: Option<f64>| x.map(|x| x.to_string()).unwrap_or_default();
+    let run_fields = |id: i64| {
+        // Every row is of one of `runs`.
+        let run = runs.iter().find(|r| r.id == id).unwrap();
+        let meta: Vec<String> = run.meta.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+
+        (format!("{}\t{}", run.id, series::format_rfc3339(run.time)), meta.join(","))
+    };
+
+    if matches.is_present("tests") {
+        outln!("Run\tTime\tSource1\tSource2\tT\tDF\tP\tMeta");
+
+        for t in ok!(db::t_tests(path, &runs, source)) {
+            let (run, meta) = run_fields(t.run);
+
+            outln!("{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                   run, t.source1, t.source2, value(t.t), value(t.df), value(t.p), meta);
+        }
+    } else {
+        let columns: Vec<&str> = db::SUMMARY_COLUMNS.iter().map(|&(_, c)| c).collect();
+
+        outln!("Run\tTime\tSource\tSize\t{}\tUnit\tMeta", columns.join("\t"));
+
+        for s in ok!(db::summaries(path, &runs, source)) {
+            let (run, meta) = run_fields(s.run);
+            let stats: Vec<String> = s.stats.iter().map(|&x| value(x)).collect();
+
+            outln!("{}\t{}\t{}\t{}\t{}\t{}",
+                   run, s.source, s.size, stats.join("\t"), s.unit.unwrap_or_default(), meta);
+        }
+    }
+}
+
+/// The form of printed summaries, as chosen by `--format`, `--tsv`, or
+/// `--brief`.
+#[derive(Clone, Copy, PartialEq)]
+enum SummaryFormat {
+    Text,
+    Tsv,
+    Prometheus,
+    Brief,
+}
+
+/// Print summaries in the requested form: as TSV, as Prometheus metrics, one
+/// line each, as a comparison of two samples, or one after another.
+fn display(
+    summaries: &[Summary],
+    sources: &[&str],
+    format: SummaryFormat,
+    plot_opts: Option<&PlotOptions>,
+    report: &ReportOptions,
+    ratio: Option<&RatioOfMeans>,
+) {
+    match format {
+        SummaryFormat::Tsv => return display_summaries_tsv(summaries, sources),
+        SummaryFormat::Prometheus => {
+            return output::write(&summary::to_prometheus(summaries, sources));
+        },
+        SummaryFormat::Text | SummaryFormat::Brief => {},
+    }
+
+    let labels = source_labels(sources);
+    let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+
+    if format == SummaryFormat::Brief {
+        return display_summaries_brief(summaries, &labels, None, report);
+    }
+
+    if let Some(ref reference) = report.reference {
+        check_reference_inputs(summaries.len());
+        let base = reference_index(reference, sources);
+        let level = report.alpha.unwrap_or(SigLevel::Alpha005);
+
+        display_summaries(summaries, &labels, plot_opts, report);
+        return display_reference(summaries, &labels, base, level, report.precision);
+    }
+
+    match summaries.len() {
+        0 => unreachable!(),
+        // We want match 1 with the case `len()` > 2.
+        2 => {
+            display_t_test(
+                &summaries[0],
+                &summaries[1],
+                &labels,
+                plot_opts,
+                report,
+                ratio,
+            );
+        }
+        n => {
+            display_summaries(
+                summaries,
+                &labels,
+                plot_opts,
+                report,
+            );
+
+            if let (Some(level), true) = (report.alpha, n > 2) {
+                display_pairwise(summaries, &labels, level, report.precision);
+            }
+        },
+    };
+}
+
+/// A plain-language sentence describing the comparison of two samples by a
+/// t-test at the significance `level`, or `None` if the t-test is undefined.
+fn explanation(s1: &Summary, s2: &Summary, names: (&str, &str), level: SigLevel) -> Option<String> {
+    let t_test = welch_t_test(s1, s2).ok()?;
+    let d = effect::cohens_d(s1, s2).ok()?;
+
+    let (m1, m2) = (s1.mean(), s2.mean());
+    let direction = if m2 > m1 { "higher" } else { "lower" };
+
+    let change = if m1 == m2 {
+        format!("{}'s mean is the same as {}'s", names.1, names.0)
+    } else if m1 != 0.0 {
+        let pct = 100.0 * (m2 - m1).abs() / m1.abs();
+        format!("{}'s mean is {:.1}% {} than {}'s", names.1, pct, direction, names.0)
+    } else {
+        format!("{}'s mean is {} {} than {}'s", names.1, fmt::f((m2 - m1).abs(), 8), direction, names.0)
+    };
+
+    let significance = match t_test.verdict(level) {
+        Verdict::Reject => "statistically significant",
+        Verdict::FailToReject => "not statistically significant",
+    };
+
+    Some(format!(
+        "{}; the difference is {} at α = {}, with a {} effect size (d = {:.2}).",
+        change,
+        significance,
+        level.alpha(),
+        Magnitude::of(d),
+        d.abs(),
+    ))
+}
+
+fn print_explanation(summaries: &[Summary], sources: &[&str], alpha: Option<SigLevel>) {
+    // Explain at the requested level, or else at 5%.
+    let level = alpha.unwrap_or(SigLevel::Alpha005);
+    let labels = source_labels(sources);
+
+    if let Some(e) = explanation(&summaries[0], &summaries[1], (&labels[0], &labels[1]), level) {
+        let mut layout = Layout::new(layout_width());
+        layout.text(vec![e], 0);
+
+        outln!();
+        outln!("{}", layout.render());
+    }
+}
+
+fn print_throughput(summaries: &[Summary], sources: &[&str], confidence: f64) {
+    let width = 12;
+
+    for (s, src) in summaries.iter().zip(sources) {
+        let t = ok!(Throughput::new(s, confidence));
+
+        outln!();
+
+        if summaries.len() > 1 {
+            outln!("{}", src);
+        }
+
+        outln!("{l:>w$} = {v} ± {se}", w = width, l = "ops/s ± SE", v = t.rate, se = t.standard_error);
+        outln!(
+            "{l:>w$} = [{lo}, {hi}]",
+            w = width,
+            l = format!("{}% CI", 100.0 * confidence),
+            lo = t.interval.0,
+            hi = t.interval.1,
+        );
+        outln!("{l:>w$} = {v}", w = width, l = "median ops/s", v = t.median_rate);
+    }
+}
+
+fn report_nonfinite(source: &str, report: &NonFiniteReport) {
+    if report.total() == 0 {
+        return;
+    }
+
+    let parts: Vec<String> = [
+        (report.nan, "NaN"),
+        (report.pos_infinite, "inf"),
+        (report.neg_infinite, "-inf"),
+    ].iter()
+        .filter(|&&(n, _)| n > 0)
+        .map(|&(n, label)| format!("{} {}", n, label))
+        .collect();
+
+    log::warning(&format!(
+        "{}: Dropped {} non-finite values ({})",
+        source,
+        report.total(),
+        parts.join(", "),
+    ));
+}
+
+fn generate(matches: &ArgMatches) {
+    // Both validated by clap, so we can unwrap.
+    let name = matches.value_of("distribution").unwrap_or_else(|| unreachable!());
+    let count = arg_value::<usize>(matches, "count");
+
+    let params: Vec<f64> = matches
+        .values_of("params")
+        .map(|vs| vs.map(|p| ok!(p.parse::<f64>())).collect())
+        .unwrap_or_default();
+
+    let dist = ok!(distribution::from_name(name, &params));
+
+    let mut rng = seeded_rng(matches);
+
+    let mut text = String::new();
+
+    for _ in 0..count {
+        text += &format!("{}\n", dist.sample(&mut rng));
+    }
+
+    output::write(&text);
+}
+
+/// The width of the terminal, if standard output is one. Output which is
+/// redirected is never wrapped.
+fn layout_width() -> Option<PlotWidth> {
+    output::stdout_size().and_then(|(w, _)| PlotWidth::new(w).ok())
+}
+
+/// The labels of `sources` in human-readable output, shortened to at most a
+/// third of the width of the terminal, if any. Machine-readable output and
+/// errors name sources in full.
+fn source_labels(sources: &[&str]) -> Vec<String> {
+    let max_width = layout_width().map(|w| (w.get() / 3).max(LABEL_WIDTH));
+
+    layout::shorten_labels(sources, max_width)
+}
+
+/// The `--width` of plots, defaulting to that of the config, then of the
+/// terminal. Exits with an error if it is zero, so call it only to draw a plot.
+fn plot_width(matches: &ArgMatches, config: &Config) -> PlotWidth {
+    let cols = matches
+        .value_of("width")
+        .and_then(|w| w.parse::<usize>().ok())
+        .or(config.width)
+        .or(output::terminal_size().map(|(w, _)| w))
+        .unwrap_or(80);
+
+    ok!(PlotWidth::new(cols))
+}
+
+/// The `--confidence` level, exiting with an error if it is invalid.
+fn parse_confidence(matches: &ArgMatches) -> f64 {
+    match matches.value_of("confidence") {
+        Some(c) => match c.parse::<f64>() {
+            Ok(c) if 0.0 < c && c < 1.0 => c,
+            _ => {
+                log::error(&format!("Invalid confidence level: {:?}", c));
+                output::exit(1);
+            },
+        },
+        None => 0.95,
+    }
+}
+
+fn print_segment(label: &str, segment: &Segment, confidence: f64) {
+    let width = 16;
+
+    outln!("{l:>w$} = {v}", w = width, l = format!("slope{}", label), v = segment.slope);
+    outln!(
+        "{l:>w$} = [{lo}, {hi}]",
+        w = width,
+        l = format!("{}% CI", 100.0 * confidence),
+        lo = segment.slope_interval.0,
+        hi = segment.slope_interval.1,
+    );
+    outln!("{l:>w$} = {v}", w = width, l = format!("intercept{}", label), v = segment.intercept);
+    outln!("{l:>w$} = {v}", w = width, l = format!("n{}", label), v = segment.size);
+}
+
+fn print_coefficient(label: &str, c: &Coefficient) {
+    let width = 16;
+
+    outln!("{l:>w$} = {v} ± {se}", w = width, l = format!("{} ± SE", label), v = c.estimate, se = c.standard_error);
+    outln!("{l:>w$} = {v}", w = width, l = "t", v = c.test.t);
+    outln!("{l:>w$} = {v}", w = width, l = "p", v = c.test.p);
+}
+
+/// Number of rows in the plot area of a scatter plot.
+const SCATTER_HEIGHT: usize = 16;
+
+/// Number of rows in the plot area of a histogram with vertical bars.
+const HISTOGRAM_HEIGHT: usize = 10;
+
+/// Values of `--histogram`, the first being the default.
+const HISTOGRAM_STYLES: [&str; 2] = ["horizontal", "vertical"];
+
+fn draw_scatter_plot(data: &[(f64, f64)], matches: &ArgMatches, config: &Config) {
+    let curve = if matches.is_present("smooth") {
+        let span = match matches.value_of("span") {
+            Some(_) => arg_value::<f64>(matches, "span"),
+            None => smooth::DEFAULT_SPAN,
+        };
+
+        Some(ok!(smooth::loess(data, span)))
+    } else {
+        None
+    };
+
+    let width = plot_width(matches, config);
+    let height = ok!(PlotHeight::new(SCATTER_HEIGHT));
+    let ascii = matches.is_present("ascii") || config.ascii == Some(true);
+
+    outln!();
+    outln!("{}", ok!(plot::scatter_plot(data, curve.as_deref(), width, height, ascii)));
+}
+
+/// Warn if the residuals of a fit to `data`, in order of the predictor, show
+/// a pattern, as when the model is of the wrong form, or spread unevenly, as
+/// when the noise grows with the predictor.
+fn check_residuals<F>(path: &str, data: &[(f64, f64)], predict: F)
+    where F: Fn(f64) -> f64 {
+    let mut data = data.to_vec();
+    data.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let predictors: Vec<f64> = data.iter().map(|&(x, _)| x).collect();
+    let residuals: Vec<f64> = data.iter().map(|&(x, y)| y - predict(x)).collect();
+
+    let mut problems = diagnostics::check_order(&residuals);
+    problems.extend(diagnostics::check_variance(&predictors, &residuals));
+
+    for d in problems {
+        log::warning(&format!("{}: residuals: {}", path, d));
+    }
+}
+
+fn linear_regression(matches: &ArgMatches, config: &Config) {
+    let width = 16;
+    let lax_parsing = matches.is_present("lax");
+
+    // Required by clap, so we can unwrap.
+    let paths: Vec<&str> = matches.values_of("files").unwrap_or_else(|| unreachable!()).collect();
+
+    let parsed = ok!(input::read_inputs(&paths, input::Format::Pairs, lax_parsing));
+
+    let mut inputs = vec![];
+    let mut lines = vec![];
+    for (path, parsed) in paths.iter().zip(parsed) {
+
+        if matches.is_present("report_skipped") {
+            input::report_skipped(path, &parsed.skipped);
+        }
+
+        // Read as `Format::Pairs`, so the pairs are present.
+        inputs.push(parsed.pairs().unwrap_or_else(|| unreachable!()));
+        lines.push(parsed.lines);
+    }
+
+    if matches.is_present("compare") {
+        if inputs.len() != 2 {
+            log::error("Comparing regressions requires exactly 2 inputs");
+            output::exit(1);
+        }
+
+        let cmp = ok!(RegressionComparison::new(&inputs[0], &inputs[1]));
+
+        outln!("{l:>w$} = {v}", w = width, l = "slope₁", v = cmp.slope1);
+        outln!("{l:>w$} = {v}", w = width, l = "intercept₁", v = cmp.intercept1);
+        outln!("{l:>w$} = {v}", w = width, l = "slope₂", v = cmp.slope2);
+        outln!("{l:>w$} = {v}", w = width, l = "intercept₂", v = cmp.intercept2);
+        outln!();
+        print_coefficient("Δ slope", &cmp.slope_difference);
+        outln!();
+        print_coefficient("Δ intercept", &cmp.intercept_difference);
+        outln!();
+        outln!("{l:>w$} = {v}", w = width, l = "DF", v = cmp.df);
+
+        return;
+    }
+
+    let segmented = matches.is_present("segmented");
+    let confidence = parse_confidence(matches);
+
+    // Validated by `possible_values`, so we can unwrap.
+    let model = ok!(Model::from_name(matches.value_of("model").unwrap_or("linear")));
+
+    let deming = if matches.is_present("deming") {
+        match matches.value_of("error_ratio") {
+            Some(_) => Some(arg_value::<f64>(matches, "error_ratio")),
+            None => Some(1.0),
+        }
+    } else {
+        None
+    };
+
+    let folds = matches.value_of("cv");
+    let mut rng = seeded_rng(matches);
+
+    for (i, (path, data)) in paths.iter().zip(&inputs).enumerate() {
+        if i > 0 {
+            outln!();
+        }
+
+        if paths.len() > 1 {
+            outln!("{}", path);
+        }
+
+        if let Some(ratio) = deming {
+            let fit = ok!(DemingRegression::new(data, ratio));
+
+            outln!("{l:>w$} = {v}", w = width, l = "error ratio", v = fit.ratio);
+            outln!("{l:>w$} = {v} ± {se}", w = width, l = "slope ± SE",
+                   v = fit.slope, se = fit.slope_standard_error);
+            outln!("{l:>w$} = {v} ± {se}", w = width, l = "intercept ± SE",
+                   v = fit.intercept, se = fit.intercept_standard_error);
+
+            check_residuals(path, data, |x| fit.slope * x + fit.intercept);
+        } else if segmented {
+            let seg = ok!(SegmentedRegression::new(data, confidence));
+
+            outln!("{l:>w$} = {v}", w = width, l = "breakpoint", v = seg.breakpoint);
+            outln!("{l:>w$} = {v}", w = width, l = "SSE", v = seg.sse);
+            outln!();
+            print_segment("₁", &seg.left, confidence);
+            outln!();
+            print_segment("₂", &seg.right, confidence);
+        } else if model != Model::Linear {
+            let fit = ok!(ModelFit::new(data, model));
+
+            outln!("{l:>w$} = {v}", w = width, l = "model", v = model.equation());
+            outln!("{l:>w$} = {v}", w = width, l = "a", v = fit.a);
+            outln!("{l:>w$} = {v}", w = width, l = "b", v = fit.b);
+            outln!("{l:>w$} = {v}", w = width, l = "R²", v = fit.r_squared);
+
+            if let Some(folds) = folds {
+                print_cross_validation(data, model, folds, &mut rng);
+            }
+
+            check_residuals(path, data, |x| fit.predict(x));
+        } else {
+            let lr = ok!(LinearRegression::new(data));
+
+            outln!("{l:>w$} = {v}", w = width, l = "slope", v = lr.slope());
+            outln!("{l:>w$} = {v}", w = width, l = "intercept", v = lr.intercept());
+            outln!("{l:>w$} = {v}", w = width, l = "r", v = lr.r());
+            outln!("{l:>w$} = {v}", w = width, l = "SE", v = lr.standard_error());
+
+            if let Some(folds) = folds {
+                print_cross_validation(data, model, folds, &mut rng);
+            }
+
+            check_residuals(path, data, |x| lr.slope() * x + lr.intercept());
+
+            if matches.is_present("influence") {
+                outln!();
+                print_influence(data, &lines[i], &ok!(lr.influence(data)));
+            }
+        }
+
+        if matches.is_present("plot") {
+            draw_scatter_plot(data, matches, config);
+        }
+    }
+}
+
+/// Print the cross-validated prediction error of `model` on `data`, with
+/// `folds` groups, or as many as there are points, for `loo`.
+fn print_cross_validation(data: &[(f64, f64)], model: Model, folds: &str, rng: &mut Rng) {
+    let width = 16;
+
+    // Validated by clap, so a count unless `loo`.
+    let k = if folds == "loo" { data.len() } else { folds.parse().unwrap_or(0) };
+
+    if k > data.len() {
+        log::error(&format!("Cannot cross-validate {} points with {} folds", data.len(), k));
+        output::exit(1);
+    }
+
+    let rmse = ok!(lr::cross_validate(data, model, k, rng));
+
+    outln!("{l:>w$} = {v}", w = width, l = "CV folds", v = k);
+    outln!("{l:>w$} = {v}", w = width, l = "CV RMSE", v = rmse);
+}
+
+/// Maximum number of observations listed by `lr --influence`.
+const MAX_INFLUENTIAL: usize = 5;
+
+/// Print the observations of `data`, from the given `lines`, with the most
+/// influence on its fit, by Cook's distance, and mark those above the usual
+/// cutoff of `4/n`.
+fn print_influence(data: &[(f64, f64)], lines: &[usize], influence: &[Influence]) {
+    let width = SUMMARY_WIDTH;
+    let cutoff = 4.0 / data.len() as f64;
+
+    let mut order: Vec<usize> = (0..data.len()).collect();
+    order.sort_by(|&i, &j| influence[j].cooks_distance.total_cmp(&influence[i].cooks_distance));
+
+    let mut table = Table::new(&[
+        ("Line", SUMMARY_SIZE_WIDTH),
+        ("X", width),
+        ("Y", width),
+        ("Residual", width),
+        ("Leverage", width),
+        ("Cook's D", width),
+        ("", 1),
+    ]);
+
+    for &i in order.iter().take(MAX_INFLUENTIAL) {
+        let d = influence[i].cooks_distance;
+
+        table.row(&[
+            lines[i].to_string(),
+            fmt::f(data[i].0, width),
+            fmt::f(data[i].1, width),
+            fmt::f(influence[i].residual, width),
+            fmt::f(influence[i].leverage, width),
+            fmt::f(d, width),
+            (if d > cutoff { "*" } else { "" }).to_string(),
+        ]);
+    }
+
+    let mut layout = Layout::new(layout_width());
+    layout.table(table);
+    layout.text(vec![format!("* Cook's distance above 4/n = {}", fmt::value(cutoff, Some(3)))], 0);
+
+    outln!("{}", layout.render());
+}
+
+/// Print fits from best to worst, as ranked by `criterion`.
+fn print_ranking(ranked: &[RankedFit], criterion: Criterion) {
+    let width = SUMMARY_WIDTH;
+    let delta = format!("Δ{}", criterion.label());
+
+    let mut table = Table::new(&[
+        ("Distribution", 0),
+        ("Log-Lik", width),
+        ("AIC", width),
+        ("BIC", width),
+        (&delta, width),
+        ("Weight", width),
+        ("K-S", width),
+    ]);
+
+    for r in ranked {
+        table.row(&[
+            r.fit.family.name().to_string(),
+            fmt::f(r.fit.log_likelihood, width),
+            fmt::f(r.fit.aic(), width),
+            fmt::f(r.fit.bic(), width),
+            fmt::f(r.delta, width),
+            fmt::f(r.weight, width),
+            fmt::f(r.fit.ks_statistic, width),
+        ]);
+    }
+
+    let mut layout = Layout::new(layout_width());
+    layout.table(table);
+
+    // `fit::rank` fails unless some distribution fits, so there is a best.
+    layout.text(
+        vec![format!("Best fit by {}: {}", criterion.label(), ranked[0].fit.family.name())],
+        0,
+    );
+
+    outln!("{}", layout.render());
+}
+
+fn fit_distribution(matches: &ArgMatches) {
+    let width = 16;
+    let lax_parsing = matches.is_present("lax");
+
+    // Validated by `possible_values`, so we can unwrap.
+    let family = ok!(Family::from_name(matches.value_of("dist").unwrap_or("normal")));
+    let criterion = if matches.is_present("rank") {
+        Some(ok!(Criterion::from_name(matches.value_of("criterion").unwrap_or("aic"))))
+    } else {
+        None
+    };
+
+    // Required by clap, so we can unwrap.
+    let paths: Vec<&str> = matches.values_of("files").unwrap_or_else(|| unreachable!()).collect();
+
+    let simulate = match matches.value_of("simulate") {
+        Some(_) if paths.len() != 1 => {
+            log::error("Simulating from a fit requires exactly 1 input");
+            output::exit(1);
+        },
+        Some(_) => Some(arg_value::<usize>(matches, "simulate")),
+        None => None,
+    };
+
+    let parsed = ok!(input::read_inputs(&paths, input::Format::Values, lax_parsing));
+
+    for (i, (path, parsed)) in paths.iter().zip(parsed).enumerate() {
+
+        if matches.is_present("report_skipped") {
+            input::report_skipped(path, &parsed.skipped);
+        }
+
+        if i > 0 {
+            outln!();
+        }
+
+        if paths.len() > 1 {
+            outln!("{}", path);
+        }
+
+        if let Some(criterion) = criterion {
+            print_ranking(&ok!(fit::rank(&parsed.data, criterion)), criterion);
+            continue;
+        }
+
+        let fit = ok!(DistributionFit::new(&parsed.data, family));
+
+        if let Some(count) = simulate {
+            let dist = ok!(fit.distribution());
+            let mut rng = seeded_rng(matches);
+
+            let mut text = String::new();
+
+            for _ in 0..count {
+                text += &format!("{}\n", dist.sample(&mut rng));
+            }
+
+            return output::write(&text);
+        }
+
+        outln!("{l:>w$} = {v}", w = width, l = "distribution", v = family.name());
+
+        for p in &fit.parameters {
+            outln!(
+                "{l:>w$} = {v} ± {se}",
+                w = width,
+                l = format!("{} ± SE", p.name),
+                v = p.estimate,
+                se = p.standard_error,
+            );
+        }
+
+        outln!("{l:>w$} = {v}", w = width, l = "log-likelihood", v = fit.log_likelihood);
+        outln!("{l:>w$} = {v}", w = width, l = "K-S statistic", v = fit.ks_statistic);
+    }
+}
+
+fn print_estimate(label: &str, e: &contingency::Estimate) {
+    let width = 16;
+
+    outln!("{l:>w$} = {v}", w = width, l = label, v = e.estimate);
+    outln!(
+        "{l:>w$} = [{lo}, {hi}]",
+        w = width,
+        l = format!("{}% CI", 100.0 * e.confidence),
+        lo = e.interval.0,
+        hi = e.interval.1,
+    );
+}
+
+fn contingency_table(matches: &ArgMatches) {
+    let width = 16;
+    let confidence = parse_confidence(matches);
+
+    // One is required by clap.
+    let table = match matches.value_of("counts") {
+        Some(counts) => ok!(TwoByTwo::parse(counts)),
+        None => {
+            let path = matches.value_of("file").unwrap_or_else(|| unreachable!());
+            ok!(TwoByTwo::parse_csv(&ok!(input::read_text(path))))
+        },
+    };
+
+    let chi_square = table.chi_square();
+
+    print_estimate("odds ratio", &ok!(table.odds_ratio(confidence)));
+    outln!();
+    print_estimate("relative risk", &ok!(table.relative_risk(confidence)));
+    outln!();
+    outln!("{l:>w$} = {v}", w = width, l = "χ²", v = chi_square.statistic);
+    outln!("{l:>w$} = {v}", w = width, l = "χ² p", v = chi_square.p);
+    outln!("{l:>w$} = {v}", w = width, l = "Fisher exact p", v = ok!(table.fisher_exact()));
+
+    if table.min_expected() < 5.0 {
+        log::warning("Some expected counts are below 5, so prefer Fisher's exact test to χ²");
+    }
+}
+
+fn two_way_anova(matches: &ArgMatches) {
+    let width = SUMMARY_WIDTH;
+
+    // Required by clap, so we can unwrap.
+    let path = matches.value_of("file").unwrap_or_else(|| unreachable!());
+    let data = ok!(LongFormat::parse(&ok!(input::read_text(path))));
+
+    let observations: Vec<(&str, &str, f64)> = data.observations
+        .iter()
+        .map(|o| (o.0.as_str(), o.1.as_str(), o.2))
+        .collect();
+    let anova = ok!(TwoWayAnova::new(&observations));
+
+    let (ref name_a, ref name_b) = data.factors;
+
+    let mut columns = vec![(name_a.as_str(), 0)];
+    columns.extend(anova.levels.1.iter().map(|l| (l.as_str(), width)));
+
+    let mut means = Table::new(&columns);
+
+    for (level, row) in anova.levels.0.iter().zip(&anova.cell_means) {
+        let mut cells = vec![level.clone()];
+        cells.extend(row.iter().map(|&m| fmt::f(m, width)));
+        means.row(&cells);
+    }
+
+    let mut table = Table::new(&[
+        ("Source", 0),
+        ("SS", width),
+        ("DF", SUMMARY_SIZE_WIDTH),
+        ("F", width),
+        ("p", width),
+        ("Partial η²", width),
+    ]);
+
+    let interaction = format!("{} × {}", name_a, name_b);
+    let effects = [(name_a, &anova.a), (name_b, &anova.b), (&interaction, &anova.interaction)];
+
+    for &(source, e) in &effects {
+        table.row(&[
+            source.to_string(),
+            fmt::f(e.sum_sq, width),
+            e.df.to_string(),
+            fmt::f(e.f, width),
+            fmt::f(e.p, width),
+            fmt::f(e.partial_eta_squared, width),
+        ]);
+    }
+
+    table.row(&[
+        "Residual".to_string(),
+        fmt::f(anova.residual_sum_sq, width),
+        anova.df_residual.to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+    ]);
+
+    let mut layout = Layout::new(layout_width());
+    layout.table(means);
+    layout.table(table);
+
+    outln!("{}", layout.render());
+}
+
+/// Width of the labels of the result lines of `meta`.
+const META_LABEL_WIDTH: usize = 16;
+
+fn combined_lines(label: &str, c: &Combined, confidence: f64) -> Vec<String> {
+    let width = META_LABEL_WIDTH;
+
+    vec![
+        format!("{l:>w$} = {v} ± {se}", w = width, l = label, v = c.estimate, se = c.standard_error),
+        format!(
+            "{l:>w$} = [{lo}, {hi}]",
+            w = width,
+            l = format!("{}% CI", 100.0 * confidence),
+            lo = c.interval.0,
+            hi = c.interval.1,
+        ),
+        format!("{l:>w$} = {v}", w = width, l = "p", v = c.p),
+    ]
+}
+
+fn meta_analysis(matches: &ArgMatches) {
+    let width = SUMMARY_WIDTH;
+    let confidence = parse_confidence(matches);
+
+    // Required by clap, so we can unwrap.
+    let path = matches.value_of("file").unwrap_or_else(|| unreachable!());
+    let studies = ok!(meta::parse_studies(&ok!(input::read_text(path))));
+    let meta = ok!(MetaAnalysis::new(studies, confidence));
+
+    let mut table = Table::new(&[
+        ("Study", 0),
+        ("Estimate", width),
+        ("SE", width),
+        ("Fixed W", width),
+        ("Random W", width),
+    ]);
+
+    let weights = meta.fixed_weights().into_iter().zip(meta.random_weights());
+
+    for (s, (fixed, random)) in meta.studies.iter().zip(weights) {
+        table.row(&[
+            s.label.clone(),
+            fmt::f(s.estimate, width),
+            fmt::f(s.standard_error, width),
+            fmt::f(fixed, width),
+            fmt::f(random, width),
+        ]);
+    }
+
+    let indent = META_LABEL_WIDTH + 3;
+
+    let mut layout = Layout::new(layout_width());
+    layout.table(table);
+    layout.text(combined_lines("fixed effect", &meta.fixed, confidence), indent);
+    layout.text(combined_lines("random effects", &meta.random, confidence), indent);
+    layout.text(vec![
+        format!("{l:>w$} = {v}", w = META_LABEL_WIDTH, l = "Q", v = meta.q),
+        format!("{l:>w$} = {v}", w = META_LABEL_WIDTH, l = "I²", v = meta.heterogeneity),
+        format!("{l:>w$} = {v}", w = META_LABEL_WIDTH, l = "τ²", v = meta.tau_squared),
+    ], indent);
+
+    outln!("{}", layout.render());
+}
+
+/// Default number of runs of each benchmarked command.
+const BENCH_RUNS: usize = 10;
+
+/// Default limit of the number of runs of each benchmarked command, when
+/// adding runs to reach a `--target-precision`.
+const BENCH_MAX_RUNS: usize = 100;
+
+/// Measure repeated runs of two commands, and compare them by each metric
+/// as two samples, with a t-test, and the speedup of the first over the
+/// second in wall-clock time.
+fn compare_commands(matches: &ArgMatches) {
+    // Required by clap, with two values, so we can unwrap.
+    let commands: Vec<&str> = matches.values_of("compare").unwrap().collect();
+
+    let runs = match matches.value_of("runs") {
+        None => BENCH_RUNS,
+        Some(_) => match arg_value::<usize>(matches, "runs") {
+            n if n >= 2 => n,
+            _ => {
+                log::error("Benchmarks require at least 2 runs of each command");
+                output::exit(1);
+            },
+        },
+    };
+    let confidence = parse_confidence(matches);
+
+    let target_precision = matches.value_of("target_precision").map(|p| {
+        let value = match p.strip_suffix('%') {
+            Some(percent) => percent.parse::<f64>().map(|x| x / 100.0),
+            None => p.parse::<f64>(),
+        };
+
+        match value {
+            Ok(x) if x > 0.0 => x,
+            _ => {
+                log::error(&format!("Invalid target precision: {:?}", p));
+                output::exit(1);
+            },
+        }
+    });
+
+    let max_runs = match matches.value_of("max_runs") {
+        None => BENCH_MAX_RUNS.max(runs),
+        Some(_) => arg_value::<usize>(matches, "max_runs"),
+    };
+
+    let plan = bench::Plan {
+        runs,
+        interleave: matches.is_present("interleave"),
+        allow_failure: matches.is_present("allow_failure"),
+        rerun_outliers: matches.is_present("rerun_outliers"),
+        target_precision,
+        confidence,
+        max_runs,
+        prepare: matches.value_of("prepare"),
+        cleanup: matches.value_of("cleanup"),
+        cold: matches.is_present("cold"),
+    };
+
+    let bench = ok!(bench::measure_commands(&commands, &plan));
+    let measurements = &bench.measurements;
+
+    if let Some(path) = matches.value_of("save_raw") {
+        ok!(bench::save_raw(path, &commands, &bench));
+    }
+
+    let sizes: Vec<usize> = measurements.iter().map(|ms| ms.len()).collect();
+    let order = match (plan.interleave, plan.cold) {
+        (true, true) => "interleaved, with cold caches",
+        (true, false) => "interleaved",
+        (false, true) => "in sequence, with cold caches",
+        (false, false) => "in sequence",
+    };
+
+    if sizes.iter().all(|&n| n == sizes[0]) {
+        outln!("Ran each command {} times, {}:", sizes[0], order);
+    } else {
+        outln!("Ran the commands {} and {} times, {}:", sizes[0], sizes[1], order);
+    }
+
+    for (label, c) in ["x₁", "x₂"].iter().zip(&commands) {
+        outln!("{:>w$} = {}", label, c, w = LABEL_WIDTH);
+    }
+
+    // Outlying runs are kept, so show how their reruns compare.
+    for ((label, ms), reruns) in ["x₁", "x₂"].iter().zip(measurements).zip(&bench.reruns) {
+        if !reruns.is_empty() {
+            let runs = if reruns.len() == 1 { "run" } else { "runs" };
+            let times: Vec<String> = reruns
+                .iter()
+                .map(|r| {
+                    let wall = |m: &bench::Measurement| fmt::value(m.wall, Some(4));
+                    format!("{} → {}", wall(&ms[r.of]), wall(&r.measurement))
+                })
+                .collect();
+
+            outln!(
+                "Ran {} outlying {} of {} again, in ms: {}",
+                reruns.len(),
+                runs,
+                label,
+                times.join(", "),
+            );
+        }
+    }
+
+    for ((c, ms), &n) in commands.iter().zip(measurements).zip(&sizes) {
+        let failed = ms.iter().filter(|m| !m.status.success()).count();
+
+        if failed > 0 {
+            log::warning(&format!("{}: {} of {} runs failed", c, failed, n));
+        }
+    }
+
+    if let Some(target) = target_precision {
+        for ((c, w), &n) in commands.iter().zip(bench.precision(confidence)).zip(&sizes) {
+            if w > target {
+                log::warning(&format!(
+                    "{}: after {} runs, the {}% interval of the mean wall time is {}% of it, \
+                     wider than the target of {}%",
+                    c,
+                    n,
+                    100.0 * confidence,
+                    fmt::f(100.0 * w, 6),
+                    100.0 * target,
+                ));
+            }
+        }
+    }
+
+    for (i, metric) in bench::METRICS.iter().enumerate() {
+        let values: Option<Vec<Vec<f64>>> = measurements
+            .iter()
+            .map(|ms| ms.iter().map(metric.value).collect())
+            .collect();
+
+        // Unknown on this system.
+        let values = match values {
+            Some(values) => values,
+            None => continue,
+        };
+
+        let summaries: Vec<Summary> = values
+            .iter()
+            .map(|v| ok!(Summary::new(v)).with_unit(metric.unit))
+            .collect();
+
+        let report = ReportOptions {
+            outliers: false,
+            alpha: None,
+            precision: None,
+            unit: Some(metric.unit.to_string()),
+            reference: None,
+            vertical: false,
+        };
+
+        // Only a ratio of times is a speedup.
+        let ratio = if i == 0 {
+            RatioOfMeans::fieller(&summaries[0], &summaries[1], confidence).ok()
+        } else {
+            None
+        };
+
+        outln!();
+        outln!("{} ({}):", metric.name, metric.unit);
+        display(&summaries, &commands, SummaryFormat::Text, None, &report, ratio.as_ref());
+    }
+}
+
+/// Exit status of `dent drift` when drift exceeds any threshold, distinct
+/// from that of an error, so that scripts can tell them apart.
+const DRIFT_EXIT_CODE: i32 = 2;
+
+/// A drift threshold given by `name`, if any, exiting with an error if it is
+/// not a non-negative number.
+fn drift_threshold(matches: &ArgMatches, name: &str) -> Option<f64> {
+    matches.value_of(name).map(|t| match t.parse::<f64>() {
+        Ok(t) if t >= 0.0 => t,
+        _ => {
+            log::error(&format!("Invalid drift threshold: {:?}", t));
+            output::exit(1);
+        },
+    })
+}
+
+fn distribution_drift(matches: &ArgMatches) {
+    let width = SUMMARY_WIDTH;
+    let lax_parsing = matches.is_present("lax");
+
+    // Both required by clap, so we can unwrap.
+    let paths = [
+        matches.value_of("baseline").unwrap_or_else(|| unreachable!()),
+        matches.value_of("current").unwrap_or_else(|| unreachable!()),
+    ];
+
+    if paths.iter().all(|&p| p == STDIN_PATH) {
+        log::error("Standard input may only be read once");
+        output::exit(1);
+    }
+
+    let bins = match matches.value_of("bins") {
+        Some(_) => arg_value::<usize>(matches, "bins"),
+        None => drift::DEFAULT_BINS,
+    };
+
+    let thresholds = [
+        ("PSI", drift_threshold(matches, "max_psi").or(Some(0.25))),
+        ("KL", drift_threshold(matches, "max_kl")),
+        ("JS", drift_threshold(matches, "max_js")),
+    ];
+
+    let mut samples = vec![];
+
+    let parsed = ok!(input::read_inputs(&paths, input::Format::Values, lax_parsing));
+
+    for (path, parsed) in paths.iter().zip(parsed) {
+
+        if matches.is_present("report_skipped") {
+            input::report_skipped(input::source_name(path), &parsed.skipped);
+        }
+
+        samples.push(parsed.data);
+    }
+
+    let drift = ok!(Drift::new(&samples[0], &samples[1], bins));
+
+    let mut table = Table::new(&[
+        ("Lower", width),
+        ("Upper", width),
+        ("Old", width),
+        ("New", width),
+        ("PSI", width),
+    ]);
+
+    for b in &drift.bins {
+        table.row(&[
+            fmt::f(b.lower, width),
+            fmt::f(b.upper, width),
+            fmt::f(b.baseline, width),
+            fmt::f(b.current, width),
+            fmt::f(b.psi(), width),
+        ]);
+    }
+
+    let values = [drift.psi, drift.kl_divergence, drift.js_divergence];
+
+    let exceeded: Vec<String> = thresholds
+        .iter()
+        .zip(&values)
+        .filter_map(|(&(name, max), &v)| match max {
+            Some(max) if v > max => Some(format!("{} {} > {}", name, v, max)),
+            _ => None,
+        })
+        .collect();
+
+    let verdict = if exceeded.is_empty() { "none" } else { "detected" };
+
+    let mut layout = Layout::new(layout_width());
+    layout.table(table);
+    layout.text(vec![
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "PSI", v = drift.psi),
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "KL", v = drift.kl_divergence),
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "JS", v = drift.js_divergence),
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "Drift", v = verdict),
+    ], RESULT_INDENT);
+
+    outln!("{}", layout.render());
+
+    if !exceeded.is_empty() {
+        log::warning(&format!("Drift exceeds threshold: {}", exceeded.join(", ")));
+        output::exit(DRIFT_EXIT_CODE);
+    }
+}
+
+/// Look up a series `Statistic` by name, exiting with an error if unknown.
+fn parse_statistic(name: &str) -> Statistic {
+    Statistic::from_name(name).unwrap_or_else(|_| {
+        log::error(&format!(
+            "Unknown statistic {:?}, expected one of: {}",
+            name,
+            series::STATISTIC_NAMES.join(", "),
+        ));
+        output::exit(1);
+    })
+}
+
+/// Summarize the runs and excursions of a series, and its crossings of
+/// `threshold`.
+fn display_series_summary(series: &Series, time_format: TimeFormat, threshold: f64) {
+    let span = |start: f64, end: f64| {
+        format!("from {} to {}", time_format.format(start), time_format.format(end))
+    };
+    let streak = |s: Option<Streak>| match s {
+        Some(s) => format!("{} steps, {}", s.steps, span(s.start, s.end)),
+        None => "none".to_string(),
+    };
+    let excursion = |e: Option<Excursion>| match e {
+        Some(e) => format!("{}, {}", e.size, span(e.start, e.end)),
+        None => "none".to_string(),
+    };
+
+    let crossings = ok!(series.crossings(threshold));
+
+    let lines = vec![
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "N", v = series.len()),
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "Longest rise", v = streak(series.longest_rise())),
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "Longest fall", v = streak(series.longest_fall())),
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "Max drawup", v = excursion(series.max_drawup())),
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "Max drawdown", v = excursion(series.max_drawdown())),
+        format!("{l:>w$} = {v}", w = LABEL_WIDTH, l = "Threshold", v = crossings.threshold),
+        format!(
+            "{l:>w$} = {v} ({u} up, {d} down)",
+            w = LABEL_WIDTH,
+            l = "Crossings",
+            v = crossings.total(),
+            u = crossings.up,
+            d = crossings.down,
+        ),
+    ];
+
+    let mut layout = Layout::new(layout_width());
+    layout.text(lines, RESULT_INDENT);
+
+    outln!("{}", layout.render());
+}
+
+/// Number of observations of each problem with the times of a series to note.
+const MAX_SERIES_EXAMPLES: usize = 3;
+
+fn time_series(matches: &ArgMatches) {
+    let lax_parsing = matches.is_present("lax");
+
+    // Required by clap, so we can unwrap.
+    let paths: Vec<&str> = matches.values_of("files").unwrap_or_else(|| unreachable!()).collect();
+
+    let mut points = vec![];
+    let mut origins = vec![];
+    let mut runs = vec![];
+    let mut time_format = None;
+
+    let parsed = ok!(input::read_inputs(&paths, input::Format::Series, lax_parsing));
+
+    for (path, parsed) in paths.iter().zip(parsed) {
+
+        if matches.is_present("report_skipped") {
+            input::report_skipped(path, &parsed.skipped);
+        }
+
+        time_format = time_format.or(parsed.time_format);
+
+        // Read as `Format::Series`, so the times are present.
+        let timed = parsed.timed().unwrap_or_else(|| unreachable!());
+
+        runs.push(timed.iter().map(|&(t, _)| t).collect::<Vec<f64>>());
+        points.extend(timed);
+        origins.extend(parsed.lines.iter().map(|&l| (input::source_name(path), l)));
+    }
+
+    let max_gap = matches.value_of("max_gap").map(|g| {
+        series::parse_duration(g).unwrap_or_else(|_| {
+            log::error(&format!("Invalid gap: {:?}", g));
+            output::exit(1);
+        })
+    });
+
+    let runs: Vec<&[f64]> = runs.iter().map(|r| r.as_slice()).collect();
+    let issues = series::check_times(&runs, max_gap);
+    let times = runs.concat();
+    let show_time = |t: f64| time_format.unwrap_or(TimeFormat::Epoch).format(t);
+
+    // Observations are sorted by time either way, but only expected out of
+    // order with --sort-time.
+    if !(issues.out_of_order.is_empty() || matches.is_present("sort_time")) {
+        let n = issues.out_of_order.len();
+        let noun = if n == 1 { "observation is" } else { "observations are" };
+        log::warning(&format!(
+            "{} {} earlier than the one before, and sorted by time; pass --sort-time if expected",
+            n,
+            noun,
+        ));
+
+        for &i in issues.out_of_order.iter().take(MAX_SERIES_EXAMPLES) {
+            let (src, line) = origins[i];
+            let note = format!(
+                "time {} is earlier than that of the line before, in {}",
+                show_time(times[i]),
+                src,
+            );
+            log::warning_at("unordered_time", &note, src, line);
+        }
+    }
+
+    if !issues.duplicates.is_empty() {
+        let n = issues.duplicates.len();
+        let noun = if n == 1 { "observation is" } else { "observations are" };
+        log::warning(&format!("{} {} at the same time as an earlier one", n, noun));
+
+        for &i in issues.duplicates.iter().take(MAX_SERIES_EXAMPLES) {
+            let (src, line) = origins[i];
+            let note = format!("time {} again, in {}", show_time(times[i]), src);
+            log::warning_at("duplicate_time", &note, src, line);
+        }
+    }
+
+    if let (false, Some(max_gap)) = (issues.gaps.is_empty(), max_gap) {
+        let n = issues.gaps.len();
+        let noun = if n == 1 { "gap" } else { "gaps" };
+        log::warning(&format!("{} {} of more than {}s between observations", n, noun, max_gap));
+
+        for &(i, gap) in issues.gaps.iter().take(MAX_SERIES_EXAMPLES) {
+            let (src, line) = origins[i];
+            let note = format!("{}s gap before time {}, in {}", gap, show_time(times[i]), src);
+            log::warning_at("time_gap", &note, src, line);
+        }
+    }
+
+    let mut series = ok!(Series::new(&points));
+
+    if let Some(mut vs) = matches.values_of("resample") {
+        // Clap requires exactly two values.
+        let interval = vs.next().unwrap_or_else(|| unreachable!());
+        let stat = parse_statistic(vs.next().unwrap_or_else(|| unreachable!()));
+
+        let interval = series::parse_duration(interval).unwrap_or_else(|_| {
+            log::error(&format!("Invalid resampling interval: {:?}", interval));
+            output::exit(1);
+        });
+
+        series = ok!(series.resample(interval, stat));
+    }
+
+    if let Some(mut vs) = matches.values_of("aggregate") {
+        // Clap requires exactly two values.
+        let size = vs.next().unwrap_or_else(|| unreachable!());
+        let stat = parse_statistic(vs.next().unwrap_or_else(|| unreachable!()));
+
+        let size = match size.parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                log::error(&format!("Invalid aggregation window: {:?}", size));
+                output::exit(1);
+            },
+        };
+
+        series = ok!(series.aggregate(size, stat));
+    }
+
+    let time_format = time_format.unwrap_or(TimeFormat::Epoch);
+
+    if matches.is_present("summary") {
+        let threshold = match matches.value_of("threshold") {
+            Some(t) => t.parse::<f64>().ok().filter(|t| t.is_finite()).unwrap_or_else(|| {
+                log::error(&format!("Invalid threshold: {:?}", t));
+                output::exit(1);
+            }),
+            None => ok!(Statistic::Median.apply(&series.values())),
+        };
+
+        return display_series_summary(&series, time_format, threshold);
+    }
+
+    let mut text = String::new();
+
+    for &(t, x) in series.points() {
+        text += &format!("{} {}\n", time_format.format(t), x);
+    }
+
+    output::write(&text);
+}
+
+fn main() {
+    run();
+    output::finish();
+}
+
+/// Help for the parameters of each distribution accepted by `gen`.
+fn distribution_help() -> String {
+    let dist_help: Vec<String> = distribution::NAMES
+        .iter()
+        .map(|&(n, p)| format!("{} {}", n, p))
+        .collect();
+
+    format!("Parameters of the distribution: {}", dist_help.join(", "))
+}
+
+/// The command-line interface, from which arguments are parsed, and shell
+/// completions and the manual page are generated.
+fn cli<'a>(styles: &'a plot::Styles, dist_help: &'a str) -> App<'a, 'a> {
+    let dist_names: Vec<&str> = distribution::NAMES.iter().map(|&(n, _)| n).collect();
+
+    let app = App::new("dent")
+        .version(crate_version!())
+        .author("Joe Ranweiler <joe@lemma.co>")
+        .about("A tiny tool for t-tests &c.")
+        .setting(AppSettings::SubcommandsNegateReqs)
+        .subcommand(SubCommand::with_name("gen")
+                    .about("Generate random sample data from a named distribution")
+                    .setting(AppSettings::AllowNegativeNumbers)
+                    .arg(Arg::with_name("distribution")
+                         .value_name("DIST")
+                         .required(true)
+                         .possible_values(&dist_names)
+                         .help("Distribution to sample from"))
+                    .arg(Arg::with_name("params")
+                         .multiple(true)
+                         .value_name("PARAMS")
+                         .help(dist_help))
+                    .arg(Arg::with_name("count")
+                         .short("n")
+                         .long("count")
+                         .value_name("N")
+                         .takes_value(true)
+                         .default_value("100")
+                         .help("Number of values to generate"))
+                    .arg(Arg::with_name("seed")
+                         .long("seed")
+                         .value_name("SEED")
+                         .takes_value(true)
+                         .help("Seed for reproducible output [default: from clock]")))
+        .subcommand(SubCommand::with_name("lr")
+                    .about("Fit linear regressions to files of `X Y` pairs")
+                    .arg(Arg::with_name("files")
+                         .multiple(true)
+                         .value_name("FILES")
+                         .required(true)
+                         .help("Path to one or more files of `X Y` pairs, or - for stdin"))
+                    .arg(Arg::with_name("compare")
+                         .long("compare")
+                         .help("Test whether the slopes and intercepts of two inputs differ"))
+                    .arg(Arg::with_name("segmented")
+                         .long("segmented")
+                         .conflicts_with("compare")
+                         .help("Fit separate lines either side of an estimated breakpoint, to find knee points"))
+                    .arg(Arg::with_name("model")
+                         .long("model")
+                         .value_name("MODEL")
+                         .takes_value(true)
+                         .possible_values(&lr::MODEL_NAMES)
+                         .conflicts_with_all(&["compare", "segmented"])
+                         .help("Model to fit, by least squares on a log scale for exp and power [default: linear]"))
+                    .arg(Arg::with_name("deming")
+                         .long("deming")
+                         .conflicts_with_all(&["compare", "segmented", "model", "cv", "influence"])
+                         .help("Fit by Deming regression, for predictors which are measured with error too"))
+                    .arg(Arg::with_name("error_ratio")
+                         .long("error-ratio")
+                         .value_name("RATIO")
+                         .takes_value(true)
+                         .requires("deming")
+                         .help("Ratio of the error variance of Y to that of X, for --deming [default: 1, orthogonal]"))
+                    .arg(Arg::with_name("cv")
+                         .long("cv")
+                         .value_name("K")
+                         .takes_value(true)
+                         .conflicts_with_all(&["compare", "segmented"])
+                         .validator(|s| match s.parse::<usize>() {
+                             Ok(k) if k >= 2 => Ok(()),
+                             _ if s == "loo" => Ok(()),
+                             _ => Err("must be an integer of at least 2, or `loo`".to_string()),
+                         })
+                         .help("Report the RMSE of predictions by K-fold cross-validation, or leave-one-out for `loo`"))
+                    .arg(Arg::with_name("seed")
+                         .long("seed")
+                         .value_name("SEED")
+                         .takes_value(true)
+                         .requires("cv")
+                         .help("Seed for reproducible cross-validation folds [default: from clock]"))
+                    .arg(Arg::with_name("influence")
+                         .long("influence")
+                         .conflicts_with_all(&["compare", "segmented", "model"])
+                         .help("List the observations with the most influence on each fit, by Cook's distance"))
+                    .arg(Arg::with_name("confidence")
+                         .long("confidence")
+                         .value_name("LEVEL")
+                         .takes_value(true)
+                         .requires("segmented")
+                         .help("Confidence level of slope intervals, between 0 and 1 [default: 0.95]"))
+                    .arg(Arg::with_name("plot")
+                         .short("p")
+                         .long("plot")
+                         .conflicts_with("compare")
+                         .help("Print a scatter plot of each input"))
+                    .arg(Arg::with_name("smooth")
+                         .long("smooth")
+                         .requires("plot")
+                         .help("Draw a LOESS smoother through the scatter plot"))
+                    .arg(Arg::with_name("span")
+                         .long("span")
+                         .value_name("SPAN")
+                         .takes_value(true)
+                         .requires("smooth")
+                         .help("Fraction of points in each local fit of the smoother [default: 0.75]"))
+                    .arg(Arg::with_name("ascii")
+                         .long("ascii")
+                         .help("Use only ASCII characters in plots"))
+                    .arg(Arg::with_name("width")
+                         .short("w")
+                         .long("width")
+                         .value_name("WIDTH")
+                         .takes_value(true)
+                         .help("Width of scatter plot"))
+                    .arg(Arg::with_name("lax")
+                         .long("lax")
+                         .help("Ignore malformed input lines"))
+                    .arg(Arg::with_name("report_skipped")
+                         .long("report-skipped")
+                         .requires("lax")
+                         .help("Report lines ignored by --lax on stderr")))
+        .subcommand(SubCommand::with_name("fit")
+                    .about("Fit a distribution to sample data by maximum likelihood")
+                    .arg(Arg::with_name("files")
+                         .multiple(true)
+                         .value_name("FILES")
+                         .required(true)
+                         .help("Path to one or more files of sample data, or - for stdin"))
+                    .arg(Arg::with_name("dist")
+                         .long("dist")
+                         .value_name("DIST")
+                         .takes_value(true)
+                         .possible_values(&fit::FAMILY_NAMES)
+                         .help("Distribution to fit [default: normal]"))
+                    .arg(Arg::with_name("rank")
+                         .long("rank")
+                         .conflicts_with("dist")
+                         .help("Fit every distribution, and rank them by an information criterion"))
+                    .arg(Arg::with_name("criterion")
+                         .long("criterion")
+                         .value_name("CRITERION")
+                         .takes_value(true)
+                         .possible_values(&fit::CRITERION_NAMES)
+                         .requires("rank")
+                         .help("Criterion by which to rank distributions [default: aic]"))
+                    .arg(Arg::with_name("simulate")
+                         .long("simulate")
+                         .value_name("N")
+                         .takes_value(true)
+                         .conflicts_with("rank")
+                         .help("Print N values sampled from the fitted distribution, instead of the fit"))
+                    .arg(Arg::with_name("seed")
+                         .long("seed")
+                         .value_name("SEED")
+                         .takes_value(true)
+                         .requires("simulate")
+                         .help("Seed for reproducible --simulate output [default: from clock]"))
+                    .arg(Arg::with_name("lax")
+                         .long("lax")
+                         .help("Ignore malformed input lines"))
+                    .arg(Arg::with_name("report_skipped")
+                         .long("report-skipped")
+                         .requires("lax")
+                         .help("Report lines ignored by --lax on stderr")))
+        .subcommand(SubCommand::with_name("table")
+                    .about("Compare the rate of an event in two groups, from a 2×2 table of counts")
+                    .arg(Arg::with_name("file")
+                         .value_name("FILE")
+                         .required_unless("counts")
+                         .help("Path to a CSV file of two rows of two counts, or - for stdin"))
+                    .arg(Arg::with_name("counts")
+                         .long("table")
+                         .value_name("A,B,C,D")
+                         .takes_value(true)
+                         .conflicts_with("file")
+                         .help("Counts of the event and its absence in group 1, then in group 2"))
+                    .arg(Arg::with_name("confidence")
+                         .long("confidence")
+                         .value_name("LEVEL")
+                         .takes_value(true)
+                         .help("Confidence level of intervals, between 0 and 1 [default: 0.95]")))
+        .subcommand(SubCommand::with_name("anova")
+                    .about("Test the main effects of two factors and their interaction by two-way ANOVA")
+                    .arg(Arg::with_name("file")
+                         .value_name("FILE")
+                         .required(true)
+                         .help("Path to a file of `LEVEL LEVEL VALUE` lines, with an optional header naming the factors, or - for stdin")))
+        .subcommand(SubCommand::with_name("meta")
+                    .about("Combine effect estimates from independent comparisons, as across machines")
+                    .arg(Arg::with_name("file")
+                         .value_name("FILE")
+                         .required(true)
+                         .help("Path to a TSV file of LABEL, ESTIMATE, SE or LABEL, M1, SE1, M2, SE2 rows, or - for stdin"))
+                    .arg(Arg::with_name("confidence")
+                         .long("confidence")
+                         .value_name("LEVEL")
+                         .takes_value(true)
+                         .help("Confidence level of intervals, between 0 and 1 [default: 0.95]")))
+        .subcommand(SubCommand::with_name("bench")
+                    .about("Measure two shell commands over repeated runs, and compare their times and memory use")
+                    .arg(Arg::with_name("compare")
+                         .long("compare")
+                         .value_names(&["CMD_A", "CMD_B"])
+                         .number_of_values(2)
+                         .required(true)
+                         .help("Commands to run and compare"))
+                    .arg(Arg::with_name("runs")
+                         .short("n")
+                         .long("runs")
+                         .value_name("N")
+                         .takes_value(true)
+                         .help("Number of runs of each command [default: 10]"))
+                    .arg(Arg::with_name("interleave")
+                         .long("interleave")
+                         .help("Alternate runs of the commands, to control for drift in the machine's state"))
+                    .arg(Arg::with_name("rerun_outliers")
+                         .long("rerun-outliers")
+                         .help("Run again, once, each of the first runs whose wall time is an outlier, and report the reruns beside them"))
+                    .arg(Arg::with_name("target_precision")
+                         .long("target-precision")
+                         .value_name("WIDTH")
+                         .takes_value(true)
+                         .help("Add runs until the interval of each mean wall time is at most WIDTH of it, such as 2%"))
+                    .arg(Arg::with_name("max_runs")
+                         .long("max-runs")
+                         .value_name("N")
+                         .takes_value(true)
+                         .requires("target_precision")
+                         .help("Most runs of each command to reach the target precision [default: 100]"))
+                    .arg(Arg::with_name("prepare")
+                         .long("prepare")
+                         .value_name("CMD")
+                         .takes_value(true)
+                         .help("Command to run, untimed, before the runs, or before each with --cold"))
+                    .arg(Arg::with_name("cleanup")
+                         .long("cleanup")
+                         .value_name("CMD")
+                         .takes_value(true)
+                         .help("Command to run, untimed, after the runs, or after each with --cold"))
+                    .arg(Arg::with_name("cold")
+                         .long("cold")
+                         .requires("prepare")
+                         .help("Run --prepare before each run, as to clear caches, and --cleanup after"))
+                    .arg(Arg::with_name("allow_failure")
+                         .long("allow-failure")
+                         .help("Keep runs which exit with a failing status, and count them"))
+                    .arg(Arg::with_name("save_raw")
+                         .long("save-raw")
+                         .value_name("FILE")
+                         .takes_value(true)
+                         .help("Save every run's measurements to FILE, as JSON if it ends in .json, or else as CSV"))
+                    .arg(Arg::with_name("confidence")
+                         .long("confidence")
+                         .value_name("LEVEL")
+                         .takes_value(true)
+                         .help("Confidence level of the speedup interval, between 0 and 1 [default: 0.95]")))
+        .subcommand(SubCommand::with_name("drift")
+                    .about("Measure how far the distribution of a sample has drifted from a baseline, exiting with status 2 if it exceeds any threshold")
+                    .arg(Arg::with_name("baseline")
+                         .value_name("OLD")
+                         .required(true)
+                         .help("Path to the baseline sample, or - for stdin"))
+                    .arg(Arg::with_name("current")
+                         .value_name("NEW")
+                         .required(true)
+                         .help("Path to the current sample, or - for stdin"))
+                    .arg(Arg::with_name("bins")
+                         .long("bins")
+                         .value_name("N")
+                         .takes_value(true)
+                         .help("Number of bins, at quantiles of the baseline [default: 10]"))
+                    .arg(Arg::with_name("max_psi")
+                         .long("max-psi")
+                         .value_name("PSI")
+                         .takes_value(true)
+                         .help("Population stability index above which to flag drift [default: 0.25]"))
+                    .arg(Arg::with_name("max_kl")
+                         .long("max-kl")
+                         .value_name("KL")
+                         .takes_value(true)
+                         .help("Kullback-Leibler divergence above which to flag drift"))
+                    .arg(Arg::with_name("max_js")
+                         .long("max-js")
+                         .value_name("JS")
+                         .takes_value(true)
+                         .help("Jensen-Shannon divergence above which to flag drift"))
+                    .arg(Arg::with_name("lax")
+                         .long("lax")
+                         .help("Ignore malformed input lines"))
+                    .arg(Arg::with_name("report_skipped")
+                         .long("report-skipped")
+                         .requires("lax")
+                         .help("Report lines ignored by --lax on stderr")))
+        .subcommand(SubCommand::with_name("series")
+                    .about("Read `TIMESTAMP VALUE` lines as one time-ordered series")
+                    .arg(Arg::with_name("files")
+                         .multiple(true)
+                         .value_name("FILES")
+                         .required(true)
+                         .help("Path to one or more files of RFC 3339 or epoch timestamps and values, or - for stdin"))
+                    .arg(Arg::with_name("resample")
+                         .long("resample")
+                         .value_names(&["INTERVAL", "STAT"])
+                         .number_of_values(2)
+                         .help("Reduce each INTERVAL (e.g. 30s, 1m, 1h) by STAT: mean, median, min, max, sum, or count"))
+                    .arg(Arg::with_name("aggregate")
+                         .long("aggregate")
+                         .value_names(&["WINDOW", "STAT"])
+                         .number_of_values(2)
+                         .help("Reduce each run of WINDOW consecutive observations by STAT, after any --resample"))
+                    .arg(Arg::with_name("summary")
+                         .long("summary")
+                         .help("Report the longest rise and fall, the largest drawup and drawdown, and crossings of a threshold, instead of the series"))
+                    .arg(Arg::with_name("threshold")
+                         .long("threshold")
+                         .value_name("X")
+                         .allow_hyphen_values(true)
+                         .requires("summary")
+                         .help("Count crossings of X, rather than of the median"))
+                    .arg(Arg::with_name("sort_time")
+                         .long("sort-time")
+                         .help("Sort observations by time without warning of any out of order within a file"))
+                    .arg(Arg::with_name("max_gap")
+                         .long("max-gap")
+                         .value_name("DURATION")
+                         .takes_value(true)
+                         .help("Warn of gaps between observations longer than DURATION (e.g. 30s, 1m, 1h)"))
+                    .arg(Arg::with_name("lax")
+                         .long("lax")
+                         .help("Ignore malformed input lines"))
+                    .arg(Arg::with_name("report_skipped")
+                         .long("report-skipped")
+                         .requires("lax")
+                         .help("Report lines ignored by --lax on stderr")))
+        .subcommand(SubCommand::with_name("completions")
+                    .about("Print a completion script for a shell")
+                    .arg(Arg::with_name("shell")
+                         .value_name("SHELL")
+                         .required(true)
+                         .possible_values(&Shell::variants())
+                         .help("Shell to complete commands for")))
+        .subcommand(SubCommand::with_name("man")
+                    .about("Print a manual page in roff format"))
+        .arg(Arg::with_name("stdin")
+             .short("s")
+             .long("stdin")
+             .help("Read and summarize data from stdin, after any files"))
+        .arg(Arg::with_name("files")
+             .multiple(true)
+             .value_name("FILES")
+             .takes_value(true)
+             .required_unless_one(&["stdin", "stats"])
+             .help("Path to one or more files of sample data, or - for stdin"))
+        .arg(Arg::with_name("stats")
+             .long("stats")
+             .value_name("N,MEAN,SD")
+             .takes_value(true)
+             .multiple(true)
+             .number_of_values(1)
+             .conflicts_with_all(&["files", "stdin"])
+             .validator(|s| Stats::parse(&s).map(|_| ()))
+             .help("Run a t-test from the aggregates of two samples, given twice"))
+        .arg(Arg::with_name("lax")
+             .long("lax")
+             .help("Ignore non-numeric input lines"))
+        .arg(Arg::with_name("report_skipped")
+             .long("report-skipped")
+             .requires("lax")
+             .help("Report lines ignored by --lax on stderr"))
+        .arg(Arg::with_name("weighted")
+             .long("weighted")
+             .conflicts_with("drop_nonfinite")
+             .help("Read lines of the form `VALUE WEIGHT` and compute weighted statistics"))
+        .arg(Arg::with_name("freq")
+             .long("freq")
+             .conflicts_with_all(&["weighted", "drop_nonfinite"])
+             .help("Read lines of the form `VALUE COUNT`, as from a histogram export"))
+        .arg(Arg::with_name("categorical")
+             .long("categorical")
+             .conflicts_with_all(&[
+                 "weighted", "freq", "diff", "ratio", "from_summary", "stats", "lax", "drop_nonfinite",
+                 "quick", "robust", "auto", "explain", "speedup", "throughput", "tsv", "quantiles",
+                 "shift",
+             ])
+             .help("Read each line as a label, and report the count and proportion of each, with a bar chart"))
+        .arg(Arg::with_name("benford")
+             .long("benford")
+             .conflicts_with_all(&[
+                 "weighted", "freq", "categorical", "diff", "ratio", "from_summary", "stats",
+                 "drop_nonfinite", "quick", "robust", "auto", "explain", "speedup", "throughput",
+                 "tsv", "quantiles", "shift",
+             ])
+             .help("Check the first digits of each input against Benford's law, with a bar chart"))
+        .arg(Arg::with_name("diversity")
+             .long("diversity")
+             .requires("categorical")
+             .help("Also report Shannon entropy, Gini impurity, and Simpson's index, with bootstrap intervals"))
+        .arg(Arg::with_name("stratify")
+             .long("stratify")
+             .value_name("COL")
+             .takes_value(true)
+             .conflicts_with_all(&[
+                 "weighted", "freq", "diff", "ratio", "from_summary", "stats", "categorical",
+                 "drop_nonfinite", "robust", "auto", "explain", "speedup", "throughput", "tsv",
+                 "quantiles", "shift", "plot",
+             ])
+             .help("Compare two inputs within each stratum, labeled by field COL of each line, and pool the differences"))
+        .arg(Arg::with_name("repeated")
+             .long("repeated")
+             .conflicts_with_all(&[
+                 "weighted", "freq", "diff", "ratio", "from_summary", "stats", "categorical",
+                 "stratify", "lax", "drop_nonfinite", "quick", "robust", "auto", "explain",
+                 "speedup", "throughput", "tsv", "quantiles", "shift", "plot",
+             ])
+             .help("Compare inputs whose lines measure the same subjects, by repeated-measures ANOVA and the Friedman test"))
+        .arg(Arg::with_name("derive")
+             .long("derive")
+             .value_name("EXPR")
+             .takes_value(true)
+             .conflicts_with_all(&[
+                 "weighted", "freq", "categorical", "benford", "stratify", "from_summary", "stats",
+             ])
+             .validator(|s| Expr::parse(&s).map(|_| ()).map_err(|e| e.to_string()))
+             .help("Read inputs with a header line naming their columns, and summarize EXPR of each row, e.g. '(a - b) / b'"))
+        .arg(Arg::with_name("bland_altman")
+             .long("bland-altman")
+             .conflicts_with_all(&[
+                 "weighted", "freq", "categorical", "benford", "stratify", "repeated", "diff",
+                 "ratio", "from_summary", "stats",
+             ])
+             .help("Compare two methods measuring the same subjects by the bias and limits of agreement of their differences"))
+        .arg(Arg::with_name("passing_bablok")
+             .long("passing-bablok")
+             .conflicts_with_all(&[
+                 "weighted", "freq", "categorical", "benford", "stratify", "repeated", "diff",
+                 "ratio", "from_summary", "stats",
+             ])
+             .help("Compare two methods measuring the same subjects by Passing-Bablok regression of the second on the first"))
+        .arg(Arg::with_name("diff")
+             .long("diff")
+             .conflicts_with_all(&["weighted", "freq", "lax"])
+             .help("Summarize the element-wise differences of two paired inputs, second minus first"))
+        .arg(Arg::with_name("ratio")
+             .long("ratio")
+             .conflicts_with_all(&["weighted", "freq", "lax"])
+             .help("Like --diff, but summarize the ratios of the second input to the first"))
+        .arg(Arg::with_name("from_summary")
+             .long("from-summary")
+             .conflicts_with_all(&[
+                 "weighted", "freq", "diff", "ratio", "lax", "drop_nonfinite", "quantiles", "shift",
+             ])
+             .help("Read summary tables written by --tsv instead of sample data"))
+        .arg(Arg::with_name("drop_nonfinite")
+             .long("drop-nonfinite")
+             .help("Drop NaN and infinite values instead of rejecting the sample"))
+        .arg(Arg::with_name("streaming")
+             .long("streaming")
+             .conflicts_with_all(&[
+                 "stats", "from_summary", "weighted", "freq", "categorical", "benford", "stratify",
+                 "repeated", "derive", "bland_altman", "passing_bablok", "diff", "ratio",
+                 "quantiles", "shift", "robust", "auto", "speedup", "throughput", "round",
+                 "bucket", "dedup", "quick", "winsorize", "trim_tails", "rank_of", "histogram",
+                 "hist", "freq_table", "threshold", "show_extremes", "verbose",
+             ])
+             .help("Summarize each input in one pass as it is read, without holding its values in memory. Quartiles are estimated"))
+        .arg(Arg::with_name("quantiles")
+             .long("quantiles")
+             .conflicts_with("tsv")
+             .help("Compare the p10, p25, p50, p75, p90, and p99 of two inputs"))
+        .arg(Arg::with_name("shift")
+             .long("shift")
+             .conflicts_with_all(&["tsv", "weighted", "freq"])
+             .help("Plot the shift function (decile differences) of two inputs"))
+        .arg(Arg::with_name("resamples")
+             .long("resamples")
+             .value_name("N")
+             .takes_value(true)
+             .help("Bootstrap resamples for shift function, speedup, and diversity intervals [default: 1000]"))
+        .arg(Arg::with_name("seed")
+             .long("seed")
+             .value_name("SEED")
+             .takes_value(true)
+             .help("Seed for reproducible bootstrap intervals and subsamples [default: from clock]"))
+
+        .arg(Arg::with_name("quick")
+             .long("quick")
+             .value_name("N")
+             .takes_value(true)
+             .conflicts_with_all(&["freq", "from_summary", "stats"])
+             .help("Summarize a random subsample of at most N values of each input, for a quick look"))
+        .arg(Arg::with_name("robust")
+             .long("robust")
+             .conflicts_with_all(&["tsv", "weighted", "freq", "from_summary", "stats", "speedup"])
+             .help("Use robust estimators: median and MAD, Mann–Whitney U, and Hodges–Lehmann shift"))
+        .arg(Arg::with_name("auto")
+             .long("auto")
+             .conflicts_with_all(&["robust", "tsv", "weighted", "freq", "from_summary", "stats", "speedup"])
+             .help("Check normality and equal variances of two inputs, and choose a test to suit"))
+        .arg(Arg::with_name("explain")
+             .long("explain")
+             .conflicts_with_all(&["tsv", "robust", "auto", "stats"])
+             .help("Describe the comparison of two inputs in a plain-language sentence"))
+        .arg(Arg::with_name("reference")
+             .long("reference")
+             .value_name("INPUT")
+             .takes_value(true)
+             .conflicts_with_all(&["tsv", "robust", "auto", "stats"])
+             .help("Compare each other input against INPUT, given by its path or 1-based index, as a baseline, with p-values adjusted for the number of inputs"))
+        .arg(Arg::with_name("speedup")
+             .long("speedup")
+             .conflicts_with_all(&["tsv", "weighted", "freq", "diff", "ratio", "stats"])
+             .help("Compare the means of two inputs as a speedup, first over second, with an interval"))
+        .arg(Arg::with_name("throughput")
+             .long("throughput")
+             .conflicts_with_all(&["tsv", "stats"])
+             .help("Treat values as durations in seconds, and also report operations per second"))
+        .arg(Arg::with_name("round")
+             .long("round")
+             .value_name("STEP")
+             .takes_value(true)
+             .conflicts_with_all(&["bucket", "from_summary", "stats"])
+             .help("Round each value to the nearest multiple of STEP before summarizing, e.g. 0.5"))
+        .arg(Arg::with_name("bucket")
+             .long("bucket")
+             .value_name("WIDTH")
+             .takes_value(true)
+             .conflicts_with_all(&["from_summary", "stats"])
+             .help("Replace each value with the lower edge of its bucket of WIDTH before summarizing"))
+        .arg(Arg::with_name("dedup")
+             .long("dedup")
+             .value_name("MODE")
+             .min_values(0)
+             .require_equals(true)
+             .possible_values(&DEDUP_NAMES)
+             .conflicts_with_all(&[
+                 "weighted", "freq", "diff", "ratio", "repeated", "from_summary", "stats",
+             ])
+             .help("Drop values equal to an earlier one, or with --dedup=consecutive, to the one before"))
+        .arg(Arg::with_name("winsorize")
+             .long("winsorize")
+             .value_name("FRACTION")
+             .takes_value(true)
+             .conflicts_with_all(&[
+                 "trim_tails", "weighted", "freq", "repeated", "from_summary", "stats",
+             ])
+             .help("Replace the FRACTION of values at each end of each input with the nearest value kept, e.g. 0.01"))
+        .arg(Arg::with_name("trim_tails")
+             .long("trim-tails")
+             .value_name("FRACTION")
+             .takes_value(true)
+             .conflicts_with_all(&["weighted", "freq", "repeated", "from_summary", "stats"])
+             .help("Drop the FRACTION of values at each end of each input before summarizing, e.g. 0.01"))
+        .arg(Arg::with_name("rank_of")
+             .long("rank-of")
+             .value_name("X")
+             .takes_value(true)
+             .multiple(true)
+             .number_of_values(1)
+             .allow_hyphen_values(true)
+             .validator(|s| s.parse::<f64>().map(|_| ()).map_err(|e| e.to_string()))
+             .conflicts_with_all(&["tsv", "weighted", "freq", "from_summary", "stats"])
+             .help("Also report the percentile and rank at which X falls in each input, e.g. 250"))
+        .arg(Arg::with_name("histogram")
+             .long("histogram")
+             .value_name("STYLE")
+             .min_values(0)
+             .require_equals(true)
+             .possible_values(&HISTOGRAM_STYLES)
+             .conflicts_with_all(&["tsv", "weighted", "freq", "from_summary", "stats"])
+             .help("Also print a histogram of each input, with bars across rows, or up columns if vertical [default: horizontal]"))
+        .arg(Arg::with_name("hist")
+             .long("hist")
+             .conflicts_with_all(&["histogram", "tsv", "weighted", "freq", "from_summary", "stats"])
+             .help("Also plot a histogram of each input with vertical bars, as --histogram=vertical"))
+        .arg(Arg::with_name("bins")
+             .long("bins")
+             .value_name("SPEC")
+             .takes_value(true)
+             .validator(|s| Binning::parse(&s).map(|_| ()).map_err(|e| e.to_string()))
+             .help("Bins of histograms and the frequency table: N of equal width, log:N of equal width in log scale, or quantile:N of equal count [default: 10]"))
+        .arg(Arg::with_name("freq_table")
+             .long("freq-table")
+             .conflicts_with_all(&["weighted", "freq", "from_summary", "stats"])
+             .help("Also print the count, proportion, and cumulative percentage of each bin of each input, or only these in a machine format"))
+        .arg(Arg::with_name("overlay")
+             .long("overlay")
+             .requires("histogram")
+             .conflicts_with("hist")
+             .help("Overlay the horizontal histograms of two inputs on one plot, as proportions of each, over bins of equal width"))
+        .arg(Arg::with_name("threshold")
+             .long("threshold")
+             .value_name("X")
+             .takes_value(true)
+             .multiple(true)
+             .number_of_values(1)
+             .allow_hyphen_values(true)
+             .validator(|s| match s.parse::<f64>() {
+                 Ok(x) if !x.is_nan() => Ok(()),
+                 _ => Err("must be a number".to_string()),
+             })
+             .conflicts_with_all(&["tsv", "weighted", "freq", "from_summary", "stats"])
+             .help("Also report how many values of each input exceed X, with an interval, and their mean, e.g. 300"))
+        .arg(Arg::with_name("exceedance_test")
+             .long("exceedance-test")
+             .requires("threshold")
+             .help("Also test whether the proportion exceeding each --threshold differs between two inputs"))
+        .arg(Arg::with_name("show_extremes")
+             .long("show-extremes")
+             .value_name("K")
+             .takes_value(true)
+             .validator(|s| s.parse::<usize>().map(|_| ()).map_err(|e| e.to_string()))
+             .conflicts_with_all(&["tsv", "from_summary", "stats"])
+             .help("Also list the K smallest and largest values of each input, with their line numbers"))
+        .arg(Arg::with_name("verbose")
+             .short("v")
+             .long("verbose")
+             .conflicts_with_all(&["tsv", "weighted", "freq", "from_summary", "stats"])
+             .help("Also report counts of zeros, negative values, duplicates, and distinct values of each input"))
+        .arg(Arg::with_name("tsv")
+             .long("tsv")
+             .help("Print summary data to stdout in TSV format"))
+        .arg(Arg::with_name("brief")
+             .long("brief")
+             .conflicts_with_all(&[
+                 "tsv", "format", "plot", "freq_table", "robust", "auto", "reference", "explain",
+                 "speedup",
+             ])
+             .help("Print one line of the size, five-number summary, and mean ± SE of each input"))
+        .arg(Arg::with_name("vertical")
+             .long("vertical")
+             .conflicts_with_all(&["tsv", "format", "brief"])
+             .help("Print summary tables with a row per statistic and a column per input"))
+        .arg(Arg::with_name("sparkline")
+             .long("sparkline")
+             .requires("brief")
+             .conflicts_with_all(&["streaming", "from_summary", "stats", "weighted", "freq"])
+             .help("End each --brief line with a sparkline of the input's histogram, over the --bins shared by all inputs"))
+        .arg(Arg::with_name("plot_outliers")
+             .long("outliers")
+             .help("Include outliers and use min/max for outer fences of boxplot"))
+        .arg(Arg::with_name("plot")
+             .short("p")
+             .long("plot")
+             .help("Print standard boxplots"))
+        .arg(Arg::with_name("ascii")
+             .long("ascii")
+             .conflicts_with("plot_style")
+             .help("Use only ASCII characters in boxplots"))
+        .arg(Arg::with_name("plot_style")
+             .long("plot-style")
+             .value_name("STYLE")
+             .takes_value(true)
+             .possible_values(&styles.names())
+             .help("Named glyph set to use in boxplots"))
+        .arg(Arg::with_name("subcell")
+             .long("subcell")
+             .conflicts_with_all(&["ascii", "plot_style"])
+             .help("Draw boxplots with sub-column precision using block characters"))
+        .arg(Arg::with_name("ci_band")
+             .long("ci-band")
+             .help("Draw the confidence interval of the mean on boxplots"))
+        .arg(Arg::with_name("confidence")
+             .long("confidence")
+             .value_name("LEVEL")
+             .takes_value(true)
+             .help("Confidence level of intervals, between 0 and 1 [default: 0.95]"))
+        .arg(Arg::with_name("alpha")
+             .long("alpha")
+             .value_name("ALPHA")
+             .takes_value(true)
+             .help("Significance level at which to report a t-test verdict, or pairwise tests of more than two samples, e.g. 0.05"))
+        .arg(Arg::with_name("width")
+             .short("w")
+             .long("width")
+             .value_name("WIDTH")
+             .takes_value(true)
+             .help("Width of boxplot"))
+        .arg(Arg::with_name("format")
+             .long("format")
+             .value_name("FORMAT")
+             .takes_value(true)
+             .possible_values(&config::FORMATS)
+             .conflicts_with("tsv")
+             .help("Format of summary output [default: text]"))
+        .arg(Arg::with_name("precision")
+             .long("precision")
+             .value_name("DIGITS")
+             .takes_value(true)
+             .help("Significant digits of test results [default: all]"))
+        .arg(Arg::with_name("unit")
+             .long("unit")
+             .value_name("UNIT")
+             .takes_value(true)
+             .help("Unit of the input values, such as ms, to label summaries, plots, and TSV output"))
+        .arg(Arg::with_name("stable_output")
+             .long("stable-output")
+             .global(true)
+             .help("Ignore the terminal and any config, and seed random numbers with a fixed default, for reproducible output"))
+        .arg(Arg::with_name("json")
+             .long("json")
+             .global(true)
+             .help("Write errors and warnings to stderr as JSON objects, one per line"))
+        .arg(Arg::with_name("no_pager")
+             .long("no-pager")
+             .global(true)
+             .help("Never page output which is taller than the terminal"));
+
+    with_db(app)
+}
+
+/// Add the options of the results database, if built with the `sqlite`
+/// feature.
+#[cfg(feature = "sqlite")]
+fn with_db<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    let meta = Arg::with_name("meta")
+        .long("meta")
+        .value_name("KEY=VALUE")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .validator(|s| db::parse_meta(&s).map(|_| ()));
+
+    app.subcommand(SubCommand::with_name("query")
+                   .about("Print the history of results recorded with --db")
+                   .arg(Arg::with_name("db")
+                        .value_name("DB")
+                        .required(true)
+                        .help("Path to a results database"))
+                   .arg(Arg::with_name("source")
+                        .long("source")
+                        .value_name("SOURCE")
+                        .takes_value(true)
+                        .help("Print only results for the given source"))
+                   .arg(meta.clone()
+                        .help("Print only runs recorded with the given metadata"))
+                   .arg(Arg::with_name("last")
+                        .long("last")
+                        .value_name("N")
+                        .takes_value(true)
+                        .validator(|s| s.parse::<usize>().map(|_| ()).map_err(|e| e.to_string()))
+                        .help("Print only the last N matching runs"))
+                   .arg(Arg::with_name("tests")
+                        .long("tests")
+                        .help("Print t-tests instead of summaries")))
+        .arg(Arg::with_name("db")
+             .long("db")
+             .value_name("DB")
+             .takes_value(true)
+             .help("Record summaries, and the t-test of two, in a SQLite results database"))
+        .arg(meta
+             .requires("db")
+             .help("Record metadata with results in the database, such as a label or commit"))
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn with_db<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    app
+}
+
+/// Exit on an error in the arguments, found before `--json` could be read, so
+/// look for it among them. Help and version requests are not errors.
+fn usage_error(e: clap::Error) -> ! {
+    let json = env::args_os().any(|a| a == "--json");
+
+    match e.kind {
+        clap::ErrorKind::HelpDisplayed | clap::ErrorKind::VersionDisplayed => e.exit(),
+        _ if !json => e.exit(),
+        _ => {
+            log::set_json(true);
+            log::fail(&log::UsageError(e));
+            output::exit(1);
+        },
+    }
+}
+
+fn run() {
+    let styles = plot::Styles::default();
+    let dist_help = distribution_help();
+
+    let matches = match cli(&styles, &dist_help).get_matches_safe() {
+        Ok(matches) => matches,
+        Err(e) => usage_error(e),
+    };
+
+    log::set_json(matches.is_present("json"));
+
+    // Written directly, since these are not for reading in a pager.
+    if let Some(m) = matches.subcommand_matches("completions") {
+        // Validated by clap, so we can unwrap.
+        let shell = arg_value::<Shell>(m, "shell");
+
+        return cli(&styles, &dist_help).gen_completions_to("dent", shell, &mut io::stdout());
+    }
+
+    if matches.subcommand_matches("man").is_some() {
+        let page = man::page("dent", crate_version!(), || cli(&styles, &dist_help));
+
+        return ok!(io::stdout().write_all(page.as_bytes()));
+    }
+
+    if matches.is_present("stable_output") {
+        output::ignore_terminal();
+    }
+
+    if !matches.is_present("no_pager") {
+        output::page();
+    }
+
+    // Stable output must not depend on the config of whoever runs it.
+    let config = if matches.is_present("stable_output") {
+        Config::default()
+    } else {
+        ok!(Config::load())
+    };
+
+    if let Some(m) = matches.subcommand_matches("gen") {
+        return generate(m);
+    }
+
+    if let Some(m) = matches.subcommand_matches("lr") {
+        return linear_regression(m, &config);
+    }
+
+    if let Some(m) = matches.subcommand_matches("fit") {
+        return fit_distribution(m);
+    }
+
+    if let Some(m) = matches.subcommand_matches("table") {
+        return contingency_table(m);
+    }
+
+    if let Some(m) = matches.subcommand_matches("anova") {
+        return two_way_anova(m);
+    }
+
+    if let Some(m) = matches.subcommand_matches("meta") {
+        return meta_analysis(m);
+    }
+
+    if let Some(m) = matches.subcommand_matches("bench") {
+        return compare_commands(m);
+    }
+
+    if let Some(m) = matches.subcommand_matches("drift") {
+        return distribution_drift(m);
+    }
+
+    if let Some(m) = matches.subcommand_matches("series") {
+        return time_series(m);
+    }
+
+    #[cfg(feature = "sqlite")]
+    {
+        if let Some(m) = matches.subcommand_matches("query") {
+            return query_results(m);
+        }
+    }
+
+    // Flags take precedence over the config, which may choose ASCII or a style.
+    let style_name = if matches.is_present("ascii") {
+        "ascii"
+    } else if let Some(name) = matches.value_of("plot_style") {
+        name
+    } else if config.ascii == Some(true) {
+        "ascii"
+    } else {
+        config.plot_style.as_deref().unwrap_or("unicode")
+    };
+    let style = match styles.get(style_name) {
+        Some(style) => style,
+        None => {
+            log::error(&format!("Unknown plot style: {:?}", style_name));
+            output::exit(1);
+        },
+    };
+    let lax_parsing = matches.is_present("lax");
+    let use_stdin = matches.is_present("stdin");
+    let outliers = matches.is_present("plot_outliers");
+    let summary_format = match matches.value_of("format").or(config.format.as_deref()) {
+        _ if matches.is_present("tsv") => SummaryFormat::Tsv,
+        _ if matches.is_present("brief") => SummaryFormat::Brief,
+        Some("tsv") => SummaryFormat::Tsv,
+        Some("prometheus") => SummaryFormat::Prometheus,
+        _ => SummaryFormat::Text,
+    };
+    let speedup = matches.is_present("speedup");
+    let explain = matches.is_present("explain");
+
+    let confidence = parse_confidence(&matches);
+
+    let alpha = match matches.value_of("alpha") {
+        Some(a) => match a.parse::<f64>() {
+            Ok(a) if 0.0 < a && a < 1.0 => Some(SigLevel::Alpha(a)),
+            _ => {
+                log::error(&format!("Invalid significance level: {:?}", a));
+                output::exit(1);
+            },
+        },
+        None => config.alpha.map(SigLevel::Alpha),
+    };
+
+    let precision = match matches.value_of("precision") {
+        Some(p) => match p.parse::<usize>() {
+            Ok(p) if p > 0 => Some(p),
+            _ => {
+                log::error(&format!("Invalid precision: {:?}", p));
+                output::exit(1);
+            },
+        },
+        None => config.precision,
+    };
+
+    let unit = matches.value_of("unit").map(|u| u.to_string());
+
+    let reference = matches.value_of("reference").map(|r| r.to_string());
+    let vertical = matches.is_present("vertical");
+
+    let report = ReportOptions { outliers, alpha, precision, unit, reference, vertical };
+
+    // The width is only checked if there is a plot to fit to it.
+    let plot_opts = if matches.is_present("plot") {
+        Some(PlotOptions {
+            width: plot_width(&matches, &config),
+            style,
+            subcell: matches.is_present("subcell"),
+            outliers,
+            mean_ci: if matches.is_present("ci_band") { Some(confidence) } else { None },
+        })
+    } else {
+        None
+    };
+    let plot_opts = plot_opts.as_ref();
+
+    if let Some(stats) = matches.values_of("stats") {
+        // Validated by clap, so we can unwrap.
+        let stats: Vec<Stats> = stats
+            .map(|s| Stats::parse(s).unwrap_or_else(|_| unreachable!()))
+            .collect();
+
+        if stats.len() != 2 {
+            log::error("T-test from statistics requires exactly two --stats");
+            output::exit(1);
+        }
+
+        return display_stats_t_test(&stats[0], &stats[1], &report);
+    }
+
+    // Required unless `stdin` or `stats` is present, so it is nonempty.
+    let mut inputs: Vec<&str> = matches
+        .values_of("files")
+        .map(|fs| fs.collect())
+        .unwrap_or_default();
+
+    if use_stdin {
+        inputs.push(STDIN_PATH);
+    }
+
+    if inputs.iter().filter(|&&i| i == STDIN_PATH).count() > 1 {
+        log::error("Standard input may only be read once");
+        output::exit(1);
+    }
+
+    // Checked here, not by clap, which would apply the conflicts of each of
+    // these with other options to all of them.
+    let resampled = ["shift", "speedup", "diversity"].iter().any(|&a| matches.is_present(a));
+
+    if matches.is_present("resamples") && !resampled {
+        log::error("--resamples requires --shift, --speedup, or --diversity");
+        output::exit(1);
+    }
+
+    if matches.is_present("seed") && !(resampled || matches.is_present("quick")) {
+        log::error("--seed requires --shift, --speedup, --diversity, or --quick");
+        output::exit(1);
+    }
+
+    if matches.is_present("categorical") {
+        let diversity = if matches.is_present("diversity") {
+            Some((bootstrap_resamples(&matches), seeded_rng(&matches)))
+        } else {
+            None
+        };
+
+        let width = plot_width(&matches, &config);
+
+        return display_categorical(&inputs, confidence, width, style.is_ascii(), diversity);
+    }
+
+    if matches.is_present("benford") {
+        let width = plot_width(&matches, &config);
+
+        return display_benford(&inputs, matches.is_present("lax"), width, style.is_ascii(), &report);
+    }
+
+    if matches.is_present("from_summary") {
+        let mut summaries = vec![];
+        let mut names = vec![];
+
+        for table in ok!(input::read_texts(&inputs)) {
+            for (src, s) in ok!(summary::parse_summary_table(&table)) {
+                names.push(src);
+                summaries.push(report.annotate(s));
+            }
+        }
+
+        let sources: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
+
+        // Without sample data, there is no bootstrap to fall back to.
+        let ratio = if speedup {
+            check_speedup_inputs(summaries.len());
+            Some(ok!(RatioOfMeans::fieller(&summaries[0], &summaries[1], confidence)))
+        } else {
+            None
+        };
+
+        display(&summaries, &sources, summary_format, plot_opts, &report, ratio.as_ref());
+        record_results(&matches, &summaries, &sources);
+
+        if explain {
+            check_explain_inputs(summaries.len());
+            print_explanation(&summaries, &sources, alpha);
+        }
+
+        return;
+    }
+
+    let nonfinite_policy = if matches.is_present("drop_nonfinite") {
+        NonFinitePolicy::DropWithReport
+    } else {
+        NonFinitePolicy::Reject
+    };
+
+    if matches.is_present("streaming") {
+        let format = summary_format;
+
+        return display_streaming(&matches, &inputs, nonfinite_policy, format, plot_opts, &report);
+    }
+
+    let stratify_column = matches.value_of("stratify").map(|_| {
+        match arg_value::<usize>(&matches, "stratify") {
+            0 => {
+                log::error("Stratum columns are numbered from 1");
+                output::exit(1);
+            },
+            column => column - 1,
+        }
+    });
+
+    if stratify_column.is_some() && inputs.len() != 2 {
+        log::error("Stratified comparison requires exactly two inputs");
+        output::exit(1);
+    }
+
+    let format = if let Some(column) = stratify_column {
+        input::Format::Stratified(column)
+    } else if matches.is_present("weighted") {
+        input::Format::Weighted
+    } else if matches.is_present("freq") {
+        input::Format::Frequency
+    } else {
+        input::Format::Values
+    };
+
+    let paired_op = if matches.is_present("ratio") {
+        Some(paired::Operation::Ratio)
+    } else if matches.is_present("diff") {
+        Some(paired::Operation::Difference)
+    } else {
+        None
+    };
+
+    let repeated = matches.is_present("repeated");
+
+    if repeated && inputs.len() < 2 {
+        log::error("Repeated-measures comparison requires at least two inputs");
+        output::exit(1);
+    }
+
+    let agreement = Agreement {
+        bland_altman: matches.is_present("bland_altman"),
+        passing_bablok: matches.is_present("passing_bablok"),
+    };
+    let agreement = if agreement.bland_altman || agreement.passing_bablok {
+        Some(agreement)
+    } else {
+        None
+    };
+
+    if agreement.is_some() && inputs.len() != 2 {
+        log::error("Method comparison requires exactly two inputs");
+        output::exit(1);
+    }
+
+    if paired_op.is_some() && inputs.len() != 2 {
+        log::error("Paired comparison requires exactly two inputs");
+        output::exit(1);
+    }
+
+    let mut parsed = match matches.value_of("derive") {
+        Some(e) => ok!(input::read_derived(&inputs, &ok!(Expr::parse(e)), lax_parsing)),
+        None => ok!(input::read_inputs(&inputs, format, lax_parsing)),
+    };
+
+    if let Some(q) = parse_quantizer(&matches) {
+        for x in parsed.iter_mut().flat_map(|p| p.data.iter_mut()) {
+            *x = q.apply(*x);
+        }
+    }
+
+    let sources: Vec<&str> = inputs
+        .iter()
+        .map(|&i| input::source_name(i))
+        .collect();
+
+    if matches.is_present("report_skipped") {
+        for (p, src) in parsed.iter().zip(&sources) {
+            input::report_skipped(src, &p.skipped);
+        }
+    }
+
+    if repeated {
+        let labels = source_labels(&sources);
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+
+        return display_repeated(&parsed, &labels, &report);
+    }
+
+    if let Some(ref agreement) = agreement {
+        let plot = plot_opts.map(|o| (o.width, style.is_ascii()));
+        let labels = source_labels(&sources);
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+
+        return display_agreement(&parsed, &labels, agreement, confidence, plot, &report);
+    }
+
+    // Replace the paired inputs with their derived sample.
+    let paired_label;
+    let (mut parsed, sources) = match paired_op {
+        Some(op) => {
+            let data = ok!(paired::derive(&parsed[0].data, &parsed[1].data, op));
+            paired_label = format!("{} {} {}", sources[1], op.symbol(), sources[0]);
+
+            // Each pair is found by the line of its value in the first input.
+            let derived = input::Parsed {
+                data,
+                lines: parsed[0].lines.clone(),
+                weights: None,
+                counts: None,
+                responses: None,
+                times: None,
+                time_format: None,
+                strata: None,
+                skipped: input::Skipped::default(),
+            };
+
+            (vec![derived], vec![paired_label.as_str()])
+        },
+        None => (parsed, sources),
+    };
+
+    if let Some(dedup) = parse_dedup(&matches) {
+        for (p, src) in parsed.iter_mut().zip(&sources) {
+            let len = p.data.len();
+            let kept = dedup.kept(&p.data);
+            p.keep(&kept);
+
+            let dropped = len - kept.len();
+
+            if dropped > 0 {
+                log::warning(&format!("{}: dropped {} duplicate values of {}", src, dropped, len));
+            }
+        }
+    }
+
+    // Cut tails after pairing, too, and before subsampling, so that they are
+    // those of the whole sample.
+    if let Some(fraction) = tail_fraction(&matches, "winsorize", "--winsorize") {
+        for (p, src) in parsed.iter_mut().zip(&sources) {
+            let (raised, lowered) = ok!(tails::winsorize(&mut p.data, fraction));
+
+            if raised + lowered > 0 {
+                let message =
+                    format!("{}: winsorized {} low and {} high values", src, raised, lowered);
+                log::warning(&message);
+            }
+        }
+    }
+
+    if let Some(fraction) = tail_fraction(&matches, "trim_tails", "--trim-tails") {
+        for (p, src) in parsed.iter_mut().zip(&sources) {
+            let len = p.data.len();
+            let kept = ok!(tails::trim(&p.data, fraction));
+            p.keep(&kept);
+
+            if kept.len() < len {
+                log::warning(&format!("{}: trimmed {} of {} values", src, len - kept.len(), len));
+            }
+        }
+    }
+
+    // Subsample after pairing, so that pairs are kept together. Keep the
+    // size of each input subsampled, to bound the error of its mean.
+    let populations: Vec<Option<usize>> = match quick_size(&matches) {
+        Some(n) => {
+            let mut rng = seeded_rng(&matches);
+
+            parsed.iter_mut().map(|p| p.subsample(n, &mut rng)).collect()
+        },
+        None => parsed.iter().map(|_| None).collect(),
+    };
+
+    if stratify_column.is_some() {
+        for ((p, src), population) in parsed.iter().zip(&sources).zip(&populations) {
+            if let Some(len) = *population {
+                report_subsample(src, p.data.len(), len, None);
+            }
+        }
+
+        return display_stratified(&parsed, confidence, &report);
+    }
+
+    let compare_quantiles = matches.is_present("quantiles");
+
+    if compare_quantiles && parsed.len() != 2 {
+        log::error("Quantile comparison requires exactly two inputs");
+        output::exit(1);
+    }
+
+    let shift = matches.is_present("shift");
+
+    if speedup {
+        check_speedup_inputs(parsed.len());
+    }
+
+    if shift && parsed.len() != 2 {
+        log::error("Shift function requires exactly two inputs");
+        output::exit(1);
+    }
+
+    // Quantiles are only computed when compared, since `Summary` can't answer
+    // for them once the data is gone.
+    let quantiles_of = |percentile: &dyn Fn(f64) -> f64| -> Vec<f64> {
+        if compare_quantiles {
+            COMPARISON_QUANTILES.iter().map(|&(p, _)| percentile(p)).collect()
+        } else {
+            vec![]
+        }
+    };
+
+    let freq_table = matches.is_present("freq_table");
+
+    let sparkline = matches.is_present("sparkline");
+    let binned = freq_table
+        || sparkline
+        || matches.is_present("histogram")
+        || matches.is_present("hist");
+
+    if matches.is_present("bins") && !binned {
+        log::error("--bins requires --histogram, --hist, --freq-table, or --sparkline");
+        output::exit(1);
+    }
+
+    let vertical_histogram =
+        matches.is_present("hist") || matches.value_of("histogram") == Some("vertical");
+
+    if vertical_histogram && matches.is_present("overlay") {
+        log::error("--overlay requires horizontal histograms");
+        output::exit(1);
+    }
+
+    // Validated by clap, so we can unwrap.
+    let binning = matches
+        .value_of("bins")
+        .map_or(Binning::Width(histogram::DEFAULT_BINS), |b| Binning::parse(b).unwrap());
+
+    // The overlay's axis is labeled only at its ends, so its bins must be of
+    // equal width to be read.
+    if matches.is_present("overlay") && !matches!(binning, Binning::Width(_)) {
+        log::error("--overlay requires bins of equal width");
+        output::exit(1);
+    }
+
+    let verbose = matches.is_present("verbose");
+    let mut value_counts = vec![];
+
+    let (summaries, quantiles): (Vec<Summary>, Vec<Vec<f64>>) = parsed
+        .iter()
+        .zip(&sources)
+        .zip(&populations)
+        .map(|((p, src), population)| {
+            let method = match *population {
+                Some(population) => Method::Subsample { size: p.data.len(), population },
+                None => Method::Exact,
+            };
+
+            if let Some(pairs) = p.weighted() {
+                let s = ok!(WeightedSummarizer::new(&pairs));
+                let q = quantiles_of(&|p| ok!(s.percentile(p)));
+
+                if let Some(len) = *population {
+                    report_subsample(src, p.data.len(), len, None);
+                }
+
+                return (report.annotate(Summary::from_weighted(&s)).with_method(method), q);
+            }
+
+            if let Some(pairs) = p.frequencies() {
+                let s = ok!(FrequencySummarizer::new(&pairs));
+                let q = quantiles_of(&|p| ok!(s.percentile(p)));
+
+                if let Some(len) = *population {
+                    report_subsample(src, p.data.len(), len, None);
+                }
+
+                return (report.annotate(Summary::from_frequencies(&s)).with_method(method), q);
+            }
+
+            let summarizer = ok!(Summarizer::new_with_policy(&p.data, nonfinite_policy));
+
+            if let Some(report) = summarizer.nonfinite_report() {
+                report_nonfinite(src, &report);
+            }
+
+            for d in diagnostics::check_sample(&summarizer) {
+                log::warning(&format!("{}: {}", src, d));
+            }
+
+            for d in diagnostics::check_order(&p.data) {
+                log::warning(&format!("{}: {}", src, d));
+            }
+
+            if verbose {
+                value_counts.push(summarizer.value_counts());
+            }
+
+            if let Some(len) = *population {
+                let mean = ok!(estimate::subsample_mean(&summarizer, len));
+
+                report_subsample(src, p.data.len(), len, mean.error_bound);
+            }
+
+            let q = quantiles_of(&|p| ok!(summarizer.percentile(p)));
+
+            (report.annotate(Summary::from_summarizer(&summarizer)).with_method(method), q)
+        })
+        .unzip();
+
+    let ratio = if speedup {
+        let x = ok!(Summarizer::new_with_policy(&parsed[0].data, nonfinite_policy));
+        let y = ok!(Summarizer::new_with_policy(&parsed[1].data, nonfinite_policy));
+        let mut rng = seeded_rng(&matches);
+
+        Some(ok!(RatioOfMeans::new(&x, &y, confidence, bootstrap_resamples(&matches), &mut rng)))
+    } else {
+        None
+    };
+
+    // In a machine format, the frequency table is printed instead of the
+    // summaries, so that the output is one table.
+    if freq_table && summary_format != SummaryFormat::Text {
+        let samples = finite_samples(&parsed, nonfinite_policy);
+        let histograms = shared_histograms(&samples, &sources, binning);
+
+        return output::write(&match summary_format {
+            SummaryFormat::Prometheus => histogram::to_prometheus(&histograms, &sources),
+            _ => histogram::to_tsv(&histograms, &sources),
+        });
+    }
+
+    // Human-readable output labels the inputs in short.
+    let labels = source_labels(&sources);
+    let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+
+    if matches.is_present("auto") {
+        if parsed.len() != 2 {
+            log::error("Automatic test selection requires exactly two inputs");
+            output::exit(1);
+        }
+
+        let x = ok!(Summarizer::new_with_policy(&parsed[0].data, nonfinite_policy));
+        let y = ok!(Summarizer::new_with_policy(&parsed[1].data, nonfinite_policy));
+
+        display_auto(&x, &y, &summaries, &labels, plot_opts, &report);
+    } else if matches.is_present("robust") {
+        let summarizers: Vec<Summarizer> = parsed
+            .iter()
+            .map(|p| ok!(Summarizer::new_with_policy(&p.data, nonfinite_policy)))
+            .collect();
+
+        // The derived sample of a paired comparison is tested by its signs,
+        // unless every pair is tied.
+        let sign_test = match paired_op.map(|op| paired::sign_test(summarizers[0].as_slice(), op)) {
+            Some(Err(Error::Undefined)) => {
+                log::warning("Skipped the sign test, as no pairs are untied");
+                None
+            },
+            test => test.map(|t| ok!(t)),
+        };
+
+        display_robust(&summarizers, &summaries, sign_test.as_ref(), &labels, plot_opts, &report);
+    } else if sparkline {
+        // Required to be brief, which needs the data to draw sparklines.
+        let samples = finite_samples(&parsed, nonfinite_policy);
+        let histograms = shared_histograms(&samples, &sources, binning);
+        let sparklines: Vec<String> = histograms
+            .iter()
+            .map(|h| plot::histogram::sparkline(&h.counts, style.is_ascii()))
+            .collect();
+
+        display_summaries_brief(&summaries, &labels, Some(&sparklines), &report);
+        record_results(&matches, &summaries, &sources);
+    } else {
+        display(&summaries, &sources, summary_format, plot_opts, &report, ratio.as_ref());
+        record_results(&matches, &summaries, &sources);
+
+        if explain {
+            check_explain_inputs(summaries.len());
+            print_explanation(&summaries, &sources, alpha);
+        }
+    }
+
+    if verbose {
+        outln!();
+
+        let mut layout = Layout::new(layout_width());
+        layout.table(value_counts_table(&value_counts, &labels));
+
+        outln!("{}", layout.render());
+    }
+
+    if let Some(k) = matches.value_of("show_extremes") {
+        // Validated by clap, so we can unwrap.
+        let k = k.parse().unwrap();
+
+        for (p, src) in parsed.iter().zip(&labels) {
+            outln!();
+            outln!("Extremes of {}:", src);
+
+            let mut layout = Layout::new(layout_width());
+            layout.table(extremes_table(p, k));
+
+            outln!("{}", layout.render());
+        }
+    }
+
+    if let Some(values) = matches.values_of("rank_of") {
+        // Validated by clap, so we can unwrap.
+        let values: Vec<f64> = values.map(|v| v.parse().unwrap()).collect();
+
+        outln!();
+        print_ranks(&finite_samples(&parsed, nonfinite_policy), &labels, &values);
+    }
+
+    if binned {
+        let samples = finite_samples(&parsed, nonfinite_policy);
+        let histograms = shared_histograms(&samples, &sources, binning);
+        let unit = report.unit.as_deref();
+
+        if vertical_histogram {
+            let width = plot_width(&matches, &config);
+
+            outln!();
+            print_vertical_histograms(&histograms, &labels, width, style.is_ascii(), unit);
+        } else if matches.is_present("histogram") {
+            let overlay = matches.is_present("overlay");
+            let width = plot_width(&matches, &config);
+
+            outln!();
+            print_histograms(&histograms, &labels, overlay, width, style.is_ascii(), unit);
+        }
+
+        if freq_table {
+            outln!();
+            print_frequency_tables(&histograms, &labels);
+        }
+    }
+
+    if let Some(thresholds) = matches.values_of("threshold") {
+        // Validated by clap, so we can unwrap.
+        let thresholds: Vec<f64> = thresholds.map(|t| t.parse().unwrap()).collect();
+        let samples = finite_samples(&parsed, nonfinite_policy);
+
+        outln!();
+        print_exceedances(&samples, &labels, &thresholds, confidence);
+
+        if matches.is_present("exceedance_test") {
+            outln!();
+            print_exceedance_changes(&samples, &labels, &thresholds, confidence, &report);
+        }
+    }
+
+    if matches.is_present("throughput") {
+        print_throughput(&summaries, &labels, confidence);
+    }
+
+    if compare_quantiles {
+        outln!();
+        print_quantile_comparison(&quantiles[0], &quantiles[1]);
+    }
+
+    if shift {
+        let resamples = bootstrap_resamples(&matches);
+        let mut rng = seeded_rng(&matches);
+
+        let x = ok!(Summarizer::new_with_policy(&parsed[0].data, nonfinite_policy));
+        let y = ok!(Summarizer::new_with_policy(&parsed[1].data, nonfinite_policy));
+        let f = ok!(ShiftFunction::new(
+            &x,
+            &y,
+            &shift::DECILES,
+            confidence,
+            resamples,
+            &mut rng,
+        ));
+
+        outln!();
+        let unit = report.unit.as_deref();
+        let width = plot_width(&matches, &config);
+
+        outln!("{}", ok!(plot::shift_plot(&f, width, style.is_ascii(), unit)));
+        outln!();
+        print_shift_function(&f, unit);
+    }
 }
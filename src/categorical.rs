@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use error::Error;
+use estimate::{self, Estimate};
+use rng::Rng;
+use special::normal_quantile;
+use summary::Summarizer;
+
+
+/// A distinct label, with its count and proportion of the sample.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Category {
+    pub label: String,
+    pub count: u64,
+    pub proportion: f64,
+
+    /// Wilson score interval [1] of the proportion, which unlike the normal
+    /// approximation stays within [0, 1] and is reliable for small counts.
+    ///
+    /// [1]: E. Wilson, "Probable inference, the law of succession, and
+    ///      statistical inference", J. Am. Stat. Assoc. 22(158), 1927.
+    pub interval: (f64, f64),
+}
+
+/// Counts of the distinct labels of a sample of categorical data.
+#[derive(Clone, Debug)]
+pub struct CategoricalSummary {
+    /// Distinct labels, from most to least frequent, with ties in label order.
+    pub categories: Vec<Category>,
+
+    pub size: u64,
+    pub confidence: f64,
+}
+
+impl CategoricalSummary {
+    /// Count the distinct labels in `labels`, with intervals of each
+    /// proportion at the given confidence level. Labels are compared exactly,
+    /// so callers should trim them first if whitespace is not significant.
+    pub fn new<'a, I>(labels: I, confidence: f64) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        if !(0.0 < confidence && confidence < 1.0) {
+            return Err(Error::BadParameter);
+        }
+
+        let mut counts: HashMap<&str, u64> = HashMap::new();
+
+        for label in labels {
+            *counts.entry(label).or_insert(0) += 1;
+        }
+
+        let size: u64 = counts.values().sum();
+
+        if size == 0 {
+            return Err(Error::EmptySample);
+        }
+
+        let z = normal_quantile(0.5 + 0.5 * confidence)?;
+
+        let mut categories: Vec<Category> = counts
+            .into_iter()
+            .map(|(label, count)| Category {
+                label: label.to_string(),
+                count,
+                proportion: count as f64 / size as f64,
+                interval: wilson_interval(count, size, z),
+            })
+            .collect();
+
+        categories.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.label.cmp(&b.label)));
+
+        Ok(CategoricalSummary { categories, size, confidence })
+    }
+}
+
+/// An index of diversity, with a percentile bootstrap interval.
+#[derive(Clone, Copy, Debug)]
+pub struct DiversityIndex {
+    pub value: f64,
+    pub interval: Estimate<(f64, f64)>,
+}
+
+/// Indices of how evenly a sample is spread over its labels, as used to
+/// track drift in the mix of categories over time.
+#[derive(Clone, Copy, Debug)]
+pub struct Diversity {
+    /// Shannon entropy `-Σ p log₂ p`, in bits. It is 0 for a single label,
+    /// and `log₂ k` for `k` equally frequent labels.
+    pub entropy: DiversityIndex,
+
+    /// Gini impurity `1 - Σ p²`, the probability that two labels drawn with
+    /// replacement differ.
+    pub gini_impurity: DiversityIndex,
+
+    /// Simpson's index `Σ n(n - 1) / N(N - 1)`, the probability that two
+    /// labels drawn without replacement are the same.
+    pub simpson: DiversityIndex,
+}
+
+impl CategoricalSummary {
+    /// Compute indices of diversity, with percentile bootstrap intervals at
+    /// the confidence level of the summary, from `resamples` resamplings of
+    /// the labels. Simpson's index needs at least two labels.
+    pub fn diversity(&self, resamples: usize, rng: &mut Rng) -> Result<Diversity, Error> {
+        if resamples == 0 {
+            return Err(Error::BadParameter);
+        }
+
+        if self.size < 2 {
+            return Err(Error::Undefined);
+        }
+
+        let counts: Vec<u64> = self.categories.iter().map(|c| c.count).collect();
+
+        // The category of each label in the sample, for resampling.
+        let codes: Vec<usize> = counts
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &n)| (0..n).map(move |_| i))
+            .collect();
+
+        let mut replicates = (vec![], vec![], vec![]);
+        let mut resampled = vec![0; counts.len()];
+
+        for _ in 0..resamples {
+            resampled.iter_mut().for_each(|n| *n = 0);
+
+            for _ in 0..codes.len() {
+                resampled[codes[rng.below(codes.len())]] += 1;
+            }
+
+            let (h, g, d) = diversity_indices(&resampled);
+            replicates.0.push(h);
+            replicates.1.push(g);
+            replicates.2.push(d);
+        }
+
+        let (h, g, d) = diversity_indices(&counts);
+
+        let index = |value: f64, replicates: Vec<f64>| -> Result<DiversityIndex, Error> {
+            let replicates = Summarizer::from_vec(replicates)?;
+            let interval = estimate::bootstrap_interval(&replicates, self.confidence, resamples)?;
+
+            Ok(DiversityIndex { value, interval })
+        };
+
+        Ok(Diversity {
+            entropy: index(h, replicates.0)?,
+            gini_impurity: index(g, replicates.1)?,
+            simpson: index(d, replicates.2)?,
+        })
+    }
+}
+
+/// Shannon entropy, Gini impurity, and Simpson's index of the counts of each
+/// label, of which there must be at least two in all.
+fn diversity_indices(counts: &[u64]) -> (f64, f64, f64) {
+    let size = counts.iter().sum::<u64>() as f64;
+
+    let mut entropy = 0.0;
+    let mut sum_sq = 0.0;
+    let mut pairs = 0.0;
+
+    for &n in counts.iter().filter(|&&n| n > 0) {
+        let n = n as f64;
+        let p = n / size;
+
+        entropy -= p * p.log2();
+        sum_sq += p * p;
+        pairs += n * (n - 1.0);
+    }
+
+    (entropy, 1.0 - sum_sq, pairs / (size * (size - 1.0)))
+}
+
+/// The Wilson score interval of `count` successes in `size` trials, for the
+/// normal critical value `z`.
+pub(crate) fn wilson_interval(count: u64, size: u64, z: f64) -> (f64, f64) {
+    let n = size as f64;
+    let p = count as f64 / n;
+    let z2 = z * z;
+
+    let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let half_width = z / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+
+    ((center - half_width).max(0.0), (center + half_width).min(1.0))
+}
@@ -0,0 +1,202 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+
+/// Keys accepted in the config file. Each may also be set by an environment
+/// variable named for it in upper case with a `DENT_` prefix, for example
+/// `DENT_PLOT_STYLE`.
+const KEYS: [&str; 6] = ["width", "plot_style", "ascii", "alpha", "precision", "format"];
+
+/// Output formats accepted by `format` and `--format`.
+pub const FORMATS: [&str; 3] = ["text", "tsv", "prometheus"];
+
+/// Defaults for command-line options, from the config file and the
+/// environment. Environment variables take precedence over the file, and
+/// flags given on the command line take precedence over both.
+#[derive(Debug, Default)]
+pub struct Config {
+    pub width: Option<usize>,
+    pub plot_style: Option<String>,
+    pub ascii: Option<bool>,
+    pub alpha: Option<f64>,
+    pub precision: Option<usize>,
+    pub format: Option<String>,
+}
+
+/// An error in the config file or a `DENT_*` environment variable.
+#[derive(Debug)]
+pub struct ConfigError {
+    /// The path of the config file, or the name of the variable, at fault.
+    pub source: String,
+
+    /// The 1-based number of the line of the config file at fault.
+    pub line: Option<usize>,
+
+    message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self.line {
+            Some(line) => write!(f, "{}: line {}: {}", self.source, line, self.message),
+            None => write!(f, "{}: {}", self.source, self.message),
+        }
+    }
+}
+
+impl Config {
+    /// Read the config file, if any, then apply `DENT_*` environment
+    /// variables.
+    pub fn load() -> Result<Self, ConfigError> {
+        let mut config = Config::default();
+
+        if let Some(path) = path() {
+            let error = |line, message| ConfigError {
+                source: path.display().to_string(),
+                line,
+                message,
+            };
+
+            match fs::read_to_string(&path) {
+                Ok(text) => config.read_file(&text).map_err(|(n, e)| error(Some(n), e))?,
+                Err(ref e) if e.kind() == io::ErrorKind::NotFound => {},
+                Err(e) => return Err(error(None, e.to_string())),
+            }
+        }
+
+        for key in &KEYS {
+            let var = format!("DENT_{}", key.to_uppercase());
+
+            if let Ok(value) = env::var(&var) {
+                config.set(key, &value).map_err(|message| ConfigError {
+                    source: var.clone(),
+                    line: None,
+                    message,
+                })?;
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Read `key = value` lines of a TOML document. Strings must be quoted.
+    /// Comments and blank lines are ignored, and tables are not supported.
+    /// Errors are returned with their 1-based line number.
+    fn read_file(&mut self, text: &str) -> Result<(), (usize, String)> {
+        for (i, line) in text.lines().enumerate() {
+            let line = strip_comment(line).trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, value) = match line.find('=') {
+                Some(eq) => (line[..eq].trim(), line[(eq + 1)..].trim()),
+                None => return Err((i + 1, "expected `key = value`".to_string())),
+            };
+
+            let value = if value.starts_with('"') {
+                match parse_string(value) {
+                    Some(s) => s,
+                    None => return Err((i + 1, "malformed string".to_string())),
+                }
+            } else {
+                value.to_string()
+            };
+
+            self.set(key, &value).map_err(|e| (i + 1, e))?;
+        }
+
+        Ok(())
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let invalid = || format!("invalid value for `{}`: {:?}", key, value);
+
+        match key {
+            "width" => match value.parse::<usize>() {
+                Ok(w) if w > 0 => self.width = Some(w),
+                _ => return Err(invalid()),
+            },
+            "plot_style" => self.plot_style = Some(value.to_string()),
+            "ascii" => match value {
+                "true" => self.ascii = Some(true),
+                "false" => self.ascii = Some(false),
+                _ => return Err(invalid()),
+            },
+            "alpha" => match value.parse::<f64>() {
+                Ok(a) if 0.0 < a && a < 1.0 => self.alpha = Some(a),
+                _ => return Err(invalid()),
+            },
+            "precision" => match value.parse::<usize>() {
+                Ok(p) if p > 0 => self.precision = Some(p),
+                _ => return Err(invalid()),
+            },
+            "format" if FORMATS.contains(&value) => self.format = Some(value.to_string()),
+            "format" => return Err(invalid()),
+            _ => return Err(format!("unknown key `{}`", key)),
+        }
+
+        Ok(())
+    }
+}
+
+/// The config file named by `$DENT_CONFIG`, or else `dent/config.toml` in
+/// `$XDG_CONFIG_HOME` or `~/.config`.
+fn path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("DENT_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+
+    let dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+
+    Some(dir.join("dent").join("config.toml"))
+}
+
+/// Remove a trailing `#` comment, unless the `#` is inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {},
+        }
+    }
+
+    line
+}
+
+/// Parse a TOML basic string, with its quotes, allowing only simple escapes.
+fn parse_string(s: &str) -> Option<String> {
+    let inner = s.strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut parsed = String::new();
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                '"' => parsed.push('"'),
+                '\\' => parsed.push('\\'),
+                'n' => parsed.push('\n'),
+                't' => parsed.push('\t'),
+                _ => return None,
+            },
+            '"' => return None,
+            c => parsed.push(c),
+        }
+    }
+
+    Some(parsed)
+}
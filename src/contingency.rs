@@ -0,0 +1,235 @@
+use error::Error;
+use special::{ln_gamma, normal_cdf, normal_quantile};
+
+
+/// Counts of an event and its absence in two groups, such as failures and
+/// successes of two configurations:
+///
+/// ```text
+///           event   no event
+/// group 1     a        b
+/// group 2     c        d
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TwoByTwo {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+    pub d: u64,
+}
+
+/// An effect measure of a `TwoByTwo` table, with a confidence interval.
+#[derive(Clone, Copy, Debug)]
+pub struct Estimate {
+    pub estimate: f64,
+    pub interval: (f64, f64),
+    pub confidence: f64,
+}
+
+/// Pearson's chi-square test of independence of a `TwoByTwo` table, with one
+/// degree of freedom.
+#[derive(Clone, Copy, Debug)]
+pub struct ChiSquare {
+    pub statistic: f64,
+    pub p: f64,
+}
+
+impl TwoByTwo {
+    /// A table of the given counts. Each group must have at least one count,
+    /// as must the event or its absence.
+    pub fn new(a: u64, b: u64, c: u64, d: u64) -> Result<Self, Error> {
+        if a + b == 0 || c + d == 0 || a + c == 0 || b + d == 0 {
+            return Err(Error::Undefined);
+        }
+
+        Ok(TwoByTwo { a, b, c, d })
+    }
+
+    /// Parse a comma-separated list of the four counts `a,b,c,d`, in rows.
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let counts = s
+            .split(',')
+            .map(|f| f.trim().parse::<u64>().map_err(|_| Error::BadRecord))
+            .collect::<Result<Vec<u64>, Error>>()?;
+
+        match counts[..] {
+            [a, b, c, d] => TwoByTwo::new(a, b, c, d),
+            _ => Err(Error::BadRecord),
+        }
+    }
+
+    /// Parse a table in CSV: two rows of two counts, one row per group. An
+    /// optional header row, and an optional first column of group labels,
+    /// are recognized by not being counts, and ignored.
+    pub fn parse_csv(text: &str) -> Result<Self, Error> {
+        let is_count = |f: &str| f.trim().parse::<u64>().is_ok();
+
+        let mut rows: Vec<Vec<&str>> = text
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.split(',').collect())
+            .collect();
+
+        if rows.first().is_some_and(|r| !r.iter().skip(1).all(|&f| is_count(f))) {
+            rows.remove(0);
+        }
+
+        let mut counts = vec![];
+
+        for row in &rows {
+            let fields = match row.first() {
+                Some(&f) if !is_count(f) => &row[1..],
+                _ => &row[..],
+            };
+
+            if fields.len() != 2 {
+                return Err(Error::BadRecord);
+            }
+
+            for f in fields {
+                counts.push(f.trim().parse::<u64>().map_err(|_| Error::BadRecord)?);
+            }
+        }
+
+        match counts[..] {
+            [a, b, c, d] => TwoByTwo::new(a, b, c, d),
+            _ => Err(Error::BadRecord),
+        }
+    }
+
+    /// The counts as floats, with 0.5 added to each if any is zero, which is
+    /// the Haldane-Anscombe correction [1]. It keeps ratios and the variances
+    /// of their logarithms finite.
+    ///
+    /// [1]: J. Haldane, "The estimation and significance of the logarithm of
+    ///      a ratio of frequencies", Ann. Hum. Genet. 20(4), 1956.
+    fn corrected(&self) -> (f64, f64, f64, f64) {
+        let correction = if self.a.min(self.b).min(self.c).min(self.d) == 0 { 0.5 } else { 0.0 };
+
+        (
+            self.a as f64 + correction,
+            self.b as f64 + correction,
+            self.c as f64 + correction,
+            self.d as f64 + correction,
+        )
+    }
+
+    /// The odds ratio `(a/b) / (c/d)` of the event in group 1 to group 2, with
+    /// Woolf's interval [1], from the standard error of its logarithm.
+    ///
+    /// [1]: B. Woolf, "On estimating the relation between blood group and
+    ///      disease", Ann. Hum. Genet. 19(4), 1955.
+    pub fn odds_ratio(&self, confidence: f64) -> Result<Estimate, Error> {
+        let (a, b, c, d) = self.corrected();
+
+        let ln_or = (a * d / (b * c)).ln();
+        let se = (a.recip() + b.recip() + c.recip() + d.recip()).sqrt();
+
+        log_interval(ln_or, se, confidence)
+    }
+
+    /// The relative risk `(a/(a+b)) / (c/(c+d))` of the event in group 1 to
+    /// group 2, with the interval of Katz et al. [1], from the standard error
+    /// of its logarithm.
+    ///
+    /// [1]: D. Katz et al., "Obtaining confidence intervals for the risk
+    ///      ratio in cohort studies", Biometrics 34(3), 1978.
+    pub fn relative_risk(&self, confidence: f64) -> Result<Estimate, Error> {
+        let (a, b, c, d) = self.corrected();
+
+        let ln_rr = (a / (a + b) / (c / (c + d))).ln();
+        let se = (a.recip() - (a + b).recip() + c.recip() - (c + d).recip()).sqrt();
+
+        log_interval(ln_rr, se, confidence)
+    }
+
+    /// Pearson's chi-square test, without continuity correction.
+    ///
+    /// The p-value is approximate, and should be preferred to that of
+    /// `fisher_exact` only when every expected count is at least 5.
+    pub fn chi_square(&self) -> ChiSquare {
+        let (a, b, c, d) = (self.a as f64, self.b as f64, self.c as f64, self.d as f64);
+        let n = a + b + c + d;
+
+        let statistic = n * (a * d - b * c).powi(2) / ((a + b) * (c + d) * (a + c) * (b + d));
+
+        // A chi-square variate with 1 degree of freedom is a squared normal.
+        let p = 2.0 * normal_cdf(-statistic.sqrt());
+
+        ChiSquare { statistic, p }
+    }
+
+    /// The smallest count expected in any cell under independence.
+    pub fn min_expected(&self) -> f64 {
+        let n = (self.a + self.b + self.c + self.d) as f64;
+        let rows = [(self.a + self.b) as f64, (self.c + self.d) as f64];
+        let columns = [(self.a + self.c) as f64, (self.b + self.d) as f64];
+
+        rows.iter()
+            .flat_map(|r| columns.iter().map(move |c| r * c / n))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// The two-sided p-value of Fisher's exact test: the probability, given
+    /// the margins, of a table no more likely than this one.
+    pub fn fisher_exact(&self) -> Result<f64, Error> {
+        let row1 = self.a + self.b;
+        let col1 = self.a + self.c;
+        let n = self.a + self.b + self.c + self.d;
+
+        let ln_factorial = |k: u64| ln_gamma(k as f64 + 1.0);
+
+        // The constant part of the hypergeometric log-probabilities.
+        let ln_margins = ln_factorial(row1)? + ln_factorial(n - row1)? +
+            ln_factorial(col1)? + ln_factorial(n - col1)? - ln_factorial(n)?;
+
+        // The log-probability of the table with `a` in its first cell.
+        let ln_p = |a: u64| -> Result<f64, Error> {
+            let b = row1 - a;
+            let c = col1 - a;
+            let d = n - row1 - c;
+
+            let ln_cells = ln_factorial(a)? + ln_factorial(b)? + ln_factorial(c)? + ln_factorial(d)?;
+
+            Ok(ln_margins - ln_cells)
+        };
+
+        let observed = ln_p(self.a)?;
+
+        // Allow for rounding error in tables as likely as the observed.
+        let threshold = observed + FISHER_RELATIVE_TOLERANCE.ln_1p();
+
+        let lo = (row1 + col1).saturating_sub(n);
+        let hi = row1.min(col1);
+
+        let mut p = 0.0;
+
+        for a in lo..(hi + 1) {
+            let ln_pa = ln_p(a)?;
+
+            if ln_pa <= threshold {
+                p += ln_pa.exp();
+            }
+        }
+
+        Ok(p.min(1.0))
+    }
+}
+
+const FISHER_RELATIVE_TOLERANCE: f64 = 1e-7;
+
+/// An estimate and interval for a ratio whose logarithm is asymptotically
+/// normal, with the given standard error.
+fn log_interval(ln_ratio: f64, se: f64, confidence: f64) -> Result<Estimate, Error> {
+    if !(0.0 < confidence && confidence < 1.0) {
+        return Err(Error::BadParameter);
+    }
+
+    let z = normal_quantile(0.5 + 0.5 * confidence)?;
+
+    Ok(Estimate {
+        estimate: ln_ratio.exp(),
+        interval: ((ln_ratio - z * se).exp(), (ln_ratio + z * se).exp()),
+        confidence,
+    })
+}
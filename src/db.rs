@@ -0,0 +1,323 @@
+use std::error;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use dent::summary::Summary;
+use dent::t_test::TTest;
+use rusqlite::{params, params_from_iter, Connection, OpenFlags};
+
+
+/// Tables of the results database. Each invocation which records results is
+/// a run, with its time in seconds since the Unix epoch, and any metadata
+/// given as `KEY=VALUE` pairs, such as a label or commit.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    time REAL NOT NULL
+);
+CREATE TABLE IF NOT EXISTS metadata (
+    run INTEGER NOT NULL REFERENCES runs (id),
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (run, key)
+);
+CREATE TABLE IF NOT EXISTS summaries (
+    run INTEGER NOT NULL REFERENCES runs (id),
+    source TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    mean REAL,
+    median REAL,
+    standard_deviation REAL,
+    variance REAL,
+    standard_error REAL,
+    min REAL,
+    max REAL,
+    lower_quartile REAL,
+    upper_quartile REAL,
+    unit TEXT
+);
+CREATE TABLE IF NOT EXISTS t_tests (
+    run INTEGER NOT NULL REFERENCES runs (id),
+    source1 TEXT NOT NULL,
+    source2 TEXT NOT NULL,
+    t REAL,
+    df REAL,
+    p REAL
+);
+";
+
+/// An error reading or writing the results database at `path`.
+#[derive(Debug)]
+pub struct DbError {
+    pub path: String,
+
+    error: rusqlite::Error,
+}
+
+impl DbError {
+    fn at(path: &str) -> impl Fn(rusqlite::Error) -> DbError + '_ {
+        move |error| DbError { path: path.to_string(), error }
+    }
+}
+
+impl fmt::Display for DbError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl error::Error for DbError {}
+
+/// Parse a `KEY=VALUE` metadata pair.
+pub fn parse_meta(s: &str) -> Result<(String, String), String> {
+    match s.find('=') {
+        Some(i) if i > 0 => Ok((s[..i].to_string(), s[i + 1..].to_string())),
+        _ => Err(format!("expected KEY=VALUE, not {:?}", s)),
+    }
+}
+
+/// Record a run of the given summaries, and of the t-test comparing them, if
+/// any, in the database at `path`, which is created if it does not exist.
+/// Returns the id of the run.
+pub fn record(
+    path: &str,
+    meta: &[(String, String)],
+    summaries: &[Summary],
+    sources: &[&str],
+    t_test: Option<&TTest>,
+) -> Result<i64, DbError> {
+    let at = DbError::at(path);
+
+    let mut conn = Connection::open(path).map_err(&at)?;
+    conn.execute_batch(SCHEMA).map_err(&at)?;
+
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0);
+
+    let tx = conn.transaction().map_err(&at)?;
+
+    tx.execute("INSERT INTO runs (time) VALUES (?1)", params![time]).map_err(&at)?;
+    let run = tx.last_insert_rowid();
+
+    for (key, value) in meta {
+        tx.execute(
+            "INSERT OR REPLACE INTO metadata (run, key, value) VALUES (?1, ?2, ?3)",
+            params![run, key, value],
+        ).map_err(&at)?;
+    }
+
+    for (s, src) in summaries.iter().zip(sources) {
+        tx.execute(
+            "INSERT INTO summaries VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                run,
+                src,
+                s.size() as i64,
+                s.mean(),
+                s.median(),
+                s.standard_deviation(),
+                s.unbiased_variance(),
+                s.standard_error(),
+                s.min(),
+                s.max(),
+                s.lower_quartile(),
+                s.upper_quartile(),
+                s.unit(),
+            ],
+        ).map_err(&at)?;
+    }
+
+    if let (Some(t), [src1, src2]) = (t_test, sources) {
+        tx.execute(
+            "INSERT INTO t_tests VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![run, src1, src2, t.t, t.df, t.p],
+        ).map_err(&at)?;
+    }
+
+    tx.commit().map_err(&at)?;
+
+    Ok(run)
+}
+
+/// Which runs to retrieve from the database: those with all the given
+/// metadata, or only the last `last` of them.
+#[derive(Debug, Default)]
+pub struct Query {
+    pub meta: Vec<(String, String)>,
+    pub last: Option<usize>,
+}
+
+/// A run, with its metadata ordered by key.
+#[derive(Debug)]
+pub struct Run {
+    pub id: i64,
+    pub time: f64,
+    pub meta: Vec<(String, String)>,
+}
+
+/// A summary recorded in a run. Statistics which were not finite are `None`.
+#[derive(Debug)]
+pub struct SummaryRow {
+    pub run: i64,
+    pub source: String,
+    pub size: i64,
+    pub stats: [Option<f64>; 9],
+    pub unit: Option<String>,
+}
+
+/// Statistics of a `SummaryRow`, as columns of the database, with their
+/// names in the columns of summary tables.
+pub const SUMMARY_COLUMNS: [(&str, &str); 9] = [
+    ("mean", "Mean"),
+    ("median", "Median"),
+    ("standard_deviation", "StandardDeviation"),
+    ("variance", "Variance"),
+    ("standard_error", "StandardError"),
+    ("min", "Min"),
+    ("max", "Max"),
+    ("lower_quartile", "LowerQuartile"),
+    ("upper_quartile", "UpperQuartile"),
+];
+
+/// A t-test recorded in a run.
+#[derive(Debug)]
+pub struct TTestRow {
+    pub run: i64,
+    pub source1: String,
+    pub source2: String,
+    pub t: Option<f64>,
+    pub df: Option<f64>,
+    pub p: Option<f64>,
+}
+
+/// Open the existing database at `path`, for reading.
+fn open(path: &str) -> Result<Connection, DbError> {
+    Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(DbError::at(path))
+}
+
+/// The runs in the database at `path` which match `query`, oldest first.
+pub fn runs(path: &str, query: &Query) -> Result<Vec<Run>, DbError> {
+    let at = DbError::at(path);
+    let conn = open(path)?;
+
+    let mut sql = "SELECT id, time FROM runs WHERE 1".to_string();
+    let mut values = vec![];
+
+    for (key, value) in &query.meta {
+        sql += " AND id IN (SELECT run FROM metadata WHERE key = ? AND value = ?)";
+        values.push(key);
+        values.push(value);
+    }
+
+    sql += " ORDER BY id";
+
+    let mut stmt = conn.prepare(&sql).map_err(&at)?;
+    let mut runs = stmt
+        .query_map(params_from_iter(values), |r| {
+            Ok(Run { id: r.get(0)?, time: r.get(1)?, meta: vec![] })
+        })
+        .map_err(&at)?
+        .collect::<Result<Vec<Run>, _>>()
+        .map_err(&at)?;
+
+    if let Some(last) = query.last {
+        runs.drain(..runs.len().saturating_sub(last));
+    }
+
+    let mut stmt = conn
+        .prepare("SELECT key, value FROM metadata WHERE run = ?1 ORDER BY key")
+        .map_err(&at)?;
+
+    for run in &mut runs {
+        run.meta = stmt
+            .query_map(params![run.id], |r| Ok((r.get(0)?, r.get(1)?)))
+            .map_err(&at)?
+            .collect::<Result<_, _>>()
+            .map_err(&at)?;
+    }
+
+    Ok(runs)
+}
+
+/// The summaries recorded in `runs`, from the database at `path`, with the
+/// given source, if any.
+pub fn summaries(path: &str, runs: &[Run], source: Option<&str>)
+                 -> Result<Vec<SummaryRow>, DbError> {
+    let at = DbError::at(path);
+    let conn = open(path)?;
+
+    let columns: Vec<&str> = SUMMARY_COLUMNS.iter().map(|&(c, _)| c).collect();
+    let sql = format!(
+        "SELECT source, size, {}, unit FROM summaries \
+         WHERE run = ?1 AND (?2 IS NULL OR source = ?2)",
+        columns.join(", "),
+    );
+    let mut stmt = conn.prepare(&sql).map_err(&at)?;
+    let mut rows = vec![];
+
+    for run in runs {
+        let found = stmt
+            .query_map(params![run.id, source], |r| {
+                let mut stats = [None; 9];
+
+                for (i, s) in stats.iter_mut().enumerate() {
+                    *s = r.get(i + 2)?;
+                }
+
+                Ok(SummaryRow {
+                    run: run.id,
+                    source: r.get(0)?,
+                    size: r.get(1)?,
+                    stats,
+                    unit: r.get(11)?,
+                })
+            })
+            .map_err(&at)?;
+
+        for row in found {
+            rows.push(row.map_err(&at)?);
+        }
+    }
+
+    Ok(rows)
+}
+
+/// The t-tests recorded in `runs`, from the database at `path`, of which the
+/// given source is either sample, if any.
+pub fn t_tests(path: &str, runs: &[Run], source: Option<&str>)
+               -> Result<Vec<TTestRow>, DbError> {
+    let at = DbError::at(path);
+    let conn = open(path)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT source1, source2, t, df, p FROM t_tests \
+             WHERE run = ?1 AND (?2 IS NULL OR ?2 IN (source1, source2))",
+        )
+        .map_err(&at)?;
+    let mut rows = vec![];
+
+    for run in runs {
+        let found = stmt
+            .query_map(params![run.id, source], |r| {
+                Ok(TTestRow {
+                    run: run.id,
+                    source1: r.get(0)?,
+                    source2: r.get(1)?,
+                    t: r.get(2)?,
+                    df: r.get(3)?,
+                    p: r.get(4)?,
+                })
+            })
+            .map_err(&at)?;
+
+        for row in found {
+            rows.push(row.map_err(&at)?);
+        }
+    }
+
+    Ok(rows)
+}
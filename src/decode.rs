@@ -0,0 +1,268 @@
+use std::fmt;
+
+use regex::Regex;
+
+use error::Error;
+
+
+/// A decoder of sample values from lines of text, one or none per line.
+///
+/// Implement this to read values from a format of your own, and pass the
+/// decoded values to `Summarizer::new`, as the `dent` tool does for its own.
+pub trait InputDecoder {
+    /// Decode the value of one line, which has no line terminator, or `None`
+    /// if it holds no value, as for a blank line.
+    fn decode_line(&self, line: &str) -> Result<Option<f64>, Error>;
+
+    /// The number of leading lines to skip, such as the header of a table.
+    fn header_lines(&self) -> usize {
+        0
+    }
+}
+
+/// An error decoding a line of input.
+#[derive(Debug)]
+pub struct DecodeError {
+    /// The 1-based number of the line at fault.
+    pub line: usize,
+
+    pub error: Error,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+/// Decode the values of each line of `text` with `decoder`, stopping at the
+/// first line which cannot be decoded.
+pub fn decode<D>(decoder: &D, text: &str) -> Result<Vec<f64>, DecodeError>
+    where D: InputDecoder + ?Sized {
+    let mut data = vec![];
+
+    for (i, line) in text.lines().enumerate().skip(decoder.header_lines()) {
+        match decoder.decode_line(line) {
+            Ok(Some(x)) => data.push(x),
+            Ok(None) => {},
+            Err(error) => return Err(DecodeError { line: i + 1, error }),
+        }
+    }
+
+    Ok(data)
+}
+
+/// Parse a value, which may be surrounded by whitespace.
+fn parse_value(s: &str) -> Result<f64, Error> {
+    s.trim().parse().map_err(|_| Error::BadValue)
+}
+
+/// A single value per line, as read by `dent` by default. Blank lines are
+/// ignored.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PlainLines;
+
+impl InputDecoder for PlainLines {
+    fn decode_line(&self, line: &str) -> Result<Option<f64>, Error> {
+        if line.trim().is_empty() {
+            return Ok(None);
+        }
+
+        parse_value(line).map(Some)
+    }
+}
+
+/// Comma-separated values, of which those in one column are decoded. Fields
+/// may be quoted with `"`, and a quote within a quoted field doubled, as in
+/// RFC 4180, but no field may span lines. Blank lines, and empty fields, are
+/// ignored.
+#[derive(Clone, Copy, Debug)]
+pub struct Csv {
+    /// The 0-based index of the column of values.
+    pub column: usize,
+
+    pub delimiter: char,
+    pub header: bool,
+}
+
+impl Csv {
+    /// A decoder of the given 0-based column of comma-separated values, with
+    /// a header line.
+    pub fn new(column: usize) -> Self {
+        Csv { column, delimiter: ',', header: true }
+    }
+
+    /// The fields of `line`, unquoted.
+    pub fn fields(&self, line: &str) -> Result<Vec<String>, Error> {
+        let mut fields = vec![];
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if quoted {
+                match c {
+                    '"' if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    },
+                    '"' => quoted = false,
+                    _ => field.push(c),
+                }
+            } else if c == '"' && field.trim().is_empty() {
+                field.clear();
+                quoted = true;
+            } else if c == self.delimiter {
+                fields.push(field);
+                field = String::new();
+            } else {
+                field.push(c);
+            }
+        }
+
+        if quoted {
+            return Err(Error::BadValue);
+        }
+
+        fields.push(field);
+
+        Ok(fields)
+    }
+}
+
+impl InputDecoder for Csv {
+    fn decode_line(&self, line: &str) -> Result<Option<f64>, Error> {
+        if line.trim().is_empty() {
+            return Ok(None);
+        }
+
+        match self.fields(line)?.get(self.column) {
+            Some(f) if f.trim().is_empty() => Ok(None),
+            Some(f) => parse_value(f).map(Some),
+            None => Err(Error::BadValue),
+        }
+    }
+
+    fn header_lines(&self) -> usize {
+        if self.header { 1 } else { 0 }
+    }
+}
+
+/// One JSON object per line, of which the numeric value of a top-level key
+/// is decoded. A `null` value, and a blank line, are ignored. Lines are
+/// scanned, not fully validated, and keys compared without unescaping.
+#[derive(Clone, Debug)]
+pub struct JsonLines {
+    pub key: String,
+}
+
+impl JsonLines {
+    pub fn new(key: &str) -> Self {
+        JsonLines { key: key.to_string() }
+    }
+
+    /// The unparsed text of the value of the top-level key of the object on
+    /// `line`, if it has the key.
+    fn value_text<'a>(&self, line: &'a str) -> Result<Option<&'a str>, Error> {
+        let line = line.trim();
+
+        if !(line.starts_with('{') && line.ends_with('}')) {
+            return Err(Error::BadValue);
+        }
+
+        let bytes = line.as_bytes();
+        let mut depth = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            match bytes[i] {
+                b'{' | b'[' => depth += 1,
+                b'}' | b']' => depth -= 1,
+                b'"' => {
+                    let start = i + 1;
+                    let end = string_end(bytes, start)?;
+                    i = end;
+
+                    let rest = line[end + 1..].trim_start();
+
+                    if depth == 1 && rest.starts_with(':') && line[start..end] == *self.key {
+                        let value = rest[1..].trim_start();
+                        let len = value.find([',', '}']).unwrap_or(value.len());
+
+                        return Ok(Some(value[..len].trim_end()));
+                    }
+                },
+                _ => {},
+            }
+
+            i += 1;
+        }
+
+        Ok(None)
+    }
+}
+
+/// The index of the quote which ends the JSON string starting at `start`.
+fn string_end(bytes: &[u8], start: usize) -> Result<usize, Error> {
+    let mut i = start;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Ok(i),
+            _ => i += 1,
+        }
+    }
+
+    Err(Error::BadValue)
+}
+
+impl InputDecoder for JsonLines {
+    fn decode_line(&self, line: &str) -> Result<Option<f64>, Error> {
+        if line.trim().is_empty() {
+            return Ok(None);
+        }
+
+        match self.value_text(line)? {
+            Some("null") => Ok(None),
+            Some(v) => parse_value(v).map(Some),
+            None => Err(Error::BadValue),
+        }
+    }
+}
+
+/// Values extracted from free-form lines, such as those of a log, by a
+/// regular expression with exactly one capture group about the value, like
+/// `took (\S+) ms`. The pattern may match anywhere in a line, and the value
+/// is the text its group captures in the first match. Lines the pattern does
+/// not match, or matches without capturing, are ignored.
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    regex: Regex,
+}
+
+impl Pattern {
+    /// A pattern of the given regular expression, which must be valid and
+    /// have exactly one capture group.
+    pub fn new(pattern: &str) -> Result<Self, Error> {
+        let regex = Regex::new(pattern).map_err(|_| Error::BadParameter)?;
+
+        // The whole match is implicitly group 0.
+        if regex.captures_len() != 2 {
+            return Err(Error::BadParameter);
+        }
+
+        Ok(Pattern { regex })
+    }
+
+    /// The text captured by the group in the first match of `line`.
+    pub fn extract<'a>(&self, line: &'a str) -> Option<&'a str> {
+        self.regex.captures(line).and_then(|c| c.get(1)).map(|m| m.as_str())
+    }
+}
+
+impl InputDecoder for Pattern {
+    fn decode_line(&self, line: &str) -> Result<Option<f64>, Error> {
+        self.extract(line).map(parse_value).transpose()
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+
+
+/// Which duplicate values to remove from a sample, as when a log reports
+/// some measurements twice. Values are duplicates only if they are exactly
+/// equal, bit for bit, except that `0.0` and `-0.0` are equal, and NaN
+/// values of the same bits are too.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Dedup {
+    /// Every value equal to an earlier one.
+    All,
+
+    /// Every value equal to the one before it.
+    Consecutive,
+}
+
+/// Names of the modes of `Dedup`, as accepted by `Dedup::from_name`.
+pub const DEDUP_NAMES: [&str; 2] = ["all", "consecutive"];
+
+impl Dedup {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "all" => Some(Dedup::All),
+            "consecutive" => Some(Dedup::Consecutive),
+            _ => None,
+        }
+    }
+
+    /// Indices of the values of `data` which are kept, in order.
+    pub fn kept(&self, data: &[f64]) -> Vec<usize> {
+        match *self {
+            Dedup::All => {
+                let mut seen = HashSet::new();
+
+                (0..data.len()).filter(|&i| seen.insert(key(data[i]))).collect()
+            },
+            Dedup::Consecutive => (0..data.len())
+                .filter(|&i| i == 0 || key(data[i]) != key(data[i - 1]))
+                .collect(),
+        }
+    }
+}
+
+// The bits of `x`, with the sign of zero dropped, to compare values by.
+fn key(x: f64) -> u64 {
+    if x == 0.0 { 0 } else { x.to_bits() }
+}
@@ -0,0 +1,279 @@
+use std::fmt;
+
+use error::Error;
+use special::normal_cdf;
+use summary::{self, Summarizer, Summary};
+
+
+/// Fraction of observations which must be tied with another before we warn.
+const HEAVY_TIE_FRACTION: f64 = 0.5;
+
+/// Minimum number of values both above and below the median before we test
+/// their order, so that the normal approximation of the runs test holds.
+const RUNS_MIN_SIZE: usize = 10;
+
+/// Significance level of the runs test below which we warn. It is strict,
+/// since the check runs on every input, unasked.
+const RUNS_ALPHA: f64 = 0.01;
+
+/// Minimum number of residuals before we test their variance, so that the
+/// chi-squared approximation of the Breusch-Pagan test holds.
+const VARIANCE_MIN_SIZE: usize = 20;
+
+/// Significance level of the Breusch-Pagan test below which we warn, strict
+/// for the same reason as `RUNS_ALPHA`.
+const VARIANCE_ALPHA: f64 = 0.01;
+
+/// A structural problem with sample data which may make downstream statistics
+/// meaningless, even though they can be computed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Diagnostic {
+    /// Every observation has the same value, so the variance is zero.
+    ConstantData { value: f64 },
+
+    /// Many observations share a value with another, which suggests the data
+    /// were rounded or quantized.
+    HeavyTies { distinct: usize, size: usize },
+
+    /// The sample is too small for the requested analysis.
+    TooSmall { size: usize, required: usize },
+
+    /// Both samples of a comparison have zero variance, so the t statistic is
+    /// undefined.
+    ZeroVariance,
+
+    /// Values in order fall above and below the median in too few or too
+    /// many runs to be independent, by the runs test.
+    NonRandomOrder { runs: usize, expected: f64, p: f64 },
+
+    /// The residuals of a fit spread more at one end of the predictor than at
+    /// the other, by the Breusch-Pagan test, so their variance is not
+    /// constant, as the standard errors of the fit assume.
+    NonConstantVariance { statistic: f64, p: f64, increasing: bool },
+}
+
+impl Diagnostic {
+    /// True if the diagnostic implies a t-test of the data would be undefined.
+    pub fn blocks_t_test(&self) -> bool {
+        matches!(*self, Diagnostic::TooSmall { .. } | Diagnostic::ZeroVariance)
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Diagnostic::ConstantData { value } =>
+                write!(f, "All values are identical ({})", value),
+            Diagnostic::HeavyTies { distinct, size } =>
+                write!(f, "Heavy ties: only {} distinct values in {} observations",
+                       distinct, size),
+            Diagnostic::TooSmall { size, required } =>
+                write!(f, "Sample size {} is too small, need at least {}", size, required),
+            Diagnostic::ZeroVariance =>
+                write!(f, "Both samples have zero variance, so the t-test is undefined"),
+            Diagnostic::NonRandomOrder { runs, expected, p } =>
+                write!(f, "Non-random order: {} runs above and below the median, \
+                           where {:.1} are expected (p = {:.1e}), suggesting {}",
+                       runs, expected, p,
+                       if (runs as f64) < expected { "a trend" } else { "oscillation" }),
+            Diagnostic::NonConstantVariance { statistic, p, increasing } =>
+                write!(f, "Non-constant variance: squared residuals {} with the predictor \
+                           (LM = {:.2}, p = {:.1e}), so standard errors may be wrong",
+                       if increasing { "grow" } else { "shrink" }, statistic, p),
+        }
+    }
+}
+
+/// Check a single sample for suspicious structure.
+pub fn check_sample(s: &Summarizer) -> Vec<Diagnostic> {
+    let data = s.as_slice();
+    let size = data.len();
+    let mut diagnostics = vec![];
+
+    if size > 1 && s.min() == s.max() {
+        diagnostics.push(Diagnostic::ConstantData { value: s.min() });
+
+        // Ties are implied, so don't report them twice.
+        return diagnostics;
+    }
+
+    // The data are sorted, so ties are adjacent.
+    let distinct = 1 + data.windows(2).filter(|w| w[0] != w[1]).count();
+    let tied = (size - distinct) as f64;
+
+    if tied / size as f64 >= HEAVY_TIE_FRACTION {
+        diagnostics.push(Diagnostic::HeavyTies { distinct, size });
+    }
+
+    diagnostics
+}
+
+/// Check whether a two-sample t-test of `s1` and `s2` is well-defined.
+pub fn check_t_test(s1: &Summary, s2: &Summary) -> Vec<Diagnostic> {
+    let required = 2;
+    let mut diagnostics = vec![];
+
+    for s in &[s1, s2] {
+        let size = s.size() as usize;
+
+        if size < required {
+            diagnostics.push(Diagnostic::TooSmall { size, required });
+        }
+    }
+
+    if diagnostics.is_empty() && s1.unbiased_variance() == 0.0 && s2.unbiased_variance() == 0.0 {
+        diagnostics.push(Diagnostic::ZeroVariance);
+    }
+
+    diagnostics
+}
+
+/// The Wald-Wolfowitz runs test [1] of a sequence, of whether its values fall
+/// above and below its median independently of their order.
+///
+/// Too few runs, or maximal sequences on one side of the median, suggest
+/// drift or autocorrelation; too many suggest oscillation.
+///
+/// [1]: A. Wald and J. Wolfowitz, "On a test whether two samples are from
+///      the same population", Ann. Math. Stat. 11(2), 1940.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RunsTest {
+    pub runs: usize,
+
+    /// Counts of values above and below the median. Values equal to it are
+    /// discarded.
+    pub above: usize,
+    pub below: usize,
+
+    /// Mean number of runs of a random order, and the standardized distance
+    /// of `runs` from it.
+    pub expected: f64,
+    pub z: f64,
+
+    /// Two-sided p-value, from the normal approximation.
+    pub p: f64,
+}
+
+/// Run the runs test on `data`, in order. There must be values both above
+/// and below the median, and at least three in all.
+pub fn runs_test(data: &[f64]) -> Result<RunsTest, Error> {
+    let median = summary::percentile_of_unsorted(&mut data.to_vec(), 0.5)?;
+
+    let sides: Vec<bool> = data.iter().filter(|&&x| x != median).map(|&x| x > median).collect();
+
+    let above = sides.iter().filter(|&&s| s).count();
+    let below = sides.len() - above;
+
+    if above == 0 || below == 0 || sides.len() < 3 {
+        return Err(Error::Undefined);
+    }
+
+    let runs = 1 + sides.windows(2).filter(|w| w[0] != w[1]).count();
+
+    let (n1, n2) = (above as f64, below as f64);
+    let n = n1 + n2;
+    let expected = 2.0 * n1 * n2 / n + 1.0;
+    let variance = (expected - 1.0) * (expected - 2.0) / (n - 1.0);
+
+    let z = (runs as f64 - expected) / variance.sqrt();
+    let p = 2.0 * normal_cdf(-z.abs());
+
+    Ok(RunsTest { runs, above, below, expected, z, p })
+}
+
+/// Check the order of a sequence, such as a sample in input order or the
+/// residuals of a fit, for drift or oscillation. Non-finite values are
+/// ignored.
+pub fn check_order(data: &[f64]) -> Vec<Diagnostic> {
+    let finite: Vec<f64> = data.iter().cloned().filter(|x| x.is_finite()).collect();
+
+    match runs_test(&finite) {
+        Ok(t) if t.above.min(t.below) >= RUNS_MIN_SIZE && t.p < RUNS_ALPHA => {
+            vec![Diagnostic::NonRandomOrder { runs: t.runs, expected: t.expected, p: t.p }]
+        },
+        _ => vec![],
+    }
+}
+
+/// The Breusch-Pagan test [1] of the residuals of a regression, of whether
+/// their variance is constant, in the studentized form of Koenker [2].
+///
+/// The statistic is `n R²` of the regression of the squared residuals on the
+/// predictor, which is asymptotically chi-squared with one degree of freedom
+/// when the variance is constant.
+///
+/// [1]: T. S. Breusch and A. R. Pagan, "A simple test for heteroscedasticity
+///      and random coefficient variation", Econometrica 47(5), 1979.
+/// [2]: R. Koenker, "A note on studentizing a test for heteroscedasticity",
+///      J. Econometrics 17(1), 1981.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BreuschPagan {
+    pub statistic: f64,
+
+    /// Slope of the squared residuals on the predictor, positive if they
+    /// spread as it grows.
+    pub slope: f64,
+
+    /// Upper-tail p-value, from the chi-squared approximation.
+    pub p: f64,
+}
+
+/// Run the Breusch-Pagan test on `residuals`, of the fits at the respective
+/// `predictors`. There must be at least three of each, and neither the
+/// predictors nor the squared residuals may all be equal.
+pub fn breusch_pagan(predictors: &[f64], residuals: &[f64]) -> Result<BreuschPagan, Error> {
+    if predictors.len() != residuals.len() {
+        return Err(Error::BadParameter);
+    }
+
+    if residuals.len() < 3 {
+        return Err(Error::Undefined);
+    }
+
+    let squares: Vec<f64> = residuals.iter().map(|e| e * e).collect();
+
+    let n = squares.len() as f64;
+    let mean_x = predictors.iter().sum::<f64>() / n;
+    let mean_u = squares.iter().sum::<f64>() / n;
+
+    let sxx: f64 = predictors.iter().map(|x| (x - mean_x).powi(2)).sum();
+    let suu: f64 = squares.iter().map(|u| (u - mean_u).powi(2)).sum();
+    let sxu: f64 = predictors.iter().zip(&squares).map(|(x, u)| (x - mean_x) * (u - mean_u)).sum();
+
+    if sxx == 0.0 || suu == 0.0 || !(sxx * suu).is_finite() {
+        return Err(Error::Undefined);
+    }
+
+    let statistic = n * sxu * sxu / (sxx * suu);
+
+    // The chi-squared distribution with one degree of freedom is that of a
+    // squared standard normal.
+    let p = 2.0 * normal_cdf(-statistic.sqrt());
+
+    Ok(BreuschPagan { statistic, slope: sxu / sxx, p })
+}
+
+/// Check the residuals of a fit, at the respective `predictors`, for variance
+/// which changes with the predictor. Pairs with a non-finite value are
+/// ignored.
+pub fn check_variance(predictors: &[f64], residuals: &[f64]) -> Vec<Diagnostic> {
+    let (xs, es): (Vec<f64>, Vec<f64>) = predictors
+        .iter()
+        .cloned()
+        .zip(residuals.iter().cloned())
+        .filter(|&(x, e)| x.is_finite() && e.is_finite())
+        .unzip();
+
+    if xs.len() < VARIANCE_MIN_SIZE {
+        return vec![];
+    }
+
+    match breusch_pagan(&xs, &es) {
+        Ok(t) if t.p < VARIANCE_ALPHA => vec![Diagnostic::NonConstantVariance {
+            statistic: t.statistic,
+            p: t.p,
+            increasing: t.slope > 0.0,
+        }],
+        _ => vec![],
+    }
+}
@@ -0,0 +1,241 @@
+use error::Error;
+use rng::Rng;
+
+
+/// A continuous probability distribution which can be sampled from.
+pub trait Distribution {
+    /// Draw one random variate using `rng`.
+    fn sample(&self, rng: &mut Rng) -> f64;
+
+    /// Draw `n` independent random variates using `rng`.
+    fn sample_n(&self, rng: &mut Rng, n: usize) -> Vec<f64> {
+        (0..n).map(|_| self.sample(rng)).collect()
+    }
+}
+
+/// Names accepted by `from_name`, with the parameters each expects.
+pub const NAMES: [(&str, &str); 5] = [
+    ("normal", "MEAN SD"),
+    ("lognormal", "MU SIGMA"),
+    ("exponential", "RATE"),
+    ("gamma", "SHAPE RATE"),
+    ("uniform", "LOW HIGH"),
+];
+
+/// Construct a distribution from its name in `NAMES` and its parameters, in
+/// the listed order.
+pub fn from_name(name: &str, params: &[f64]) -> Result<Box<dyn Distribution>, Error> {
+    let arity = |n| if params.len() == n { Ok(()) } else { Err(Error::BadParameter) };
+
+    let dist: Box<dyn Distribution> = match name {
+        "normal" => {
+            arity(2)?;
+            Box::new(Normal::new(params[0], params[1])?)
+        },
+        "lognormal" => {
+            arity(2)?;
+            Box::new(LogNormal::new(params[0], params[1])?)
+        },
+        "exponential" => {
+            arity(1)?;
+            Box::new(Exponential::new(params[0])?)
+        },
+        "gamma" => {
+            arity(2)?;
+            Box::new(Gamma::new(params[0], params[1])?)
+        },
+        "uniform" => {
+            arity(2)?;
+            Box::new(Uniform::new(params[0], params[1])?)
+        },
+        _ => return Err(Error::BadParameter),
+    };
+
+    Ok(dist)
+}
+
+fn check(valid: bool) -> Result<(), Error> {
+    if valid { Ok(()) } else { Err(Error::BadParameter) }
+}
+
+/// The normal distribution with the given mean and standard deviation.
+#[derive(Clone, Copy, Debug)]
+pub struct Normal {
+    mean: f64,
+    sd: f64,
+}
+
+impl Normal {
+    pub fn new(mean: f64, sd: f64) -> Result<Self, Error> {
+        check(mean.is_finite() && sd.is_finite() && sd > 0.0)?;
+
+        Ok(Normal { mean, sd })
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    pub fn sd(&self) -> f64 {
+        self.sd
+    }
+}
+
+impl Distribution for Normal {
+    /// Sampled using the Box-Muller transform [1].
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Box%E2%80%93Muller_transform
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        use std::f64::consts::PI;
+
+        // Use `1 - u` to avoid taking the log of 0.
+        let u1 = 1.0 - rng.next_f64();
+        let u2 = rng.next_f64();
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos();
+
+        self.mean + self.sd * z
+    }
+}
+
+/// The log-normal distribution, whose logarithm is normal with mean `mu` and
+/// standard deviation `sigma`.
+#[derive(Clone, Copy, Debug)]
+pub struct LogNormal {
+    normal: Normal,
+}
+
+impl LogNormal {
+    pub fn new(mu: f64, sigma: f64) -> Result<Self, Error> {
+        Ok(LogNormal { normal: Normal::new(mu, sigma)? })
+    }
+
+    pub fn mu(&self) -> f64 {
+        self.normal.mean()
+    }
+
+    pub fn sigma(&self) -> f64 {
+        self.normal.sd()
+    }
+}
+
+impl Distribution for LogNormal {
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        self.normal.sample(rng).exp()
+    }
+}
+
+/// The exponential distribution with the given rate (inverse mean).
+#[derive(Clone, Copy, Debug)]
+pub struct Exponential {
+    rate: f64,
+}
+
+impl Exponential {
+    pub fn new(rate: f64) -> Result<Self, Error> {
+        check(rate.is_finite() && rate > 0.0)?;
+
+        Ok(Exponential { rate })
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+}
+
+impl Distribution for Exponential {
+    /// Sampled by inverting the CDF.
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        -(1.0 - rng.next_f64()).ln() / self.rate
+    }
+}
+
+/// The gamma distribution with the given shape and rate, whose mean is
+/// `shape / rate`.
+#[derive(Clone, Copy, Debug)]
+pub struct Gamma {
+    shape: f64,
+    rate: f64,
+}
+
+impl Gamma {
+    pub fn new(shape: f64, rate: f64) -> Result<Self, Error> {
+        check(shape.is_finite() && shape > 0.0 && rate.is_finite() && rate > 0.0)?;
+
+        Ok(Gamma { shape, rate })
+    }
+
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+}
+
+impl Distribution for Gamma {
+    /// Sampled by the rejection method of Marsaglia and Tsang [1]. Shapes
+    /// below 1 are sampled with the shape increased by 1, then scaled by
+    /// `u^(1/shape)`, as in [1, §6].
+    ///
+    /// [1]: G. Marsaglia and W. Tsang, "A Simple Method for Generating Gamma
+    ///      Variables", ACM Trans. Math. Softw. 26(3), 2000.
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        let standard_normal = Normal { mean: 0.0, sd: 1.0 };
+
+        let (shape, scale) = if self.shape < 1.0 {
+            let u = 1.0 - rng.next_f64();
+            (self.shape + 1.0, u.powf(self.shape.recip()))
+        } else {
+            (self.shape, 1.0)
+        };
+
+        let d = shape - 1.0 / 3.0;
+        let c = (9.0 * d).sqrt().recip();
+
+        loop {
+            let z = standard_normal.sample(rng);
+            let v = (1.0 + c * z).powi(3);
+
+            if v <= 0.0 {
+                continue;
+            }
+
+            // Use `1 - u` to avoid taking the log of 0.
+            let u = 1.0 - rng.next_f64();
+
+            if u.ln() < 0.5 * z * z + d - d * v + d * v.ln() {
+                return scale * d * v / self.rate;
+            }
+        }
+    }
+}
+
+/// The continuous uniform distribution on the interval [low, high).
+#[derive(Clone, Copy, Debug)]
+pub struct Uniform {
+    low: f64,
+    high: f64,
+}
+
+impl Uniform {
+    pub fn new(low: f64, high: f64) -> Result<Self, Error> {
+        check(low.is_finite() && high.is_finite() && low < high)?;
+
+        Ok(Uniform { low, high })
+    }
+
+    pub fn low(&self) -> f64 {
+        self.low
+    }
+
+    pub fn high(&self) -> f64 {
+        self.high
+    }
+}
+
+impl Distribution for Uniform {
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        self.low + (self.high - self.low) * rng.next_f64()
+    }
+}
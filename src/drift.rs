@@ -0,0 +1,128 @@
+use error::Error;
+use summary::Summarizer;
+
+
+/// Default number of bins, the deciles of the baseline sample.
+pub const DEFAULT_BINS: usize = 10;
+
+/// Pseudo-count added to each bin of both samples if any bin of either is
+/// empty, so that every term of each divergence is finite.
+const EMPTY_BIN_CORRECTION: f64 = 0.5;
+
+/// One bin of the shared binning of two samples, with the proportion of each
+/// sample which falls in it.
+#[derive(Clone, Copy, Debug)]
+pub struct Bin {
+    /// Exclusive lower and inclusive upper bounds, which are infinite for the
+    /// first and last bins.
+    pub lower: f64,
+    pub upper: f64,
+
+    pub baseline: f64,
+    pub current: f64,
+}
+
+impl Bin {
+    /// The term of the population stability index for this bin.
+    pub fn psi(&self) -> f64 {
+        (self.current - self.baseline) * (self.current / self.baseline).ln()
+    }
+}
+
+/// Measures of how far the distribution of a current sample has drifted from
+/// that of a baseline, computed over bins at quantiles of the baseline.
+///
+/// All are in nats, and are 0 when the binned distributions are equal.
+#[derive(Clone, Debug)]
+pub struct Drift {
+    pub bins: Vec<Bin>,
+
+    /// Population stability index, `Σ (c - b) ln(c / b)` over bins with
+    /// proportions `b` of the baseline and `c` of the current sample. This
+    /// is the symmetrized Kullback-Leibler divergence.
+    pub psi: f64,
+
+    /// Kullback-Leibler divergence `Σ c ln(c / b)` of the current sample from
+    /// the baseline.
+    pub kl_divergence: f64,
+
+    /// Jensen-Shannon divergence, the mean divergence of each sample from
+    /// their mixture. Unlike the others, it is bounded, by `ln 2`.
+    pub js_divergence: f64,
+}
+
+impl Drift {
+    /// Compare `current` to `baseline`, in `bins` bins with edges at evenly
+    /// spaced quantiles of the baseline. Repeated edges, as from ties in the
+    /// baseline, are merged, so there may be fewer bins.
+    pub fn new(baseline: &[f64], current: &[f64], bins: usize) -> Result<Self, Error> {
+        if bins < 2 {
+            return Err(Error::BadParameter);
+        }
+
+        if current.is_empty() {
+            return Err(Error::EmptySample);
+        }
+
+        if current.iter().any(|x| !x.is_finite()) {
+            return Err(Error::BadSample);
+        }
+
+        let summarizer = Summarizer::new(baseline)?;
+
+        let mut edges = vec![];
+
+        for i in 1..bins {
+            let edge = summarizer.percentile(i as f64 / bins as f64)?;
+
+            if edges.last() != Some(&edge) {
+                edges.push(edge);
+            }
+        }
+
+        let counts = |data: &[f64]| {
+            let mut counts = vec![0.0; edges.len() + 1];
+
+            for &x in data {
+                counts[edges.partition_point(|&e| e < x)] += 1.0;
+            }
+
+            counts
+        };
+
+        let mut baseline_counts = counts(baseline);
+        let mut current_counts = counts(current);
+
+        if baseline_counts.iter().chain(&current_counts).any(|&c| c == 0.0) {
+            for c in baseline_counts.iter_mut().chain(current_counts.iter_mut()) {
+                *c += EMPTY_BIN_CORRECTION;
+            }
+        }
+
+        let baseline_total: f64 = baseline_counts.iter().sum();
+        let current_total: f64 = current_counts.iter().sum();
+
+        let bins: Vec<Bin> = (0..baseline_counts.len())
+            .map(|i| Bin {
+                lower: if i == 0 { f64::NEG_INFINITY } else { edges[i - 1] },
+                upper: edges.get(i).cloned().unwrap_or(f64::INFINITY),
+                baseline: baseline_counts[i] / baseline_total,
+                current: current_counts[i] / current_total,
+            })
+            .collect();
+
+        let psi = bins.iter().map(Bin::psi).sum();
+
+        let kl = |p: &dyn Fn(&Bin) -> f64, q: &dyn Fn(&Bin) -> f64| -> f64 {
+            bins.iter().map(|b| p(b) * (p(b) / q(b)).ln()).sum()
+        };
+
+        let mixture = |b: &Bin| 0.5 * (b.baseline + b.current);
+
+        let kl_divergence = kl(&|b| b.current, &|b| b.baseline);
+        let js_divergence =
+            0.5 * kl(&|b| b.baseline, &mixture) + 0.5 * kl(&|b| b.current, &mixture);
+
+        Ok(Drift { bins, psi, kl_divergence, js_divergence })
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt;
+
+use error::Error;
+use summary::Summary;
+
+
+/// Cohen's d, the standardized difference of means `(m₂ - m₁) / s`, where `s`
+/// is the pooled standard deviation of the samples.
+pub fn cohens_d(s1: &Summary, s2: &Summary) -> Result<f64, Error> {
+    let (n1, n2) = (s1.size(), s2.size());
+
+    if n1 + n2 < 3.0 {
+        return Err(Error::Undefined);
+    }
+
+    let pooled_var = ((n1 - 1.0) * s1.unbiased_variance().max(0.0) +
+                      (n2 - 1.0) * s2.unbiased_variance().max(0.0)) / (n1 + n2 - 2.0);
+
+    if pooled_var.is_nan() || pooled_var == 0.0 {
+        return Err(Error::Undefined);
+    }
+
+    Ok((s2.mean() - s1.mean()) / pooled_var.sqrt())
+}
+
+/// Conventional description of the size of an effect, after Cohen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Magnitude {
+    Negligible,
+    Small,
+    Medium,
+    Large,
+}
+
+impl Magnitude {
+    /// Classify a standardized difference `d` by the thresholds 0.2, 0.5, and
+    /// 0.8 on `|d|`.
+    pub fn of(d: f64) -> Self {
+        match d.abs() {
+            a if a < 0.2 => Magnitude::Negligible,
+            a if a < 0.5 => Magnitude::Small,
+            a if a < 0.8 => Magnitude::Medium,
+            _ => Magnitude::Large,
+        }
+    }
+}
+
+impl fmt::Display for Magnitude {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let s = match *self {
+            Magnitude::Negligible => "negligible",
+            Magnitude::Small => "small",
+            Magnitude::Medium => "medium",
+            Magnitude::Large => "large",
+        };
+
+        write!(f, "{}", s)
+    }
+}
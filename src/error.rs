@@ -3,10 +3,34 @@ use std;
 
 #[derive(Debug)]
 pub enum Error {
+    BadParameter,
+    BadRecord,
     BadSample,
+    BadTimestamp,
+    BadValue,
     Diverged,
     EmptySample,
+    InvalidSummary,
     Undefined,
+    UnpairedSamples,
+}
+
+impl Error {
+    /// A stable identifier of the kind of error, for machine-readable output.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            Error::BadParameter => "bad_parameter",
+            Error::BadRecord => "bad_record",
+            Error::BadSample => "bad_sample",
+            Error::BadTimestamp => "bad_timestamp",
+            Error::BadValue => "bad_value",
+            Error::Diverged => "diverged",
+            Error::EmptySample => "empty_sample",
+            Error::InvalidSummary => "invalid_summary",
+            Error::Undefined => "undefined",
+            Error::UnpairedSamples => "unpaired_samples",
+        }
+    }
 }
 
 impl std::fmt::Display for Error {
@@ -18,10 +42,16 @@ impl std::fmt::Display for Error {
 impl std::error::Error for Error {
     fn description(&self) -> &str {
         match *self {
+            Error::BadParameter => "Invalid parameter value",
+            Error::BadRecord => "Malformed summary table record",
             Error::BadSample => "All sample data must be finite",
+            Error::BadTimestamp => "Timestamps must be RFC 3339 date-times or epoch seconds",
+            Error::BadValue => "Input line has no valid value",
             Error::Diverged => "Numeric evaluation diverged",
             Error::EmptySample => "Sample data set cannot be empty",
+            Error::InvalidSummary => "Summary statistics violate an invariant",
             Error::Undefined => "Function undefined for argument",
+            Error::UnpairedSamples => "Paired samples must have equal size",
         }
     }
 }
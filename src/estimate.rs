@@ -0,0 +1,174 @@
+use std::fmt;
+
+use error::Error;
+use special::normal_quantile;
+use summary::Summarizer;
+
+
+/// Confidence level of the error bounds of approximate estimates.
+pub const ERROR_BOUND_CONFIDENCE: f64 = 0.95;
+
+/// How a result was computed: exactly from all of the data, or approximately,
+/// by random resampling or subsampling, or in one pass without the data.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Method {
+    /// Computed from all of the data, deterministically.
+    Exact,
+
+    /// Estimated by the percentile bootstrap from `resamples` resamplings.
+    Bootstrap { resamples: usize },
+
+    /// Estimated from a random subsample of `size` of `population` values.
+    Subsample { size: usize, population: usize },
+
+    /// Estimated in one pass over the data, without holding it: quantiles by
+    /// the P² algorithm, and adjacent values taken to be the fences, 1.5 IQR
+    /// beyond the quartiles, within the range of the data.
+    Sketch,
+}
+
+impl Method {
+    pub fn is_exact(&self) -> bool {
+        *self == Method::Exact
+    }
+
+    /// A compact label of the method, for machine-readable output, such as
+    /// `exact`, `bootstrap:1000`, `subsample:100/5000`, or `sketch`.
+    pub fn label(&self) -> String {
+        match *self {
+            Method::Exact => "exact".to_string(),
+            Method::Bootstrap { resamples } => format!("bootstrap:{}", resamples),
+            Method::Subsample { size, population } => format!("subsample:{}/{}", size, population),
+            Method::Sketch => "sketch".to_string(),
+        }
+    }
+
+    /// Parse a label written by `label`.
+    pub fn parse_label(text: &str) -> Result<Self, Error> {
+        let count = |n: &str| n.parse::<usize>().map_err(|_| Error::BadRecord);
+
+        match text.split_once(':') {
+            None if text == "exact" => Ok(Method::Exact),
+            None if text == "sketch" => Ok(Method::Sketch),
+            Some(("bootstrap", n)) => Ok(Method::Bootstrap { resamples: count(n)? }),
+            Some(("subsample", sizes)) => match sizes.split_once('/') {
+                Some((size, population)) => Ok(Method::Subsample {
+                    size: count(size)?,
+                    population: count(population)?,
+                }),
+                None => Err(Error::BadRecord),
+            },
+            _ => Err(Error::BadRecord),
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Method::Exact => write!(f, "exact"),
+            Method::Bootstrap { resamples } => {
+                write!(f, "bootstrap of {} resamples", resamples)
+            },
+            Method::Subsample { size, population } => {
+                write!(f, "subsample of {} of {} values", size, population)
+            },
+            Method::Sketch => write!(f, "P² quantiles, with the fences as adjacent values"),
+        }
+    }
+}
+
+/// A result, with how it was computed. An approximate result may have an
+/// error bound: the half-width of an interval about it which holds the
+/// result of an exact computation with `ERROR_BOUND_CONFIDENCE`.
+///
+/// The bound is of the error due to approximation alone, not of the sampling
+/// error of the data, which intervals report.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estimate<T> {
+    pub value: T,
+    pub method: Method,
+    pub error_bound: Option<f64>,
+}
+
+impl<T> Estimate<T> {
+    pub fn exact(value: T) -> Self {
+        Estimate { value, method: Method::Exact, error_bound: None }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.method.is_exact()
+    }
+}
+
+/// A percentile bootstrap interval at the given `confidence` level, from
+/// the statistics of `resamples` resamplings, of which those in `boot` were
+/// defined.
+///
+/// The error bound is the Monte Carlo error of the interval's endpoints: the
+/// greatest distance from either to the resampled statistics whose ranks may
+/// differ from its own by chance, at `ERROR_BOUND_CONFIDENCE`.
+pub fn bootstrap_interval(boot: &Summarizer, confidence: f64, resamples: usize)
+                          -> Result<Estimate<(f64, f64)>, Error> {
+    if !(0.0 < confidence && confidence < 1.0) {
+        return Err(Error::BadParameter);
+    }
+
+    let alpha = 1.0 - confidence;
+    let z = normal_quantile(0.5 + 0.5 * ERROR_BOUND_CONFIDENCE)?;
+    let n = boot.size();
+
+    let mut endpoints = [0.0; 2];
+    let mut error_bound: f64 = 0.0;
+
+    for (x, &p) in endpoints.iter_mut().zip(&[alpha / 2.0, 1.0 - alpha / 2.0]) {
+        let delta = z * (p * (1.0 - p) / n).sqrt();
+
+        *x = boot.percentile(p)?;
+
+        let lo = boot.percentile((p - delta).max(0.0))?;
+        let hi = boot.percentile((p + delta).min(1.0))?;
+
+        error_bound = error_bound.max(*x - lo).max(hi - *x);
+    }
+
+    Ok(Estimate {
+        value: (endpoints[0], endpoints[1]),
+        method: Method::Bootstrap { resamples },
+        error_bound: Some(error_bound),
+    })
+}
+
+/// The mean of `sample`, a random subsample of `population` values drawn
+/// without replacement.
+///
+/// The error bound is that of the subsample mean as an estimate of the mean
+/// of all of the values, with the finite population correction. It is
+/// undefined for a subsample of one value. A subsample of every value is
+/// exact.
+pub fn subsample_mean(sample: &Summarizer, population: usize) -> Result<Estimate<f64>, Error> {
+    let size = sample.as_slice().len();
+
+    if size > population {
+        return Err(Error::BadParameter);
+    }
+
+    if size == population {
+        return Ok(Estimate::exact(sample.mean()));
+    }
+
+    let error_bound = if size > 1 {
+        let z = normal_quantile(0.5 + 0.5 * ERROR_BOUND_CONFIDENCE)?;
+        let correction = (population - size) as f64 / (population - 1) as f64;
+
+        Some(z * sample.standard_error() * correction.sqrt())
+    } else {
+        None
+    };
+
+    Ok(Estimate {
+        value: sample.mean(),
+        method: Method::Subsample { size, population },
+        error_bound,
+    })
+}
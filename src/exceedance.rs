@@ -0,0 +1,133 @@
+use categorical::wilson_interval;
+use error::Error;
+use special::{normal_cdf, normal_quantile};
+
+
+/// The observations of a sample on one side of a threshold.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Side {
+    pub count: u64,
+    pub proportion: f64,
+
+    /// Wilson score interval of the proportion, as of `Category::interval`.
+    pub interval: (f64, f64),
+
+    /// Mean of the observations on this side, if any. Beyond a threshold of
+    /// latency, this is the expected shortfall, or conditional value at risk:
+    /// how bad the exceedances are, and not only how many.
+    pub mean: Option<f64>,
+}
+
+/// How many observations of a sample exceed a threshold, as of an objective
+/// that 99% of requests take at most 300 ms, and by how much.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Exceedance {
+    pub threshold: f64,
+    pub size: u64,
+    pub confidence: f64,
+
+    /// Observations strictly above the threshold.
+    pub above: Side,
+
+    /// Observations at or below the threshold.
+    pub below: Side,
+}
+
+impl Exceedance {
+    /// Count the values of `data`, which must be nonempty, above and at or
+    /// below `threshold`, with intervals of each proportion at the given
+    /// confidence level. NaN values are rejected.
+    pub fn new(data: &[f64], threshold: f64, confidence: f64) -> Result<Self, Error> {
+        if !(0.0 < confidence && confidence < 1.0) || threshold.is_nan() {
+            return Err(Error::BadParameter);
+        }
+
+        if data.is_empty() {
+            return Err(Error::EmptySample);
+        }
+
+        if data.iter().any(|x| x.is_nan()) {
+            return Err(Error::BadSample);
+        }
+
+        let z = normal_quantile(0.5 + 0.5 * confidence)?;
+        let size = data.len() as u64;
+
+        let side = |values: Vec<f64>| {
+            let count = values.len() as u64;
+            let mean = if values.is_empty() {
+                None
+            } else {
+                Some(values.iter().sum::<f64>() / values.len() as f64)
+            };
+
+            Side {
+                count,
+                proportion: count as f64 / size as f64,
+                interval: wilson_interval(count, size, z),
+                mean,
+            }
+        };
+
+        let (above, below): (Vec<f64>, Vec<f64>) = data.iter().partition(|&&x| x > threshold);
+
+        Ok(Exceedance { threshold, size, confidence, above: side(above), below: side(below) })
+    }
+}
+
+/// The change in the proportion of a sample exceeding a threshold, from a
+/// first sample to a second, as in the fraction of requests which violate an
+/// objective before and after a change, with the two-proportion z-test of
+/// whether it differs from zero.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExceedanceChange {
+    /// The proportion above the threshold of the second sample, less that of
+    /// the first.
+    pub difference: f64,
+
+    /// Newcombe's hybrid score interval [1] of the difference, from the Wilson
+    /// intervals of each proportion.
+    ///
+    /// [1]: R. G. Newcombe, "Interval estimation for the difference between
+    ///      independent proportions", Stat. Med. 17(8), 1998.
+    pub interval: (f64, f64),
+
+    pub z: f64,
+
+    /// Two-sided p-value of the z-test, from the pooled proportion.
+    pub p: f64,
+}
+
+impl ExceedanceChange {
+    /// Compare the exceedances of two samples, of the same threshold and at
+    /// the same confidence level.
+    pub fn new(first: &Exceedance, second: &Exceedance) -> Result<Self, Error> {
+        if first.threshold != second.threshold || first.confidence != second.confidence {
+            return Err(Error::BadParameter);
+        }
+
+        let (p1, (l1, u1)) = (first.above.proportion, first.above.interval);
+        let (p2, (l2, u2)) = (second.above.proportion, second.above.interval);
+        let difference = p2 - p1;
+
+        let interval = (
+            difference - ((p2 - l2).powi(2) + (u1 - p1).powi(2)).sqrt(),
+            difference + ((u2 - p2).powi(2) + (p1 - l1).powi(2)).sqrt(),
+        );
+
+        let (n1, n2) = (first.size as f64, second.size as f64);
+        let pooled = (first.above.count + second.above.count) as f64 / (n1 + n2);
+        let se = (pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
+
+        // If neither or both samples exceed the threshold throughout, they do
+        // not differ.
+        let (z, p) = if se == 0.0 {
+            (0.0, 1.0)
+        } else {
+            let z = difference / se;
+            (z, 2.0 * normal_cdf(-z.abs()))
+        };
+
+        Ok(ExceedanceChange { difference, interval, z, p })
+    }
+}
@@ -0,0 +1,260 @@
+use std::error;
+use std::fmt;
+
+
+/// An arithmetic expression of named columns of a row, such as
+/// `(a - b) / b`, which derives a value from each row of a table.
+///
+/// Expressions are made of numbers, column names, the operators `+`, `-`,
+/// `*`, and `/`, with the usual precedence, negation, and parentheses. A
+/// name begins with a letter or `_`, and holds only letters, digits, and
+/// `_`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Column(String),
+    Neg(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// Reasons an expression cannot be parsed, or evaluated.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExprError {
+    /// A character which cannot begin a token, or a token out of place, at
+    /// the given 1-based character position.
+    Unexpected { position: usize },
+
+    /// The expression ended where an operand or `)` was expected.
+    UnexpectedEnd,
+
+    /// A column named in the expression is not among those of the input.
+    UnknownColumn(String),
+}
+
+impl ExprError {
+    /// A stable identifier of the kind of error, for machine-readable output.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            ExprError::Unexpected { .. } | ExprError::UnexpectedEnd => "bad_expression",
+            ExprError::UnknownColumn(_) => "unknown_column",
+        }
+    }
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            ExprError::Unexpected { position } => {
+                write!(f, "Unexpected character in expression at position {}", position)
+            },
+            ExprError::UnexpectedEnd => write!(f, "Unexpected end of expression"),
+            ExprError::UnknownColumn(ref name) => write!(f, "No column named {:?}", name),
+        }
+    }
+}
+
+impl error::Error for ExprError {}
+
+impl Expr {
+    pub fn parse(text: &str) -> Result<Expr, ExprError> {
+        let mut parser = Parser { chars: text.chars().collect(), pos: 0 };
+
+        let expr = parser.sum()?;
+        parser.skip_space();
+
+        match parser.peek() {
+            None => Ok(expr),
+            Some(_) => Err(parser.unexpected()),
+        }
+    }
+
+    /// The names of the columns in the expression, in order of appearance,
+    /// with any repeats.
+    pub fn columns(&self) -> Vec<&str> {
+        match *self {
+            Expr::Number(_) => vec![],
+            Expr::Column(ref name) => vec![name.as_str()],
+            Expr::Neg(ref e) => e.columns(),
+            Expr::Binary(_, ref l, ref r) => {
+                let mut names = l.columns();
+                names.extend(r.columns());
+                names
+            },
+        }
+    }
+
+    /// Check that every column of the expression is among `columns`.
+    pub fn check_columns(&self, columns: &[&str]) -> Result<(), ExprError> {
+        match self.columns().into_iter().find(|c| !columns.contains(c)) {
+            Some(c) => Err(ExprError::UnknownColumn(c.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Evaluate the expression, with the value of each column given by
+    /// `value`. Division by zero is infinite or NaN, as for floats.
+    pub fn eval<F>(&self, value: &F) -> Result<f64, ExprError>
+        where F: Fn(&str) -> Option<f64> {
+        Ok(match *self {
+            Expr::Number(x) => x,
+            Expr::Column(ref name) => match value(name) {
+                Some(x) => x,
+                None => return Err(ExprError::UnknownColumn(name.clone())),
+            },
+            Expr::Neg(ref e) => -e.eval(value)?,
+            Expr::Binary(op, ref l, ref r) => {
+                let (x, y) = (l.eval(value)?, r.eval(value)?);
+
+                match op {
+                    Op::Add => x + y,
+                    Op::Sub => x - y,
+                    Op::Mul => x * y,
+                    Op::Div => x / y,
+                }
+            },
+        })
+    }
+}
+
+/// A recursive-descent parser of expressions, of the grammar:
+///
+/// ```text
+/// sum     = product (("+" | "-") product)*
+/// product = factor (("*" | "/") factor)*
+/// factor  = "-" factor | "(" sum ")" | number | name
+/// ```
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn skip_space(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn unexpected(&self) -> ExprError {
+        if self.pos < self.chars.len() {
+            ExprError::Unexpected { position: self.pos + 1 }
+        } else {
+            ExprError::UnexpectedEnd
+        }
+    }
+
+    /// The characters from the current position while `f` holds of them.
+    fn take_while<F>(&mut self, f: F) -> String
+        where F: Fn(char) -> bool {
+        let start = self.pos;
+
+        while self.peek().is_some_and(&f) {
+            self.pos += 1;
+        }
+
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn sum(&mut self) -> Result<Expr, ExprError> {
+        let mut expr = self.product()?;
+
+        loop {
+            self.skip_space();
+
+            let op = match self.peek() {
+                Some('+') => Op::Add,
+                Some('-') => Op::Sub,
+                _ => return Ok(expr),
+            };
+
+            self.pos += 1;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.product()?));
+        }
+    }
+
+    fn product(&mut self) -> Result<Expr, ExprError> {
+        let mut expr = self.factor()?;
+
+        loop {
+            self.skip_space();
+
+            let op = match self.peek() {
+                Some('*') => Op::Mul,
+                Some('/') => Op::Div,
+                _ => return Ok(expr),
+            };
+
+            self.pos += 1;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.factor()?));
+        }
+    }
+
+    fn factor(&mut self) -> Result<Expr, ExprError> {
+        self.skip_space();
+
+        match self.peek() {
+            Some('-') => {
+                self.pos += 1;
+                Ok(Expr::Neg(Box::new(self.factor()?)))
+            },
+            Some('(') => {
+                self.pos += 1;
+                let expr = self.sum()?;
+                self.skip_space();
+
+                if self.peek() != Some(')') {
+                    return Err(self.unexpected());
+                }
+
+                self.pos += 1;
+                Ok(expr)
+            },
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let start = self.pos;
+                let mut text = self.take_while(|c| c.is_ascii_digit() || c == '.');
+
+                // An exponent, such as `e-3`, if followed by digits.
+                if let Some('e') | Some('E') = self.peek() {
+                    let mark = self.pos;
+                    self.pos += 1;
+
+                    let sign = match self.peek() {
+                        Some(s) if s == '+' || s == '-' => {
+                            self.pos += 1;
+                            s.to_string()
+                        },
+                        _ => String::new(),
+                    };
+
+                    let digits = self.take_while(|c| c.is_ascii_digit());
+
+                    if digits.is_empty() {
+                        self.pos = mark;
+                    } else {
+                        text = format!("{}e{}{}", text, sign, digits);
+                    }
+                }
+
+                text.parse()
+                    .map(Expr::Number)
+                    .map_err(|_| ExprError::Unexpected { position: start + 1 })
+            },
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                Ok(Expr::Column(self.take_while(|c| c.is_alphanumeric() || c == '_')))
+            },
+            _ => Err(self.unexpected()),
+        }
+    }
+}
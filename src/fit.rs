@@ -0,0 +1,403 @@
+use distribution::{self, Distribution};
+use error::Error;
+use special::{digamma, inc_gamma, ln_gamma, normal_cdf, trigamma};
+
+
+/// Names accepted by `Family::from_name`.
+pub const FAMILY_NAMES: [&str; 4] = ["normal", "lognormal", "exponential", "gamma"];
+
+/// A family of continuous distributions which can be fit to sample data.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Family {
+    /// Parameterized by its mean and standard deviation.
+    Normal,
+
+    /// Parameterized by the mean `mu` and standard deviation `sigma` of the
+    /// logarithm of the data.
+    LogNormal,
+
+    /// Parameterized by its rate, the inverse of its mean.
+    Exponential,
+
+    /// Parameterized by its shape `k` and rate `β`, with mean `k / β`.
+    Gamma,
+}
+
+impl Family {
+    /// Look up a family by its name in `FAMILY_NAMES`.
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        match name {
+            "normal" => Ok(Family::Normal),
+            "lognormal" => Ok(Family::LogNormal),
+            "exponential" => Ok(Family::Exponential),
+            "gamma" => Ok(Family::Gamma),
+            _ => Err(Error::BadParameter),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Family::Normal => "normal",
+            Family::LogNormal => "lognormal",
+            Family::Exponential => "exponential",
+            Family::Gamma => "gamma",
+        }
+    }
+}
+
+/// A fitted parameter and its asymptotic standard error.
+#[derive(Clone, Copy, Debug)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub estimate: f64,
+    pub standard_error: f64,
+}
+
+/// Maximum-likelihood fit of a distribution family to sample data.
+#[derive(Clone, Debug)]
+pub struct DistributionFit {
+    pub family: Family,
+
+    /// Number of values in the sample.
+    pub size: usize,
+
+    /// The fitted parameters, in the order listed for each `Family`.
+    pub parameters: Vec<Parameter>,
+
+    /// Log-likelihood of the sample under the fitted distribution.
+    pub log_likelihood: f64,
+
+    /// Kolmogorov-Smirnov statistic: the greatest distance between the
+    /// empirical CDF of the sample and the fitted CDF.
+    ///
+    /// Since the parameters are estimated from the same sample, the usual
+    /// critical values of the test are too conservative. The statistic is
+    /// best used to compare families fit to one sample.
+    pub ks_statistic: f64,
+}
+
+impl DistributionFit {
+    /// Fit `family` to `data` by maximum likelihood. Standard errors are
+    /// from the inverse of the Fisher information.
+    ///
+    /// The log-normal and gamma families require every value to be positive,
+    /// and the exponential family every value to be non-negative. The data
+    /// must not all be equal.
+    pub fn new(data: &[f64], family: Family) -> Result<Self, Error> {
+        if data.len() < 2 {
+            return Err(Error::EmptySample);
+        }
+
+        if data.iter().any(|x| !x.is_finite()) {
+            return Err(Error::BadSample);
+        }
+
+        let n = data.len() as f64;
+
+        let parameters = match family {
+            Family::Normal => normal_parameters(["mean", "sd"], normal_mle(data)?, n),
+            Family::LogNormal => {
+                normal_parameters(["mu", "sigma"], normal_mle(&positive_logs(data)?)?, n)
+            },
+            Family::Exponential => {
+                if data.iter().any(|&x| x < 0.0) {
+                    return Err(Error::Undefined);
+                }
+
+                let mean = data.iter().sum::<f64>() / n;
+
+                if mean == 0.0 {
+                    return Err(Error::Undefined);
+                }
+
+                let rate = mean.recip();
+
+                vec![Parameter { name: "rate", estimate: rate, standard_error: rate / n.sqrt() }]
+            },
+            Family::Gamma => {
+                let (shape, rate) = gamma_mle(data)?;
+
+                // From the inverse of the Fisher information of `n` observations.
+                let trigamma_shape = trigamma(shape)?;
+                let det = n * (shape * trigamma_shape - 1.0);
+
+                vec![
+                    Parameter {
+                        name: "shape",
+                        estimate: shape,
+                        standard_error: (shape / det).sqrt(),
+                    },
+                    Parameter {
+                        name: "rate",
+                        estimate: rate,
+                        standard_error: rate * (trigamma_shape / det).sqrt(),
+                    },
+                ]
+            },
+        };
+
+        let mut fit = DistributionFit {
+            family,
+            size: data.len(),
+            parameters,
+            log_likelihood: 0.0,
+            ks_statistic: 0.0,
+        };
+
+        fit.log_likelihood = fit.log_likelihood_of(data)?;
+
+        let mut sorted = data.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        for (i, &x) in sorted.iter().enumerate() {
+            let f = fit.cdf(x)?;
+            let d = (f - i as f64 / n).max((i + 1) as f64 / n - f);
+
+            fit.ks_statistic = fit.ks_statistic.max(d);
+        }
+
+        Ok(fit)
+    }
+
+    /// Akaike information criterion, `2k - 2 ln L` for `k` parameters.
+    pub fn aic(&self) -> f64 {
+        2.0 * self.parameters.len() as f64 - 2.0 * self.log_likelihood
+    }
+
+    /// Bayesian information criterion, `k ln n - 2 ln L` for `k` parameters
+    /// and `n` values, which penalizes parameters more than the AIC.
+    pub fn bic(&self) -> f64 {
+        self.parameters.len() as f64 * (self.size as f64).ln() - 2.0 * self.log_likelihood
+    }
+
+    /// The fitted distribution, from which synthetic data can be sampled.
+    pub fn distribution(&self) -> Result<Box<dyn Distribution>, Error> {
+        let estimates: Vec<f64> = self.parameters.iter().map(|p| p.estimate).collect();
+
+        // Each family is named as in `distribution::NAMES`, with parameters
+        // in the same order.
+        distribution::from_name(self.family.name(), &estimates)
+    }
+
+    /// The fitted cumulative distribution function.
+    pub fn cdf(&self, x: f64) -> Result<f64, Error> {
+        let (p0, p1) = self.estimates();
+
+        let p = match self.family {
+            Family::Normal => normal_cdf((x - p0) / p1),
+            _ if x <= 0.0 => 0.0,
+            Family::LogNormal => normal_cdf((x.ln() - p0) / p1),
+            Family::Exponential => -(-p0 * x).exp_m1(),
+            Family::Gamma => inc_gamma(p1 * x, p0)?,
+        };
+
+        Ok(p)
+    }
+
+    /// The first two parameter estimates, with 0 for any which are absent.
+    fn estimates(&self) -> (f64, f64) {
+        let p = |i| self.parameters.get(i).map_or(0.0, |p: &Parameter| p.estimate);
+
+        (p(0), p(1))
+    }
+
+    fn log_likelihood_of(&self, data: &[f64]) -> Result<f64, Error> {
+        use std::f64::consts::PI;
+
+        let n = data.len() as f64;
+        let (p0, p1) = self.estimates();
+
+        let ll = match self.family {
+            Family::Normal => {
+                let ss: f64 = data.iter().map(|x| ((x - p0) / p1).powi(2)).sum();
+
+                -0.5 * n * (2.0 * PI).ln() - n * p1.ln() - 0.5 * ss
+            },
+            Family::LogNormal => {
+                let ss: f64 = data.iter().map(|x| ((x.ln() - p0) / p1).powi(2)).sum();
+                let sum_logs: f64 = data.iter().map(|x| x.ln()).sum();
+
+                -0.5 * n * (2.0 * PI).ln() - n * p1.ln() - sum_logs - 0.5 * ss
+            },
+            Family::Exponential => n * p0.ln() - p0 * data.iter().sum::<f64>(),
+            Family::Gamma => {
+                let sum: f64 = data.iter().sum();
+                let sum_logs: f64 = data.iter().map(|x| x.ln()).sum();
+
+                n * (p0 * p1.ln() - ln_gamma(p0)?) + (p0 - 1.0) * sum_logs - p1 * sum
+            },
+        };
+
+        Ok(ll)
+    }
+}
+
+/// Names accepted by `Criterion::from_name`.
+pub const CRITERION_NAMES: [&str; 2] = ["aic", "bic"];
+
+/// An information criterion by which to compare fits. Lower is better.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Criterion {
+    Aic,
+    Bic,
+}
+
+impl Criterion {
+    /// Look up a criterion by its name in `CRITERION_NAMES`.
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        match name {
+            "aic" => Ok(Criterion::Aic),
+            "bic" => Ok(Criterion::Bic),
+            _ => Err(Error::BadParameter),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match *self {
+            Criterion::Aic => "AIC",
+            Criterion::Bic => "BIC",
+        }
+    }
+
+    pub fn score(&self, fit: &DistributionFit) -> f64 {
+        match *self {
+            Criterion::Aic => fit.aic(),
+            Criterion::Bic => fit.bic(),
+        }
+    }
+}
+
+/// A fit, scored relative to the best of those it was ranked with.
+#[derive(Clone, Debug)]
+pub struct RankedFit {
+    pub fit: DistributionFit,
+
+    /// Value of the criterion for this fit.
+    pub score: f64,
+
+    /// Difference in the criterion from that of the best fit.
+    pub delta: f64,
+
+    /// Relative likelihood of this fit, normalized over all fits ranked,
+    /// which is its Akaike weight [1] when ranking by AIC.
+    ///
+    /// [1]: K. Burnham and D. Anderson, "Model Selection and Multimodel
+    ///      Inference", 2nd Ed., §2.9.
+    pub weight: f64,
+}
+
+/// Fit every family in `FAMILY_NAMES` to `data`, and rank them from best to
+/// worst by `criterion`. Families which cannot describe the data, such as
+/// the log-normal for data with non-positive values, are left out.
+pub fn rank(data: &[f64], criterion: Criterion) -> Result<Vec<RankedFit>, Error> {
+    let mut fits = vec![];
+
+    for name in &FAMILY_NAMES {
+        match DistributionFit::new(data, Family::from_name(name)?) {
+            Ok(fit) => fits.push(fit),
+            Err(Error::Undefined) => {},
+            Err(e) => return Err(e),
+        }
+    }
+
+    let best = fits
+        .iter()
+        .map(|f| criterion.score(f))
+        .fold(None, |best: Option<f64>, s| Some(best.map_or(s, |b| b.min(s))))
+        .ok_or(Error::Undefined)?;
+
+    let mut ranked: Vec<RankedFit> = fits
+        .into_iter()
+        .map(|fit| {
+            let score = criterion.score(&fit);
+            let delta = score - best;
+
+            RankedFit { fit, score, delta, weight: (-0.5 * delta).exp() }
+        })
+        .collect();
+
+    let total: f64 = ranked.iter().map(|r| r.weight).sum();
+
+    for r in &mut ranked {
+        r.weight /= total;
+    }
+
+    ranked.sort_by(|a, b| a.score.total_cmp(&b.score));
+
+    Ok(ranked)
+}
+
+/// Maximum-likelihood mean and standard deviation, which is the square root
+/// of the biased sample variance.
+fn normal_mle(data: &[f64]) -> Result<(f64, f64), Error> {
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let sd = (data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n).sqrt();
+
+    if sd == 0.0 {
+        return Err(Error::Undefined);
+    }
+
+    Ok((mean, sd))
+}
+
+/// Parameters of a normal fit, named by `names`, with their standard errors.
+fn normal_parameters(names: [&'static str; 2], (mean, sd): (f64, f64), n: f64) -> Vec<Parameter> {
+    vec![
+        Parameter { name: names[0], estimate: mean, standard_error: sd / n.sqrt() },
+        Parameter { name: names[1], estimate: sd, standard_error: sd / (2.0 * n).sqrt() },
+    ]
+}
+
+fn positive_logs(data: &[f64]) -> Result<Vec<f64>, Error> {
+    if data.iter().any(|&x| x <= 0.0) {
+        return Err(Error::Undefined);
+    }
+
+    Ok(data.iter().map(|x| x.ln()).collect())
+}
+
+const GAMMA_CONVERGENCE_LIMIT: f64 = 1e-12;
+const GAMMA_MAX_ITER: usize = 100;
+
+/// Maximum-likelihood shape and rate of a gamma distribution.
+///
+/// The shape `k` solves `ln k - ψ(k) = ln(mean) - mean(ln x)`, which we find
+/// by Newton's method from the approximation of Minka [1]. The rate is then
+/// `k / mean`.
+///
+/// [1]: T. Minka, "Estimating a Gamma distribution", 2002.
+fn gamma_mle(data: &[f64]) -> Result<(f64, f64), Error> {
+    let logs = positive_logs(data)?;
+
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let s = mean.ln() - logs.iter().sum::<f64>() / n;
+
+    // By Jensen's inequality, `s` is zero only when the data are all equal.
+    if s <= 0.0 {
+        return Err(Error::Undefined);
+    }
+
+    let mut shape = (3.0 - s + ((s - 3.0).powi(2) + 24.0 * s).sqrt()) / (12.0 * s);
+
+    for _ in 0..GAMMA_MAX_ITER {
+        let f = shape.ln() - digamma(shape)? - s;
+        let df = shape.recip() - trigamma(shape)?;
+
+        // Keep a step which overshoots zero within the domain.
+        let mut next = shape - f / df;
+        if next <= 0.0 {
+            next = 0.5 * shape;
+        }
+
+        let converged = ((next - shape) / shape).abs() < GAMMA_CONVERGENCE_LIMIT;
+        shape = next;
+
+        if converged {
+            return Ok((shape, shape / mean));
+        }
+    }
+
+    Err(Error::Diverged)
+}
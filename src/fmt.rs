@@ -62,3 +62,26 @@ pub fn f(x: f64, max_len: usize) -> String {
 
     format!("{:.0e}", x)
 }
+
+/// Format a float `x` rounded to `digits` significant digits, or exactly if
+/// `digits` is `None`. As with `%g`, scientific notation is used when the
+/// exponent is less than -4, or at least `digits`.
+pub fn value(x: f64, digits: Option<usize>) -> String {
+    let digits = match digits {
+        Some(d) if x != 0.0 && x.is_finite() => d.max(1),
+        _ => return format!("{}", x),
+    };
+
+    // Round first, since rounding may carry into the next power of ten.
+    let rounded: f64 = match format!("{:.*e}", digits - 1, x).parse() {
+        Ok(r) => r,
+        Err(_) => return format!("{}", x),
+    };
+    let exp = rounded.abs().log10().floor() as i32;
+
+    if exp < -4 || exp >= digits as i32 {
+        format!("{:.*e}", digits - 1, rounded)
+    } else {
+        format!("{:.*}", (digits as i32 - 1 - exp) as usize, rounded)
+    }
+}
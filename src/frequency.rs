@@ -0,0 +1,217 @@
+use error::Error;
+
+
+/// Wraps sample data given as `(value, count)` pairs, and provides methods for
+/// computing summary statistics without materializing repeated values.
+///
+/// Every statistic agrees with that of a `Summarizer` constructed from the
+/// expanded sample, in which each value appears `count` times.
+#[derive(Debug)]
+pub struct FrequencySummarizer {
+    // Distinct values, sorted.
+    values: Vec<f64>,
+
+    // Count of each distinct value.
+    counts: Vec<u64>,
+
+    // Number of sample points up to and including each distinct value.
+    cumulative: Vec<u64>,
+}
+
+impl FrequencySummarizer {
+    /// Construct a `FrequencySummarizer` from `(value, count)` pairs.
+    ///
+    /// This constructor is partial, and we obtain the following guarantees
+    /// about the resulting sample data:
+    ///
+    ///   - The sample size is positive
+    ///   - All values are finite
+    ///   - The distinct values are sorted, and each has a positive count
+    ///
+    /// Values may repeat, in which case their counts are added.
+    pub fn new(data: &[(f64, u64)]) -> Result<Self, Error> {
+        if data.iter().any(|&(x, _)| !x.is_finite()) {
+            return Err(Error::BadSample);
+        }
+
+        let mut data: Vec<(f64, u64)> = data
+            .iter()
+            .cloned()
+            .filter(|&(_, c)| c > 0)
+            .collect();
+
+        if data.is_empty() {
+            return Err(Error::EmptySample);
+        }
+
+        // Won't panic: we have checked that each float is finite.
+        data.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or_else(|| unreachable!()));
+
+        let mut values: Vec<f64> = vec![];
+        let mut counts: Vec<u64> = vec![];
+
+        for (x, c) in data {
+            if values.last() == Some(&x) {
+                let last = counts.len() - 1;
+                counts[last] += c;
+            } else {
+                values.push(x);
+                counts.push(c);
+            }
+        }
+
+        let mut total = 0;
+        let cumulative = counts
+            .iter()
+            .map(|c| {
+                total += c;
+                total
+            })
+            .collect();
+
+        Ok(FrequencySummarizer { values, counts, cumulative })
+    }
+
+    /// Number of distinct values in the sample.
+    pub fn distinct(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Size of the expanded sample data as a floating point value.
+    pub fn size(&self) -> f64 {
+        self.len() as f64
+    }
+
+    fn len(&self) -> u64 {
+        self.cumulative[self.cumulative.len() - 1]
+    }
+
+    /// The value at index `i` of the expanded, sorted sample.
+    fn nth(&self, i: u64) -> f64 {
+        let k = match self.cumulative.binary_search(&i) {
+            // Index `i` is the first point past the value ending at `k`.
+            Ok(k) => k + 1,
+            Err(k) => k,
+        };
+
+        self.values[k]
+    }
+
+    /// The minimum value in the data set.
+    pub fn min(&self) -> f64 {
+        self.values[0]
+    }
+
+    /// The maximum value in the data set.
+    pub fn max(&self) -> f64 {
+        self.values[self.values.len() - 1]
+    }
+
+    /// The arithmetic sample mean.
+    pub fn mean(&self) -> f64 {
+        let t: f64 = self.values
+            .iter()
+            .zip(&self.counts)
+            .map(|(x, &c)| x * c as f64)
+            .sum();
+
+        t / self.size()
+    }
+
+    /// Closest-ranks percentile computed via linear interpolation, as in
+    /// `Summarizer::percentile`.
+    pub fn percentile(&self, p: f64) -> Result<f64, Error> {
+        if !p.is_finite() { return Err(Error::Undefined); }
+        if !(0.0..=1.0).contains(&p) {
+            return Err(Error::Undefined);
+        }
+
+        let rank = (self.size() - 1.0) * p;
+        let frac = rank.fract();
+
+        let i = rank.floor() as u64;
+        let j = i + 1;
+
+        if j == self.len() {
+            return Ok(self.nth(i));
+        }
+
+        let xi = self.nth(i);
+        let xj = self.nth(j);
+
+        Ok(xi + frac * (xj - xi))
+    }
+
+    /// The 25th percentile.
+    pub fn lower_quartile(&self) -> f64 {
+        // Statically known to be defined.
+        self.percentile(0.25).unwrap_or_else(|_| unreachable!())
+    }
+
+    /// The 50th percentile.
+    pub fn median(&self) -> f64 {
+        // Statically known to be defined.
+        self.percentile(0.5).unwrap_or_else(|_| unreachable!())
+    }
+
+    /// The 75th percentile.
+    pub fn upper_quartile(&self) -> f64 {
+        // Statically known to be defined.
+        self.percentile(0.75).unwrap_or_else(|_| unreachable!())
+    }
+
+    /// Difference between the upper and lower quartiles.
+    pub fn iqr(&self) -> f64 {
+        self.upper_quartile() - self.lower_quartile()
+    }
+
+    /// The difference between the minimum and maximum value.
+    pub fn range(&self) -> f64 {
+        self.max() - self.min()
+    }
+
+    /// The minimum non-outlier value in the data set.
+    pub fn min_adjacent(&self) -> f64 {
+        let lower_outlier_bound = self.lower_quartile() - 1.5 * self.iqr();
+
+        self.values
+            .iter()
+            .cloned()
+            .find(|&x| lower_outlier_bound <= x)
+            .unwrap_or_else(|| unreachable!())  // By definition of quartile.
+    }
+
+    /// The maximum non-outlier value in the data set.
+    pub fn max_adjacent(&self) -> f64 {
+        let upper_outlier_bound = self.upper_quartile() + 1.5 * self.iqr();
+
+        self.values
+            .iter()
+            .cloned()
+            .rev()
+            .find(|&x| x <= upper_outlier_bound)
+            .unwrap_or_else(|| unreachable!())  // By definition of quartile.
+    }
+
+    /// Sample variance, with Bessel's correction.
+    pub fn unbiased_variance(&self) -> f64 {
+        let m = self.mean();
+        let sum_sq_diff: f64 = self.values
+            .iter()
+            .zip(&self.counts)
+            .map(|(x, &c)| c as f64 * (x - m).powi(2))
+            .sum();
+
+        (1.0 / (self.size() - 1.0)) * sum_sq_diff
+    }
+
+    /// Standard deviation of the sample.
+    pub fn standard_deviation(&self) -> f64 {
+        self.unbiased_variance().sqrt()
+    }
+
+    /// Standard error, the standard deviation of the sample mean.
+    pub fn standard_error(&self) -> f64 {
+        self.standard_deviation() / self.size().sqrt()
+    }
+}
@@ -0,0 +1,254 @@
+use error::Error;
+use summary::{self, Summarizer, METRIC_PREFIX};
+
+
+/// Default number of bins.
+pub const DEFAULT_BINS: usize = 10;
+
+/// Columns of the frequency table written by `to_tsv`.
+pub const TSV_COLUMNS: [&str; 6] = [
+    "Source",
+    "Lower",
+    "Upper",
+    "Count",
+    "Proportion",
+    "CumulativePercent",
+];
+
+/// How the range of a sample is cut into the bins of a histogram.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Binning {
+    /// Bins of equal width, from the least value to the greatest.
+    Width(usize),
+
+    /// Bins of equal width in the logarithm of the values, which must all be
+    /// positive. Suits data spanning orders of magnitude, such as latencies.
+    Log(usize),
+
+    /// Bins holding equal counts of values, with edges at evenly spaced
+    /// quantiles. Repeated edges, as from ties, are merged, so there may be
+    /// fewer bins.
+    Quantile(usize),
+}
+
+impl Binning {
+    /// Parse a binning such as `20`, `width:20`, `log:20`, or `quantile:10`.
+    /// A bare count is of equal-width bins.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let (kind, count) = match text.split_once(':') {
+            Some((kind, count)) => (kind, count),
+            None => ("width", text),
+        };
+
+        let count: usize = count.trim().parse().map_err(|_| Error::BadParameter)?;
+
+        if count == 0 {
+            return Err(Error::BadParameter);
+        }
+
+        match kind.trim() {
+            "width" => Ok(Binning::Width(count)),
+            "log" => Ok(Binning::Log(count)),
+            "quantile" => Ok(Binning::Quantile(count)),
+            _ => Err(Error::BadParameter),
+        }
+    }
+
+    /// The edges of the bins of `data`, ascending, one more than there are
+    /// bins. The first edge is the least value and the last the greatest.
+    pub fn edges(&self, data: &[f64]) -> Result<Vec<f64>, Error> {
+        let summarizer = Summarizer::new(data)?;
+        let (min, max) = (summarizer.min(), summarizer.max());
+
+        if min == max {
+            return Ok(vec![min, max]);
+        }
+
+        let edges = match *self {
+            Binning::Width(n) => {
+                let step = (max - min) / n as f64;
+                (0..=n).map(|i| if i == n { max } else { min + step * i as f64 }).collect()
+            },
+            Binning::Log(n) => {
+                if min <= 0.0 {
+                    return Err(Error::Undefined);
+                }
+
+                let (lo, hi) = (min.ln(), max.ln());
+                let step = (hi - lo) / n as f64;
+
+                (0..=n)
+                    .map(|i| match i {
+                        0 => min,
+                        _ if i == n => max,
+                        _ => (lo + step * i as f64).exp(),
+                    })
+                    .collect()
+            },
+            Binning::Quantile(n) => {
+                let mut edges: Vec<f64> = vec![];
+
+                for i in 0..=n {
+                    let edge = summarizer.percentile(i as f64 / n as f64)?;
+
+                    if edges.last() != Some(&edge) {
+                        edges.push(edge);
+                    }
+                }
+
+                edges
+            },
+        };
+
+        Ok(edges)
+    }
+}
+
+/// The counts of a sample in bins, each of which holds values at or above
+/// its lower edge and below its upper, except the last, which holds its
+/// upper edge too.
+#[derive(Clone, Debug)]
+pub struct Histogram {
+    pub edges: Vec<f64>,
+    pub counts: Vec<u64>,
+}
+
+impl Histogram {
+    pub fn new(data: &[f64], binning: Binning) -> Result<Self, Error> {
+        Histogram::with_edges(data, binning.edges(data)?)
+    }
+
+    /// Histograms of several samples over one set of bins, with edges from
+    /// all the samples pooled, so that they can be compared bin by bin.
+    pub fn shared(samples: &[&[f64]], binning: Binning) -> Result<Vec<Self>, Error> {
+        let pooled: Vec<f64> = samples.iter().flat_map(|s| s.iter().cloned()).collect();
+        let edges = binning.edges(&pooled)?;
+
+        samples.iter().map(|s| Histogram::with_edges(s, edges.clone())).collect()
+    }
+
+    /// Count `data` in bins with the given ascending `edges`, of which there
+    /// must be at least two. Values outside the edges are not counted.
+    pub fn with_edges(data: &[f64], edges: Vec<f64>) -> Result<Self, Error> {
+        let ascending = edges.windows(2).all(|w| w[0] <= w[1]);
+
+        if edges.len() < 2 || !ascending || edges.iter().any(|e| !e.is_finite()) {
+            return Err(Error::BadParameter);
+        }
+
+        let bins = edges.len() - 1;
+        let range = edges[0]..=edges[bins];
+        let mut counts = vec![0; bins];
+
+        for &x in data {
+            if !range.contains(&x) {
+                continue;
+            }
+
+            let i = edges.partition_point(|&e| e <= x).saturating_sub(1);
+            counts[i.min(bins - 1)] += 1;
+        }
+
+        Ok(Histogram { edges, counts })
+    }
+
+    /// The lower and upper edges of each bin, in order.
+    pub fn bins(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.edges.windows(2).map(|w| (w[0], w[1]))
+    }
+
+    /// The number of values counted, in any bin.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// The frequency table of the histogram, with one row per bin.
+    pub fn frequencies(&self) -> Vec<Frequency> {
+        let total = self.total() as f64;
+        let mut cumulative = 0;
+
+        self.bins()
+            .zip(&self.counts)
+            .map(|((lower, upper), &count)| {
+                cumulative += count;
+
+                Frequency {
+                    lower,
+                    upper,
+                    count,
+                    proportion: count as f64 / total,
+                    cumulative_percent: 100.0 * cumulative as f64 / total,
+                }
+            })
+            .collect()
+    }
+}
+
+/// One row of a frequency table: the count of a bin, its proportion of all
+/// values counted, and the percentage counted in it and all bins below it.
+#[derive(Clone, Copy, Debug)]
+pub struct Frequency {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: u64,
+    pub proportion: f64,
+    pub cumulative_percent: f64,
+}
+
+/// Write the frequency tables of histograms as TSV, with a header row of
+/// `TSV_COLUMNS`, and one record per bin of each, labeled with its source.
+pub fn to_tsv(histograms: &[Histogram], sources: &[&str]) -> String {
+    let mut out = TSV_COLUMNS.join("\t") + "\n";
+
+    for (h, src) in histograms.iter().zip(sources) {
+        for f in h.frequencies() {
+            out += &format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\n",
+                src,
+                f.lower,
+                f.upper,
+                f.count,
+                f.proportion,
+                f.cumulative_percent,
+            );
+        }
+    }
+
+    out
+}
+
+/// Write histograms in the Prometheus text exposition format, as one
+/// histogram metric with a series per source, of cumulative counts in
+/// buckets labeled by the upper edge of each bin.
+///
+/// Each bin holds values below its upper edge, so a value which falls on an
+/// edge is counted in the bucket above it, rather than at or below it as
+/// Prometheus would. The sum of the values is not kept, so it is not written.
+pub fn to_prometheus(histograms: &[Histogram], sources: &[&str]) -> String {
+    let name = format!("{}histogram", METRIC_PREFIX);
+
+    let mut out = format!("# HELP {} Counts of the sample in bins.\n", name);
+    out += &format!("# TYPE {} histogram\n", name);
+
+    for (h, src) in histograms.iter().zip(sources) {
+        let source = summary::label_value(src);
+        let mut cumulative = 0;
+
+        for (upper, &count) in h.edges[1..].iter().zip(&h.counts) {
+            cumulative += count;
+
+            out += &format!(
+                "{}_bucket{{source=\"{}\",le=\"{}\"}} {}\n",
+                name,
+                source,
+                summary::metric_value(*upper),
+                cumulative,
+            );
+        }
+
+        out += &format!("{}_bucket{{source=\"{}\",le=\"+Inf\"}} {}\n", name, source, cumulative);
+        out += &format!("{}_count{{source=\"{}\"}} {}\n", name, source, cumulative);
+    }
+
+    out
+}
@@ -0,0 +1,716 @@
+use std::error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::str::FromStr;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use dent::decode::{Csv, InputDecoder, PlainLines};
+use dent::expr::Expr;
+use dent::rng::Rng;
+use dent::series::{self, TimeFormat};
+
+use log;
+#[cfg(feature = "net")] use ureq;
+
+
+/// Pseudo-path which stands for standard input wherever files are accepted.
+pub const STDIN_PATH: &str = "-";
+
+/// Prefixes of paths which are URLs, whose inputs are fetched.
+const URL_SCHEMES: [&str; 2] = ["http://", "https://"];
+
+/// How long to wait on inputs which are read concurrently before warning of
+/// those which have not yet been read.
+const WAIT_WARNING_DELAY: Duration = Duration::from_secs(2);
+
+/// Maximum number of rejected lines to retain as examples, per input.
+const MAX_SKIPPED_EXAMPLES: usize = 3;
+
+/// Record of the non-numeric lines dropped while parsing in lax mode.
+#[derive(Debug, Default)]
+pub struct Skipped {
+    /// Total number of lines dropped.
+    pub count: usize,
+
+    /// The first few dropped lines, with their 1-based line numbers.
+    pub examples: Vec<(usize, String)>,
+}
+
+impl Skipped {
+    fn record(&mut self, line_number: usize, line: &str) {
+        self.count += 1;
+
+        if self.examples.len() < MAX_SKIPPED_EXAMPLES {
+            self.examples.push((line_number, line.to_string()));
+        }
+    }
+}
+
+/// The layout of each line of an input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    /// A single value per line.
+    Values,
+
+    /// A value and its weight per line, separated by whitespace.
+    Weighted,
+
+    /// A value and its integer frequency per line, separated by whitespace.
+    Frequency,
+
+    /// A predictor and a response value per line, separated by whitespace.
+    Pairs,
+
+    /// A timestamp and a value per line, separated by whitespace.
+    Series,
+
+    /// Whitespace-separated fields per line, of which the one at the given
+    /// 0-based index is a stratum label, and the first other is the value.
+    Stratified(usize),
+}
+
+/// A value, with the 1-based number of its line.
+pub type Observation = (f64, usize);
+
+/// Sample data parsed from a single input.
+#[derive(Debug)]
+pub struct Parsed {
+    pub data: Vec<f64>,
+
+    /// The 1-based number of the line of each value in `data`.
+    pub lines: Vec<usize>,
+
+    /// Weight of each value in `data`, for `Format::Weighted` inputs.
+    pub weights: Option<Vec<f64>>,
+
+    /// Count of each value in `data`, for `Format::Frequency` inputs.
+    pub counts: Option<Vec<u64>>,
+
+    /// Response paired with each value in `data`, for `Format::Pairs` inputs.
+    pub responses: Option<Vec<f64>>,
+
+    /// Time of each value in `data`, in seconds since the Unix epoch, and the
+    /// format of the first timestamp, for `Format::Series` inputs.
+    pub times: Option<Vec<f64>>,
+    pub time_format: Option<TimeFormat>,
+
+    /// Stratum of each value in `data`, for `Format::Stratified` inputs.
+    pub strata: Option<Vec<String>>,
+    pub skipped: Skipped,
+}
+
+impl Parsed {
+    /// Pairs of values and their weights, if the input was weighted.
+    pub fn weighted(&self) -> Option<Vec<(f64, f64)>> {
+        self.weights
+            .as_ref()
+            .map(|ws| self.data.iter().cloned().zip(ws.iter().cloned()).collect())
+    }
+
+    /// Pairs of values and their counts, if the input was frequencies.
+    pub fn frequencies(&self) -> Option<Vec<(f64, u64)>> {
+        self.counts
+            .as_ref()
+            .map(|cs| self.data.iter().cloned().zip(cs.iter().cloned()).collect())
+    }
+
+    /// Pairs of times and values, if the input was a series.
+    pub fn timed(&self) -> Option<Vec<(f64, f64)>> {
+        self.times
+            .as_ref()
+            .map(|ts| ts.iter().cloned().zip(self.data.iter().cloned()).collect())
+    }
+
+    /// Pairs of predictor and response values, if the input was pairs.
+    pub fn pairs(&self) -> Option<Vec<(f64, f64)>> {
+        self.responses
+            .as_ref()
+            .map(|ys| self.data.iter().cloned().zip(ys.iter().cloned()).collect())
+    }
+
+    /// Pairs of strata and values, if the input was stratified.
+    pub fn stratified(&self) -> Option<Vec<(&str, f64)>> {
+        self.strata
+            .as_ref()
+            .map(|ss| ss.iter().map(|s| s.as_str()).zip(self.data.iter().cloned()).collect())
+    }
+
+    /// The `k` smallest and the `k` largest values, each with its line, from
+    /// the most extreme inward. NaN values are ignored.
+    pub fn extremes(&self, k: usize) -> (Vec<Observation>, Vec<Observation>) {
+        let mut values: Vec<Observation> = self.data
+            .iter()
+            .cloned()
+            .zip(self.lines.iter().cloned())
+            .filter(|&(x, _)| !x.is_nan())
+            .collect();
+        values.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let smallest = values.iter().take(k).cloned().collect();
+        let largest = values.iter().rev().take(k).cloned().collect();
+
+        (smallest, largest)
+    }
+
+    /// Keep a random subsample of at most `n` lines, in their original order.
+    /// Returns the number of values before subsampling, if any were dropped.
+    pub fn subsample(&mut self, n: usize, rng: &mut Rng) -> Option<usize> {
+        let len = self.data.len();
+
+        if len <= n {
+            return None;
+        }
+
+        self.keep(&rng.subsample_indices(len, n));
+
+        Some(len)
+    }
+
+    /// Keep only the lines at the given indices, in the order given.
+    pub fn keep(&mut self, indices: &[usize]) {
+        fn pick<T: Copy>(xs: &[T], indices: &[usize]) -> Vec<T> {
+            indices.iter().map(|&i| xs[i]).collect()
+        }
+
+        self.data = pick(&self.data, indices);
+        self.lines = pick(&self.lines, indices);
+        self.weights = self.weights.as_ref().map(|ws| pick(ws, indices));
+        self.counts = self.counts.as_ref().map(|cs| pick(cs, indices));
+        self.responses = self.responses.as_ref().map(|ys| pick(ys, indices));
+        self.times = self.times.as_ref().map(|ts| pick(ts, indices));
+        self.strata = self.strata
+            .as_ref()
+            .map(|ss| indices.iter().map(|&i| ss[i].clone()).collect());
+    }
+}
+
+/// A line which could not be parsed in the expected `Format`.
+#[derive(Debug)]
+struct LineError {
+    line_number: usize,
+    expected: &'static str,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "Line {}: expected {}", self.line_number, self.expected)
+    }
+}
+
+impl error::Error for LineError {}
+
+/// An error reading an input, with the name of the input and the line at
+/// fault, if any.
+#[derive(Debug)]
+pub struct InputError {
+    /// The input, as named in messages: its path, or `stdin`.
+    pub input: String,
+
+    /// The 1-based number of the line at fault.
+    pub line: Option<usize>,
+
+    /// A stable identifier of the kind of error, for machine-readable output.
+    pub code: &'static str,
+
+    error: Box<dyn error::Error + Send + Sync>,
+}
+
+impl InputError {
+    fn new<E>(input: &str, line: Option<usize>, code: &'static str, error: E) -> Self
+        where E: Into<Box<dyn error::Error + Send + Sync>> {
+        InputError { input: input.to_string(), line, code, error: error.into() }
+    }
+
+    /// An error opening or reading `input`.
+    fn unreadable<E>(input: &str, error: E) -> Self
+        where E: Into<Box<dyn error::Error + Send + Sync>> {
+        InputError::new(input, None, "unreadable_input", error)
+    }
+
+    /// An error parsing line `line` of `input`.
+    fn malformed<E>(input: &str, line: usize, error: E) -> Self
+        where E: Into<Box<dyn error::Error + Send + Sync>> {
+        InputError::new(input, Some(line), "malformed_line", error)
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl error::Error for InputError {}
+
+/// The name of the input at `path` in messages.
+pub fn source_name(path: &str) -> &str {
+    if path == STDIN_PATH { "stdin" } else { path }
+}
+
+/// Read sample data from the file at `path`, from stdin if `path` is
+/// `STDIN_PATH`, or from the body of the response if it is an HTTP(S) URL.
+pub fn read_input(path: &str, format: Format, lax_parsing: bool)
+                  -> Result<Parsed, InputError> {
+    read_data(open(path)?, source_name(path), format, lax_parsing)
+}
+
+/// Read values, one per line, from `path` as by `read_input`, passing each to
+/// `push` as it is decoded, rather than collecting them. Returns the lines
+/// skipped in lax mode.
+pub fn stream_input<F>(path: &str, lax_parsing: bool, mut push: F) -> Result<Skipped, InputError>
+    where F: FnMut(f64) {
+    let mut skipped = Skipped::default();
+
+    decode_lines(open(path)?, source_name(path), &PlainLines, lax_parsing, &mut skipped, |x, _| {
+        push(x)
+    })?;
+
+    Ok(skipped)
+}
+
+/// Read the whole of the file at `path`, of stdin if `path` is `STDIN_PATH`,
+/// or of the body of the response if it is an HTTP(S) URL.
+pub fn read_text(path: &str) -> Result<String, InputError> {
+    let mut text = String::new();
+
+    open(path)?
+        .read_to_string(&mut text)
+        .map_err(|e| InputError::unreadable(source_name(path), e))?;
+
+    Ok(text)
+}
+
+/// Read sample data from each of `paths`, as by `read_input`.
+pub fn read_inputs(paths: &[&str], format: Format, lax_parsing: bool)
+                   -> Result<Vec<Parsed>, InputError> {
+    read_all(paths, move |p| read_input(p, format, lax_parsing))
+}
+
+/// Read the whole of each of `paths`, as by `read_text`.
+pub fn read_texts(paths: &[&str]) -> Result<Vec<String>, InputError> {
+    read_all(paths, read_text)
+}
+
+/// Read sample data from each of `paths`, of a table with a header line
+/// naming its columns, whose value on each line is `expr` of its named
+/// columns. Fields are separated by commas if the header has any, and
+/// otherwise by whitespace.
+pub fn read_derived(paths: &[&str], expr: &Expr, lax_parsing: bool)
+                    -> Result<Vec<Parsed>, InputError> {
+    let texts = read_texts(paths)?;
+
+    paths
+        .iter()
+        .zip(&texts)
+        .map(|(p, t)| derive_data(t.as_bytes(), source_name(p), expr, lax_parsing))
+        .collect()
+}
+
+/// Read each of `paths` with `read`, in order if they are all regular files.
+///
+/// Otherwise, some may be pipes, as from process substitution, whose writers
+/// block until they are read. They are then read concurrently, so that no
+/// writer waits on the reading of another input, and the first error is
+/// returned as soon as it occurs, without waiting on the others. If some are
+/// still unread after `WAIT_WARNING_DELAY`, a warning names them, so that a
+/// blocked input is not mistaken for a hang.
+fn read_all<T, F>(paths: &[&str], read: F) -> Result<Vec<T>, InputError>
+    where T: Send + 'static, F: Fn(&str) -> Result<T, InputError> + Copy + Send + 'static {
+    if paths.len() < 2 || paths.iter().all(|p| is_regular_file(p)) {
+        return paths.iter().map(|p| read(p)).collect();
+    }
+
+    let (tx, rx) = mpsc::channel();
+
+    for (i, path) in paths.iter().enumerate() {
+        let tx = tx.clone();
+        let path = path.to_string();
+
+        thread::spawn(move || tx.send((i, read(&path))));
+    }
+
+    drop(tx);
+
+    let mut results: Vec<Option<T>> = paths.iter().map(|_| None).collect();
+    let mut warned = false;
+
+    while results.iter().any(|r| r.is_none()) {
+        match rx.recv_timeout(WAIT_WARNING_DELAY) {
+            Ok((i, r)) => results[i] = Some(r?),
+            Err(RecvTimeoutError::Timeout) if !warned => {
+                let waiting: Vec<&str> = paths
+                    .iter()
+                    .zip(&results)
+                    .filter(|&(_, r)| r.is_none())
+                    .map(|(&p, _)| source_name(p))
+                    .collect();
+
+                log::warning(&format!("Waiting for input from {}", waiting.join(", ")));
+                warned = true;
+            },
+            Err(RecvTimeoutError::Timeout) => {},
+            Err(RecvTimeoutError::Disconnected) => {
+                // Every reader has exited, so those without a result failed
+                // without sending one, as by panicking.
+                let i = results.iter().position(|r| r.is_none()).unwrap();
+                let path = source_name(paths[i]);
+
+                return Err(InputError::unreadable(path, "Input reader exited without a result"));
+            },
+        }
+    }
+
+    Ok(results.into_iter().map(|r| r.unwrap()).collect())
+}
+
+/// Whether `path` names a regular file, which may be read without blocking on
+/// a writer.
+fn is_regular_file(path: &str) -> bool {
+    path != STDIN_PATH && !is_url(path) && fs::metadata(path).is_ok_and(|m| m.is_file())
+}
+
+/// Whether `path` is an HTTP(S) URL, to be fetched rather than opened.
+pub fn is_url(path: &str) -> bool {
+    URL_SCHEMES.iter().any(|s| path.starts_with(s))
+}
+
+fn open(path: &str) -> Result<Box<dyn BufRead>, InputError> {
+    if path == STDIN_PATH {
+        Ok(Box::new(io::stdin().lock()))
+    } else if is_url(path) {
+        open_url(path)
+    } else {
+        let f = File::open(path).map_err(|e| InputError::unreadable(path, open_error(path, e)))?;
+
+        Ok(Box::new(BufReader::new(f)))
+    }
+}
+
+/// Stream the body of the response to a GET request for `url`.
+#[cfg(feature = "net")]
+fn open_url(url: &str) -> Result<Box<dyn BufRead>, InputError> {
+    let response = ureq::get(url).call().map_err(|e| {
+        InputError::unreadable(url, format!("Could not fetch URL: {:?}: {}", url, e))
+    })?;
+
+    Ok(Box::new(BufReader::new(response.into_reader())))
+}
+
+#[cfg(not(feature = "net"))]
+fn open_url(url: &str) -> Result<Box<dyn BufRead>, InputError> {
+    let msg = format!("Could not fetch URL: {:?}: dent was built without the `net` feature", url);
+
+    Err(InputError::unreadable(url, msg))
+}
+
+fn open_error(path: &str, e: io::Error) -> String {
+    format!("Could not open file: {:?}: {}", path, e)
+}
+
+/// Decode the lines read from `reader` with `decoder`, passing each value to
+/// `push` with the 1-based number of its line. In lax mode, lines which cannot
+/// be decoded are recorded in `skipped`, and otherwise are an error.
+fn decode_lines<R, F>(
+    reader: R,
+    source: &str,
+    decoder: &dyn InputDecoder,
+    lax_parsing: bool,
+    skipped: &mut Skipped,
+    mut push: F,
+) -> Result<(), InputError>
+    where R: BufRead, F: FnMut(f64, usize) {
+    for (i, l) in reader.lines().enumerate() {
+        let l = l.map_err(|e| InputError::unreadable(source, e))?;
+
+        if i < decoder.header_lines() {
+            continue;
+        }
+
+        match decoder.decode_line(&l) {
+            Ok(Some(x)) => push(x, i + 1),
+            Ok(None) => {},
+            Err(e) => if lax_parsing {
+                skipped.record(i + 1, l.trim());
+            } else {
+                return Err(InputError::malformed(source, i + 1, e));
+            },
+        }
+    }
+
+    Ok(())
+}
+
+fn read_data<R>(reader: R, source: &str, format: Format, lax_parsing: bool)
+                -> Result<Parsed, InputError>
+    where R: BufRead {
+    if format == Format::Values {
+        return read_values(reader, source, &PlainLines, lax_parsing);
+    }
+
+    let mut data: Vec<f64> = vec![];
+    let mut lines: Vec<usize> = vec![];
+    let mut weights: Vec<f64> = vec![];
+    let mut counts: Vec<u64> = vec![];
+    let mut responses: Vec<f64> = vec![];
+    let mut times: Vec<f64> = vec![];
+    let mut time_format = None;
+    let mut strata: Vec<String> = vec![];
+    let mut skipped = Skipped::default();
+
+    for (i, l) in reader.lines().enumerate() {
+        let s = l.map_err(|e| InputError::unreadable(source, e))?.trim().to_string();
+
+        if s.is_empty() {
+            continue;
+        }
+
+        match format {
+            // Read by `read_values`, above.
+            Format::Values => unreachable!(),
+            Format::Weighted => match parse_pair(&s) {
+                Some((d, w)) => {
+                    data.push(d);
+                    weights.push(w);
+                },
+                None => if lax_parsing {
+                    skipped.record(i + 1, &s);
+                } else {
+                    let expected = "a value and a weight";
+                    let e = LineError { line_number: i + 1, expected };
+                    return Err(InputError::malformed(source, i + 1, e));
+                },
+            },
+            Format::Frequency => match parse_pair(&s) {
+                Some((d, c)) => {
+                    data.push(d);
+                    counts.push(c);
+                },
+                None => if lax_parsing {
+                    skipped.record(i + 1, &s);
+                } else {
+                    let expected = "a value and a non-negative integer count";
+                    let e = LineError { line_number: i + 1, expected };
+                    return Err(InputError::malformed(source, i + 1, e));
+                },
+            },
+            Format::Pairs => match parse_pair(&s) {
+                Some((x, y)) => {
+                    data.push(x);
+                    responses.push(y);
+                },
+                None => if lax_parsing {
+                    skipped.record(i + 1, &s);
+                } else {
+                    let expected = "a predictor and a response value";
+                    let e = LineError { line_number: i + 1, expected };
+                    return Err(InputError::malformed(source, i + 1, e));
+                },
+            },
+            Format::Series => match parse_timed(&s) {
+                Some((t, f, x)) => {
+                    times.push(t);
+                    data.push(x);
+                    time_format = time_format.or(Some(f));
+                },
+                None => if lax_parsing {
+                    skipped.record(i + 1, &s);
+                } else {
+                    let expected = "an RFC 3339 or epoch timestamp and a value";
+                    let e = LineError { line_number: i + 1, expected };
+                    return Err(InputError::malformed(source, i + 1, e));
+                },
+            },
+            Format::Stratified(column) => match parse_stratified(&s, column) {
+                Some((label, x)) => {
+                    strata.push(label.to_string());
+                    data.push(x);
+                },
+                None => if lax_parsing {
+                    skipped.record(i + 1, &s);
+                } else {
+                    let expected = "a stratum label in the given column, and a value";
+                    let e = LineError { line_number: i + 1, expected };
+                    return Err(InputError::malformed(source, i + 1, e));
+                },
+            },
+        }
+
+        if data.len() > lines.len() {
+            lines.push(i + 1);
+        }
+    }
+
+    let weights = if format == Format::Weighted { Some(weights) } else { None };
+    let counts = if format == Format::Frequency { Some(counts) } else { None };
+    let responses = if format == Format::Pairs { Some(responses) } else { None };
+    let times = if format == Format::Series { Some(times) } else { None };
+    let strata = if let Format::Stratified(_) = format { Some(strata) } else { None };
+
+    Ok(Parsed { data, lines, weights, counts, responses, times, time_format, strata, skipped })
+}
+
+/// Read sample data of one value per line, decoded by `decoder`.
+fn read_values<R>(reader: R, source: &str, decoder: &dyn InputDecoder, lax_parsing: bool)
+                  -> Result<Parsed, InputError>
+    where R: BufRead {
+    let mut data: Vec<f64> = vec![];
+    let mut lines: Vec<usize> = vec![];
+    let mut skipped = Skipped::default();
+
+    decode_lines(reader, source, decoder, lax_parsing, &mut skipped, |x, n| {
+        data.push(x);
+        lines.push(n);
+    })?;
+
+    Ok(Parsed {
+        data,
+        lines,
+        weights: None,
+        counts: None,
+        responses: None,
+        times: None,
+        time_format: None,
+        strata: None,
+        skipped,
+    })
+}
+
+fn derive_data<R>(reader: R, source: &str, expr: &Expr, lax_parsing: bool)
+                  -> Result<Parsed, InputError>
+    where R: BufRead {
+    let mut data: Vec<f64> = vec![];
+    let mut lines: Vec<usize> = vec![];
+    let mut skipped = Skipped::default();
+    let mut header: Option<(Vec<String>, bool)> = None;
+
+    for (i, l) in reader.lines().enumerate() {
+        let s = l.map_err(|e| InputError::unreadable(source, e))?.trim().to_string();
+
+        if s.is_empty() {
+            continue;
+        }
+
+        let (columns, commas) = match header {
+            Some((ref columns, commas)) => (columns, commas),
+            None => {
+                let commas = s.contains(',');
+                let columns = split_fields(&s, commas)
+                    .ok_or_else(|| InputError::malformed(source, i + 1, "Unbalanced quotes"))?;
+
+                let names: Vec<&str> = columns.iter().map(|c| c.as_str()).collect();
+                expr.check_columns(&names)
+                    .map_err(|e| InputError::new(source, Some(i + 1), e.code(), e))?;
+
+                header = Some((columns, commas));
+                continue;
+            },
+        };
+
+        let values: Option<Vec<f64>> = split_fields(&s, commas)
+            .filter(|fs| fs.len() == columns.len())
+            .and_then(|fs| fs.iter().map(|f| f.parse().ok()).collect());
+
+        match values {
+            Some(values) => {
+                let value = |name: &str| columns.iter().position(|c| c == name).map(|j| values[j]);
+
+                data.push(expr.eval(&value).map_err(|e| InputError::malformed(source, i + 1, e))?);
+                lines.push(i + 1);
+            },
+            None => if lax_parsing {
+                skipped.record(i + 1, &s);
+            } else {
+                let expected = "a number in each column of the header";
+                let e = LineError { line_number: i + 1, expected };
+                return Err(InputError::malformed(source, i + 1, e));
+            },
+        }
+    }
+
+    Ok(Parsed {
+        data,
+        lines,
+        weights: None,
+        counts: None,
+        responses: None,
+        times: None,
+        time_format: None,
+        strata: None,
+        skipped,
+    })
+}
+
+/// The trimmed fields of `line`, separated by commas, which may be quoted, or
+/// else by whitespace.
+fn split_fields(line: &str, commas: bool) -> Option<Vec<String>> {
+    if commas {
+        let fields = Csv::new(0).fields(line).ok()?;
+
+        Some(fields.iter().map(|f| f.trim().to_string()).collect())
+    } else {
+        Some(line.split_whitespace().map(|f| f.to_string()).collect())
+    }
+}
+
+/// Parse a line of exactly two whitespace-separated numbers.
+fn parse_pair<T: FromStr>(line: &str) -> Option<(f64, T)> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+
+    if fields.len() != 2 {
+        return None;
+    }
+
+    match (fields[0].parse(), fields[1].parse()) {
+        (Ok(x), Ok(y)) => Some((x, y)),
+        _ => None,
+    }
+}
+
+/// Parse a line of a timestamp and a value. An RFC 3339 timestamp may separate
+/// its date and time with a space.
+fn parse_timed(line: &str) -> Option<(f64, TimeFormat, f64)> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+
+    let (stamp, value) = match fields.len() {
+        2 => (fields[0].to_string(), fields[1]),
+        3 => (format!("{} {}", fields[0], fields[1]), fields[2]),
+        _ => return None,
+    };
+
+    match (series::parse_timestamp(&stamp), value.parse()) {
+        (Ok((t, f)), Ok(x)) => Some((t, f, x)),
+        _ => None,
+    }
+}
+
+/// Parse a line of whitespace-separated fields into the stratum label at
+/// index `column`, and the value of the first other field.
+fn parse_stratified(line: &str, column: usize) -> Option<(&str, f64)> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+
+    let label = fields.get(column)?;
+    let value = fields.iter().enumerate().find(|&(i, _)| i != column)?.1;
+
+    value.parse().ok().map(|x| (*label, x))
+}
+
+/// Warn on stderr about any lines of `source` which were skipped.
+pub fn report_skipped(source: &str, skipped: &Skipped) {
+    if skipped.count == 0 {
+        return;
+    }
+
+    let noun = if skipped.count == 1 { "line" } else { "lines" };
+    log::warning(&format!("Skipped {} non-numeric {} in {}", skipped.count, noun, source));
+
+    for &(n, ref line) in &skipped.examples {
+        log::warning_at("skipped_line", &format!("{:?}", line), source, n);
+    }
+
+    if skipped.count > skipped.examples.len() && !log::is_json() {
+        eprintln!("    ...");
+    }
+}
@@ -0,0 +1,333 @@
+use plot::{PlotWidth, RenderedPlot};
+
+
+/// Separator between adjacent table columns.
+const COLUMN_GAP: &str = "  ";
+
+/// A table of right-aligned columns, each headed by a label.
+#[derive(Clone, Debug)]
+pub struct Table {
+    headers: Vec<String>,
+    min_widths: Vec<usize>,
+    rows: Vec<Vec<String>>,
+
+    /// Whether the first column labels the rows, so is aligned left, and
+    /// repeated in each table when wrapped.
+    labeled: bool,
+}
+
+impl Table {
+    /// An empty table with the given `(header, minimum width)` columns.
+    pub fn new(columns: &[(&str, usize)]) -> Self {
+        Table {
+            headers: columns.iter().map(|c| c.0.to_string()).collect(),
+            min_widths: columns.iter().map(|c| c.1).collect(),
+            rows: vec![],
+            labeled: false,
+        }
+    }
+
+    /// The table with rows and columns swapped: each row becomes a column,
+    /// headed by the corresponding one of `headers`, and each column a row,
+    /// labeled by its header. If `headers` are all blank, there is no header
+    /// row.
+    pub fn transpose(&self, headers: &[String]) -> Table {
+        let rows = self.headers
+            .iter()
+            .enumerate()
+            .map(|(i, h)| {
+                let mut row = vec![h.clone()];
+                row.extend(self.rows.iter().map(|r| r[i].clone()));
+                row
+            })
+            .collect();
+
+        let mut columns = vec![String::new()];
+        columns.extend((0..self.rows.len()).map(|j| headers.get(j).cloned().unwrap_or_default()));
+
+        Table {
+            min_widths: vec![0; columns.len()],
+            headers: columns,
+            rows,
+            labeled: true,
+        }
+    }
+
+    /// Append a row of cells, one per column. Missing cells are left blank,
+    /// and extra cells are ignored.
+    pub fn row(&mut self, cells: &[String]) {
+        let mut row: Vec<String> = cells.iter().take(self.headers.len()).cloned().collect();
+        row.resize(self.headers.len(), String::new());
+
+        self.rows.push(row);
+    }
+
+    fn column_width(&self, i: usize) -> usize {
+        self.rows
+            .iter()
+            .map(|r| width(&r[i]))
+            .chain(Some(width(&self.headers[i])))
+            .fold(self.min_widths[i], usize::max)
+    }
+
+    /// Render the table. If it is wider than `max_width`, its columns are
+    /// wrapped into successive tables which each fit, and any column which
+    /// cannot fit alone is truncated.
+    fn render(&self, max_width: Option<usize>) -> Vec<String> {
+        let widths: Vec<usize> = (0..self.headers.len()).map(|i| self.column_width(i)).collect();
+
+        // Greedily group consecutive columns into tables that fit.
+        let mut groups: Vec<Vec<usize>> = vec![];
+        let mut used = 0;
+
+        for (i, &w) in widths.iter().enumerate() {
+            let fits = match max_width {
+                Some(max) => used + COLUMN_GAP.len() + w <= max,
+                None => true,
+            };
+
+            match groups.last_mut() {
+                Some(group) if fits => {
+                    group.push(i);
+                    used += COLUMN_GAP.len() + w;
+                },
+                // Lead each wrapped table with the labels, if there are any.
+                Some(_) if self.labeled => {
+                    groups.push(vec![0, i]);
+                    used = widths[0] + COLUMN_GAP.len() + w;
+                },
+                _ => {
+                    groups.push(vec![i]);
+                    used = w;
+                },
+            }
+        }
+
+        let mut lines = vec![];
+
+        for (g, group) in groups.iter().enumerate() {
+            if g > 0 {
+                lines.push(String::new());
+            }
+
+            let line = |cells: &[String]| -> String {
+                let cols: Vec<String> = group
+                    .iter()
+                    .map(|&i| {
+                        let w = max_width.map_or(widths[i], |max| widths[i].min(max));
+                        let cell = truncate(&cells[i], w);
+                        let pad = " ".repeat(w - width(&cell));
+
+                        if self.labeled && i == 0 {
+                            format!("{}{}", cell, pad)
+                        } else {
+                            format!("{}{}", pad, cell)
+                        }
+                    })
+                    .collect();
+
+                // Blank trailing cells leave no trailing spaces.
+                cols.join(COLUMN_GAP).trim_end().to_string()
+            };
+
+            if self.headers.iter().any(|h| !h.is_empty()) {
+                lines.push(line(&self.headers));
+            }
+
+            for r in &self.rows {
+                lines.push(line(r));
+            }
+        }
+
+        lines
+    }
+}
+
+/// One part of a `Layout`.
+#[derive(Clone, Debug)]
+enum Block {
+    Plot(RenderedPlot),
+    Table(Table),
+
+    /// Lines of text, and the indent of any continuation lines made when
+    /// wrapping them.
+    Text(Vec<String>, usize),
+}
+
+/// Plots, tables, and text, stacked into one figure which fits a terminal of
+/// a given width.
+///
+/// Blocks are separated by blank lines. When the figure is too narrow, tables
+/// wrap their columns, text wraps at spaces with a hanging indent, and plots
+/// are truncated.
+#[derive(Clone, Debug)]
+pub struct Layout {
+    max_width: Option<usize>,
+    blocks: Vec<Block>,
+}
+
+impl Layout {
+    /// An empty layout, at most `max_width` columns wide, or unbounded if
+    /// `None`.
+    pub fn new(max_width: Option<PlotWidth>) -> Self {
+        Layout {
+            max_width: max_width.map(PlotWidth::get),
+            blocks: vec![],
+        }
+    }
+
+    pub fn plot(&mut self, plot: RenderedPlot) {
+        self.blocks.push(Block::Plot(plot));
+    }
+
+    pub fn table(&mut self, table: Table) {
+        self.blocks.push(Block::Table(table));
+    }
+
+    /// Add lines of text. Where a line must be wrapped, its continuation lines
+    /// are indented by `hanging` columns.
+    pub fn text(&mut self, lines: Vec<String>, hanging: usize) {
+        self.blocks.push(Block::Text(lines, hanging));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    pub fn render(&self) -> String {
+        let blocks: Vec<String> = self.blocks
+            .iter()
+            .map(|b| {
+                let lines = match *b {
+                    Block::Plot(ref p) => p
+                        .as_str()
+                        .lines()
+                        .map(|l| match self.max_width {
+                            Some(max) => truncate(l, max),
+                            None => l.to_string(),
+                        })
+                        .collect(),
+                    Block::Table(ref t) => t.render(self.max_width),
+                    Block::Text(ref lines, hanging) => lines
+                        .iter()
+                        .flat_map(|l| match self.max_width {
+                            Some(max) => wrap(l, max, hanging),
+                            None => vec![l.clone()],
+                        })
+                        .collect(),
+                };
+
+                lines.join("\n")
+            })
+            .collect();
+
+        blocks.join("\n\n")
+    }
+}
+
+/// Shorten labels of inputs, such as file paths, for display. If there are
+/// several, any leading directories common to all are stripped. Then any
+/// label wider than `max_width` has its middle replaced by an ellipsis, so
+/// that both its start and its end, often a file name, are kept.
+pub fn shorten_labels(labels: &[&str], max_width: Option<usize>) -> Vec<String> {
+    let parts: Vec<Vec<&str>> = labels.iter().map(|l| l.split('/').collect()).collect();
+
+    // Count the directories common to all, never stripping the last part.
+    let common = match parts.split_first() {
+        Some((first, rest)) if !rest.is_empty() => (0..first.len() - 1)
+            .take_while(|&i| rest.iter().all(|p| i + 1 < p.len() && p[i] == first[i]))
+            .count(),
+        _ => 0,
+    };
+
+    parts
+        .iter()
+        .map(|p| {
+            let label = p[common..].join("/");
+
+            match max_width {
+                Some(max) => ellipsize_middle(&label, max),
+                None => label,
+            }
+        })
+        .collect()
+}
+
+/// Cut `s` to at most `max` columns by replacing its middle with an
+/// ellipsis.
+fn ellipsize_middle(s: &str, max: usize) -> String {
+    if width(s) <= max {
+        return s.to_string();
+    }
+
+    if max == 0 {
+        return String::new();
+    }
+
+    let chars: Vec<char> = s.chars().collect();
+    let head = (max - 1) / 2;
+    let tail = max - 1 - head;
+
+    let mut cut: String = chars[..head].iter().collect();
+    cut.push('…');
+    cut.extend(&chars[(chars.len() - tail)..]);
+
+    cut
+}
+
+/// Display width of `s`, counting each character as one column.
+fn width(s: &str) -> usize {
+    s.chars().count()
+}
+
+/// Cut `s` to at most `max` columns, marking any cut with an ellipsis.
+fn truncate(s: &str, max: usize) -> String {
+    if width(s) <= max {
+        return s.to_string();
+    }
+
+    if max == 0 {
+        return String::new();
+    }
+
+    let mut cut: String = s.chars().take(max - 1).collect();
+    cut.push('…');
+
+    cut
+}
+
+/// Break `line` at spaces into lines of at most `max` columns, indenting all
+/// but the first by `hanging` columns. Words too long to fit are split.
+fn wrap(line: &str, max: usize, hanging: usize) -> Vec<String> {
+    // Always leave room for some text after the indent.
+    let hanging = if hanging < max { hanging } else { 0 };
+
+    let mut lines = vec![];
+    let mut rest: Vec<char> = line.chars().collect();
+    let mut indent = 0;
+
+    while indent + rest.len() > max {
+        let room = max - indent;
+
+        // Break at the last space which fits, if any text precedes it.
+        let (head, skip) = match rest[..(room + 1)].iter().rposition(|&c| c == ' ') {
+            Some(i) if rest[..i].iter().any(|&c| c != ' ') => (i, 1),
+            _ => (room, 0),
+        };
+
+        let text: String = rest[..head].iter().collect();
+        lines.push(format!("{}{}", " ".repeat(indent), text.trim_end()));
+
+        rest = rest[(head + skip)..].iter().cloned().skip_while(|&c| c == ' ').collect();
+        indent = hanging;
+
+        if rest.is_empty() {
+            return lines;
+        }
+    }
+
+    let text: String = rest.iter().collect();
+    lines.push(format!("{}{}", " ".repeat(indent), text));
+
+    lines
+}
@@ -1,8 +1,41 @@
+extern crate regex;
 extern crate stamp;
 
+pub mod agreement;
+pub mod anova;
+pub mod assumptions;
+pub mod benford;
+pub mod categorical;
+pub mod contingency;
+pub mod decode;
+pub mod dedup;
+pub mod diagnostics;
+pub mod distribution;
+pub mod drift;
+pub mod effect;
 pub mod error;
+pub mod estimate;
+pub mod exceedance;
+pub mod expr;
+pub mod fit;
+pub mod frequency;
+pub mod histogram;
+pub mod layout;
 pub mod lr;
-mod num;
+pub mod meta;
+pub mod paired;
 pub mod plot;
+pub mod quantize;
+pub mod ratio;
+pub mod rng;
+pub mod robust;
+pub mod series;
+pub mod shift;
+pub mod smooth;
+pub mod special;
+pub mod stratify;
 pub mod summary;
 pub mod t_test;
+pub mod tails;
+pub mod throughput;
+pub mod weighted;
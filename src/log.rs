@@ -1,16 +1,228 @@
+use std::fmt;
+use std::io;
+use std::num::ParseFloatError;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use clap;
+use dent::error::Error;
+use dent::expr::ExprError;
+use dent::plot::PlotError;
 use term;
 
+use bench::BenchError;
+use config::ConfigError;
+#[cfg(feature = "sqlite")] use db::DbError;
+use input::InputError;
+
+
+/// Whether to write messages as JSON objects, one per line.
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// An error which can be reported with a stable code, and the input and line
+/// at fault, if known.
+pub trait Report: fmt::Display {
+    fn code(&self) -> &'static str;
+
+    fn input(&self) -> Option<&str> {
+        None
+    }
+
+    fn line(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl Report for Error {
+    fn code(&self) -> &'static str {
+        Error::code(self)
+    }
+}
+
+impl Report for ExprError {
+    fn code(&self) -> &'static str {
+        ExprError::code(self)
+    }
+}
+
+impl Report for PlotError {
+    fn code(&self) -> &'static str {
+        PlotError::code(self)
+    }
+}
+
+impl Report for InputError {
+    fn code(&self) -> &'static str {
+        self.code
+    }
+
+    fn input(&self) -> Option<&str> {
+        Some(&self.input)
+    }
 
+    fn line(&self) -> Option<usize> {
+        self.line
+    }
+}
+
+impl Report for BenchError {
+    fn code(&self) -> &'static str {
+        "command_failed"
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl Report for DbError {
+    fn code(&self) -> &'static str {
+        "database_error"
+    }
+
+    fn input(&self) -> Option<&str> {
+        Some(&self.path)
+    }
+}
+
+impl Report for ConfigError {
+    fn code(&self) -> &'static str {
+        "bad_config"
+    }
+
+    fn input(&self) -> Option<&str> {
+        Some(&self.source)
+    }
+
+    fn line(&self) -> Option<usize> {
+        self.line
+    }
+}
+
+impl Report for ParseFloatError {
+    fn code(&self) -> &'static str {
+        "bad_parameter"
+    }
+}
+
+impl Report for io::Error {
+    fn code(&self) -> &'static str {
+        "io"
+    }
+}
+
+/// An error in the arguments, found by `clap`. Shown by its first line, without
+/// the usage that `clap` prints after it.
+pub struct UsageError(pub clap::Error);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let first = self.0.message.lines().next().unwrap_or_default();
+
+        write!(f, "{}", first.trim_start_matches("error: "))
+    }
+}
+
+impl Report for UsageError {
+    fn code(&self) -> &'static str {
+        "usage"
+    }
+}
+
+/// Write messages as JSON objects with `level`, `code`, `message`, `input`,
+/// and `line` fields, instead of as styled text.
+pub fn set_json(json: bool) {
+    JSON.store(json, Ordering::Relaxed);
+}
+
+pub fn is_json() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
+/// Report an error in how `dent` was invoked.
 pub fn error(err: &str) {
-    let mut t = term::stderr().expect("Couldn't open terminal device");
+    log("error", term::color::RED, "usage", err, None, None);
+}
+
+/// Report an error with its code, and where it occurred.
+pub fn fail(err: &dyn Report) {
+    let msg = err.to_string();
+
+    log("error", term::color::RED, err.code(), &msg, err.input(), err.line());
+}
+
+pub fn warning(msg: &str) {
+    log("warning", term::color::YELLOW, "warning", msg, None, None);
+}
+
+/// Warn about line `line` of `input`. As text, this is an indented note
+/// under the preceding warning.
+pub fn warning_at(code: &str, msg: &str, input: &str, line: usize) {
+    if is_json() {
+        eprintln!("{}", to_json("warning", code, msg, Some(input), Some(line)));
+    } else {
+        eprintln!("    line {}: {}", line, msg);
+    }
+}
+
+fn log(
+    level: &str,
+    color: term::color::Color,
+    code: &str,
+    msg: &str,
+    input: Option<&str>,
+    line: Option<usize>,
+) {
+    if is_json() {
+        eprintln!("{}", to_json(level, code, msg, input, line));
+        return;
+    }
+
+    let mut t = match term::stderr() {
+        Some(t) => t,
+        None => {
+            // No usable terminal description, so write without styling.
+            eprintln!("dent: {}: {}", level, msg);
+            return;
+        },
+    };
 
     let _ = t.attr(term::Attr::Bold);
     let _ = t.fg(term::color::BLACK);
     let _ = write!(t, "dent: ");
 
-    let _ = t.fg(term::color::RED);
-    let _ = write!(t, "error: ");
+    let _ = t.fg(color);
+    let _ = write!(t, "{}: ", level);
 
     let _ = t.reset();
-    let _ = writeln!(t, "{}", err);
+    let _ = writeln!(t, "{}", msg);
+}
+
+/// A message as a one-line JSON object. Unknown fields are `null`.
+fn to_json(level: &str, code: &str, msg: &str, input: Option<&str>, line: Option<usize>) -> String {
+    format!(
+        "{{\"level\":{},\"code\":{},\"message\":{},\"input\":{},\"line\":{}}}",
+        json_string(level),
+        json_string(code),
+        json_string(msg),
+        input.map_or("null".to_string(), json_string),
+        line.map_or("null".to_string(), |l| l.to_string()),
+    )
+}
+
+/// A JSON string of `s`, quoted and escaped.
+pub fn json_string(s: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+
+    quoted
 }
@@ -1,5 +1,7 @@
 use error::Error;
+use rng::Rng;
 use summary::Summarizer;
+use t_test::{self, TTest};
 
 
 /// The results of a simple linear regression with one predictor variable and
@@ -9,6 +11,31 @@ pub struct LinearRegression {
     r: f64,
     slope: f64,
     standard_error: f64,
+
+    // Sums of the fitted data, for the influence of each point.
+    mean_x: f64,
+    sxx: f64,
+    residual_variance: f64,
+}
+
+/// The leverage and influence of one observation on a linear regression.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Influence {
+    /// Observed response, less the fitted.
+    pub residual: f64,
+
+    /// Diagonal entry of the hat matrix: how far the predictor lies from the
+    /// others, and so how strongly the fit is drawn toward the point, from
+    /// `1/n` to 1.
+    pub leverage: f64,
+
+    /// Cook's distance [1]: the change in the fitted values when the point is
+    /// left out, scaled by the residual variance. Values above `4/n` are
+    /// commonly taken to be influential.
+    ///
+    /// [1]: R. D. Cook, "Detection of influential observation in linear
+    ///      regression", Technometrics 19(1), 1977.
+    pub cooks_distance: f64,
 }
 
 impl LinearRegression {
@@ -46,6 +73,40 @@ impl LinearRegression {
         self.standard_error
     }
 
+    /// The leverage and influence of each point of `data`, the sample data to
+    /// which the model was fit, in order. There must be more than two, which
+    /// the model does not fit exactly.
+    pub fn influence(&self, data: &[(f64, f64)]) -> Result<Vec<Influence>, Error> {
+        if data.len() < 3 {
+            return Err(Error::Undefined);
+        }
+
+        // Cook's distance is relative to the residual variance, so no point
+        // of an exact fit has any.
+        if self.residual_variance <= 0.0 {
+            return Err(Error::Undefined);
+        }
+
+        let n = data.len() as f64;
+
+        // Number of fitted parameters.
+        let p = 2.0;
+
+        let influence = data
+            .iter()
+            .map(|&(x, y)| {
+                let residual = y - (self.slope * x + self.intercept);
+                let leverage = 1.0 / n + (x - self.mean_x).powi(2) / self.sxx;
+                let cooks_distance = residual.powi(2) / (p * self.residual_variance)
+                    * leverage / (1.0 - leverage).powi(2);
+
+                Influence { residual, leverage, cooks_distance }
+            })
+            .collect();
+
+        Ok(influence)
+    }
+
     fn simple_lr(data: &[(f64, f64)]) -> Result<Self, Error> {
         let n = data.len() as f64;
 
@@ -72,11 +133,467 @@ impl LinearRegression {
         let df = n - 2.0;
         let standard_error = (slope / df.sqrt()) * (1.0 / r.powi(2) - 1.0).sqrt();
 
+        let sxx = (n - 1.0) * std_x.powi(2);
+        let residual_variance = (1.0 - r.powi(2)) * (n - 1.0) * std_y.powi(2) / df;
+
         Ok(LinearRegression {
             intercept,
             r,
             slope,
             standard_error,
+            mean_x,
+            sxx,
+            residual_variance,
+        })
+    }
+}
+
+/// An estimated model coefficient, with a two-sided t-test of the null
+/// hypothesis that it is zero.
+pub struct Coefficient {
+    pub estimate: f64,
+    pub standard_error: f64,
+    pub test: TTest,
+}
+
+impl Coefficient {
+    fn new(estimate: f64, standard_error: f64, df: f64) -> Result<Self, Error> {
+        let test = t_test::t_test_2_sided(estimate / standard_error, df)?;
+
+        Ok(Coefficient { estimate, standard_error, test })
+    }
+}
+
+/// Least-squares line through one group of sample data, with the sums needed
+/// to pool groups.
+struct LineFit {
+    n: f64,
+    mean_x: f64,
+    sxx: f64,
+    slope: f64,
+    intercept: f64,
+    sse: f64,
+}
+
+impl LineFit {
+    fn new(data: &[(f64, f64)]) -> Result<Self, Error> {
+        if data.len() < 2 {
+            return Err(Error::EmptySample);
+        }
+
+        if data.iter().any(|&(x, y)| !x.is_finite() || !y.is_finite()) {
+            return Err(Error::BadSample);
+        }
+
+        let n = data.len() as f64;
+        let mean_x = data.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_y = data.iter().map(|p| p.1).sum::<f64>() / n;
+
+        let sxx: f64 = data.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum();
+        let sxy: f64 = data.iter().map(|&(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let syy: f64 = data.iter().map(|&(_, y)| (y - mean_y).powi(2)).sum();
+
+        // The slope is undefined if all predictor values are equal.
+        if sxx == 0.0 {
+            return Err(Error::Undefined);
+        }
+
+        let slope = sxy / sxx;
+        let intercept = mean_y - slope * mean_x;
+        let sse = (syy - slope * sxy).max(0.0);
+
+        Ok(LineFit { n, mean_x, sxx, slope, intercept, sse })
+    }
+}
+
+/// A comparison of the linear regressions of two groups of sample data, as
+/// by fitting the model `Y = β₀ + β₁X + β₂G + β₃XG` to the pooled data, where
+/// `G` indicates membership in the second group.
+///
+/// The interaction coefficient `β₃` is the difference between the slopes of
+/// the groups, and `β₂` the difference between their intercepts. Both are
+/// tested against the residual variance pooled from the two groups.
+pub struct RegressionComparison {
+    /// Slope and intercept of the first group, `β₁` and `β₀`.
+    pub slope1: f64,
+    pub intercept1: f64,
+
+    /// Slope and intercept of the second group, `β₁ + β₃` and `β₀ + β₂`.
+    pub slope2: f64,
+    pub intercept2: f64,
+
+    /// The slope difference `β₃`.
+    pub slope_difference: Coefficient,
+
+    /// The intercept difference `β₂`.
+    pub intercept_difference: Coefficient,
+
+    /// Residual degrees of freedom, `n - 4`.
+    pub df: f64,
+}
+
+impl RegressionComparison {
+    /// Fit and compare the regressions of two groups of `(x, y)` points. Each
+    /// group needs at least two points with distinct `x`, and there must be at
+    /// least five points in all.
+    pub fn new(data1: &[(f64, f64)], data2: &[(f64, f64)]) -> Result<Self, Error> {
+        let g1 = LineFit::new(data1)?;
+        let g2 = LineFit::new(data2)?;
+
+        let df = g1.n + g2.n - 4.0;
+        if df < 1.0 {
+            return Err(Error::Undefined);
+        }
+
+        // Pooled residual variance.
+        let var = (g1.sse + g2.sse) / df;
+
+        let se_slope = (var * (1.0 / g1.sxx + 1.0 / g2.sxx)).sqrt();
+        let se_intercept = (var * (
+            1.0 / g1.n + g1.mean_x.powi(2) / g1.sxx +
+            1.0 / g2.n + g2.mean_x.powi(2) / g2.sxx
+        )).sqrt();
+
+        Ok(RegressionComparison {
+            slope1: g1.slope,
+            intercept1: g1.intercept,
+            slope2: g2.slope,
+            intercept2: g2.intercept,
+            slope_difference: Coefficient::new(g2.slope - g1.slope, se_slope, df)?,
+            intercept_difference: Coefficient::new(
+                g2.intercept - g1.intercept,
+                se_intercept,
+                df,
+            )?,
+            df,
+        })
+    }
+}
+
+/// One segment of a `SegmentedRegression`.
+pub struct Segment {
+    pub slope: f64,
+    pub intercept: f64,
+
+    /// Confidence interval of the slope.
+    pub slope_interval: (f64, f64),
+
+    /// Number of points in the segment.
+    pub size: usize,
+}
+
+impl Segment {
+    fn new(fit: &LineFit, confidence: f64) -> Result<Self, Error> {
+        let df = fit.n - 2.0;
+        let se = (fit.sse / df / fit.sxx).sqrt();
+        let t = t_test::t_critical_value(confidence, df)?;
+
+        Ok(Segment {
+            slope: fit.slope,
+            intercept: fit.intercept,
+            slope_interval: (fit.slope - t * se, fit.slope + t * se),
+            size: fit.n as usize,
+        })
+    }
+}
+
+/// Minimum number of points in each segment, so that every slope has a
+/// confidence interval.
+const MIN_SEGMENT_SIZE: usize = 3;
+
+/// A regression with a single breakpoint, fitting a separate line to the
+/// points on either side of it.
+///
+/// Useful for locating the knee in scaling data, where the response grows at
+/// one rate up to some size and at another rate after it.
+pub struct SegmentedRegression {
+    /// Estimated breakpoint, midway between the largest `x` of the left
+    /// segment and the smallest `x` of the right segment.
+    pub breakpoint: f64,
+    pub left: Segment,
+    pub right: Segment,
+
+    /// Sum of squared residuals of both segments.
+    pub sse: f64,
+}
+
+impl SegmentedRegression {
+    /// Fit a segmented regression by grid search, trying each split between
+    /// distinct `x` values and keeping the one with the least total squared
+    /// error, or the earliest such split if several tie. Slope intervals are
+    /// computed at the given `confidence` level.
+    pub fn new(data: &[(f64, f64)], confidence: f64) -> Result<Self, Error> {
+        if data.iter().any(|&(x, y)| !x.is_finite() || !y.is_finite()) {
+            return Err(Error::BadSample);
+        }
+
+        if data.len() < 2 * MIN_SEGMENT_SIZE {
+            return Err(Error::EmptySample);
+        }
+
+        let mut data = Vec::from(data);
+
+        // Won't panic: we have checked that each float is finite.
+        data.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or_else(|| unreachable!()));
+
+        let mut best: Option<(usize, LineFit, LineFit)> = None;
+
+        for k in MIN_SEGMENT_SIZE..=(data.len() - MIN_SEGMENT_SIZE) {
+            // Points with equal `x` must fall in the same segment.
+            if data[k - 1].0 == data[k].0 {
+                continue;
+            }
+
+            let (left, right) = match (LineFit::new(&data[..k]), LineFit::new(&data[k..])) {
+                (Ok(l), Ok(r)) => (l, r),
+                _ => continue,
+            };
+
+            if best.as_ref().map_or(true, |b| left.sse + right.sse < b.1.sse + b.2.sse) {
+                best = Some((k, left, right));
+            }
+        }
+
+        let (k, left, right) = best.ok_or(Error::Undefined)?;
+
+        Ok(SegmentedRegression {
+            breakpoint: 0.5 * (data[k - 1].0 + data[k].0),
+            sse: left.sse + right.sse,
+            left: Segment::new(&left, confidence)?,
+            right: Segment::new(&right, confidence)?,
         })
     }
 }
+
+/// Deming regression [1] of data in which both the predictor and the response
+/// are measured with error, as when comparing two methods of measurement, and
+/// ordinary least squares underestimates the slope.
+///
+/// The fit minimizes squared distances to the line along the direction set by
+/// the ratio of the error variance of the response to that of the predictor.
+/// A ratio of 1 gives orthogonal regression. Standard errors are estimated by
+/// the jackknife.
+///
+/// [1]: W. E. Deming, "Statistical Adjustment of Data", Wiley, 1943.
+pub struct DemingRegression {
+    /// Ratio of the error variance of the response to that of the predictor.
+    pub ratio: f64,
+
+    pub slope: f64,
+    pub intercept: f64,
+    pub slope_standard_error: f64,
+    pub intercept_standard_error: f64,
+}
+
+impl DemingRegression {
+    /// Fit a line to `(x, y)` points, of which there must be at least three,
+    /// with the given positive error variance `ratio`.
+    pub fn new(data: &[(f64, f64)], ratio: f64) -> Result<Self, Error> {
+        if !(ratio > 0.0 && ratio.is_finite()) {
+            return Err(Error::BadParameter);
+        }
+
+        if data.len() < 3 {
+            return Err(Error::EmptySample);
+        }
+
+        let (slope, intercept) = deming_line(data, ratio)?;
+
+        // Fits with each point left out in turn.
+        let mut slopes = vec![];
+        let mut intercepts = vec![];
+
+        for i in 0..data.len() {
+            let rest: Vec<(f64, f64)> = data[..i].iter().chain(&data[i + 1..]).cloned().collect();
+            let (b, a) = deming_line(&rest, ratio)?;
+
+            slopes.push(b);
+            intercepts.push(a);
+        }
+
+        Ok(DemingRegression {
+            ratio,
+            slope,
+            intercept,
+            slope_standard_error: jackknife_standard_error(&slopes),
+            intercept_standard_error: jackknife_standard_error(&intercepts),
+        })
+    }
+}
+
+/// The slope and intercept of the Deming regression of `data`, with the
+/// error variance `ratio`.
+fn deming_line(data: &[(f64, f64)], ratio: f64) -> Result<(f64, f64), Error> {
+    if data.iter().any(|&(x, y)| !x.is_finite() || !y.is_finite()) {
+        return Err(Error::BadSample);
+    }
+
+    let n = data.len() as f64;
+    let mean_x = data.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = data.iter().map(|p| p.1).sum::<f64>() / n;
+
+    let sxx: f64 = data.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum();
+    let sxy: f64 = data.iter().map(|&(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let syy: f64 = data.iter().map(|&(_, y)| (y - mean_y).powi(2)).sum();
+
+    // The direction of the line is undefined if the data are uncorrelated.
+    if sxy == 0.0 {
+        return Err(Error::Undefined);
+    }
+
+    let d = syy - ratio * sxx;
+    let slope = (d + (d * d + 4.0 * ratio * sxy * sxy).sqrt()) / (2.0 * sxy);
+
+    Ok((slope, mean_y - slope * mean_x))
+}
+
+/// The jackknife standard error of an estimate, from its `estimates` with
+/// each point left out in turn.
+fn jackknife_standard_error(estimates: &[f64]) -> f64 {
+    let n = estimates.len() as f64;
+    let mean = estimates.iter().sum::<f64>() / n;
+    let ss: f64 = estimates.iter().map(|e| (e - mean).powi(2)).sum();
+
+    ((n - 1.0) / n * ss).sqrt()
+}
+
+/// Names of the models accepted by `Model::from_name`.
+pub const MODEL_NAMES: [&str; 3] = ["linear", "exp", "power"];
+
+/// A two-parameter regression model which is linear in its parameters after
+/// a log transform.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Model {
+    /// `y = a + b·x`
+    Linear,
+
+    /// `y = a·e^(b·x)`, fit as `ln y = ln a + b·x`.
+    Exponential,
+
+    /// `y = a·x^b`, fit as `ln y = ln a + b·ln x`.
+    Power,
+}
+
+impl Model {
+    /// Look up a model by its name in `MODEL_NAMES`.
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        match name {
+            "linear" => Ok(Model::Linear),
+            "exp" => Ok(Model::Exponential),
+            "power" => Ok(Model::Power),
+            _ => Err(Error::BadParameter),
+        }
+    }
+
+    /// The model equation, for display.
+    pub fn equation(&self) -> &'static str {
+        match *self {
+            Model::Linear => "y = a + b·x",
+            Model::Exponential => "y = a·e^(b·x)",
+            Model::Power => "y = a·x^b",
+        }
+    }
+
+    /// Map a data point to the scale on which the model is linear.
+    fn transform(&self, (x, y): (f64, f64)) -> Result<(f64, f64), Error> {
+        match *self {
+            Model::Linear => Ok((x, y)),
+            Model::Exponential if y > 0.0 => Ok((x, y.ln())),
+            Model::Power if x > 0.0 && y > 0.0 => Ok((x.ln(), y.ln())),
+            _ => Err(Error::Undefined),
+        }
+    }
+}
+
+/// A `Model` fit by least squares on its linearizing scale, with coefficients
+/// transformed back to the original scale.
+pub struct ModelFit {
+    pub model: Model,
+    pub a: f64,
+    pub b: f64,
+
+    /// Coefficient of determination of the back-transformed fit, computed on
+    /// the original scale of the response.
+    pub r_squared: f64,
+}
+
+impl ModelFit {
+    /// Fit `model` to `(x, y)` points. The exponential model requires every
+    /// `y` to be positive, and the power model every `x` and `y`.
+    pub fn new(data: &[(f64, f64)], model: Model) -> Result<Self, Error> {
+        let transformed: Vec<(f64, f64)> = data
+            .iter()
+            .map(|&p| model.transform(p))
+            .collect::<Result<_, _>>()?;
+
+        let fit = LineFit::new(&transformed)?;
+
+        let (a, b) = match model {
+            Model::Linear => (fit.intercept, fit.slope),
+            Model::Exponential | Model::Power => (fit.intercept.exp(), fit.slope),
+        };
+
+        let mut model_fit = ModelFit { model, a, b, r_squared: 0.0 };
+
+        let n = data.len() as f64;
+        let mean_y = data.iter().map(|p| p.1).sum::<f64>() / n;
+        let ss_tot: f64 = data.iter().map(|&(_, y)| (y - mean_y).powi(2)).sum();
+        let ss_res: f64 = data.iter().map(|&(x, y)| (y - model_fit.predict(x)).powi(2)).sum();
+
+        model_fit.r_squared = 1.0 - ss_res / ss_tot;
+
+        Ok(model_fit)
+    }
+
+    /// The fitted response at `x`.
+    pub fn predict(&self, x: f64) -> f64 {
+        match self.model {
+            Model::Linear => self.a + self.b * x,
+            Model::Exponential => self.a * (self.b * x).exp(),
+            Model::Power => self.a * x.powf(self.b),
+        }
+    }
+}
+
+/// The root-mean-square error of the predictions of `model`, by `folds`-fold
+/// cross-validation of `data`.
+///
+/// The points are shuffled by `rng` and dealt into `folds` groups of nearly
+/// equal size. The model is fit to all but each group in turn, and predicts
+/// the responses of that group, on the original scale. With as many folds as
+/// points, this is leave-one-out cross-validation, and does not depend on
+/// the shuffle.
+///
+/// Unlike R², the error is out of sample, so it does not favor a model for
+/// fitting noise, and it is in units of the response, so it compares models
+/// fit on different scales.
+pub fn cross_validate(data: &[(f64, f64)], model: Model, folds: usize, rng: &mut Rng)
+                      -> Result<f64, Error> {
+    if folds < 2 || folds > data.len() {
+        return Err(Error::BadParameter);
+    }
+
+    let mut order: Vec<usize> = (0..data.len()).collect();
+
+    for i in (1..order.len()).rev() {
+        order.swap(i, rng.below(i + 1));
+    }
+
+    let mut sse = 0.0;
+
+    for fold in 0..folds {
+        let (held_out, kept): (Vec<usize>, Vec<usize>) =
+            (0..order.len()).partition(|j| j % folds == fold);
+
+        let training: Vec<(f64, f64)> = kept.iter().map(|&j| data[order[j]]).collect();
+        let fit = ModelFit::new(&training, model)?;
+
+        sse += held_out
+            .iter()
+            .map(|&j| data[order[j]])
+            .map(|(x, y)| (y - fit.predict(x)).powi(2))
+            .sum::<f64>();
+    }
+
+    Ok((sse / data.len() as f64).sqrt())
+}
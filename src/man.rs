@@ -0,0 +1,155 @@
+use clap::{App, ErrorKind};
+
+
+/// One titled section of help, such as `FLAGS`, as `(term, description)`
+/// entries. The description is empty for entries without one.
+struct Section {
+    title: String,
+    entries: Vec<(String, String)>,
+}
+
+/// Help for one command, split into its preamble and sections.
+struct Help {
+    about: Option<String>,
+    sections: Vec<Section>,
+}
+
+/// Render a manual page for the command `name`, in roff, from its help and
+/// that of each of its subcommands. The `cli` function is called once per
+/// command, since asking `clap` for help consumes the `App`.
+pub fn page<'a, F>(name: &str, version: &str, cli: F) -> String
+where
+    F: Fn() -> App<'a, 'a>,
+{
+    let main = help(cli(), &[name]);
+
+    let title = escape(&name.to_uppercase());
+
+    let mut roff = vec![
+        format!(".TH {} 1 \"\" \"{} {}\"", title, escape(name), escape(version)),
+        ".SH NAME".to_string(),
+        match main.about {
+            Some(ref about) => format!("{} \\- {}", escape(name), escape(about)),
+            None => escape(name),
+        },
+    ];
+
+    for s in &main.sections {
+        roff.extend(section(s, ".SH"));
+    }
+
+    let subcommands = main.sections
+        .iter()
+        .filter(|s| s.title == "SUBCOMMANDS")
+        .flat_map(|s| s.entries.iter().map(|e| e.0.as_str()))
+        .filter(|&sub| sub != "help");
+
+    for sub in subcommands {
+        let h = help(cli(), &[name, sub]);
+
+        roff.push(format!(".SH \"{}\"", escape(&format!("{} {}", name, sub).to_uppercase())));
+
+        if let Some(ref about) = h.about {
+            roff.push(escape(about));
+        }
+
+        for s in &h.sections {
+            roff.extend(section(s, ".SS"));
+        }
+    }
+
+    let mut roff = roff.join("\n");
+    roff.push('\n');
+
+    roff
+}
+
+/// Roff for one section of help, headed by the `heading` macro.
+fn section(s: &Section, heading: &str) -> Vec<String> {
+    if s.title == "USAGE" {
+        let mut lines = vec![format!("{} SYNOPSIS", heading), ".nf".to_string()];
+        lines.extend(s.entries.iter().map(|e| escape(&e.0)));
+        lines.push(".fi".to_string());
+
+        return lines;
+    }
+
+    let mut lines = vec![format!("{} {}", heading, escape(&s.title))];
+
+    for (term, description) in &s.entries {
+        lines.push(".TP".to_string());
+        lines.push(format!("\\fB{}\\fR", escape(term)));
+
+        if !description.is_empty() {
+            lines.push(escape(description));
+        }
+    }
+
+    lines
+}
+
+/// The help of `app` for the command line `args`, without line wrapping.
+fn help<'a>(app: App<'a, 'a>, args: &[&str]) -> Help {
+    let mut args = args.to_vec();
+    args.push("--help");
+
+    let text = match app.set_term_width(0).get_matches_from_safe(args) {
+        Err(ref e) if e.kind == ErrorKind::HelpDisplayed => e.message.clone(),
+        _ => String::new(),
+    };
+
+    parse_help(&text)
+}
+
+/// Split help text into its preamble, whose last line is the description of
+/// the command, and the sections which follow, whose entries are indented.
+fn parse_help(text: &str) -> Help {
+    let mut preamble = vec![];
+    let mut sections: Vec<Section> = vec![];
+
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if !line.starts_with(' ') && line.ends_with(':') {
+            sections.push(Section {
+                title: line.trim_end_matches(':').to_string(),
+                entries: vec![],
+            });
+
+            continue;
+        }
+
+        let entry = line.trim();
+
+        match sections.last_mut() {
+            Some(s) => {
+                // Terms and descriptions are separated by at least two spaces.
+                let entry = match entry.find("  ") {
+                    Some(i) => (entry[..i].to_string(), entry[i..].trim().to_string()),
+                    None => (entry.to_string(), String::new()),
+                };
+
+                s.entries.push(entry);
+            },
+            None => preamble.push(entry.to_string()),
+        }
+    }
+
+    // The first line names the command, and any others precede the description.
+    let about = if preamble.len() > 1 { preamble.pop() } else { None };
+
+    Help { about, sections }
+}
+
+/// Escape `s` for use as roff text.
+fn escape(s: &str) -> String {
+    let escaped = s.replace('\\', "\\e").replace('-', "\\-");
+
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{}", escaped)
+    } else {
+        escaped
+    }
+}
@@ -0,0 +1,179 @@
+use error::Error;
+use special::{normal_cdf, normal_quantile};
+
+
+/// The estimate of an effect from one independent comparison, such as the
+/// difference of means of a benchmark run on one machine.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Study {
+    pub label: String,
+    pub estimate: f64,
+    pub standard_error: f64,
+}
+
+impl Study {
+    /// A study of the given estimate, whose standard error must be positive.
+    pub fn new(label: &str, estimate: f64, standard_error: f64) -> Result<Self, Error> {
+        if !(estimate.is_finite() && standard_error > 0.0 && standard_error.is_finite()) {
+            return Err(Error::BadParameter);
+        }
+
+        Ok(Study { label: label.to_string(), estimate, standard_error })
+    }
+
+    /// A study of the difference `m₂ - m₁` of two independent means, given
+    /// with their standard errors.
+    pub fn from_means(label: &str, m1: f64, se1: f64, m2: f64, se2: f64) -> Result<Self, Error> {
+        Study::new(label, m2 - m1, se1.hypot(se2))
+    }
+}
+
+/// Parse studies from tab-separated lines, each either `LABEL ESTIMATE SE`,
+/// or `LABEL M1 SE1 M2 SE2` for the difference of two means. An optional
+/// header line, recognized by not being a study, is ignored, as are blank
+/// lines.
+pub fn parse_studies(text: &str) -> Result<Vec<Study>, Error> {
+    let mut studies = vec![];
+
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split('\t').map(|f| f.trim()).collect();
+
+        let values: Result<Vec<f64>, _> = fields[1..].iter().map(|f| f.parse::<f64>()).collect();
+
+        let study = match (values, fields[0]) {
+            (Ok(ref v), label) if v.len() == 2 => Study::new(label, v[0], v[1])?,
+            (Ok(ref v), label) if v.len() == 4 => Study::from_means(label, v[0], v[1], v[2], v[3])?,
+            (Err(_), _) if i == 0 => continue,
+            _ => return Err(Error::BadRecord),
+        };
+
+        studies.push(study);
+    }
+
+    Ok(studies)
+}
+
+/// An estimate combined from several studies by inverse-variance weighting.
+#[derive(Clone, Copy, Debug)]
+pub struct Combined {
+    pub estimate: f64,
+    pub standard_error: f64,
+    pub interval: (f64, f64),
+
+    /// Two-sided p-value of the estimate, from the normal approximation.
+    pub p: f64,
+}
+
+/// A meta-analysis of independent estimates of an effect, under both the
+/// fixed-effect and random-effects models.
+///
+/// The fixed-effect model assumes every study estimates one common effect,
+/// and weights each by its inverse variance. The random-effects model of
+/// DerSimonian and Laird [1] lets the effect itself vary between studies, as
+/// between machines, with variance `τ²`; it weights studies more evenly, and
+/// its interval is wider when they disagree.
+///
+/// [1]: R. DerSimonian and N. Laird, "Meta-analysis in clinical trials",
+///      Control. Clin. Trials 7(3), 1986.
+#[derive(Clone, Debug)]
+pub struct MetaAnalysis {
+    pub studies: Vec<Study>,
+    pub fixed: Combined,
+    pub random: Combined,
+
+    /// Cochran's Q, the weighted sum of squared deviations of the studies
+    /// from the fixed-effect estimate. It has expectation `k - 1` for `k`
+    /// studies of a common effect.
+    pub q: f64,
+
+    /// Higgins' `I²`, the proportion of the variation between studies due to
+    /// heterogeneity rather than chance.
+    pub heterogeneity: f64,
+
+    /// The estimated variance `τ²` of the effect between studies.
+    pub tau_squared: f64,
+
+    pub confidence: f64,
+}
+
+impl MetaAnalysis {
+    /// Combine `studies`, with intervals at the given confidence level.
+    pub fn new(studies: Vec<Study>, confidence: f64) -> Result<Self, Error> {
+        if !(0.0 < confidence && confidence < 1.0) {
+            return Err(Error::BadParameter);
+        }
+
+        if studies.is_empty() {
+            return Err(Error::EmptySample);
+        }
+
+        let z = normal_quantile(0.5 + 0.5 * confidence)?;
+
+        let weights: Vec<f64> = studies.iter().map(|s| s.standard_error.powi(-2)).collect();
+        let fixed = combine(&studies, &weights, z);
+
+        let q: f64 = studies
+            .iter()
+            .zip(&weights)
+            .map(|(s, w)| w * (s.estimate - fixed.estimate).powi(2))
+            .sum();
+        let df = (studies.len() - 1) as f64;
+        let heterogeneity = if q > df { (q - df) / q } else { 0.0 };
+
+        let sum: f64 = weights.iter().sum();
+        let sum_sq: f64 = weights.iter().map(|w| w * w).sum();
+        let tau_squared = if q > df { (q - df) / (sum - sum_sq / sum) } else { 0.0 };
+
+        let random_weights: Vec<f64> = studies
+            .iter()
+            .map(|s| (s.standard_error.powi(2) + tau_squared).recip())
+            .collect();
+        let random = combine(&studies, &random_weights, z);
+
+        Ok(MetaAnalysis { studies, fixed, random, q, heterogeneity, tau_squared, confidence })
+    }
+
+    /// The relative weight of each study under the fixed-effect model, which
+    /// sum to 1.
+    pub fn fixed_weights(&self) -> Vec<f64> {
+        self.relative_weights(0.0)
+    }
+
+    /// The relative weight of each study under the random-effects model,
+    /// which sum to 1.
+    pub fn random_weights(&self) -> Vec<f64> {
+        self.relative_weights(self.tau_squared)
+    }
+
+    fn relative_weights(&self, tau_squared: f64) -> Vec<f64> {
+        let weights: Vec<f64> = self.studies
+            .iter()
+            .map(|s| (s.standard_error.powi(2) + tau_squared).recip())
+            .collect();
+        let sum: f64 = weights.iter().sum();
+
+        weights.iter().map(|w| w / sum).collect()
+    }
+}
+
+/// Combine `studies` with the given inverse-variance `weights`, with an
+/// interval for the normal critical value `z`.
+fn combine(studies: &[Study], weights: &[f64], z: f64) -> Combined {
+    let sum: f64 = weights.iter().sum();
+
+    let estimate = studies
+        .iter()
+        .zip(weights)
+        .map(|(s, w)| w * s.estimate)
+        .sum::<f64>() / sum;
+    let standard_error = sum.sqrt().recip();
+
+    let interval = (estimate - z * standard_error, estimate + z * standard_error);
+    let p = 2.0 * normal_cdf(-(estimate / standard_error).abs());
+
+    Combined { estimate, standard_error, interval, p }
+}
@@ -0,0 +1,147 @@
+use std;
+use std::cell::{Cell, RefCell};
+use std::env;
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+
+use term_size;
+
+
+/// Output on its way to a pager.
+enum Paging {
+    /// Output held back until it fills the terminal, of `height` lines, when
+    /// it is paged, or until `finish`, when it is written as is.
+    Held { text: String, lines: usize, height: usize },
+
+    /// A running pager, to which the rest of the output is streamed.
+    Pager(Child),
+}
+
+thread_local! {
+    // Output held back or streamed to be paged, if paging has been started.
+    static PAGING: RefCell<Option<Paging>> = const { RefCell::new(None) };
+
+    // Whether to behave as if no terminal were attached.
+    static IGNORE_TERMINAL: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Behave as if no terminal were attached, so that output is the same
+/// wherever it is written.
+pub fn ignore_terminal() {
+    IGNORE_TERMINAL.with(|i| i.set(true));
+}
+
+/// The width and height of standard output, if it is a terminal.
+pub fn stdout_size() -> Option<(usize, usize)> {
+    if IGNORE_TERMINAL.with(Cell::get) {
+        return None;
+    }
+
+    term_size::dimensions_stdout()
+}
+
+/// The width and height of the terminal, if any standard stream is one.
+pub fn terminal_size() -> Option<(usize, usize)> {
+    if IGNORE_TERMINAL.with(Cell::get) {
+        return None;
+    }
+
+    term_size::dimensions()
+}
+
+/// Hold back standard output until it fills the terminal, and then show it,
+/// and the rest, through a pager. Has no effect unless standard output is a
+/// terminal.
+pub fn page() {
+    if let Some((_, height)) = stdout_size() {
+        let held = Paging::Held { text: String::new(), lines: 0, height };
+        PAGING.with(|p| *p.borrow_mut() = Some(held));
+    }
+}
+
+/// Write `text` to standard output, or hold it back or page it if paging.
+pub fn write(text: &str) {
+    PAGING.with(|p| {
+        let mut paging = p.borrow_mut();
+
+        *paging = match paging.take() {
+            None => {
+                print!("{}", text);
+                None
+            },
+            Some(Paging::Held { text: mut held, lines, height }) => {
+                held.push_str(text);
+                let lines = lines + text.matches('\n').count();
+
+                if lines < height {
+                    Some(Paging::Held { text: held, lines, height })
+                } else {
+                    start_pager(&held)
+                }
+            },
+            Some(Paging::Pager(mut child)) => {
+                write_to_pager(&mut child, text);
+                Some(Paging::Pager(child))
+            },
+        };
+    });
+}
+
+/// Show any held output, and wait for the pager to exit, if it was started.
+pub fn finish() {
+    match PAGING.with(|p| p.borrow_mut().take()) {
+        Some(Paging::Held { text, .. }) => print!("{}", text),
+        Some(Paging::Pager(mut child)) => {
+            // Close its input, so that it can exit.
+            drop(child.stdin.take());
+            let _ = child.wait();
+        },
+        None => {},
+    }
+}
+
+/// Show any held output, then exit with `code`.
+pub fn exit(code: i32) -> ! {
+    finish();
+    std::process::exit(code);
+}
+
+/// Start `$PAGER`, or `less` if it is unset, and write `held` to it. If no
+/// pager could be run, `held` is written to standard output instead, and
+/// paging ends.
+fn start_pager(held: &str) -> Option<Paging> {
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+
+    let child = if pager.is_empty() || pager == "cat" {
+        None
+    } else {
+        // Run through the shell, as `git` does, so that `$PAGER` may have arguments.
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(&pager).stdin(Stdio::piped());
+
+        // Like `git`, keep color, and don't clear the screen on exit.
+        if env::var_os("LESS").is_none() {
+            cmd.env("LESS", "FRX");
+        }
+
+        cmd.spawn().ok()
+    };
+
+    match child {
+        Some(mut child) => {
+            write_to_pager(&mut child, held);
+            Some(Paging::Pager(child))
+        },
+        None => {
+            print!("{}", held);
+            None
+        },
+    }
+}
+
+fn write_to_pager(child: &mut Child, text: &str) {
+    if let Some(ref mut stdin) = child.stdin {
+        // The pager may quit before reading everything, which is fine.
+        let _ = stdin.write_all(text.as_bytes());
+    }
+}
@@ -0,0 +1,101 @@
+use std::f64::consts::LN_2;
+
+use error::Error;
+use special::ln_gamma;
+
+
+/// How to combine each pair of observations into a derived sample.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operation {
+    /// The difference `after - before`.
+    Difference,
+
+    /// The ratio `after / before`.
+    Ratio,
+}
+
+impl Operation {
+    /// Infix symbol for the operation, for labeling derived samples.
+    pub fn symbol(&self) -> &'static str {
+        match *self {
+            Operation::Difference => "-",
+            Operation::Ratio => "/",
+        }
+    }
+}
+
+/// Combine paired observations element-wise, in order, using `op`.
+///
+/// The samples must be of equal length. The result is not checked for
+/// finiteness: a ratio with a zero denominator is infinite or NaN, and is
+/// handled like any other non-finite sample value.
+pub fn derive(before: &[f64], after: &[f64], op: Operation) -> Result<Vec<f64>, Error> {
+    if before.len() != after.len() {
+        return Err(Error::UnpairedSamples);
+    }
+
+    let derived = before
+        .iter()
+        .zip(after)
+        .map(|(b, a)| match op {
+            Operation::Difference => a - b,
+            Operation::Ratio => a / b,
+        })
+        .collect();
+
+    Ok(derived)
+}
+
+/// The sign test of paired observations, of whether each derived value is as
+/// likely to fall above as below the value of no change.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SignTest {
+    /// Counts of derived values above, below, and equal to no change.
+    pub above: u64,
+    pub below: u64,
+    pub ties: u64,
+
+    /// The exact two-sided p-value, from the binomial distribution of the
+    /// untied values.
+    pub p: f64,
+}
+
+/// Run the sign test on a sample `derived` by `op`, against no change: a
+/// difference of 0, or a ratio of 1.
+///
+/// It assumes only that pairs are independent, not that the distribution of
+/// derived values is symmetric or continuous, so it is the most conservative
+/// paired test, and robust to ties and to outliers of any size. Ties are
+/// discarded, and at least one value must be untied.
+pub fn sign_test(derived: &[f64], op: Operation) -> Result<SignTest, Error> {
+    if derived.iter().any(|x| x.is_nan()) {
+        return Err(Error::BadSample);
+    }
+
+    let center = match op {
+        Operation::Difference => 0.0,
+        Operation::Ratio => 1.0,
+    };
+
+    let above = derived.iter().filter(|&&x| x > center).count() as u64;
+    let below = derived.iter().filter(|&&x| x < center).count() as u64;
+    let ties = derived.len() as u64 - above - below;
+
+    let n = above + below;
+
+    if n == 0 {
+        return Err(Error::Undefined);
+    }
+
+    // Twice the probability of a count at least as far below `n / 2` as the
+    // smaller count, in logarithms to avoid overflow of the binomial terms.
+    let ln_factorial = |k: u64| ln_gamma(k as f64 + 1.0);
+    let mut tail = 0.0;
+
+    for k in 0..(above.min(below) + 1) {
+        let ln_choose = ln_factorial(n)? - ln_factorial(k)? - ln_factorial(n - k)?;
+        tail += (ln_choose - n as f64 * LN_2).exp();
+    }
+
+    Ok(SignTest { above, below, ties, p: (2.0 * tail).min(1.0) })
+}
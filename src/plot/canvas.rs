@@ -0,0 +1,69 @@
+/// A fixed-size grid of cells, each holding one single-width glyph, which can
+/// be drawn on in any order. Later drawing replaces earlier drawing.
+pub struct Canvas {
+    cells: Vec<Vec<String>>,
+    height: usize,
+    width: usize,
+}
+
+impl Canvas {
+    pub fn new(width: usize, height: usize) -> Self {
+        let cells = (0..height)
+            .map(|_| (0..width).map(|_| String::from(" ")).collect())
+            .collect();
+
+        Canvas {
+            cells,
+            height,
+            width,
+        }
+    }
+
+    /// Set the glyph at column `x` and row `y`. Out-of-bounds cells are
+    /// ignored, so callers may draw shapes which are partially clipped.
+    pub fn put(&mut self, x: usize, y: usize, glyph: &str) {
+        if x < self.width && y < self.height {
+            self.cells[y][x] = glyph.to_string();
+        }
+    }
+
+    /// Write each char of `text` to consecutive cells, starting at `(x, y)`.
+    pub fn text(&mut self, x: usize, y: usize, text: &str) {
+        for (i, c) in text.chars().enumerate() {
+            self.put(x + i, y, &c.to_string());
+        }
+    }
+
+    /// Copy a multi-line rendered figure onto the canvas, with its top left
+    /// corner at `(x, y)`.
+    pub fn paste(&mut self, x: usize, y: usize, figure: &str) {
+        for (i, line) in figure.lines().enumerate() {
+            self.text(x, y + i, line);
+        }
+    }
+
+    /// Draw a horizontal line on row `y`, spanning columns `x0` to `x1`,
+    /// inclusive.
+    pub fn hline(&mut self, y: usize, x0: usize, x1: usize, glyph: &str) {
+        for x in x0..=x1 {
+            self.put(x, y, glyph);
+        }
+    }
+
+    /// Draw a vertical line in column `x`, spanning rows `y0` to `y1`,
+    /// inclusive.
+    pub fn vline(&mut self, x: usize, y0: usize, y1: usize, glyph: &str) {
+        for y in y0..=y1 {
+            self.put(x, y, glyph);
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let rows: Vec<String> = self.cells
+            .iter()
+            .map(|r| r.join("").trim_end().to_string())
+            .collect();
+
+        rows.join("\n")
+    }
+}
@@ -0,0 +1,149 @@
+use histogram::{Binning, Histogram};
+use summary::Summarizer;
+
+use super::{
+    axis_label, axis_label_in, canvas, figure, PlotError, PlotHeight, PlotWidth, RenderedPlot,
+};
+
+
+/// Glyphs of the top cell of a bar, by eighths of the cell filled.
+const EIGHTHS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+/// Plot the counts of `histogram` as vertical bars on a bordered grid of
+/// `height` rows, with the bins side by side in order, each spanning an equal
+/// share of the columns. The y-axis is labeled with the greatest count, and
+/// the x-axis with the range of the bins, in `unit`, if given.
+///
+/// Bars are drawn to the nearest eighth of a row with block characters, or
+/// to the nearest row in ASCII. Nonzero counts always get some bar, so they
+/// are not mistaken for 0.
+pub fn render(
+    histogram: &Histogram,
+    width: PlotWidth,
+    height: PlotHeight,
+    ascii: bool,
+    unit: Option<&str>,
+) -> Result<RenderedPlot, PlotError> {
+    let counts = &histogram.counts;
+    let edges = &histogram.edges;
+
+    if counts.is_empty() {
+        return Err(PlotError::Empty);
+    }
+
+    let (lo, hi) = (edges[0], edges[edges.len() - 1]);
+
+    if !lo.is_finite() || !hi.is_finite() {
+        return Err(PlotError::BadData);
+    }
+
+    let (border_style, full) = if ascii {
+        (figure::ASCII_BORDER, "#")
+    } else {
+        (figure::UNICODE_BORDER, EIGHTHS[7])
+    };
+
+    let top = counts.iter().cloned().max().unwrap_or(0);
+    let top_label = top.to_string();
+    let label_width = top_label.len() + 1;
+
+    let lo_label = axis_label(lo);
+    let hi_label = axis_label_in(hi, unit);
+    let hi_len = hi_label.chars().count();
+
+    // Border columns and one column of padding on either side, and at least
+    // one column per bin, and room for the labels of the range beneath.
+    let bins = counts.len();
+    let min_cols = bins.max(lo_label.len() + 1 + hi_len);
+    let width = width.at_least(label_width + 4 + min_cols)?;
+    let height = height.at_least(2)?;
+
+    let left = label_width + 2;
+    let cols = width - label_width - 4;
+
+    // The height of each bar, in eighths of a row in Unicode, or in rows.
+    let step = if ascii { 1 } else { 8 };
+    let extent = |count: u64| -> usize {
+        if top == 0 {
+            0
+        } else {
+            let scaled = count as f64 / top as f64 * (height * step) as f64;
+            (scaled.round() as usize).max((count > 0) as usize)
+        }
+    };
+
+    let mut canvas = canvas::Canvas::new(width, height + 3);
+    let border = figure::Border::new(border_style, width - label_width, height + 2);
+    canvas.paste(label_width, 0, &border.render());
+
+    canvas.text(label_width - 1 - top_label.len(), 1, &top_label);
+    canvas.text(label_width - 2, height, "0");
+
+    for c in 0..cols {
+        let e = extent(counts[c * bins / cols]);
+
+        // Row `height` is the bottom of the plot area.
+        for r in 0..e / step {
+            canvas.put(left + c, height - r, full);
+        }
+
+        if e % step > 0 {
+            canvas.put(left + c, height - e / step, EIGHTHS[e % step - 1]);
+        }
+    }
+
+    let axis = height + 2;
+
+    canvas.text(left, axis, &lo_label);
+    canvas.text((left + cols).saturating_sub(hi_len), axis, &hi_label);
+
+    Ok(RenderedPlot::new(canvas.render()))
+}
+
+/// A one-line histogram of `counts`, one glyph per bin, of eight heights
+/// scaled so that the greatest count is the tallest. Empty bins are blank,
+/// and others always at least the shortest glyph. In ASCII, heights are
+/// drawn with increasingly dense punctuation.
+pub fn sparkline(counts: &[u64], ascii: bool) -> String {
+    const ASCII_EIGHTHS: [&str; 8] = ["_", ".", ",", "-", "=", "+", "*", "#"];
+
+    let glyphs = if ascii { ASCII_EIGHTHS } else { EIGHTHS };
+    let top = counts.iter().cloned().max().unwrap_or(0);
+
+    counts
+        .iter()
+        .map(|&c| if c == 0 {
+            " "
+        } else {
+            let level = (c as f64 / top as f64 * 8.0).ceil() as usize;
+            glyphs[level.clamp(1, 8) - 1]
+        })
+        .collect()
+}
+
+/// Plot a histogram of `data` in `bins` bins of equal width, as by `render`.
+pub fn from_slice(data: &[f64], bins: usize, width: PlotWidth, height: PlotHeight, ascii: bool)
+                  -> Result<RenderedPlot, PlotError> {
+    if data.is_empty() {
+        return Err(PlotError::Empty);
+    }
+
+    if bins == 0 {
+        return Err(PlotError::BadData);
+    }
+
+    let histogram = plot!(Histogram::new(data, Binning::Width(bins)))?;
+
+    render(&histogram, width, height, ascii, None)
+}
+
+/// Plot a histogram of the sample data of `s`, as by `from_slice`.
+pub fn from_summarizer(
+    s: &Summarizer,
+    bins: usize,
+    width: PlotWidth,
+    height: PlotHeight,
+    ascii: bool,
+) -> Result<RenderedPlot, PlotError> {
+    from_slice(s.as_slice(), bins, width, height, ascii)
+}
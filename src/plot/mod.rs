@@ -1,7 +1,11 @@
+mod canvas;
 mod figure;
 
+use std;
+
 use stamp;
 
+use shift::ShiftFunction;
 use summary::Summary;
 
 
@@ -9,11 +13,172 @@ macro_rules! plot {
     ($p: expr) => {
         match $p {
             Ok(t) => Ok(t),
-            Err(_) => Err("Unable to plot sample data"),
+            Err(_) => Err(PlotError::BadData),
         }
     }
 }
 
+// After `plot!`, which it uses.
+pub mod histogram;
+
+/// Reasons a plot cannot be drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlotError {
+    /// A plot width of zero columns was requested.
+    ZeroWidth,
+
+    /// A plot height of zero rows was requested.
+    ZeroHeight,
+
+    /// The plot needs at least `min` columns for its labels, border, and
+    /// padding.
+    TooNarrow { min: usize },
+
+    /// The plot needs at least `min` rows.
+    TooShort { min: usize },
+
+    /// There is no data to plot.
+    Empty,
+
+    /// The data cannot be placed on the plot, for example because it is not
+    /// finite.
+    BadData,
+
+    /// The confidence interval of the mean could not be computed.
+    BadConfidenceInterval,
+
+    /// A glyph of a boxplot style is not exactly one character.
+    BadGlyph,
+}
+
+impl PlotError {
+    /// A stable identifier of the kind of error, for machine-readable output.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            PlotError::ZeroWidth => "zero_width",
+            PlotError::ZeroHeight => "zero_height",
+            PlotError::TooNarrow { .. } => "too_narrow",
+            PlotError::TooShort { .. } => "too_short",
+            PlotError::Empty => "empty_sample",
+            PlotError::BadData => "bad_plot_data",
+            PlotError::BadConfidenceInterval => "bad_confidence_interval",
+            PlotError::BadGlyph => "bad_glyph",
+        }
+    }
+}
+
+impl std::fmt::Display for PlotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match *self {
+            PlotError::ZeroWidth => write!(f, "Plot width must be positive"),
+            PlotError::ZeroHeight => write!(f, "Plot height must be positive"),
+            PlotError::TooNarrow { min } => write!(f, "Plot width is too small, must be at least {}", min),
+            PlotError::TooShort { min } => write!(f, "Plot height is too small, must be at least {}", min),
+            PlotError::Empty => write!(f, "Cannot plot empty sample data"),
+            PlotError::BadData => write!(f, "Unable to plot sample data"),
+            PlotError::BadConfidenceInterval => write!(f, "Unable to compute confidence interval of mean"),
+            PlotError::BadGlyph => write!(f, "Boxplot glyphs must be single characters"),
+        }
+    }
+}
+
+impl std::error::Error for PlotError {}
+
+/// The width of a plot, in columns. Always positive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PlotWidth(usize);
+
+impl PlotWidth {
+    pub fn new(cols: usize) -> Result<Self, PlotError> {
+        if cols == 0 {
+            return Err(PlotError::ZeroWidth);
+        }
+
+        Ok(PlotWidth(cols))
+    }
+
+    pub fn get(self) -> usize {
+        self.0
+    }
+
+    /// The width in columns, if it is at least `min`.
+    fn at_least(self, min: usize) -> Result<usize, PlotError> {
+        if self.0 < min {
+            return Err(PlotError::TooNarrow { min });
+        }
+
+        Ok(self.0)
+    }
+}
+
+/// The height of a plot, in rows. Always positive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PlotHeight(usize);
+
+impl PlotHeight {
+    pub fn new(rows: usize) -> Result<Self, PlotError> {
+        if rows == 0 {
+            return Err(PlotError::ZeroHeight);
+        }
+
+        Ok(PlotHeight(rows))
+    }
+
+    pub fn get(self) -> usize {
+        self.0
+    }
+
+    /// The height in rows, if it is at least `min`.
+    fn at_least(self, min: usize) -> Result<usize, PlotError> {
+        if self.0 < min {
+            return Err(PlotError::TooShort { min });
+        }
+
+        Ok(self.0)
+    }
+}
+
+/// A drawn plot, as lines of text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderedPlot {
+    text: String,
+    width: usize,
+    height: usize,
+}
+
+impl RenderedPlot {
+    fn new(text: String) -> Self {
+        let width = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+        let height = text.lines().count();
+
+        RenderedPlot { text, width, height }
+    }
+
+    /// Width of the widest line, in characters.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of lines.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn into_string(self) -> String {
+        self.text
+    }
+}
+
+impl std::fmt::Display for RenderedPlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.text)
+    }
+}
+
 struct Boxplot {
     box_lo: f64,
     box_mid: f64,
@@ -21,10 +186,32 @@ struct Boxplot {
     marker: f64,
     wh_lo: f64,
     wh_hi: f64,
+    mean_ci: Option<(f64, f64)>,
 }
 
 impl Boxplot {
-    fn from_summary(summary: &Summary) -> Self {
+    /// Normalized boxplot positions. If `mean_ci` is a confidence level, also
+    /// include the confidence interval of the mean, clamped to the plot.
+    fn new(summary: &Summary, outliers: bool, mean_ci: Option<f64>)
+           -> Result<Self, PlotError> {
+        let ci = match mean_ci {
+            Some(level) => match summary.mean_confidence_interval(level) {
+                Ok(ci) => Some(ci),
+                Err(_) => return Err(PlotError::BadConfidenceInterval),
+            },
+            None => None,
+        };
+
+        let data = if outliers {
+            Boxplot::from_summary(summary, ci)
+        } else {
+            Boxplot::from_summary_no_outliers(summary, ci)
+        };
+
+        Ok(data)
+    }
+
+    fn from_summary(summary: &Summary, ci: Option<(f64, f64)>) -> Self {
         let range = summary.range();
         let n = |x| (x - summary.min()) / range;
 
@@ -35,12 +222,12 @@ impl Boxplot {
             marker: n(summary.mean()),
             wh_lo: n(summary.min()),
             wh_hi: n(summary.max()),
+            mean_ci: ci.map(|(lo, hi)| (n(lo).max(0.0), n(hi).min(1.0))),
         }
     }
 
-    fn from_summary_no_outliers(summary: &Summary) -> Self {
-        let min = summary.min_adjacent().min(summary.mean());
-        let max = summary.max_adjacent().max(summary.mean());
+    fn from_summary_no_outliers(summary: &Summary, ci: Option<(f64, f64)>) -> Self {
+        let (min, max) = extent_no_outliers(summary);
         let range = max - min;
         let n = |x| (x - min) / range;
 
@@ -51,10 +238,22 @@ impl Boxplot {
             marker: n(summary.mean()),
             wh_lo: n(summary.min_adjacent()),
             wh_hi: n(summary.max_adjacent()),
+            mean_ci: ci.map(|(lo, hi)| (n(lo).max(0.0), n(hi).min(1.0))),
         }
     }
 }
 
+/// The least and greatest values drawn by a boxplot without outliers.
+///
+/// In small samples, an adjacent value can fall inside the interpolated
+/// quartile on its side, so the box may extend past the whisker.
+fn extent_no_outliers(summary: &Summary) -> (f64, f64) {
+    let min = summary.min_adjacent().min(summary.lower_quartile()).min(summary.mean());
+    let max = summary.max_adjacent().max(summary.upper_quartile()).max(summary.mean());
+
+    (min, max)
+}
+
 struct BoxplotCols {
     box_lo: usize,
     box_mid: usize,
@@ -80,20 +279,38 @@ impl BoxplotCols {
     }
 }
 
-struct RowChars {
-    wh_lo: &'static str,
-    wh_lo_box_lo_fill: &'static str,
-    box_lo: &'static str,
-    box_lo_box_mid_fill: &'static str,
-    box_mid: &'static str,
-    box_mid_box_hi_fill: &'static str,
-    box_hi: &'static str,
-    box_hi_wh_hi_fill: &'static str,
-    wh_hi: &'static str,
+/// The glyphs used to draw one row of a boxplot, from the lower whisker end on
+/// the left to the upper whisker end on the right. Each glyph must occupy
+/// exactly one column.
+#[derive(Clone, Copy, Debug)]
+pub struct RowChars {
+    pub wh_lo: &'static str,
+    pub wh_lo_box_lo_fill: &'static str,
+    pub box_lo: &'static str,
+    pub box_lo_box_mid_fill: &'static str,
+    pub box_mid: &'static str,
+    pub box_mid_box_hi_fill: &'static str,
+    pub box_hi: &'static str,
+    pub box_hi_wh_hi_fill: &'static str,
+    pub wh_hi: &'static str,
 }
 
 impl RowChars {
-    pub fn render(&self, row: &mut Vec<String>, cols: &BoxplotCols) {
+    fn glyphs(&self) -> [&'static str; 9] {
+        [
+            self.wh_lo,
+            self.wh_lo_box_lo_fill,
+            self.box_lo,
+            self.box_lo_box_mid_fill,
+            self.box_mid,
+            self.box_mid_box_hi_fill,
+            self.box_hi,
+            self.box_hi_wh_hi_fill,
+            self.wh_hi,
+        ]
+    }
+
+    fn render(&self, row: &mut [String], cols: &BoxplotCols) {
         // Lower whisker extent.
         for i in (cols.wh_lo + 1)..cols.box_lo {
             row[i] = self.wh_lo_box_lo_fill.to_string();
@@ -131,19 +348,33 @@ impl RowChars {
     }
 }
 
-struct BoxplotChars {
+/// A complete glyph set for drawing a three-row boxplot, plus the marker used
+/// to indicate the sample mean.
+#[derive(Clone, Copy, Debug)]
+pub struct BoxplotChars {
     marker: &'static str,
+    ci_band: &'static str,
     rows: [RowChars; 3],
 }
 
 impl BoxplotChars {
-    pub fn render(&self, summary: &Summary, width: usize, outliers: bool)
-                  -> Result<String, &'static str> {
-        let data = if outliers {
-            Boxplot::from_summary(summary)
-        } else {
-            Boxplot::from_summary_no_outliers(summary)
-        };
+    /// Start building a new glyph set, using the glyphs of `base` for anything
+    /// which is not explicitly overridden.
+    pub fn builder(base: &BoxplotChars) -> BoxplotCharsBuilder {
+        BoxplotCharsBuilder { chars: *base }
+    }
+
+    /// True if every glyph of the set is an ASCII character.
+    pub fn is_ascii(&self) -> bool {
+        self.marker.is_ascii() &&
+            self.ci_band.is_ascii() &&
+            self.rows.iter().all(|r| r.glyphs().iter().all(|g| g.is_ascii()))
+    }
+
+    fn render(&self, summary: &Summary, width: PlotWidth, outliers: bool, mean_ci: Option<f64>)
+              -> Result<RenderedPlot, PlotError> {
+        let width = width.get();
+        let data = Boxplot::new(summary, outliers, mean_ci)?;
         let cols = BoxplotCols::new(&data, width);
         let mut plot = Plot::new(width);
 
@@ -151,18 +382,86 @@ impl BoxplotChars {
         self.rows[1].render(&mut plot.1, &cols);
         self.rows[2].render(&mut plot.2, &cols);
 
+        if let Some((lo, hi)) = data.mean_ci {
+            let max_col = (width - 1) as f64;
+            let lo = (lo * max_col).floor() as usize;
+            let hi = (hi * max_col).floor() as usize;
+            let edges = [cols.wh_lo, cols.box_lo, cols.box_mid, cols.box_hi, cols.wh_hi];
+
+            // Draw the band over fill, but leave the boxplot edges visible.
+            for c in (lo..(hi + 1)).filter(|c| !edges.contains(c)) {
+                plot.1[c] = self.ci_band.to_string();
+            }
+        }
+
         let no_marker = plot.render();
 
         let base = plot!(stamp::Stamp::new(&no_marker))?;
         let marker = plot!(stamp::Stamp::new(self.marker))?;
         let layered = plot!(base.layer(&marker, cols.marker, 1))?;
 
-        Ok(layered.render())
+        Ok(RenderedPlot::new(layered.render()))
     }
 }
 
-static ASCII_CHARS: BoxplotChars = BoxplotChars {
+/// Builder for a custom `BoxplotChars` glyph set.
+pub struct BoxplotCharsBuilder {
+    chars: BoxplotChars,
+}
+
+impl BoxplotCharsBuilder {
+    /// Glyph used to mark the sample mean on the middle row.
+    pub fn marker(mut self, marker: &'static str) -> Self {
+        self.chars.marker = marker;
+        self
+    }
+
+    /// Glyph used to draw the confidence interval of the mean on the middle row.
+    pub fn ci_band(mut self, ci_band: &'static str) -> Self {
+        self.chars.ci_band = ci_band;
+        self
+    }
+
+    /// Glyphs for the top row of the boxplot.
+    pub fn top(mut self, row: RowChars) -> Self {
+        self.chars.rows[0] = row;
+        self
+    }
+
+    /// Glyphs for the middle row of the boxplot, which contains the whiskers.
+    pub fn middle(mut self, row: RowChars) -> Self {
+        self.chars.rows[1] = row;
+        self
+    }
+
+    /// Glyphs for the bottom row of the boxplot.
+    pub fn bottom(mut self, row: RowChars) -> Self {
+        self.chars.rows[2] = row;
+        self
+    }
+
+    /// Validate and return the glyph set. Fails if any glyph is not exactly
+    /// one character wide.
+    pub fn build(self) -> Result<BoxplotChars, PlotError> {
+        let single = |g: &str| g.chars().count() == 1;
+
+        if !single(self.chars.marker) || !single(self.chars.ci_band) {
+            return Err(PlotError::BadGlyph);
+        }
+
+        for r in &self.chars.rows {
+            if !r.glyphs().iter().all(|g| single(g)) {
+                return Err(PlotError::BadGlyph);
+            }
+        }
+
+        Ok(self.chars)
+    }
+}
+
+pub static ASCII_CHARS: BoxplotChars = BoxplotChars {
     marker: "x",
+    ci_band: "=",
     rows: [
         RowChars {
             wh_lo: " ",
@@ -200,8 +499,9 @@ static ASCII_CHARS: BoxplotChars = BoxplotChars {
     ],
 };
 
-static UNICODE_CHARS: BoxplotChars = BoxplotChars {
+pub static UNICODE_CHARS: BoxplotChars = BoxplotChars {
     marker: "✕",
+    ci_band: "━",
     rows: [
         RowChars {
             wh_lo: "┬",
@@ -239,6 +539,92 @@ static UNICODE_CHARS: BoxplotChars = BoxplotChars {
     ],
 };
 
+pub static HEAVY_CHARS: BoxplotChars = BoxplotChars {
+    marker: "✖",
+    ci_band: "═",
+    rows: [
+        RowChars {
+            wh_lo: "┳",
+            wh_lo_box_lo_fill: " ",
+            box_lo: "┏",
+            box_lo_box_mid_fill: "━",
+            box_mid: "┳",
+            box_mid_box_hi_fill: "━",
+            box_hi: "┓",
+            box_hi_wh_hi_fill: " ",
+            wh_hi: "┳",
+        },
+        RowChars {
+            wh_lo: "┣",
+            wh_lo_box_lo_fill: "━",
+            box_lo: "┫",
+            box_lo_box_mid_fill: " ",
+            box_mid: "┃",
+            box_mid_box_hi_fill: " ",
+            box_hi: "┣",
+            box_hi_wh_hi_fill: "━",
+            wh_hi: "┫",
+        },
+        RowChars {
+            wh_lo: "┻",
+            wh_lo_box_lo_fill: " ",
+            box_lo: "┗",
+            box_lo_box_mid_fill: "━",
+            box_mid: "┻",
+            box_mid_box_hi_fill: "━",
+            box_hi: "┛",
+            box_hi_wh_hi_fill: " ",
+            wh_hi: "┻",
+        },
+    ],
+};
+
+/// A set of named boxplot glyph sets, so that styles can be selected by name.
+pub struct Styles {
+    styles: Vec<(String, BoxplotChars)>,
+}
+
+impl Styles {
+    /// An empty set of styles.
+    pub fn new() -> Self {
+        Styles { styles: vec![] }
+    }
+
+    /// Register `chars` under `name`, replacing any style with the same name.
+    pub fn register(&mut self, name: &str, chars: BoxplotChars) {
+        match self.styles.iter_mut().find(|entry| entry.0 == name) {
+            Some(entry) => entry.1 = chars,
+            None => self.styles.push((name.to_string(), chars)),
+        }
+    }
+
+    /// Look up the style registered under `name`.
+    pub fn get(&self, name: &str) -> Option<&BoxplotChars> {
+        self.styles
+            .iter()
+            .find(|entry| entry.0 == name)
+            .map(|entry| &entry.1)
+    }
+
+    /// Names of all registered styles, in order of registration.
+    pub fn names(&self) -> Vec<&str> {
+        self.styles.iter().map(|entry| entry.0.as_str()).collect()
+    }
+}
+
+impl Default for Styles {
+    /// The built-in styles: `unicode`, `ascii`, and `heavy`.
+    fn default() -> Self {
+        let mut styles = Styles::new();
+
+        styles.register("unicode", UNICODE_CHARS);
+        styles.register("ascii", ASCII_CHARS);
+        styles.register("heavy", HEAVY_CHARS);
+
+        styles
+    }
+}
+
 fn make_row(width: usize) -> Vec<String> {
     use std::iter::repeat;
 
@@ -266,38 +652,182 @@ impl Plot {
     }
 }
 
-pub fn summary_plot(summary: &Summary, width: usize, ascii: bool, outliers: bool)
-                    -> Result<String, &'static str> {
+pub fn summary_plot(summary: &Summary, width: PlotWidth, ascii: bool, outliers: bool)
+                    -> Result<RenderedPlot, PlotError> {
     let plot_style = if ascii { &ASCII_CHARS } else { &UNICODE_CHARS };
 
-    plot_style.render(summary, width, outliers)
+    summary_plot_with_style(summary, width, plot_style, outliers, None)
+}
+
+/// Like `summary_plot`, but drawn using the glyph set `style`. If `mean_ci` is
+/// a confidence level, the confidence interval of the mean is drawn as a band
+/// on the middle row.
+pub fn summary_plot_with_style(
+    summary: &Summary,
+    width: PlotWidth,
+    style: &BoxplotChars,
+    outliers: bool,
+    mean_ci: Option<f64>,
+) -> Result<RenderedPlot, PlotError> {
+    style.render(summary, width, outliers, mean_ci)
+}
+
+/// Glyphs filling the leftmost `i` eighths of a column, indexed by `i`.
+static LEFT_EIGHTHS: [&str; 9] = [" ", "▏", "▎", "▍", "▌", "▋", "▊", "▉", "█"];
+
+/// Draw a boxplot whose box edges and median are placed with sub-column
+/// precision, using Unicode block characters.
+///
+/// The upper quartile and median are accurate to an eighth of a column, and
+/// the lower quartile to about half a column, since Unicode only provides
+/// right-aligned blocks in those widths. The first row marks the mean, and the
+/// second row holds the whiskers and a solid box, broken just after the median.
+/// If `mean_ci` is a confidence level, the confidence interval of the mean is
+/// drawn as a band around the mean marker.
+pub fn summary_plot_subcell(
+    summary: &Summary,
+    width: PlotWidth,
+    outliers: bool,
+    mean_ci: Option<f64>,
+) -> Result<RenderedPlot, PlotError> {
+    let width = width.get();
+    let data = Boxplot::new(summary, outliers, mean_ci)?;
+
+    // Split a normalized position into a column and the number of eighths of
+    // that column to its left.
+    let w = width as f64;
+    let to_cell = |x: f64| {
+        let pos = (x * w).max(0.0);
+        let col = (pos.floor() as usize).min(width - 1);
+        let eighths = (((pos - col as f64) * 8.0).round() as usize).min(8);
+
+        (col, eighths)
+    };
+
+    let mut marker_row = make_row(width);
+    let mut row = make_row(width);
+
+    let (wh_lo, _) = to_cell(data.wh_lo);
+    let (wh_hi, _) = to_cell(data.wh_hi);
+    let (box_lo, lo_eighths) = to_cell(data.box_lo);
+    let (box_mid, mid_eighths) = to_cell(data.box_mid);
+    let (box_hi, hi_eighths) = to_cell(data.box_hi);
+    let (marker, _) = to_cell(data.marker);
+
+    for cell in row.iter_mut().take(wh_hi + 1).skip(wh_lo) {
+        *cell = "─".to_string();
+    }
+    row[wh_lo] = "├".to_string();
+    row[wh_hi] = "┤".to_string();
+
+    for cell in row.iter_mut().take(box_hi).skip(box_lo) {
+        *cell = "█".to_string();
+    }
+
+    // Only full and half right-aligned blocks exist, plus a one-eighth sliver.
+    row[box_lo] = match lo_eighths {
+        0..=2 => "█",
+        3..=5 => "▐",
+        _ => "▕",
+    }.to_string();
+
+    // Always leave a visible gap after the median.
+    row[box_mid] = LEFT_EIGHTHS[mid_eighths.min(7)].to_string();
+
+    if box_hi != box_mid {
+        row[box_hi] = LEFT_EIGHTHS[hi_eighths.max(1)].to_string();
+    }
+
+    if let Some((lo, hi)) = data.mean_ci {
+        let (lo, _) = to_cell(lo);
+        let (hi, _) = to_cell(hi);
+
+        for cell in marker_row.iter_mut().take(hi + 1).skip(lo) {
+            *cell = "─".to_string();
+        }
+        marker_row[lo] = "╶".to_string();
+        marker_row[hi] = "╴".to_string();
+    }
+
+    marker_row[marker] = "▾".to_string();
+
+    Ok(RenderedPlot::new(format!("{}\n{}", marker_row.join(""), row.join(""))))
 }
 
 pub fn comparison_plot(
     summaries: &[&Summary],
-    width: usize,
+    width: PlotWidth,
     ascii: bool,
     border: bool,
     outliers: bool,
-) -> Result<String, &'static str> {
-    if summaries.is_empty() {
-        return Err("Cannot plot empty list of summaries");
-    }
+) -> Result<RenderedPlot, PlotError> {
+    let plot_style = if ascii { &ASCII_CHARS } else { &UNICODE_CHARS };
 
-    let padding = if border { 2 } else { 0 };
-    let content_width = (width - 2 * padding) as f64;
-    let border_style = if ascii {
+    comparison_plot_with_style(summaries, width, plot_style, border, outliers, None)
+}
+
+/// Like `comparison_plot`, but drawn using the glyph set `style`. The border,
+/// if any, uses ASCII characters exactly when `style` does. See
+/// `summary_plot_with_style` for the meaning of `mean_ci`.
+pub fn comparison_plot_with_style(
+    summaries: &[&Summary],
+    width: PlotWidth,
+    style: &BoxplotChars,
+    border: bool,
+    outliers: bool,
+    mean_ci: Option<f64>,
+) -> Result<RenderedPlot, PlotError> {
+    let border_style = if style.is_ascii() {
         figure::ASCII_BORDER
     } else {
         figure::UNICODE_BORDER
     };
+    let render = |s: &Summary, w: PlotWidth| summary_plot_with_style(s, w, style, outliers, mean_ci);
+
+    layout(summaries, width, border_style, border, outliers, render)
+}
+
+/// Like `comparison_plot`, but each boxplot is drawn using `summary_plot_subcell`.
+pub fn comparison_plot_subcell(
+    summaries: &[&Summary],
+    width: PlotWidth,
+    border: bool,
+    outliers: bool,
+    mean_ci: Option<f64>,
+) -> Result<RenderedPlot, PlotError> {
+    let render = |s: &Summary, w: PlotWidth| summary_plot_subcell(s, w, outliers, mean_ci);
+
+    layout(summaries, width, figure::UNICODE_BORDER, border, outliers, render)
+}
+
+/// Stack the plots of `summaries`, drawn by `render` at a common scale, and
+/// optionally enclose them in a border. If the summaries share a unit, the
+/// bottom of the border is labeled with it.
+fn layout<F>(
+    summaries: &[&Summary],
+    width: PlotWidth,
+    border_style: figure::BorderChars,
+    border: bool,
+    outliers: bool,
+    render: F,
+) -> Result<RenderedPlot, PlotError>
+    where F: Fn(&Summary, PlotWidth) -> Result<RenderedPlot, PlotError> {
+    if summaries.is_empty() {
+        return Err(PlotError::Empty);
+    }
+
+    let padding = if border { 2 } else { 0 };
+
+    // Leave at least one column for the boxplots inside the padding.
+    let width = width.at_least(2 * padding + 1)?;
+    let content_width = (width - 2 * padding) as f64;
 
     use std::f64;
 
     let plot_min = |s: &Summary| if outliers {
         s.min()
     } else {
-        s.min_adjacent().min(s.mean())
+        extent_no_outliers(s).0
     };
     let min = summaries
         .iter()
@@ -307,7 +837,7 @@ pub fn comparison_plot(
     let plot_max = |s: &Summary| if outliers {
         s.max()
     } else {
-        s.max_adjacent().max(s.mean())
+        extent_no_outliers(s).1
     };
     let max = summaries
         .iter()
@@ -320,16 +850,8 @@ pub fn comparison_plot(
     let mut plots = vec![];
 
     for s in summaries {
-        let s_min = if outliers {
-            s.min()
-        } else {
-            s.min_adjacent().min(s.mean())
-        };
-        let s_max = if outliers {
-            s.max()
-        } else {
-            s.max_adjacent().max(s.mean())
-        };
+        let s_min = plot_min(s);
+        let s_max = plot_max(s);
 
         // Proportion of total content width spanned by this plot.
         let p = (s_max - s_min) / range;
@@ -339,7 +861,7 @@ pub fn comparison_plot(
         assert!(1.0 <= w);
         assert!(w <= content_width);
 
-        let plot = plot!(stamp::Stamp::new(&summary_plot(s, w as usize, ascii, outliers)?))?;
+        let plot = plot!(stamp::Stamp::new(render(s, PlotWidth(w as usize))?.as_str()))?;
 
         assert!(min <= s_min);
         let offset_p = (s_min - min) / range;
@@ -355,8 +877,17 @@ pub fn comparison_plot(
         .map(|&(ref p, _)| p.height())
         .sum() + (padding * 2);
 
+    let unit = summaries[0].unit();
+
     let base = if border {
-        figure::Border::new(border_style, width, height).render()
+        let base = figure::Border::new(border_style, width, height).render();
+
+        match unit {
+            Some(unit) if summaries.iter().all(|s| s.unit() == Some(unit)) => {
+                label_border(&base, unit)
+            },
+            _ => base,
+        }
     } else {
         figure::Filled::blank(width, height).render()
     };
@@ -367,5 +898,468 @@ pub fn comparison_plot(
         all_plots = plot!(all_plots.layer(&plot, left_offset, padding + i * plot.height()))?;
     }
 
-    Ok(all_plots.render())
+    Ok(RenderedPlot::new(all_plots.render()))
+}
+
+/// Write `label`, padded by a space on either side, over the bottom edge of
+/// the rendered `border`, ending just before its last two columns, if it fits.
+fn label_border(border: &str, label: &str) -> String {
+    let mut lines: Vec<String> = border.lines().map(|l| l.to_string()).collect();
+    let label = format!(" {} ", label);
+    let len = label.chars().count();
+
+    if let Some(bottom) = lines.last_mut() {
+        let mut cells: Vec<char> = bottom.chars().collect();
+
+        if cells.len() >= len + 4 {
+            let start = cells.len() - len - 2;
+            cells.splice(start..start + len, label.chars());
+            *bottom = cells.into_iter().collect();
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Format an axis label compactly, switching to scientific notation for very
+/// large or small magnitudes.
+fn axis_label(x: f64) -> String {
+    let a = x.abs();
+
+    if a == 0.0 || (1e-2..1e5).contains(&a) {
+        format!("{:.2}", x)
+    } else {
+        format!("{:.2e}", x)
+    }
+}
+
+/// Format an axis label as `axis_label` does, followed by `unit`, if given.
+fn axis_label_in(x: f64, unit: Option<&str>) -> String {
+    match unit {
+        Some(unit) => format!("{} {}", axis_label(x), unit),
+        None => axis_label(x),
+    }
+}
+
+/// Plot a shift function, with one row per quantile. Each row marks the
+/// difference at that quantile, spanned by its confidence interval. A
+/// vertical rule marks zero difference, and the axis below is labeled with
+/// the plotted range, in `unit`, if given.
+pub fn shift_plot(shift: &ShiftFunction, width: PlotWidth, ascii: bool, unit: Option<&str>)
+                  -> Result<RenderedPlot, PlotError> {
+    if shift.points.is_empty() {
+        return Err(PlotError::Empty);
+    }
+
+    let (border_style, point, band, zero) = if ascii {
+        (figure::ASCII_BORDER, "o", "-", ":")
+    } else {
+        (figure::UNICODE_BORDER, "●", "─", "┊")
+    };
+
+    let labels: Vec<String> = shift.points
+        .iter()
+        .map(|p| format!("p{}", (100.0 * p.quantile).round()))
+        .collect();
+    let label_width = labels.iter().map(|l| l.len()).max().unwrap_or(0) + 1;
+
+    // Border columns and one column of padding on either side.
+    let width = width.at_least(label_width + 8)?;
+
+    let rows = shift.points.len();
+    let height = rows + 3;
+
+    let lo = shift.points.iter().map(|p| p.interval.value.0).fold(0.0, f64::min);
+    let hi = shift.points.iter().map(|p| p.interval.value.1).fold(0.0, f64::max);
+
+    if !lo.is_finite() || !hi.is_finite() {
+        return Err(PlotError::BadData);
+    }
+
+    let left = label_width + 2;
+    let cols = width - label_width - 4;
+
+    let col = |x: f64| -> usize {
+        if hi == lo {
+            left + cols / 2
+        } else {
+            left + ((x - lo) / (hi - lo) * (cols - 1) as f64).round() as usize
+        }
+    };
+
+    let mut canvas = canvas::Canvas::new(width, height);
+    let border = figure::Border::new(border_style, width - label_width, rows + 2);
+    canvas.paste(label_width, 0, &border.render());
+
+    canvas.vline(col(0.0), 1, rows, zero);
+
+    for (i, (p, label)) in shift.points.iter().zip(&labels).enumerate() {
+        let y = i + 1;
+
+        canvas.text(label_width - 1 - label.len(), y, label);
+        canvas.hline(y, col(p.interval.value.0), col(p.interval.value.1), band);
+        canvas.put(col(p.difference), y, point);
+    }
+
+    let axis = rows + 2;
+    let lo_label = axis_label(lo);
+    let hi_label = axis_label_in(hi, unit);
+    let hi_len = hi_label.chars().count();
+
+    canvas.text(left, axis, &lo_label);
+    canvas.text((left + cols).saturating_sub(hi_len), axis, &hi_label);
+
+    // Label zero too, unless it would collide with the range labels.
+    let zero_col = col(0.0);
+    if left + lo_label.len() < zero_col && zero_col + 2 < (left + cols).saturating_sub(hi_len) {
+        canvas.put(zero_col, axis, "0");
+    }
+
+    Ok(RenderedPlot::new(canvas.render()))
+}
+
+/// Plot `(x, y)` points on a bordered grid of `height` rows, with the ranges
+/// of both axes labeled. If a `curve` is given, such as a smoother fit to the
+/// points, it is drawn beneath them, linearly interpolated between its points.
+pub fn scatter_plot(
+    points: &[(f64, f64)],
+    curve: Option<&[(f64, f64)]>,
+    width: PlotWidth,
+    height: PlotHeight,
+    ascii: bool,
+) -> Result<RenderedPlot, PlotError> {
+    scatter(points, curve, &[], width, height, ascii, None)
+}
+
+/// Like `scatter_plot`, but with a horizontal line across the plot at each of
+/// `levels`, as at the bias and limits of agreement of a Bland-Altman plot.
+/// Both axes are labeled in `unit`, if given, as the means and differences of
+/// measurements are.
+pub fn difference_plot(
+    points: &[(f64, f64)],
+    levels: &[f64],
+    width: PlotWidth,
+    height: PlotHeight,
+    ascii: bool,
+    unit: Option<&str>,
+) -> Result<RenderedPlot, PlotError> {
+    scatter(points, None, levels, width, height, ascii, unit)
+}
+
+fn scatter(
+    points: &[(f64, f64)],
+    curve: Option<&[(f64, f64)]>,
+    levels: &[f64],
+    width: PlotWidth,
+    height: PlotHeight,
+    ascii: bool,
+    unit: Option<&str>,
+) -> Result<RenderedPlot, PlotError> {
+    if points.is_empty() {
+        return Err(PlotError::Empty);
+    }
+
+    let (border_style, point, trace, rule) = if ascii {
+        (figure::ASCII_BORDER, "o", ".", "-")
+    } else {
+        (figure::UNICODE_BORDER, "●", "·", "─")
+    };
+
+    let all = || points.iter().chain(curve.unwrap_or(&[]));
+
+    let x_lo = all().map(|p| p.0).fold(f64::INFINITY, f64::min);
+    let x_hi = all().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max);
+    let y_lo = all().map(|p| p.1).chain(levels.iter().cloned()).fold(f64::INFINITY, f64::min);
+    let y_hi = all().map(|p| p.1).chain(levels.iter().cloned()).fold(f64::NEG_INFINITY, f64::max);
+
+    if !(x_lo.is_finite() && x_hi.is_finite() && y_lo.is_finite() && y_hi.is_finite()) {
+        return Err(PlotError::BadData);
+    }
+
+    let y_lo_label = axis_label(y_lo);
+    let y_hi_label = axis_label_in(y_hi, unit);
+    let y_hi_len = y_hi_label.chars().count();
+    let label_width = y_lo_label.len().max(y_hi_len) + 1;
+
+    // Border columns and one column of padding on either side.
+    let width = width.at_least(label_width + 8)?;
+    let height = height.at_least(2)?;
+
+    let left = label_width + 2;
+    let cols = width - label_width - 4;
+
+    let col = |x: f64| -> usize {
+        if x_hi == x_lo {
+            left + cols / 2
+        } else {
+            left + ((x - x_lo) / (x_hi - x_lo) * (cols - 1) as f64).round() as usize
+        }
+    };
+
+    // Row 1 is the top of the plot area, and holds the largest `y`.
+    let row = |y: f64| -> usize {
+        if y_hi == y_lo {
+            1 + height / 2
+        } else {
+            height - ((y - y_lo) / (y_hi - y_lo) * (height - 1) as f64).round() as usize
+        }
+    };
+
+    let mut canvas = canvas::Canvas::new(width, height + 3);
+    let border = figure::Border::new(border_style, width - label_width, height + 2);
+    canvas.paste(label_width, 0, &border.render());
+
+    canvas.text(label_width - 1 - y_hi_len, 1, &y_hi_label);
+    canvas.text(label_width - 1 - y_lo_label.len(), height, &y_lo_label);
+
+    for &y in levels {
+        for c in left..(left + cols) {
+            canvas.put(c, row(y), rule);
+        }
+    }
+
+    if let Some(curve) = curve {
+        for c in left..(left + cols) {
+            let x = if cols > 1 {
+                (x_lo + (c - left) as f64 / (cols - 1) as f64 * (x_hi - x_lo)).min(x_hi)
+            } else {
+                x_lo
+            };
+
+            if let Some(y) = interpolate(curve, x) {
+                canvas.put(c, row(y), trace);
+            }
+        }
+    }
+
+    for &(x, y) in points {
+        canvas.put(col(x), row(y), point);
+    }
+
+    let axis = height + 2;
+    let x_hi_label = axis_label_in(x_hi, unit);
+
+    canvas.text(left, axis, &axis_label(x_lo));
+    canvas.text((left + cols).saturating_sub(x_hi_label.chars().count()), axis, &x_hi_label);
+
+    Ok(RenderedPlot::new(canvas.render()))
+}
+
+/// The glyphs drawn by `overlay_histogram` where only the first histogram
+/// reaches, where only the second does, and where both do, for a legend.
+pub fn overlay_glyphs(ascii: bool) -> (&'static str, &'static str, &'static str) {
+    if ascii { (".", "+", "#") } else { ("░", "▓", "█") }
+}
+
+/// Plot two histograms over the same bins on one bordered grid of `height`
+/// rows, with each bin drawn as a column of equal width, in order. Each bin
+/// has a height in `first` and in `second`, such as the proportion of each
+/// sample which falls in it. Where only one histogram reaches, its glyph is
+/// drawn, and where both do, a third. The axis below is labeled with the
+/// `range` of the bins, in `unit`, if given.
+pub fn overlay_histogram(
+    first: &[f64],
+    second: &[f64],
+    range: (f64, f64),
+    width: PlotWidth,
+    height: PlotHeight,
+    ascii: bool,
+    unit: Option<&str>,
+) -> Result<RenderedPlot, PlotError> {
+    if first.is_empty() {
+        return Err(PlotError::Empty);
+    }
+
+    let bad = |x: &f64| !x.is_finite() || *x < 0.0;
+
+    if first.len() != second.len() || first.iter().chain(second).any(bad) {
+        return Err(PlotError::BadData);
+    }
+
+    if !range.0.is_finite() || !range.1.is_finite() {
+        return Err(PlotError::BadData);
+    }
+
+    let border_style = if ascii { figure::ASCII_BORDER } else { figure::UNICODE_BORDER };
+    let (one, two, both) = overlay_glyphs(ascii);
+
+    let top = first.iter().chain(second).cloned().fold(0.0, f64::max);
+
+    let y_lo_label = axis_label(0.0);
+    let y_hi_label = axis_label(top);
+    let label_width = y_lo_label.len().max(y_hi_label.len()) + 1;
+
+    // Border columns and one column of padding on either side, and at least
+    // one column per bin, and room for the labels of the range beneath.
+    let x_lo_label = axis_label(range.0);
+    let x_hi_label = axis_label_in(range.1, unit);
+    let x_hi_len = x_hi_label.chars().count();
+
+    let bins = first.len();
+    let min_cols = bins.max(4).max(x_lo_label.len() + 1 + x_hi_len);
+    let width = width.at_least(label_width + 4 + min_cols)?;
+    let height = height.at_least(2)?;
+
+    let left = label_width + 2;
+    let cols = width - label_width - 4;
+
+    // Nonzero heights always get some bar, so they are not mistaken for 0.
+    let rows = |h: f64| -> usize {
+        if top == 0.0 {
+            0
+        } else {
+            ((h / top * height as f64).round() as usize).max((h > 0.0) as usize)
+        }
+    };
+
+    let mut canvas = canvas::Canvas::new(width, height + 3);
+    let border = figure::Border::new(border_style, width - label_width, height + 2);
+    canvas.paste(label_width, 0, &border.render());
+
+    canvas.text(label_width - 1 - y_hi_label.len(), 1, &y_hi_label);
+    canvas.text(label_width - 1 - y_lo_label.len(), height, &y_lo_label);
+
+    for c in 0..cols {
+        let bin = c * bins / cols;
+        let (a, b) = (rows(first[bin]), rows(second[bin]));
+
+        // Row `height` is the bottom of the plot area.
+        for r in 0..a.max(b) {
+            let glyph = match (r < a, r < b) {
+                (true, true) => both,
+                (true, false) => one,
+                _ => two,
+            };
+
+            canvas.put(left + c, height - r, glyph);
+        }
+    }
+
+    let axis = height + 2;
+
+    canvas.text(left, axis, &x_lo_label);
+    canvas.text((left + cols).saturating_sub(x_hi_len), axis, &x_hi_label);
+
+    Ok(RenderedPlot::new(canvas.render()))
+}
+
+/// Plot one horizontal bar per labeled count, each labeled on its left and
+/// followed by its count. Bars are scaled so that the largest count spans the
+/// plot. Labels longer than a third of the width are truncated.
+pub fn bar_chart(bars: &[(&str, u64)], width: PlotWidth, ascii: bool)
+                 -> Result<RenderedPlot, PlotError> {
+    let max = bars.iter().map(|b| b.1).max().unwrap_or(0);
+    let bars: Vec<(&str, f64, String)> = bars
+        .iter()
+        .map(|&(l, c)| (l, c as f64, c.to_string()))
+        .collect();
+
+    draw_bars(&bars, max as f64, width, ascii)
+}
+
+/// Plot a bar chart as `bar_chart` does, but with each bar scaled by its count
+/// as a proportion of `total`, and followed by that proportion too, as a
+/// percentage. A proportion of `max` spans the plot, so that charts of
+/// samples of different sizes drawn with the same `max` can be compared bar
+/// by bar. No count may exceed `total`, nor proportion `max`.
+pub fn proportion_chart(
+    bars: &[(&str, u64)],
+    total: u64,
+    max: f64,
+    width: PlotWidth,
+    ascii: bool,
+) -> Result<RenderedPlot, PlotError> {
+    let proportion = |c: u64| if total == 0 { 0.0 } else { c as f64 / total as f64 };
+
+    if bars.iter().any(|b| b.1 > total || proportion(b.1) > max) {
+        return Err(PlotError::BadData);
+    }
+
+    let bars: Vec<(&str, f64, String)> = bars
+        .iter()
+        .map(|&(l, c)| {
+            let p = proportion(c);
+            (l, p, format!("{} ({:.1}%)", c, 100.0 * p))
+        })
+        .collect();
+
+    draw_bars(&bars, max, width, ascii)
+}
+
+/// Plot one horizontal bar per label, of its value, followed by its note.
+/// A value of `max` spans the plot.
+fn draw_bars(bars: &[(&str, f64, String)], max: f64, width: PlotWidth, ascii: bool)
+             -> Result<RenderedPlot, PlotError> {
+    if bars.is_empty() {
+        return Err(PlotError::Empty);
+    }
+
+    let (axis, bar, ellipsis) = if ascii { ("|", "#", "~") } else { ("│", "█", "…") };
+
+    let max_label = (width.get() / 3).max(1);
+    let labels: Vec<String> = bars
+        .iter()
+        .map(|&(l, _, _)| if l.chars().count() > max_label {
+            let mut truncated: String = l.chars().take(max_label - 1).collect();
+            truncated.push_str(ellipsis);
+            truncated
+        } else {
+            l.to_string()
+        })
+        .collect();
+
+    let label_width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let note_width = bars.iter().map(|b| b.2.chars().count()).max().unwrap_or(0);
+
+    // A gap and the axis after the labels, and a gap and the note after the
+    // bars, which need at least one column.
+    let left = label_width + 2;
+    let width = width.at_least(left + note_width + 2)?;
+    let cols = width - left - note_width - 1;
+
+    let mut canvas = canvas::Canvas::new(width, bars.len());
+
+    for (y, (&(_, value, ref note), label)) in bars.iter().zip(&labels).enumerate() {
+        canvas.text(label_width - label.chars().count(), y, label);
+        canvas.put(label_width + 1, y, axis);
+
+        // Nonzero values always get some bar, so they are not mistaken for 0.
+        let len = if max == 0.0 {
+            0
+        } else {
+            ((value / max * cols as f64).round() as usize).max((value > 0.0) as usize)
+        };
+
+        if len > 0 {
+            canvas.hline(y, left, left + len - 1, bar);
+        }
+
+        canvas.text(left + len + 1, y, note);
+    }
+
+    Ok(RenderedPlot::new(canvas.render()))
+}
+
+/// Linearly interpolate the piecewise-linear curve through `curve`, sorted by
+/// `x`, at `x`. Undefined outside the range of the curve.
+fn interpolate(curve: &[(f64, f64)], x: f64) -> Option<f64> {
+    let i = curve.partition_point(|p| p.0 < x);
+
+    if i == curve.len() {
+        return None;
+    }
+
+    let (x1, y1) = curve[i];
+
+    if x1 == x {
+        return Some(y1);
+    }
+
+    if i == 0 {
+        return None;
+    }
+
+    let (x0, y0) = curve[i - 1];
+
+    Some(y0 + (x - x0) / (x1 - x0) * (y1 - y0))
 }
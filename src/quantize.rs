@@ -0,0 +1,66 @@
+use error::Error;
+
+
+/// A quantization of values to evenly spaced points, as of times read from a
+/// timer of some resolution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Quantizer {
+    /// Round each value to the nearest multiple of the step, and values
+    /// halfway between two away from zero.
+    Round(f64),
+
+    /// Replace each value with the lower edge of its bucket, `[k w, (k + 1) w)`
+    /// for the width `w` and some integer `k`.
+    Bucket(f64),
+}
+
+impl Quantizer {
+    /// Round to multiples of `step`, which must be positive and finite.
+    pub fn round(step: f64) -> Result<Self, Error> {
+        check_spacing(step).map(Quantizer::Round)
+    }
+
+    /// Bucket by `width`, which must be positive and finite.
+    pub fn bucket(width: f64) -> Result<Self, Error> {
+        check_spacing(width).map(Quantizer::Bucket)
+    }
+
+    /// The spacing of the quantized values.
+    pub fn spacing(&self) -> f64 {
+        match *self {
+            Quantizer::Round(s) | Quantizer::Bucket(s) => s,
+        }
+    }
+
+    /// Quantize `x`. Values which are not finite are kept as they are.
+    ///
+    /// When the spacing is the reciprocal of an integer, as `0.1` is, the
+    /// multiple is found by dividing by that integer, so that it is the same
+    /// float as the parsed decimal, as `0.3` is, but `3.0 * 0.1` is not.
+    pub fn apply(&self, x: f64) -> f64 {
+        if !x.is_finite() {
+            return x;
+        }
+
+        let spacing = self.spacing();
+        let inverse = (1.0 / spacing).round();
+        let exact_inverse = inverse > 1.0 && (inverse * spacing - 1.0).abs() < 1e-12;
+
+        let scaled = if exact_inverse { x * inverse } else { x / spacing };
+
+        let k = match *self {
+            Quantizer::Round(_) => scaled.round(),
+            Quantizer::Bucket(_) => scaled.floor(),
+        };
+
+        if exact_inverse { k / inverse } else { k * spacing }
+    }
+}
+
+fn check_spacing(spacing: f64) -> Result<f64, Error> {
+    if spacing > 0.0 && spacing.is_finite() {
+        Ok(spacing)
+    } else {
+        Err(Error::BadParameter)
+    }
+}
@@ -0,0 +1,140 @@
+use error::Error;
+use estimate::{self, Estimate};
+use rng::Rng;
+use summary::{Summarizer, Summary};
+use t_test;
+
+
+/// How the confidence interval of a `RatioOfMeans` was computed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RatioMethod {
+    Fieller,
+    Bootstrap,
+}
+
+impl RatioMethod {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            RatioMethod::Fieller => "Fieller",
+            RatioMethod::Bootstrap => "bootstrap",
+        }
+    }
+}
+
+/// The ratio of the means of two independent samples, with a confidence
+/// interval.
+///
+/// When the samples are durations of the same task, the ratio of the baseline
+/// mean to the candidate mean is the speedup of the candidate.
+#[derive(Debug)]
+pub struct RatioOfMeans {
+    pub ratio: f64,
+    pub interval: Estimate<(f64, f64)>,
+    pub confidence: f64,
+    pub method: RatioMethod,
+}
+
+impl RatioOfMeans {
+    /// Estimate the ratio of the mean of `num` to the mean of `den`, with an
+    /// interval by Fieller's theorem, using a t quantile with the Welch
+    /// degrees of freedom of the two means.
+    ///
+    /// The interval is undefined when the mean of `den` is not significantly
+    /// different from zero, since then it is unbounded.
+    pub fn fieller(num: &Summary, den: &Summary, confidence: f64) -> Result<Self, Error> {
+        let a = num.mean();
+        let b = den.mean();
+
+        if b == 0.0 {
+            return Err(Error::Undefined);
+        }
+
+        let ratio = a / b;
+        let va = num.standard_error().powi(2);
+        let vb = den.standard_error().powi(2);
+
+        if va == 0.0 && vb == 0.0 {
+            return Ok(RatioOfMeans {
+                ratio,
+                interval: Estimate::exact((ratio, ratio)),
+                confidence,
+                method: RatioMethod::Fieller,
+            });
+        }
+
+        let df = (va + vb).powi(2) /
+            (va.powi(2) / (num.size() - 1.0) + vb.powi(2) / (den.size() - 1.0));
+        let t = t_test::t_critical_value(confidence, df)?;
+
+        let g = t.powi(2) * vb / b.powi(2);
+
+        if g >= 1.0 {
+            return Err(Error::Undefined);
+        }
+
+        let center = ratio / (1.0 - g);
+        let half_width = (t / b.abs()) * (va * (1.0 - g) + ratio.powi(2) * vb).sqrt() / (1.0 - g);
+
+        Ok(RatioOfMeans {
+            ratio,
+            interval: Estimate::exact((center - half_width, center + half_width)),
+            confidence,
+            method: RatioMethod::Fieller,
+        })
+    }
+
+    /// Estimate the ratio of the mean of `num` to the mean of `den`, with a
+    /// percentile bootstrap interval from `resamples` resamplings of each.
+    ///
+    /// Resamplings in which the mean of `den` is zero are skipped.
+    pub fn bootstrap(
+        num: &Summarizer,
+        den: &Summarizer,
+        confidence: f64,
+        resamples: usize,
+        rng: &mut Rng,
+    ) -> Result<Self, Error> {
+        if !(0.0 < confidence && confidence < 1.0) || resamples == 0 {
+            return Err(Error::BadParameter);
+        }
+
+        let mean = |xs: &[f64]| xs.iter().sum::<f64>() / xs.len() as f64;
+
+        let ratios: Vec<f64> = (0..resamples)
+            .map(|_| {
+                mean(&rng.resample(num.as_slice())) / mean(&rng.resample(den.as_slice()))
+            })
+            .filter(|r| r.is_finite())
+            .collect();
+
+        let ratios = Summarizer::from_vec(ratios).map_err(|_| Error::Undefined)?;
+
+        Ok(RatioOfMeans {
+            ratio: num.mean() / den.mean(),
+            interval: estimate::bootstrap_interval(&ratios, confidence, resamples)?,
+            confidence,
+            method: RatioMethod::Bootstrap,
+        })
+    }
+
+    /// Estimate the ratio of means by Fieller's theorem, falling back to the
+    /// bootstrap where the Fieller interval is unbounded.
+    pub fn new(
+        num: &Summarizer,
+        den: &Summarizer,
+        confidence: f64,
+        resamples: usize,
+        rng: &mut Rng,
+    ) -> Result<Self, Error> {
+        let fieller = RatioOfMeans::fieller(
+            &Summary::from_summarizer(num),
+            &Summary::from_summarizer(den),
+            confidence,
+        );
+
+        match fieller {
+            Err(Error::Undefined) => RatioOfMeans::bootstrap(num, den, confidence, resamples, rng),
+            r => r,
+        }
+    }
+}
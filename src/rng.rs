@@ -0,0 +1,107 @@
+/// A small, seedable pseudorandom number generator.
+///
+/// Implements xoshiro256** [1], seeded by expanding a single `u64` with
+/// SplitMix64 [2]. It is fast and statistically strong, but not suitable for
+/// cryptographic use. Given the same seed, the sequence of outputs is stable
+/// across platforms and releases, so seeded results can be reproduced.
+///
+/// [1]: http://xoshiro.di.unimi.it/xoshiro256starstar.c
+/// [2]: http://xoshiro.di.unimi.it/splitmix64.c
+#[derive(Clone, Debug)]
+pub struct Rng {
+    s: [u64; 4],
+}
+
+impl Rng {
+    /// Construct a generator whose output is entirely determined by `seed`.
+    pub fn new(seed: u64) -> Self {
+        let mut x = seed;
+        let mut next = || {
+            x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+
+        Rng { s: [next(), next(), next(), next()] }
+    }
+
+    /// Construct a generator seeded from the system clock.
+    pub fn from_time() -> Self {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() ^ u64::from(d.subsec_nanos()).rotate_left(32))
+            .unwrap_or(0);
+
+        Rng::new(seed)
+    }
+
+    /// The next 64 uniformly random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.s;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+
+        result
+    }
+
+    /// A uniformly random float in the half-open interval [0, 1).
+    pub fn next_f64(&mut self) -> f64 {
+        // Use the top 53 bits, the precision of an `f64` mantissa.
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// A uniformly random index in [0, n). Panics if `n` is 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Cannot sample an index from an empty range");
+
+        // Rejection sampling avoids modulo bias.
+        let n = n as u64;
+        let zone = u64::MAX - (u64::MAX % n);
+
+        loop {
+            let x = self.next_u64();
+
+            if x < zone {
+                return (x % n) as usize;
+            }
+        }
+    }
+
+    /// Choose `n` distinct indices in [0, len) uniformly at random, in
+    /// increasing order. All indices are chosen if `n` is at least `len`.
+    pub fn subsample_indices(&mut self, len: usize, n: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..len).collect();
+
+        if n >= len {
+            return indices;
+        }
+
+        // A partial Fisher-Yates shuffle leaves a uniform subset at the front.
+        for i in 0..n {
+            let j = i + self.below(len - i);
+            indices.swap(i, j);
+        }
+
+        indices.truncate(n);
+        indices.sort_unstable();
+
+        indices
+    }
+
+    /// Draw a sample of the same size from `data`, with replacement.
+    pub fn resample(&mut self, data: &[f64]) -> Vec<f64> {
+        (0..data.len()).map(|_| data[self.below(data.len())]).collect()
+    }
+}
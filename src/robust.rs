@@ -0,0 +1,176 @@
+use error::Error;
+use special;
+use summary::Summarizer;
+
+
+/// The result of a two-sided Mann–Whitney U test.
+#[derive(Debug)]
+pub struct MannWhitney {
+    /// The U statistic of the first sample: the number of pairs `(x, y)` in
+    /// which `x > y`, counting ties as one half.
+    pub u: f64,
+
+    /// Standardized U, with corrections for ties and continuity.
+    pub z: f64,
+
+    /// Two-sided p-value, from the normal approximation.
+    pub p: f64,
+}
+
+/// Conduct a two-sided Mann–Whitney U test of the null hypothesis that values
+/// drawn from either sample are equally likely to be the larger.
+///
+/// Unlike the t-test, this makes no assumption about the shape of either
+/// distribution, and is insensitive to outliers. The p-value is computed from
+/// the normal approximation to the distribution of U, so is approximate for
+/// very small samples.
+pub fn mann_whitney_u_test(x: &Summarizer, y: &Summarizer) -> Result<MannWhitney, Error> {
+    let n1 = x.size();
+    let n2 = y.size();
+    let n = n1 + n2;
+
+    // Merge the sorted samples, tagging values of `x`.
+    let mut merged: Vec<(f64, bool)> = x.as_slice().iter().map(|&v| (v, true)).collect();
+    merged.extend(y.as_slice().iter().map(|&v| (v, false)));
+
+    // Won't panic: each float is finite by construction of `Summarizer`.
+    merged.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or_else(|| unreachable!()));
+
+    // Sum the midranks of `x`, and the tie correction term `Σ(t³ - t)`.
+    let mut rank_sum = 0.0;
+    let mut ties = 0.0;
+    let mut i = 0;
+
+    while i < merged.len() {
+        let j = i + merged[i..].iter().take_while(|v| v.0 == merged[i].0).count();
+        let midrank = (i + j + 1) as f64 / 2.0;
+        let t = (j - i) as f64;
+
+        rank_sum += midrank * merged[i..j].iter().filter(|v| v.1).count() as f64;
+        ties += t.powi(3) - t;
+
+        i = j;
+    }
+
+    let u = rank_sum - n1 * (n1 + 1.0) / 2.0;
+    let mean = n1 * n2 / 2.0;
+    let var = n1 * n2 / 12.0 * ((n + 1.0) - ties / (n * (n - 1.0)));
+
+    // All values are tied, so there is nothing to rank.
+    if var.is_nan() || var <= 0.0 {
+        return Err(Error::Undefined);
+    }
+
+    let d = u - mean;
+    let z = d.signum() * (d.abs() - 0.5).max(0.0) / var.sqrt();
+    let p = (2.0 * special::normal_cdf(-z.abs())).min(1.0);
+
+    Ok(MannWhitney { u, z, p })
+}
+
+/// The Hodges–Lehmann estimate of the shift from `x` to `y`: the median of
+/// the differences `y - x` over all pairs of points.
+///
+/// A robust counterpart to the difference of means. The median is selected
+/// without forming the differences, as by Johnson and Mitchell (1978), so it
+/// takes space proportional to the sum of the sample sizes, not their product.
+pub fn hodges_lehmann_shift(x: &Summarizer, y: &Summarizer) -> f64 {
+    let differences = PairwiseDifferences { x: x.as_slice(), y: y.as_slice() };
+    let n = x.as_slice().len() * y.as_slice().len();
+
+    // Interpolate between the middle two differences of an even count, as
+    // `percentile` does.
+    let lower = differences.select((n - 1) / 2);
+
+    if n % 2 == 1 {
+        return lower;
+    }
+
+    lower + 0.5 * (differences.select(n / 2) - lower)
+}
+
+/// The differences `y[j] - x[i]` of two sorted samples, as a matrix whose
+/// rows, indexed by `j`, and columns, indexed by `i` from the last, are both
+/// ascending.
+struct PairwiseDifferences<'a> {
+    x: &'a [f64],
+    y: &'a [f64],
+}
+
+impl<'a> PairwiseDifferences<'a> {
+    fn get(&self, row: usize, col: usize) -> f64 {
+        self.y[row] - self.x[self.x.len() - 1 - col]
+    }
+
+    /// The number of differences in `row` less than `pivot`, or, if
+    /// `inclusive`, at most `pivot`.
+    fn count_below(&self, row: usize, pivot: f64, inclusive: bool) -> usize {
+        let y = self.y[row];
+        let above = self.x.partition_point(|&a| {
+            if inclusive { y - a > pivot } else { y - a >= pivot }
+        });
+
+        self.x.len() - above
+    }
+
+    /// The `k`th smallest difference, from 0.
+    ///
+    /// Each row keeps a range of candidate columns, outside of which every
+    /// difference is known to be above or below the `k`th. Each round
+    /// counts the differences on either side of a pivot, the weighted
+    /// median of the middle candidates of the rows, and so discards at least
+    /// a quarter of the candidates, until few enough are left to sort.
+    fn select(&self, k: usize) -> f64 {
+        let rows = self.y.len();
+        let mut lo = vec![0; rows];
+        let mut hi = vec![self.x.len(); rows];
+
+        loop {
+            let candidates: usize = (0..rows).map(|j| hi[j] - lo[j]).sum();
+
+            if candidates <= rows + self.x.len() {
+                let below: usize = lo.iter().sum();
+                let mut rest: Vec<f64> = (0..rows)
+                    .flat_map(|j| (lo[j]..hi[j]).map(move |i| (j, i)))
+                    .map(|(j, i)| self.get(j, i))
+                    .collect();
+
+                // Won't panic: the differences of finite samples are finite.
+                rest.sort_by(|a, b| a.partial_cmp(b).unwrap_or_else(|| unreachable!()));
+
+                return rest[k - below];
+            }
+
+            let mut middles: Vec<(f64, usize)> = (0..rows)
+                .filter(|&j| hi[j] > lo[j])
+                .map(|j| (self.get(j, lo[j] + (hi[j] - lo[j]) / 2), hi[j] - lo[j]))
+                .collect();
+            middles.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or_else(|| unreachable!()));
+
+            let mut weight = 0;
+            let pivot = middles
+                .iter()
+                .find(|&&(_, w)| {
+                    weight += w;
+                    2 * weight >= candidates
+                })
+                .map(|&(m, _)| m)
+                .unwrap_or_else(|| unreachable!());
+
+            let less: Vec<usize> = (0..rows).map(|j| self.count_below(j, pivot, false)).collect();
+            let most: Vec<usize> = (0..rows).map(|j| self.count_below(j, pivot, true)).collect();
+
+            if k < less.iter().sum() {
+                for j in 0..rows {
+                    hi[j] = hi[j].min(less[j]);
+                }
+            } else if k < most.iter().sum() {
+                return pivot;
+            } else {
+                for j in 0..rows {
+                    lo[j] = lo[j].max(most[j]);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,553 @@
+use error::Error;
+use summary::percentile_of_unsorted;
+
+
+/// Names of the statistics accepted by `Statistic::from_name`.
+pub const STATISTIC_NAMES: [&str; 6] = ["mean", "median", "min", "max", "sum", "count"];
+
+/// A statistic used to reduce a group of observations to a single value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Statistic {
+    Mean,
+    Median,
+    Min,
+    Max,
+    Sum,
+    Count,
+}
+
+impl Statistic {
+    /// Look up a statistic by its name in `STATISTIC_NAMES`.
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        match name {
+            "mean" => Ok(Statistic::Mean),
+            "median" => Ok(Statistic::Median),
+            "min" => Ok(Statistic::Min),
+            "max" => Ok(Statistic::Max),
+            "sum" => Ok(Statistic::Sum),
+            "count" => Ok(Statistic::Count),
+            _ => Err(Error::BadParameter),
+        }
+    }
+
+    /// Reduce a non-empty group of finite values.
+    pub fn apply(&self, values: &[f64]) -> Result<f64, Error> {
+        if values.is_empty() {
+            return Err(Error::EmptySample);
+        }
+
+        let v = match *self {
+            Statistic::Mean => values.iter().sum::<f64>() / values.len() as f64,
+            Statistic::Median => percentile_of_unsorted(&mut Vec::from(values), 0.5)?,
+            Statistic::Min => values.iter().cloned().fold(f64::INFINITY, f64::min),
+            Statistic::Max => values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            Statistic::Sum => values.iter().sum(),
+            Statistic::Count => values.len() as f64,
+        };
+
+        Ok(v)
+    }
+}
+
+/// How the timestamps of a series were written.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimeFormat {
+    /// Seconds since the Unix epoch.
+    Epoch,
+
+    /// RFC 3339 date-times, such as `2019-03-01T12:00:00Z`.
+    Rfc3339,
+}
+
+impl TimeFormat {
+    /// Write `t`, in seconds since the Unix epoch, in this format.
+    pub fn format(&self, t: f64) -> String {
+        match *self {
+            TimeFormat::Epoch => format!("{}", t),
+            TimeFormat::Rfc3339 => format_rfc3339(t),
+        }
+    }
+}
+
+/// A run of consecutive observations which each rise above, or each fall
+/// below, the one before.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Streak {
+    /// Number of consecutive rises or falls, one less than the number of
+    /// observations in the run.
+    pub steps: usize,
+
+    /// Times of the first and last observations of the run.
+    pub start: f64,
+    pub end: f64,
+}
+
+/// The largest fall of a series from an earlier peak (a drawdown), or rise
+/// from an earlier trough (a drawup).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Excursion {
+    /// Size of the fall or rise, which is positive.
+    pub size: f64,
+
+    /// Times of the peak or trough, and of the observation furthest from it.
+    pub start: f64,
+    pub end: f64,
+}
+
+/// The number of times a series crosses a threshold, in each direction.
+/// Observations equal to the threshold touch it without crossing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Crossings {
+    pub threshold: f64,
+    pub up: usize,
+    pub down: usize,
+}
+
+impl Crossings {
+    pub fn total(&self) -> usize {
+        self.up + self.down
+    }
+}
+
+/// A sequence of observations indexed by time, ordered by time.
+#[derive(Debug)]
+pub struct Series {
+    // Pairs of seconds since the Unix epoch and observed values.
+    points: Vec<(f64, f64)>,
+}
+
+impl Series {
+    /// Construct a `Series` from `(time, value)` pairs, where each time is in
+    /// seconds since the Unix epoch. Times and values must be finite.
+    ///
+    /// The pairs are sorted by time. Observations with equal times keep their
+    /// input order.
+    pub fn new(points: &[(f64, f64)]) -> Result<Self, Error> {
+        if points.is_empty() {
+            return Err(Error::EmptySample);
+        }
+
+        if points.iter().any(|&(t, x)| !t.is_finite() || !x.is_finite()) {
+            return Err(Error::BadSample);
+        }
+
+        let mut points = Vec::from(points);
+
+        // Won't panic: we have checked that each float is finite.
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or_else(|| unreachable!()));
+
+        Ok(Series { points })
+    }
+
+    /// The `(time, value)` pairs of the series, in time order.
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// The observed values, in time order.
+    pub fn values(&self) -> Vec<f64> {
+        self.points.iter().map(|p| p.1).collect()
+    }
+
+    /// Number of observations.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// A series is never empty, but we provide this for consistency with
+    /// `len`.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The longest streak of strict rises, or `None` if the series never
+    /// rises. Of equally long streaks, the earliest is chosen.
+    pub fn longest_rise(&self) -> Option<Streak> {
+        self.longest_streak(|a, b| b > a)
+    }
+
+    /// The longest streak of strict falls, or `None` if the series never
+    /// falls. Of equally long streaks, the earliest is chosen.
+    pub fn longest_fall(&self) -> Option<Streak> {
+        self.longest_streak(|a, b| b < a)
+    }
+
+    fn longest_streak<F>(&self, step: F) -> Option<Streak>
+        where F: Fn(f64, f64) -> bool {
+        let mut longest: Option<Streak> = None;
+        let mut start = 0;
+
+        for (i, w) in self.points.windows(2).enumerate() {
+            if !step(w[0].1, w[1].1) {
+                start = i + 1;
+                continue;
+            }
+
+            let steps = i + 1 - start;
+
+            if longest.map_or(true, |l| steps > l.steps) {
+                longest = Some(Streak { steps, start: self.points[start].0, end: w[1].0 });
+            }
+        }
+
+        longest
+    }
+
+    /// The largest fall from a running maximum, or `None` if the series
+    /// never falls.
+    pub fn max_drawdown(&self) -> Option<Excursion> {
+        self.max_excursion(|peak, x| peak - x)
+    }
+
+    /// The largest rise from a running minimum, or `None` if the series
+    /// never rises.
+    pub fn max_drawup(&self) -> Option<Excursion> {
+        self.max_excursion(|trough, x| x - trough)
+    }
+
+    /// The largest `distance(extreme, x)` of any observation `x` from the
+    /// running extreme before it, which is updated whenever the distance is
+    /// negative.
+    fn max_excursion<F>(&self, distance: F) -> Option<Excursion>
+        where F: Fn(f64, f64) -> f64 {
+        let mut largest: Option<Excursion> = None;
+        let (mut start, mut extreme) = self.points[0];
+
+        for &(t, x) in &self.points[1..] {
+            let size = distance(extreme, x);
+
+            if size < 0.0 {
+                start = t;
+                extreme = x;
+            } else if size > 0.0 && largest.map_or(true, |l| size > l.size) {
+                largest = Some(Excursion { size, start, end: t });
+            }
+        }
+
+        largest
+    }
+
+    /// Count the crossings of `threshold`, which must be finite.
+    pub fn crossings(&self, threshold: f64) -> Result<Crossings, Error> {
+        if !threshold.is_finite() {
+            return Err(Error::BadParameter);
+        }
+
+        let mut crossings = Crossings { threshold, up: 0, down: 0 };
+        let mut above = None;
+
+        for &(_, x) in &self.points {
+            if x == threshold {
+                continue;
+            }
+
+            let now = x > threshold;
+
+            match above {
+                Some(false) if now => crossings.up += 1,
+                Some(true) if !now => crossings.down += 1,
+                _ => {},
+            }
+
+            above = Some(now);
+        }
+
+        Ok(crossings)
+    }
+
+    /// Group the observations into consecutive windows of `size`
+    /// observations, and reduce each window by `stat`. Each point of the
+    /// result is timed at the first observation of its window. The last
+    /// window holds any remaining observations, and may be smaller.
+    pub fn aggregate(&self, size: usize, stat: Statistic) -> Result<Series, Error> {
+        if size == 0 {
+            return Err(Error::BadParameter);
+        }
+
+        let points = self.points
+            .chunks(size)
+            .map(|w| {
+                let values: Vec<f64> = w.iter().map(|p| p.1).collect();
+
+                Ok((w[0].0, stat.apply(&values)?))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Series { points })
+    }
+
+    /// Group the observations into consecutive time intervals of `interval`
+    /// seconds, aligned to the epoch, and reduce each non-empty interval by
+    /// `stat`. Each point of the result is timed at the start of its interval.
+    pub fn resample(&self, interval: f64, stat: Statistic) -> Result<Series, Error> {
+        if !(interval.is_finite() && interval > 0.0) {
+            return Err(Error::BadParameter);
+        }
+
+        let mut points = vec![];
+        let mut start = 0;
+
+        while start < self.points.len() {
+            let bin = (self.points[start].0 / interval).floor();
+            let end = start + self.points[start..]
+                .iter()
+                .take_while(|p| (p.0 / interval).floor() == bin)
+                .count();
+
+            let values: Vec<f64> = self.points[start..end].iter().map(|p| p.1).collect();
+            points.push((bin * interval, stat.apply(&values)?));
+
+            start = end;
+        }
+
+        Ok(Series { points })
+    }
+}
+
+/// Problems with the times of observations, as read, which would otherwise
+/// go unnoticed once they are sorted into a `Series`. Observations are given
+/// by their 0-based index in the order read.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TimeIssues {
+    /// Observations earlier than the one before in the same run.
+    pub out_of_order: Vec<usize>,
+
+    /// Observations at the same time as an earlier one.
+    pub duplicates: Vec<usize>,
+
+    /// Observations which follow the one before them in time by more than
+    /// the greatest allowed gap, with the size of each gap, in seconds.
+    pub gaps: Vec<(usize, f64)>,
+}
+
+impl TimeIssues {
+    pub fn is_empty(&self) -> bool {
+        self.out_of_order.is_empty() && self.duplicates.is_empty() && self.gaps.is_empty()
+    }
+}
+
+/// Check the times of observations, read in consecutive `runs`, such as the
+/// lines of separate files, for any out of order, any duplicated, and, if
+/// `max_gap` is given, any gaps between consecutive times of more than it, in
+/// seconds. Only times within a run can be out of order, since one run may
+/// begin before another ends. Duplicates and gaps are among all the times,
+/// which are consecutive once sorted, whatever the order read.
+pub fn check_times(runs: &[&[f64]], max_gap: Option<f64>) -> TimeIssues {
+    let mut issues = TimeIssues::default();
+    let mut start = 0;
+
+    for run in runs {
+        for i in 1..run.len() {
+            if run[i] < run[i - 1] {
+                issues.out_of_order.push(start + i);
+            }
+        }
+
+        start += run.len();
+    }
+
+    let times = runs.concat();
+
+    // Stable, so the first of equal times is the earliest read.
+    let mut order: Vec<usize> = (0..times.len()).collect();
+    order.sort_by(|&i, &j| times[i].total_cmp(&times[j]));
+
+    for pair in order.windows(2) {
+        let (i, j) = (pair[0], pair[1]);
+        let gap = times[j] - times[i];
+
+        if gap == 0.0 {
+            issues.duplicates.push(j);
+        }
+
+        if max_gap.is_some_and(|max| gap > max) {
+            issues.gaps.push((j, gap));
+        }
+    }
+
+    issues.duplicates.sort_unstable();
+    issues.gaps.sort_by_key(|&(i, _)| i);
+
+    issues
+}
+
+/// Parse a timestamp, either as seconds since the Unix epoch or as an RFC 3339
+/// date-time, returning seconds since the epoch and the detected format.
+pub fn parse_timestamp(s: &str) -> Result<(f64, TimeFormat), Error> {
+    if let Ok(t) = s.parse::<f64>() {
+        if !t.is_finite() {
+            return Err(Error::BadTimestamp);
+        }
+
+        return Ok((t, TimeFormat::Epoch));
+    }
+
+    parse_rfc3339(s).map(|t| (t, TimeFormat::Rfc3339))
+}
+
+/// Parse a duration such as `500ms`, `30s`, `1m`, `2h`, or `1d`, in seconds. A
+/// bare number is taken to be in seconds.
+pub fn parse_duration(s: &str) -> Result<f64, Error> {
+    let units: [(&str, f64); 5] = [
+        ("ms", 1e-3),
+        ("s", 1.0),
+        ("m", 60.0),
+        ("h", 3600.0),
+        ("d", 86400.0),
+    ];
+
+    let (number, scale) = units
+        .iter()
+        .find(|&&(u, _)| s.ends_with(u))
+        .map(|&(u, scale)| (&s[..(s.len() - u.len())], scale))
+        .unwrap_or((s, 1.0));
+
+    match number.parse::<f64>() {
+        Ok(n) if n.is_finite() && n > 0.0 => Ok(n * scale),
+        _ => Err(Error::BadParameter),
+    }
+}
+
+/// Parse an RFC 3339 date-time, such as `2019-03-01T12:00:00.5+01:00`.
+fn parse_rfc3339(s: &str) -> Result<f64, Error> {
+    let b = s.as_bytes();
+
+    // The fixed-width prefix `YYYY-MM-DDTHH:MM:SS`.
+    if b.len() < 20 || !b[..19].is_ascii() || b[4] != b'-' || b[7] != b'-' || b[13] != b':' || b[16] != b':' {
+        return Err(Error::BadTimestamp);
+    }
+
+    if !matches!(b[10], b'T' | b't' | b' ') {
+        return Err(Error::BadTimestamp);
+    }
+
+    let field = |i: usize, j: usize| -> Result<i64, Error> {
+        let f = &s[i..j];
+
+        if !f.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(Error::BadTimestamp);
+        }
+
+        f.parse().map_err(|_| Error::BadTimestamp)
+    };
+
+    let year = field(0, 4)?;
+    let month = field(5, 7)?;
+    let day = field(8, 10)?;
+    let hour = field(11, 13)?;
+    let minute = field(14, 16)?;
+    let second = field(17, 19)?;
+
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) ||
+        hour > 23 || minute > 59 || second > 60 {
+        return Err(Error::BadTimestamp);
+    }
+
+    // Optional fractional seconds, then the offset.
+    let mut rest = &s[19..];
+    let mut frac = 0.0;
+
+    if rest.starts_with('.') {
+        let digits = rest[1..].bytes().take_while(|c| c.is_ascii_digit()).count();
+
+        if digits == 0 {
+            return Err(Error::BadTimestamp);
+        }
+
+        frac = rest[..(digits + 1)].parse::<f64>().map_err(|_| Error::BadTimestamp)?;
+        rest = &rest[(digits + 1)..];
+    }
+
+    let offset = match rest {
+        "Z" | "z" => 0,
+        _ if rest.len() == 6 && rest.is_ascii() && rest.as_bytes()[3] == b':' => {
+            let sign = match rest.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return Err(Error::BadTimestamp),
+            };
+
+            let h = &rest[1..3];
+            let m = &rest[4..6];
+
+            if !h.bytes().chain(m.bytes()).all(|c| c.is_ascii_digit()) {
+                return Err(Error::BadTimestamp);
+            }
+
+            // Won't fail: both are two ASCII digits.
+            let h: i64 = h.parse().unwrap_or_else(|_| unreachable!());
+            let m: i64 = m.parse().unwrap_or_else(|_| unreachable!());
+
+            sign * (60 * h + m) * 60
+        },
+        _ => return Err(Error::BadTimestamp),
+    };
+
+    let days = days_from_civil(year, month, day);
+    let seconds = days * 86400 + hour * 3600 + minute * 60 + second - offset;
+
+    Ok(seconds as f64 + frac)
+}
+
+/// Write `t`, in seconds since the Unix epoch, as an RFC 3339 date-time in UTC.
+/// Fractional seconds are written only if present, to millisecond precision.
+pub fn format_rfc3339(t: f64) -> String {
+    let whole = t.floor();
+    let millis = ((t - whole) * 1000.0).round() as i64;
+
+    // Rounding may carry into the next second.
+    let (whole, millis) = if millis == 1000 { (whole as i64 + 1, 0) } else { (whole as i64, millis) };
+
+    let days = whole.div_euclid(86400);
+    let secs = whole.rem_euclid(86400);
+    let (year, month, day) = civil_from_days(days);
+
+    let hms = format!("{:02}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60);
+
+    if millis == 0 {
+        format!("{:04}-{:02}-{:02}T{}Z", year, month, day, hms)
+    } else {
+        format!("{:04}-{:02}-{:02}T{}.{:03}Z", year, month, day, hms, millis)
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Conversions between days since the Unix epoch and proleptic Gregorian dates,
+// after Howard Hinnant's `days_from_civil` and `civil_from_days`.
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146097 + doe - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
@@ -0,0 +1,85 @@
+use error::Error;
+use estimate::{self, Estimate};
+use rng::Rng;
+use summary::Summarizer;
+
+
+/// The deciles, at which a shift function is conventionally evaluated.
+pub const DECILES: [f64; 9] = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9];
+
+/// The difference between two samples at a single quantile, with a bootstrap
+/// confidence interval.
+#[derive(Clone, Copy, Debug)]
+pub struct ShiftPoint {
+    pub quantile: f64,
+    pub difference: f64,
+    pub interval: Estimate<(f64, f64)>,
+}
+
+/// A shift function in the sense of Doksum: the difference between the
+/// quantiles of two samples, as a function of the quantile.
+///
+/// Where one sample's distribution is a translate of the other's, the shift
+/// function is constant. Otherwise, it shows which parts of the distribution
+/// differ, and in what direction.
+#[derive(Debug)]
+pub struct ShiftFunction {
+    pub points: Vec<ShiftPoint>,
+    pub confidence: f64,
+}
+
+impl ShiftFunction {
+    /// Compute the shift function `y - x` at each of `quantiles`.
+    ///
+    /// Confidence intervals at the given `confidence` level are estimated by
+    /// the percentile bootstrap, using `resamples` independent resamplings of
+    /// both `x` and `y` drawn from `rng`.
+    pub fn new(
+        x: &Summarizer,
+        y: &Summarizer,
+        quantiles: &[f64],
+        confidence: f64,
+        resamples: usize,
+        rng: &mut Rng,
+    ) -> Result<Self, Error> {
+        if !(0.0 < confidence && confidence < 1.0) || resamples == 0 {
+            return Err(Error::BadParameter);
+        }
+
+        let differences = quantile_differences(x, y, quantiles)?;
+
+        // Bootstrap differences, one `Vec` per quantile.
+        let mut boot: Vec<Vec<f64>> = quantiles.iter().map(|_| vec![]).collect();
+
+        for _ in 0..resamples {
+            let rx = Summarizer::from_vec(rng.resample(x.as_slice()))?;
+            let ry = Summarizer::from_vec(rng.resample(y.as_slice()))?;
+
+            for (b, d) in boot.iter_mut().zip(quantile_differences(&rx, &ry, quantiles)?) {
+                b.push(d);
+            }
+        }
+
+        let mut points = vec![];
+
+        for ((&quantile, difference), b) in quantiles.iter().zip(differences).zip(boot) {
+            let b = Summarizer::from_vec(b)?;
+
+            points.push(ShiftPoint {
+                quantile,
+                difference,
+                interval: estimate::bootstrap_interval(&b, confidence, resamples)?,
+            });
+        }
+
+        Ok(ShiftFunction { points, confidence })
+    }
+}
+
+fn quantile_differences(x: &Summarizer, y: &Summarizer, quantiles: &[f64])
+                        -> Result<Vec<f64>, Error> {
+    quantiles
+        .iter()
+        .map(|&q| Ok(y.percentile(q)? - x.percentile(q)?))
+        .collect()
+}
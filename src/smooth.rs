@@ -0,0 +1,94 @@
+use error::Error;
+
+
+/// Default fraction of the data used for each local fit, as in R's `loess`.
+pub const DEFAULT_SPAN: f64 = 0.75;
+
+/// Smooth `(x, y)` points by LOESS: at each distinct `x`, fit a line by
+/// weighted least squares to the nearest `span` fraction of the points, and
+/// evaluate it there.
+///
+/// Neighbours are weighted by the tricube function of their distance, scaled
+/// by the distance to the farthest neighbour. Returns the smoothed points
+/// sorted by `x`.
+pub fn loess(data: &[(f64, f64)], span: f64) -> Result<Vec<(f64, f64)>, Error> {
+    if !(0.0 < span && span <= 1.0) {
+        return Err(Error::BadParameter);
+    }
+
+    if data.iter().any(|&(x, y)| !x.is_finite() || !y.is_finite()) {
+        return Err(Error::BadSample);
+    }
+
+    if data.is_empty() {
+        return Err(Error::EmptySample);
+    }
+
+    let mut data = Vec::from(data);
+
+    // Won't panic: we have checked that each float is finite.
+    data.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or_else(|| unreachable!()));
+
+    let n = data.len();
+    let q = ((span * n as f64).ceil() as usize).clamp(2.min(n), n);
+
+    let mut smoothed: Vec<(f64, f64)> = vec![];
+
+    // The `q` nearest neighbours of each `x` are a window of the sorted data,
+    // which only moves right as `x` increases.
+    let mut lo = 0;
+
+    for &(x0, _) in &data {
+        if smoothed.last().is_some_and(|&(x, _)| x == x0) {
+            continue;
+        }
+
+        while lo + q < n && data[lo + q].0 - x0 < x0 - data[lo].0 {
+            lo += 1;
+        }
+
+        let window = &data[lo..(lo + q)];
+        smoothed.push((x0, local_fit(window, x0)));
+    }
+
+    Ok(smoothed)
+}
+
+/// Evaluate at `x0` the tricube-weighted least squares line through `window`.
+fn local_fit(window: &[(f64, f64)], x0: f64) -> f64 {
+    let max_d = window
+        .iter()
+        .map(|&(x, _)| (x - x0).abs())
+        .fold(0.0, f64::max);
+
+    let weights: Vec<f64> = window
+        .iter()
+        .map(|&(x, _)| {
+            if max_d == 0.0 {
+                1.0
+            } else {
+                (1.0 - ((x - x0).abs() / max_d).powi(3)).powi(3)
+            }
+        })
+        .collect();
+
+    let w: f64 = weights.iter().sum();
+
+    // Every neighbour but the farthest has positive weight, so `w > 0`.
+    let mean_x = window.iter().zip(&weights).map(|(p, w)| w * p.0).sum::<f64>() / w;
+    let mean_y = window.iter().zip(&weights).map(|(p, w)| w * p.1).sum::<f64>() / w;
+
+    let sxx: f64 = window.iter().zip(&weights).map(|(p, w)| w * (p.0 - mean_x).powi(2)).sum();
+    let sxy: f64 = window
+        .iter()
+        .zip(&weights)
+        .map(|(p, w)| w * (p.0 - mean_x) * (p.1 - mean_y))
+        .sum();
+
+    // Locally constant if all weighted neighbours share one `x`.
+    if sxx == 0.0 {
+        return mean_y;
+    }
+
+    mean_y + (sxy / sxx) * (x0 - mean_x)
+}
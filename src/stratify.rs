@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use error::Error;
+use meta::{MetaAnalysis, Study};
+use summary::Summary;
+use t_test::{welch_t_test, TTest};
+
+
+/// The comparison of two samples within one stratum.
+pub struct Stratum {
+    pub label: String,
+    pub summaries: (Summary, Summary),
+
+    /// Difference of means, second minus first, and its standard error.
+    pub difference: f64,
+    pub standard_error: f64,
+
+    pub t_test: TTest,
+}
+
+/// The differences of means of each stratum combined by inverse-variance
+/// weighting, as in a fixed-effect meta-analysis.
+#[derive(Clone, Copy, Debug)]
+pub struct Pooled {
+    pub estimate: f64,
+    pub standard_error: f64,
+    pub interval: (f64, f64),
+
+    /// Two-sided p-value of the pooled difference, from the normal
+    /// approximation.
+    pub p: f64,
+
+    /// Higgins' `I²`, the proportion of the variation between strata due to
+    /// heterogeneity rather than chance. When it is large, the strata differ
+    /// by more than a common difference allows, and the pooled estimate
+    /// should be read as an average of differing effects.
+    pub heterogeneity: f64,
+}
+
+/// A comparison of two samples whose values are labeled by stratum, such as
+/// latencies labeled by endpoint, run within each stratum and then pooled.
+///
+/// Comparing aggregates of samples with different mixes of strata can
+/// suggest a difference which holds in no stratum, or even reverse one which
+/// holds in every stratum: Simpson's paradox. Pooling differences within
+/// strata is immune to the mix.
+pub struct StratifiedComparison {
+    /// Strata with at least two values in each sample, by label.
+    pub strata: Vec<Stratum>,
+
+    /// Labels of strata which could not be compared, since they had fewer
+    /// than two values in some sample, or no variation in either.
+    pub skipped: Vec<String>,
+
+    pub pooled: Pooled,
+
+    /// The difference of the means of all values, ignoring strata.
+    pub aggregate: f64,
+
+    pub confidence: f64,
+}
+
+impl StratifiedComparison {
+    /// Compare samples of `(stratum, value)` pairs, with a pooled interval at
+    /// the given confidence level. At least one stratum must be comparable.
+    pub fn new(x: &[(&str, f64)], y: &[(&str, f64)], confidence: f64) -> Result<Self, Error> {
+        if !(0.0 < confidence && confidence < 1.0) {
+            return Err(Error::BadParameter);
+        }
+
+        if x.is_empty() || y.is_empty() {
+            return Err(Error::EmptySample);
+        }
+
+        if x.iter().chain(y).any(|&(_, v)| !v.is_finite()) {
+            return Err(Error::BadSample);
+        }
+
+        let mut groups: BTreeMap<&str, (Vec<f64>, Vec<f64>)> = BTreeMap::new();
+
+        for &(label, v) in x {
+            groups.entry(label).or_default().0.push(v);
+        }
+
+        for &(label, v) in y {
+            groups.entry(label).or_default().1.push(v);
+        }
+
+        let mut strata = vec![];
+        let mut skipped = vec![];
+
+        for (label, (xs, ys)) in groups {
+            if xs.len() < 2 || ys.len() < 2 {
+                skipped.push(label.to_string());
+                continue;
+            }
+
+            let summaries = (Summary::new(&xs)?, Summary::new(&ys)?);
+
+            let standard_error = summaries.0.standard_error().hypot(summaries.1.standard_error());
+
+            if standard_error == 0.0 {
+                skipped.push(label.to_string());
+                continue;
+            }
+
+            strata.push(Stratum {
+                label: label.to_string(),
+                difference: summaries.1.mean() - summaries.0.mean(),
+                standard_error,
+                t_test: welch_t_test(&summaries.0, &summaries.1)?,
+                summaries,
+            });
+        }
+
+        if strata.is_empty() {
+            return Err(Error::Undefined);
+        }
+
+        let pooled = pool(&strata, confidence)?;
+
+        let mean = |s: &[(&str, f64)]| s.iter().map(|&(_, v)| v).sum::<f64>() / s.len() as f64;
+        let aggregate = mean(y) - mean(x);
+
+        Ok(StratifiedComparison { strata, skipped, pooled, aggregate, confidence })
+    }
+
+    /// Whether the aggregate difference and the pooled difference within
+    /// strata have opposite signs, as in Simpson's paradox.
+    pub fn is_reversed(&self) -> bool {
+        self.aggregate * self.pooled.estimate < 0.0
+    }
+}
+
+/// Pool the differences of `strata`, weighting each by its inverse variance.
+fn pool(strata: &[Stratum], confidence: f64) -> Result<Pooled, Error> {
+    let studies = strata
+        .iter()
+        .map(|s| Study::new(&s.label, s.difference, s.standard_error))
+        .collect::<Result<Vec<Study>, Error>>()?;
+
+    let meta = MetaAnalysis::new(studies, confidence)?;
+
+    Ok(Pooled {
+        estimate: meta.fixed.estimate,
+        standard_error: meta.fixed.standard_error,
+        interval: meta.fixed.interval,
+        p: meta.fixed.p,
+        heterogeneity: meta.heterogeneity,
+    })
+}
@@ -1,11 +1,69 @@
 use error::Error;
+use estimate::Method;
+use frequency::FrequencySummarizer;
+use t_test;
+use weighted::WeightedSummarizer;
 
 
+/// How a `Summarizer` treats non-finite values (NaN and infinities).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NonFinitePolicy {
+    /// Fail with `Error::BadSample` if any value is not finite.
+    Reject,
+
+    /// Silently discard non-finite values.
+    Drop,
+
+    /// Discard non-finite values, and keep a `NonFiniteReport` of them.
+    DropWithReport,
+}
+
+/// Counts of the non-finite values discarded from a sample.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NonFiniteReport {
+    pub nan: usize,
+    pub pos_infinite: usize,
+    pub neg_infinite: usize,
+}
+
+impl NonFiniteReport {
+    /// Total number of values discarded.
+    pub fn total(&self) -> usize {
+        self.nan + self.pos_infinite + self.neg_infinite
+    }
+}
+
+/// Structural counts of the values of a sample, which can reveal problems in
+/// how it was collected, such as missing values recorded as zero, or a
+/// counter which saturates, before any statistic is computed.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ValueCounts {
+    pub zeros: usize,
+    pub negatives: usize,
+
+    /// Values equal to some other, earlier value.
+    pub duplicates: usize,
+
+    pub distinct: usize,
+}
+
 /// Wraps a sorted `Vec` of sample data and provides methods for computing
 /// various summary statistics.
 #[derive(Debug)]
 pub struct Summarizer {
     data: Vec<f64>,
+    nonfinite: Option<NonFiniteReport>,
+
+    // Moments, computed once on construction.
+    mean: f64,
+    sum_sq_diff: f64,
+
+    // Quartiles and the adjacent values they bound, which `Summary` and
+    // boxplots need repeatedly, so are also computed once.
+    lower_quartile: f64,
+    upper_quartile: f64,
+    min_adjacent: f64,
+    max_adjacent: f64,
 }
 
 impl Summarizer {
@@ -19,22 +77,107 @@ impl Summarizer {
     ///   - The data are sorted
     ///
     pub fn new(data: &[f64]) -> Result<Self, Error> {
+        Summarizer::new_with_policy(data, NonFinitePolicy::Reject)
+    }
+
+    /// Construct a `Summarizer`, handling non-finite values according to
+    /// `policy`. The guarantees of `new` hold for the retained data; if no
+    /// finite values remain, this fails with `Error::EmptySample`.
+    pub fn new_with_policy(data: &[f64], policy: NonFinitePolicy) -> Result<Self, Error> {
+        Summarizer::from_vec_with_policy(Vec::from(data), policy)
+    }
+
+    /// Construct a `Summarizer` which takes ownership of `data` and sorts it in
+    /// place, rather than copying it. Otherwise, the same as `new`.
+    pub fn from_vec(data: Vec<f64>) -> Result<Self, Error> {
+        Summarizer::from_vec_with_policy(data, NonFinitePolicy::Reject)
+    }
+
+    /// Like `from_vec`, handling non-finite values as in `new_with_policy`.
+    /// Dropped values are removed in place.
+    pub fn from_vec_with_policy(mut data: Vec<f64>, policy: NonFinitePolicy)
+                                -> Result<Self, Error> {
         if data.is_empty() {
             return Err(Error::EmptySample);
         }
 
-        if data.iter().any(|x| !x.is_finite()) {
-            return Err(Error::BadSample);
+        let mut report = NonFiniteReport::default();
+
+        match policy {
+            NonFinitePolicy::Reject => {
+                if data.iter().any(|x| !x.is_finite()) {
+                    return Err(Error::BadSample);
+                }
+            },
+            NonFinitePolicy::Drop | NonFinitePolicy::DropWithReport => {
+                for x in data.iter().filter(|x| !x.is_finite()) {
+                    if x.is_nan() {
+                        report.nan += 1;
+                    } else if x.is_sign_positive() {
+                        report.pos_infinite += 1;
+                    } else {
+                        report.neg_infinite += 1;
+                    }
+                }
+
+                data.retain(|x| x.is_finite());
+            },
         }
 
-        let mut data = Vec::from(data);
+        if data.is_empty() {
+            return Err(Error::EmptySample);
+        }
 
         // Won't panic: we have checked that each float is finite.
         data.sort_by(|a, b| a.partial_cmp(b).unwrap_or_else(|| unreachable!()));
 
-        let s = Summarizer { data };
+        let nonfinite = if policy == NonFinitePolicy::DropWithReport {
+            Some(report)
+        } else {
+            None
+        };
+
+        Ok(Summarizer::from_sorted(data, nonfinite))
+    }
+
+    /// Wrap sorted, finite, nonempty sample data, computing its moments,
+    /// quartiles, and adjacent values up front. Only the moments need a pass
+    /// over the data, since the data are sorted.
+    fn from_sorted(data: Vec<f64>, nonfinite: Option<NonFiniteReport>) -> Self {
+        let (t, sum_sq_diff) = sum_and_sum_sq_diff(&data);
+        let mean = t / data.len() as f64;
+
+        // Statically known to be defined.
+        let quartile = |p| percentile_of_sorted(&data, p).unwrap_or_else(|_| unreachable!());
+        let lower_quartile = quartile(0.25);
+        let upper_quartile = quartile(0.75);
+
+        let iqr = upper_quartile - lower_quartile;
+        let lower_outlier_bound = lower_quartile - 1.5 * iqr;
+        let upper_outlier_bound = upper_quartile + 1.5 * iqr;
+
+        // The data are sorted, so we can binary search for the least and
+        // greatest values within the bounds. They exist by definition of
+        // quartile.
+        let min_adjacent = data[data.partition_point(|&x| x < lower_outlier_bound)];
+        let max_adjacent = data[data.partition_point(|&x| x <= upper_outlier_bound) - 1];
 
-        Ok(s)
+        Summarizer {
+            data,
+            nonfinite,
+            mean,
+            sum_sq_diff,
+            lower_quartile,
+            upper_quartile,
+            min_adjacent,
+            max_adjacent,
+        }
+    }
+
+    /// Counts of the non-finite values discarded during construction. Only
+    /// available when constructed with `NonFinitePolicy::DropWithReport`.
+    pub fn nonfinite_report(&self) -> Option<NonFiniteReport> {
+        self.nonfinite
     }
 
     /// Get a shared reference to owned copy of sorted sample data.
@@ -42,6 +185,18 @@ impl Summarizer {
         self.data.as_slice()
     }
 
+    /// Count the zeros, negative values, duplicates, and distinct values of
+    /// the sample. Since the data are sorted, only the distinct values need a
+    /// pass over it.
+    pub fn value_counts(&self) -> ValueCounts {
+        let negatives = self.data.partition_point(|&x| x < 0.0);
+        let zeros = self.data.partition_point(|&x| x <= 0.0) - negatives;
+
+        let distinct = 1 + self.data.windows(2).filter(|w| w[0] != w[1]).count();
+
+        ValueCounts { zeros, negatives, duplicates: self.data.len() - distinct, distinct }
+    }
+
     /// Size of the sample data as a floating point value.
     pub fn size(&self) -> f64 {
         self.data.len() as f64
@@ -54,8 +209,7 @@ impl Summarizer {
 
     /// The 25th percentile.
     pub fn lower_quartile(&self) -> f64 {
-        // Statically known to be defined.
-        self.percentile(0.25).unwrap_or_else(|_| unreachable!())
+        self.lower_quartile
     }
 
     /// The minimum value in the data set.
@@ -65,13 +219,7 @@ impl Summarizer {
 
     /// The minimum non-outlier value in the data set.
     pub fn min_adjacent(&self) -> f64 {
-        let lower_outlier_bound = self.lower_quartile() - 1.5 * self.iqr();
-
-        self.data
-            .iter()
-            .cloned()
-            .find(|&x| lower_outlier_bound <= x)
-            .unwrap_or_else(|| unreachable!())  // By definition of quartile.
+        self.min_adjacent
     }
 
     /// The maximum value in the data set.
@@ -81,21 +229,12 @@ impl Summarizer {
 
     /// The maximum non-outlier value in the data set.
     pub fn max_adjacent(&self) -> f64 {
-        let upper_outlier_bound = self.upper_quartile() + 1.5 * self.iqr();
-
-        self.data
-            .iter()
-            .cloned()
-            .rev()
-            .find(|&x| x <= upper_outlier_bound)
-            .unwrap_or_else(|| unreachable!())  // By definition of quartile.
+        self.max_adjacent
     }
 
     /// The arithmetic sample mean.
     pub fn mean(&self) -> f64 {
-        let t: f64 = self.data.iter().sum();
-
-        t / self.size()
+        self.mean
     }
 
     /// The 50th percentile.
@@ -118,28 +257,50 @@ impl Summarizer {
     /// common statistics packages. In particular, our implementation guarantees that the
     /// boundary percentiles correspond to the sample min and max.
     pub fn percentile(&self, p: f64) -> Result<f64, Error> {
-        if !p.is_finite() { return Err(Error::Undefined); }
-        if p < 0.0 || 1.0 < p {
-            return Err(Error::Undefined);
-        }
+        percentile_of_sorted(&self.data, p)
+    }
 
-        let rank = (self.size() - 1.0) * p;
-        let frac = rank.fract();
+    /// The fractional, 0-based rank of `x` among the sorted values, the
+    /// inverse of `percentile` on the scale of ranks. Between two values, it
+    /// is linearly interpolated, and a value of the sample takes the mean rank
+    /// of the values equal to it. Values outside the sample take the rank of
+    /// the nearest end.
+    pub fn rank_of(&self, x: f64) -> Result<f64, Error> {
+        if x.is_nan() { return Err(Error::Undefined); }
 
-        let i = rank.floor() as usize;
-        let j = i + 1;
+        let data = &self.data;
+        let below = data.partition_point(|&v| v < x);
+        let not_above = data.partition_point(|&v| v <= x);
 
-        if j == self.data.len() {
-            // This implies that `i` indexes the largest data point in the sample.
-            // Dereferencing at `j` would be an error, but `i` is exactly the max.
-            return Ok(self.data[i]);
+        if below < not_above {
+            return Ok(0.5 * (below + not_above - 1) as f64);
         }
 
-        let xi = self.data[i];
-        let xj = self.data[j];
-        let x = xi + frac * (xj - xi);
+        if below == 0 {
+            return Ok(0.0);
+        }
 
-        Ok(x)
+        if below == data.len() {
+            return Ok((data.len() - 1) as f64);
+        }
+
+        let (xi, xj) = (data[below - 1], data[below]);
+
+        Ok((below - 1) as f64 + (x - xi) / (xj - xi))
+    }
+
+    /// The fraction of the sample at or below `x`, interpolated between values
+    /// as by `rank_of`, so that `cdf_at(percentile(p)) == p` for distinct
+    /// values. It is 0 at the minimum and below, and 1 at the maximum and
+    /// above, unless there is only one value, at which it is 0.5.
+    pub fn cdf_at(&self, x: f64) -> Result<f64, Error> {
+        let rank = self.rank_of(x)?;
+
+        if self.data.len() == 1 {
+            return Ok(if x < self.data[0] { 0.0 } else if x > self.data[0] { 1.0 } else { 0.5 });
+        }
+
+        Ok(rank / (self.data.len() - 1) as f64)
     }
 
     /// The difference between the minimum and maximum value.
@@ -149,8 +310,7 @@ impl Summarizer {
 
     /// The 75th percentile.
     pub fn upper_quartile(&self) -> f64 {
-        // Statically known to be defined.
-        self.percentile(0.75).unwrap_or_else(|_| unreachable!())
+        self.upper_quartile
     }
 
     /// Sample variance.
@@ -158,13 +318,7 @@ impl Summarizer {
     /// Computed using Bessel's correction to provide an unbiased estimate of
     /// population variance.
     pub fn unbiased_variance(&self) -> f64 {
-        let m = self.mean();
-        let sum_sq_diff: f64 = self.data
-            .iter()
-            .map(|x| (x - m).powi(2))
-            .sum();
-
-        (1.0 / (self.size() - 1.0)) * sum_sq_diff
+        (1.0 / (self.size() - 1.0)) * self.sum_sq_diff
     }
 
     /// Standard deviation of the sample.
@@ -176,13 +330,622 @@ impl Summarizer {
     pub fn standard_error(&self) -> f64 {
         self.standard_deviation() / self.size().sqrt()
     }
+
+    /// Median absolute deviation from the median, a robust measure of spread.
+    /// Unscaled: multiply by about 1.4826 to estimate the standard deviation
+    /// of normal data.
+    pub fn median_absolute_deviation(&self) -> f64 {
+        let m = self.median();
+        let mut deviations: Vec<f64> = self.data.iter().map(|x| (x - m).abs()).collect();
+
+        // Statically known to be defined: deviations of finite data are finite.
+        percentile_of_unsorted(&mut deviations, 0.5).unwrap_or_else(|_| unreachable!())
+    }
+
+    /// Two-sided confidence interval for the population mean, at the given
+    /// `confidence` level (e.g. 0.95), using Student's t-distribution.
+    ///
+    /// Undefined for samples with fewer than two points.
+    pub fn mean_confidence_interval(&self, confidence: f64) -> Result<(f64, f64), Error> {
+        mean_confidence_interval(self.mean(), self.standard_error(), self.size(), confidence)
+    }
+}
+
+/// The `p`th percentile of unsorted sample data, as `Summarizer::percentile`
+/// would compute it, but without sorting.
+///
+/// Uses selection, so takes linear time on average, and partially reorders
+/// `data` in place. For one or two order statistics of a large sample, this
+/// is much cheaper than constructing a `Summarizer`.
+pub fn percentile_of_unsorted(data: &mut [f64], p: f64) -> Result<f64, Error> {
+    if data.is_empty() {
+        return Err(Error::EmptySample);
+    }
+
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(Error::BadSample);
+    }
+
+    if !p.is_finite() { return Err(Error::Undefined); }
+    if !(0.0..=1.0).contains(&p) {
+        return Err(Error::Undefined);
+    }
+
+    let rank = (data.len() - 1) as f64 * p;
+    let frac = rank.fract();
+    let i = rank.floor() as usize;
+
+    // Won't panic: we have checked that each float is finite.
+    let (_, &mut xi, above) = data.select_nth_unstable_by(i, |a, b| {
+        a.partial_cmp(b).unwrap_or_else(|| unreachable!())
+    });
+
+    if above.is_empty() {
+        return Ok(xi);
+    }
+
+    // The next order statistic is the least value above the `i`th.
+    let xj = above.iter().cloned().fold(f64::INFINITY, f64::min);
+
+    Ok(xi + frac * (xj - xi))
+}
+
+/// The percentile `p` of sorted, nonempty `data`, as in
+/// `Summarizer::percentile`.
+fn percentile_of_sorted(data: &[f64], p: f64) -> Result<f64, Error> {
+    if !p.is_finite() { return Err(Error::Undefined); }
+    if p < 0.0 || 1.0 < p {
+        return Err(Error::Undefined);
+    }
+
+    let rank = (data.len() - 1) as f64 * p;
+    let frac = rank.fract();
+
+    let i = rank.floor() as usize;
+    let j = i + 1;
+
+    if j == data.len() {
+        // This implies that `i` indexes the largest data point in the sample.
+        // Dereferencing at `j` would be an error, but `i` is exactly the max.
+        return Ok(data[i]);
+    }
+
+    let xi = data[i];
+    let xj = data[j];
+    let x = xi + frac * (xj - xi);
+
+    Ok(x)
+}
+
+/// The sum of sorted, nonempty `data` and the sum of squared deviations
+/// from its mean, in one pass.
+///
+/// Deviations are accumulated by Welford's algorithm [1], which avoids the
+/// catastrophic cancellation of accumulating a sum of squares. The data are
+/// first shifted by their middle value, which is close to the mean since the
+/// data are sorted, so that the running mean stays small even when the mean
+/// of the data is large relative to their spread.
+///
+/// [1]: B. P. Welford, "Note on a Method for Calculating Corrected Sums of
+///      Squares and Products", Technometrics 4(3), 1962.
+fn sum_and_sum_sq_diff(data: &[f64]) -> (f64, f64) {
+    let shift = data[data.len() / 2];
+
+    let mut sum = 0.0;
+    let mut mean = 0.0;
+    let mut sum_sq_diff = 0.0;
+
+    for (i, &x) in data.iter().enumerate() {
+        sum += x;
+
+        let d = (x - shift) - mean;
+        mean += d / (i + 1) as f64;
+        sum_sq_diff += d * ((x - shift) - mean);
+    }
+
+    (sum, sum_sq_diff)
+}
+
+fn mean_confidence_interval(mean: f64, se: f64, n: f64, confidence: f64)
+                            -> Result<(f64, f64), Error> {
+    if n < 2.0 {
+        return Err(Error::Undefined);
+    }
+
+    let t = t_test::t_critical_value(confidence, n - 1.0)?;
+    let half_width = t * se;
+
+    Ok((mean - half_width, mean + half_width))
+}
+
+/// Summarizes sample data given one value at a time, in constant memory, so
+/// that inputs too large to hold can be summarized in one pass.
+///
+/// The moments are exact, accumulated by Welford's algorithm, but the data
+/// are not kept, so the quartiles are estimates, by the P² algorithm [1].
+/// These are close for large samples of any smooth distribution, and exact
+/// for at most five values. Non-finite values are handled as by `Summarizer`
+/// under the policy given on construction.
+///
+/// [1]: R. Jain and I. Chlamtac, "The P² algorithm for dynamic calculation of
+///      quantiles and histograms without storing observations", Commun. ACM
+///      28(10), 1985.
+#[derive(Clone, Debug)]
+pub struct StreamingSummarizer {
+    policy: NonFinitePolicy,
+    nonfinite: NonFiniteReport,
+
+    len: usize,
+    min: f64,
+    max: f64,
+
+    // The first value, by which the rest are shifted, so that the running
+    // mean stays small even when the mean of the data is large relative to
+    // their spread.
+    shift: f64,
+    shifted_mean: f64,
+    sum_sq_diff: f64,
+
+    quartiles: [P2Quantile; 3],
+}
+
+impl Default for StreamingSummarizer {
+    fn default() -> Self {
+        StreamingSummarizer::new()
+    }
+}
+
+impl StreamingSummarizer {
+    /// Construct an empty summarizer, which rejects non-finite values.
+    pub fn new() -> Self {
+        StreamingSummarizer::with_policy(NonFinitePolicy::Reject)
+    }
+
+    /// Construct an empty summarizer, which handles non-finite values
+    /// according to `policy`.
+    pub fn with_policy(policy: NonFinitePolicy) -> Self {
+        StreamingSummarizer {
+            policy,
+            nonfinite: NonFiniteReport::default(),
+            len: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            shift: 0.0,
+            shifted_mean: 0.0,
+            sum_sq_diff: 0.0,
+            quartiles: [P2Quantile::new(0.25), P2Quantile::new(0.5), P2Quantile::new(0.75)],
+        }
+    }
+
+    /// Add a value to the sample.
+    pub fn push(&mut self, x: f64) {
+        if !x.is_finite() {
+            if x.is_nan() {
+                self.nonfinite.nan += 1;
+            } else if x.is_sign_positive() {
+                self.nonfinite.pos_infinite += 1;
+            } else {
+                self.nonfinite.neg_infinite += 1;
+            }
+
+            return;
+        }
+
+        if self.len == 0 {
+            self.shift = x;
+        }
+
+        self.len += 1;
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+
+        let d = (x - self.shift) - self.shifted_mean;
+        self.shifted_mean += d / self.len as f64;
+        self.sum_sq_diff += d * ((x - self.shift) - self.shifted_mean);
+
+        for q in &mut self.quartiles {
+            q.push(x);
+        }
+    }
+
+    /// Counts of the non-finite values pushed, which are not in the sample.
+    /// Only available under `NonFinitePolicy::DropWithReport`.
+    pub fn nonfinite_report(&self) -> Option<NonFiniteReport> {
+        if self.policy == NonFinitePolicy::DropWithReport {
+            Some(self.nonfinite)
+        } else {
+            None
+        }
+    }
+
+    /// Summarize the values pushed so far, marked as estimated by
+    /// `Method::Sketch`. Fails with `Error::EmptySample` if there are none,
+    /// and with `Error::BadSample` if any was not finite and the policy
+    /// rejects such values.
+    pub fn summary(&self) -> Result<Summary, Error> {
+        if self.policy == NonFinitePolicy::Reject && self.nonfinite.total() > 0 {
+            return Err(Error::BadSample);
+        }
+
+        if self.len == 0 {
+            return Err(Error::EmptySample);
+        }
+
+        let n = self.len as f64;
+        let mean = self.shift + self.shifted_mean;
+        let unbiased_variance = self.sum_sq_diff / (n - 1.0);
+        let standard_deviation = unbiased_variance.sqrt();
+
+        // Independent estimates may cross by a little, so they are put in
+        // order, within the range of the data.
+        let estimate = |i: usize| self.quartiles[i].estimate().max(self.min).min(self.max);
+        let median = estimate(1);
+        let lower_quartile = estimate(0).min(median);
+        let upper_quartile = estimate(2).max(median);
+        let iqr = upper_quartile - lower_quartile;
+
+        // Without the data, the adjacent values are taken to be the outlier
+        // bounds, unless the data do not reach them.
+        let min_adjacent = (lower_quartile - 1.5 * iqr).max(self.min);
+        let max_adjacent = (upper_quartile + 1.5 * iqr).min(self.max);
+
+        Ok(Summary {
+            iqr,
+            len: self.len,
+            lower_quartile,
+            min: self.min,
+            min_adjacent,
+            max: self.max,
+            max_adjacent,
+            mean: mean.max(self.min).min(self.max),
+            median,
+            range: self.max - self.min,
+            standard_deviation,
+            standard_error: standard_deviation / n.sqrt(),
+            unbiased_variance,
+            upper_quartile,
+            percentiles: None,
+            unit: None,
+            method: Method::Sketch,
+        })
+    }
+}
+
+/// A P² estimate of one quantile of a stream of values, from five markers:
+/// the least and greatest values, the quantile, and the quantiles halfway
+/// from it to either end.
+#[derive(Clone, Debug)]
+struct P2Quantile {
+    p: f64,
+    len: usize,
+
+    // The height of each marker, and its actual and desired 1-based
+    // positions among the values, and how far its desired position moves
+    // with each value.
+    heights: [f64; 5],
+    positions: [f64; 5],
+    desired: [f64; 5],
+    increments: [f64; 5],
+}
+
+impl P2Quantile {
+    fn new(p: f64) -> Self {
+        P2Quantile {
+            p,
+            len: 0,
+            heights: [0.0; 5],
+            positions: [1.0, 2.0, 3.0, 4.0, 5.0],
+            desired: [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0],
+            increments: [0.0, 0.5 * p, p, 0.5 * (1.0 + p), 1.0],
+        }
+    }
+
+    fn push(&mut self, x: f64) {
+        let q = &mut self.heights;
+
+        // The first five values are the initial heights, in order.
+        if self.len < 5 {
+            q[self.len] = x;
+            self.len += 1;
+            q[..self.len].sort_by(|a, b| a.total_cmp(b));
+            return;
+        }
+
+        self.len += 1;
+
+        // The cell `k` between markers `k` and `k + 1` in which `x` falls,
+        // extending the extreme markers to hold it if need be.
+        let k = if x < q[0] {
+            q[0] = x;
+            0
+        } else if x >= q[4] {
+            q[4] = x;
+            3
+        } else {
+            (1..4).find(|&i| x < q[i]).map_or(3, |i| i - 1)
+        };
+
+        for i in k + 1..5 {
+            self.positions[i] += 1.0;
+        }
+
+        for i in 0..5 {
+            self.desired[i] += self.increments[i];
+        }
+
+        let n = &mut self.positions;
+
+        // Move each middle marker toward its desired position, if it is off
+        // by a position or more, and can move without meeting a neighbor.
+        for i in 1..4 {
+            let d = self.desired[i] - n[i];
+
+            if (d >= 1.0 && n[i + 1] - n[i] > 1.0) || (d <= -1.0 && n[i - 1] - n[i] < -1.0) {
+                let d = d.signum();
+
+                let parabolic = q[i] + d / (n[i + 1] - n[i - 1]) * (
+                    (n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                        + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1])
+                );
+
+                q[i] = if q[i - 1] < parabolic && parabolic < q[i + 1] {
+                    parabolic
+                } else {
+                    let j = if d > 0.0 { i + 1 } else { i - 1 };
+                    q[i] + d * (q[j] - q[i]) / (n[j] - n[i])
+                };
+
+                n[i] += d;
+            }
+        }
+    }
+
+    /// The estimate of the quantile, which is exact for at most five values.
+    fn estimate(&self) -> f64 {
+        if self.len <= 5 {
+            // Defined once there is a value, and NaN before.
+            percentile_of_sorted(&self.heights[..self.len], self.p).unwrap_or(f64::NAN)
+        } else {
+            self.heights[2]
+        }
+    }
+}
+
+/// A snapshot of a fixed set of percentiles of some sample data, which can
+/// answer percentile queries after the data itself has been discarded.
+#[derive(Clone, Debug)]
+pub struct Percentiles {
+    // Pairs `(p, x)` of a percentile and its value, sorted and deduplicated
+    // by `p`.
+    points: Vec<(f64, f64)>,
+}
+
+impl Percentiles {
+    /// Record the percentiles `ps` of the sample data in `s`. Each element of
+    /// `ps` must be in [0, 1], and at least one must be given.
+    pub fn new(s: &Summarizer, ps: &[f64]) -> Result<Self, Error> {
+        if ps.is_empty() {
+            return Err(Error::BadParameter);
+        }
+
+        let mut points = ps
+            .iter()
+            .map(|&p| Ok((p, s.percentile(p)?)))
+            .collect::<Result<Vec<(f64, f64)>, Error>>()?;
+
+        // Won't panic: `percentile` has rejected any non-finite `p`.
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or_else(|| unreachable!()));
+        points.dedup_by(|a, b| a.0 == b.0);
+
+        Ok(Percentiles { points })
+    }
+
+    /// The `n + 1` evenly spaced percentiles `0, 1/n, ..., 1`. For example,
+    /// `evenly_spaced(100)` gives every 1%.
+    pub fn evenly_spaced(n: usize) -> Vec<f64> {
+        (0..=n).map(|i| i as f64 / n as f64).collect()
+    }
+
+    /// The recorded `(p, x)` pairs, sorted by `p`.
+    pub fn as_slice(&self) -> &[(f64, f64)] {
+        self.points.as_slice()
+    }
+
+    /// The value of the `p`th percentile. Exact if `p` was recorded, and
+    /// otherwise linearly interpolated between the nearest recorded
+    /// percentiles. Undefined outside the range of those recorded.
+    pub fn percentile(&self, p: f64) -> Result<f64, Error> {
+        if !p.is_finite() { return Err(Error::Undefined); }
+
+        let j = match self.points.iter().position(|&(q, _)| p <= q) {
+            Some(j) => j,
+            None => return Err(Error::Undefined),
+        };
+
+        let (pj, xj) = self.points[j];
+
+        if p == pj {
+            return Ok(xj);
+        }
+
+        if j == 0 {
+            return Err(Error::Undefined);
+        }
+
+        let (pi, xi) = self.points[j - 1];
+
+        Ok(xi + (p - pi) / (pj - pi) * (xj - xi))
+    }
+}
+
+/// Columns of a summary table, one `Summary` per row, as read and written by
+/// `Summary::from_tsv_record` and `Summary::to_tsv_record`.
+pub const TSV_COLUMNS: [&str; 15] = [
+    "Source",
+    "Size",
+    "Mean",
+    "Median",
+    "StandardDeviation",
+    "Variance",
+    "StandardError",
+    "Min",
+    "Max",
+    "Range",
+    "LowerQuartile",
+    "UpperQuartile",
+    "IQR",
+    "MinAdjacent",
+    "MaxAdjacent",
+];
+
+/// An optional last column of a summary table, naming the unit of each
+/// summary, if any, as given by `Summary::unit`.
+pub const TSV_UNIT_COLUMN: &str = "Unit";
+
+/// An optional column after `TSV_UNIT_COLUMN`, labeling how each summary was
+/// computed, as by `Method::label`, so that approximate summaries are marked.
+pub const TSV_METHOD_COLUMN: &str = "Method";
+
+/// Parse a summary table: a header row of `TSV_COLUMNS`, optionally followed
+/// by `TSV_UNIT_COLUMN` and then `TSV_METHOD_COLUMN`, and then one record per
+/// `Summary`. Returns the source and summary of each record.
+pub fn parse_summary_table(table: &str) -> Result<Vec<(String, Summary)>, Error> {
+    let mut lines = table.lines().filter(|l| !l.trim().is_empty());
+
+    let header: Option<Vec<&str>> = lines.next().map(|h| h.trim_end().split('\t').collect());
+
+    match header {
+        Some(ref h) if h[..] == TSV_COLUMNS[..] => {},
+        Some(ref h) if h.split_last() == Some((&TSV_UNIT_COLUMN, &TSV_COLUMNS[..])) => {},
+        Some(ref h) if h.len() == TSV_COLUMNS.len() + 2
+            && h[..TSV_COLUMNS.len()] == TSV_COLUMNS[..]
+            && h[TSV_COLUMNS.len()..] == [TSV_UNIT_COLUMN, TSV_METHOD_COLUMN] => {},
+        _ => return Err(Error::BadRecord),
+    }
+
+    let records = lines
+        .map(Summary::from_tsv_record)
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    if records.is_empty() {
+        return Err(Error::EmptySample);
+    }
+
+    Ok(records)
+}
+
+/// Prefix of the name of each metric written by `to_prometheus`.
+pub const METRIC_PREFIX: &str = "dent_";
+
+/// A statistic of a summary.
+type Statistic = fn(&Summary) -> f64;
+
+/// Whether a statistic of a summary is approximate.
+type Approximate = fn(&Summary) -> bool;
+
+/// The size of a summary, which is never approximate.
+fn size_is_approximate(_: &Summary) -> bool {
+    false
+}
+
+/// The statistics written by `to_prometheus`, as metric names without
+/// `METRIC_PREFIX`, with their help text, and whether they are approximate.
+const METRICS: [(&str, &str, Statistic, Approximate); 7] = [
+    ("size", "Number of values in the sample.", Summary::size, size_is_approximate),
+    ("mean", "Mean of the sample.", Summary::mean, Summary::moments_are_approximate),
+    ("median", "Median of the sample.", Summary::median, Summary::quantiles_are_approximate),
+    (
+        "stddev",
+        "Standard deviation of the sample.",
+        Summary::standard_deviation,
+        Summary::moments_are_approximate,
+    ),
+    (
+        "stderr",
+        "Standard error of the mean of the sample.",
+        Summary::standard_error,
+        Summary::moments_are_approximate,
+    ),
+    ("min", "Least value of the sample.", Summary::min, Summary::moments_are_approximate),
+    ("max", "Greatest value of the sample.", Summary::max, Summary::moments_are_approximate),
+];
+
+/// The labels of a series of `s` from `source`, with its unit if it has one,
+/// and its method if `approximate`.
+fn metric_labels(s: &Summary, source: &str, approximate: bool) -> String {
+    let mut labels = format!("source=\"{}\"", label_value(source));
+
+    if let Some(u) = s.unit() {
+        labels += &format!(",unit=\"{}\"", label_value(u));
+    }
+
+    if approximate {
+        labels += &format!(",method=\"{}\"", label_value(&s.method().label()));
+    }
+
+    labels
+}
+
+/// Write summaries as gauges in the Prometheus text exposition format, each
+/// labeled with its source, and with its unit if it has one. Quartiles are
+/// written as a `quantile` gauge, labeled with their quantile. Approximate
+/// values are labeled with the `method` by which they were estimated.
+pub fn to_prometheus(summaries: &[Summary], sources: &[&str]) -> String {
+    let mut out = String::new();
+
+    for &(name, help, statistic, approximate) in &METRICS {
+        out += &format!("# HELP {}{} {}\n", METRIC_PREFIX, name, help);
+        out += &format!("# TYPE {}{} gauge\n", METRIC_PREFIX, name);
+
+        for (s, src) in summaries.iter().zip(sources) {
+            let l = metric_labels(s, src, approximate(s));
+
+            out += &format!("{}{}{{{}}} {}\n", METRIC_PREFIX, name, l, metric_value(statistic(s)));
+        }
+    }
+
+    out += &format!("# HELP {}quantile Quartiles of the sample.\n", METRIC_PREFIX);
+    out += &format!("# TYPE {}quantile gauge\n", METRIC_PREFIX);
+
+    for (s, src) in summaries.iter().zip(sources) {
+        let l = metric_labels(s, src, s.quantiles_are_approximate());
+        let quartiles = [(0.25, s.lower_quartile()), (0.5, s.median()), (0.75, s.upper_quartile())];
+
+        for &(q, value) in &quartiles {
+            out += &format!(
+                "{}quantile{{{},quantile=\"{}\"}} {}\n",
+                METRIC_PREFIX,
+                l,
+                q,
+                metric_value(value),
+            );
+        }
+    }
+
+    out
+}
+
+/// Escape a label value of the Prometheus text format.
+pub(crate) fn label_value(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// A sample value of the Prometheus text format, which spells infinities as
+/// `+Inf` and `-Inf`.
+pub(crate) fn metric_value(x: f64) -> String {
+    match x {
+        x if x == f64::INFINITY => "+Inf".to_string(),
+        x if x == f64::NEG_INFINITY => "-Inf".to_string(),
+        x => format!("{}", x),
+    }
 }
 
 /// Like a static `Summarizer`, with all fields computed upon initialization.
 ///
 /// Does not retain a sorted copy of the sample data, and so cannot compute
-/// arbitrary percentiles. For descriptions of individual methods, see the
-/// `Summarizer` documentation.
+/// arbitrary percentiles, unless constructed with a `Percentiles` snapshot
+/// via `new_with_percentiles`. For descriptions of individual methods, see
+/// the `Summarizer` documentation.
 #[derive(Debug)]
 pub struct Summary {
     iqr: f64,
@@ -199,6 +962,9 @@ pub struct Summary {
     standard_error: f64,
     unbiased_variance: f64,
     upper_quartile: f64,
+    percentiles: Option<Percentiles>,
+    unit: Option<String>,
+    method: Method,
 }
 
 impl Summary {
@@ -214,7 +980,22 @@ impl Summary {
     pub fn new(data: &[f64]) -> Result<Self, Error> {
         let s = Summarizer::new(data)?;
 
-        Ok(Summary {
+        Ok(Summary::from_summarizer(&s))
+    }
+
+    /// Construct a `Summary` which also records the percentiles `ps` of the
+    /// sample data, so that `percentile` can answer for them, and approximate
+    /// others between them. See `Percentiles::evenly_spaced`.
+    pub fn new_with_percentiles(data: &[f64], ps: &[f64]) -> Result<Self, Error> {
+        let s = Summarizer::new(data)?;
+        let percentiles = Percentiles::new(&s, ps)?;
+
+        Ok(Summary { percentiles: Some(percentiles), ..Summary::from_summarizer(&s) })
+    }
+
+    /// Construct a `Summary` from the sample data of an existing `Summarizer`.
+    pub fn from_summarizer(s: &Summarizer) -> Self {
+        Summary {
             iqr: s.iqr(),
             len: s.data.len(),
             lower_quartile: s.lower_quartile(),
@@ -229,7 +1010,59 @@ impl Summary {
             unbiased_variance: s.unbiased_variance(),
             standard_deviation: s.standard_deviation(),
             standard_error: s.standard_error(),
-        })
+            percentiles: None,
+            unit: None,
+            method: Method::Exact,
+        }
+    }
+
+    /// Construct a `Summary` of weighted sample data. The size is the number
+    /// of observations with positive weight, and every other statistic is its
+    /// weighted analogue.
+    pub fn from_weighted(s: &WeightedSummarizer) -> Self {
+        Summary {
+            iqr: s.iqr(),
+            len: s.size() as usize,
+            lower_quartile: s.lower_quartile(),
+            min: s.min(),
+            min_adjacent: s.min_adjacent(),
+            max: s.max(),
+            max_adjacent: s.max_adjacent(),
+            mean: s.mean(),
+            median: s.median(),
+            range: s.range(),
+            upper_quartile: s.upper_quartile(),
+            unbiased_variance: s.unbiased_variance(),
+            standard_deviation: s.standard_deviation(),
+            standard_error: s.standard_error(),
+            percentiles: None,
+            unit: None,
+            method: Method::Exact,
+        }
+    }
+
+    /// Construct a `Summary` of sample data given as value frequencies. The
+    /// result is that of the expanded sample.
+    pub fn from_frequencies(s: &FrequencySummarizer) -> Self {
+        Summary {
+            iqr: s.iqr(),
+            len: s.size() as usize,
+            lower_quartile: s.lower_quartile(),
+            min: s.min(),
+            min_adjacent: s.min_adjacent(),
+            max: s.max(),
+            max_adjacent: s.max_adjacent(),
+            mean: s.mean(),
+            median: s.median(),
+            range: s.range(),
+            upper_quartile: s.upper_quartile(),
+            unbiased_variance: s.unbiased_variance(),
+            standard_deviation: s.standard_deviation(),
+            standard_error: s.standard_error(),
+            percentiles: None,
+            unit: None,
+            method: Method::Exact,
+        }
     }
 
     pub fn size(&self) -> f64 {
@@ -287,4 +1120,221 @@ impl Summary {
     pub fn standard_error(&self) -> f64 {
         self.standard_error
     }
+
+    pub fn mean_confidence_interval(&self, confidence: f64) -> Result<(f64, f64), Error> {
+        mean_confidence_interval(self.mean, self.standard_error, self.size(), confidence)
+    }
+
+    /// Format as a tab-separated record with the columns `TSV_COLUMNS`. Values
+    /// are written with enough precision to be read back exactly.
+    pub fn to_tsv_record(&self, source: &str) -> String {
+        let fields: Vec<String> = [
+            self.size(),
+            self.mean,
+            self.median,
+            self.standard_deviation,
+            self.unbiased_variance,
+            self.standard_error,
+            self.min,
+            self.max,
+            self.range,
+            self.lower_quartile,
+            self.upper_quartile,
+            self.iqr,
+            self.min_adjacent,
+            self.max_adjacent,
+        ].iter().map(|x| format!("{}", x)).collect();
+
+        format!("{}\t{}", source, fields.join("\t"))
+    }
+
+    /// Like `to_tsv_record`, followed by the `TSV_UNIT_COLUMN`, which is empty
+    /// if there is no unit.
+    pub fn to_tsv_record_with_unit(&self, source: &str) -> String {
+        format!("{}\t{}", self.to_tsv_record(source), self.unit().unwrap_or(""))
+    }
+
+    /// Like `to_tsv_record_with_unit`, followed by the `TSV_METHOD_COLUMN`.
+    pub fn to_tsv_record_with_method(&self, source: &str) -> String {
+        format!("{}\t{}", self.to_tsv_record_with_unit(source), self.method.label())
+    }
+
+    /// Parse a record written by `to_tsv_record`, `to_tsv_record_with_unit`,
+    /// or `to_tsv_record_with_method`, returning its source and summary, with
+    /// its unit if it has a nonempty `TSV_UNIT_COLUMN`, and its method if it
+    /// has a `TSV_METHOD_COLUMN`. Fails with `Error::BadRecord` if the record is
+    /// malformed, and with `Error::InvalidSummary` if its statistics are
+    /// inconsistent (see `debug_validate`).
+    pub fn from_tsv_record(record: &str) -> Result<(String, Self), Error> {
+        let mut fields: Vec<&str> = record.trim_end_matches(['\r', '\n']).split('\t').collect();
+
+        let method = match fields.len() {
+            n if n == TSV_COLUMNS.len() + 2 => match fields.pop() {
+                Some(m) => Method::parse_label(m.trim())?,
+                None => unreachable!(),
+            },
+            _ => Method::Exact,
+        };
+
+        let unit = match fields.len() {
+            n if n == TSV_COLUMNS.len() => None,
+            n if n == TSV_COLUMNS.len() + 1 => fields.pop().map(|u| u.trim()),
+            _ => return Err(Error::BadRecord),
+        };
+        let unit = unit.filter(|u| !u.is_empty()).map(|u| u.to_string());
+
+        let values = fields[1..]
+            .iter()
+            .map(|f| f.trim().parse::<f64>().map_err(|_| Error::BadRecord))
+            .collect::<Result<Vec<f64>, Error>>()?;
+
+        let size = values[0];
+        if !(size >= 1.0 && size.fract() == 0.0) {
+            return Err(Error::BadRecord);
+        }
+
+        let summary = Summary {
+            len: size as usize,
+            mean: values[1],
+            median: values[2],
+            standard_deviation: values[3],
+            unbiased_variance: values[4],
+            standard_error: values[5],
+            min: values[6],
+            max: values[7],
+            range: values[8],
+            lower_quartile: values[9],
+            upper_quartile: values[10],
+            iqr: values[11],
+            min_adjacent: values[12],
+            max_adjacent: values[13],
+            percentiles: None,
+            unit,
+            method,
+        };
+
+        summary.debug_validate()?;
+
+        Ok((fields[0].to_string(), summary))
+    }
+
+    /// Check that the statistics are mutually consistent, failing with
+    /// `Error::InvalidSummary` otherwise. In particular:
+    ///
+    ///   - The size is positive, and every statistic is finite, except that
+    ///     the variance and its dependents are NaN for a single point
+    ///   - min ≤ Q1 ≤ median ≤ Q3 ≤ max
+    ///   - min ≤ min adjacent ≤ max adjacent ≤ max
+    ///   - min ≤ mean ≤ max
+    ///   - The range, IQR, and standard deviation agree with their definitions
+    ///   - Any recorded percentiles are nondecreasing
+    ///
+    /// Comparisons allow for a few ulps of rounding error. A `Summary` built
+    /// by this crate from finite data whose sum does not overflow should
+    /// always pass; this is meant for debugging, and for checking summaries
+    /// obtained elsewhere.
+    pub fn debug_validate(&self) -> Result<(), Error> {
+        let scale = self.min.abs().max(self.max.abs());
+        let tolerance = 8.0 * f64::EPSILON * scale;
+        let le = |a: f64, b: f64| a <= b + tolerance;
+        let appx_eq = |a: f64, b: f64| (a - b).abs() <= tolerance;
+
+        let ordered = [
+            self.min,
+            self.lower_quartile,
+            self.median,
+            self.upper_quartile,
+            self.max,
+        ];
+
+        // Adjacent values are data points, and so may fall inside the
+        // interpolated quartiles of small samples.
+        let adjacent = [self.min, self.min_adjacent, self.max_adjacent, self.max];
+
+        let valid = self.len > 0
+            && ordered.iter().chain(&adjacent).all(|x| x.is_finite())
+            && self.mean.is_finite()
+            && ordered.windows(2).all(|w| le(w[0], w[1]))
+            && adjacent.windows(2).all(|w| le(w[0], w[1]))
+            && le(self.min, self.mean) && le(self.mean, self.max)
+            && appx_eq(self.range, self.max - self.min)
+            && appx_eq(self.iqr, self.upper_quartile - self.lower_quartile)
+            && self.spread_is_valid()
+            && self.percentiles
+                .as_ref()
+                .map_or(true, |ps| ps.points.windows(2).all(|w| le(w[0].1, w[1].1)));
+
+        if valid { Ok(()) } else { Err(Error::InvalidSummary) }
+    }
+
+    fn spread_is_valid(&self) -> bool {
+        if self.len == 1 {
+            return self.unbiased_variance.is_nan();
+        }
+
+        let sd = self.unbiased_variance.sqrt();
+
+        self.unbiased_variance >= 0.0
+            && self.unbiased_variance.is_finite()
+            && (self.standard_deviation - sd).abs() <= 4.0 * f64::EPSILON * sd
+            && self.standard_error <= self.standard_deviation
+    }
+
+    /// The recorded percentile snapshot, if any.
+    pub fn percentiles(&self) -> Option<&Percentiles> {
+        self.percentiles.as_ref()
+    }
+
+    /// The `p`th percentile, from the recorded snapshot. Undefined if this
+    /// `Summary` has no snapshot, or if `p` is outside its range.
+    pub fn percentile(&self, p: f64) -> Result<f64, Error> {
+        match self.percentiles {
+            Some(ref ps) => ps.percentile(p),
+            None => Err(Error::Undefined),
+        }
+    }
+
+    /// Annotate the statistics as measured in `unit`, such as `ms`. The unit
+    /// is only carried along to label output; no statistic is converted.
+    pub fn with_unit(self, unit: &str) -> Self {
+        Summary { unit: Some(unit.to_string()), ..self }
+    }
+
+    /// The unit of the statistics, if annotated.
+    pub fn unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+
+    /// Mark the statistics as estimated by `method`, such as from a random
+    /// subsample, rather than computed from all of the data.
+    pub fn with_method(self, method: Method) -> Self {
+        Summary { method, ..self }
+    }
+
+    /// How the statistics were computed.
+    pub fn method(&self) -> Method {
+        self.method
+    }
+
+    /// Whether the quantiles are approximate: the quartiles, the median, the
+    /// IQR, and the adjacent values.
+    pub fn quantiles_are_approximate(&self) -> bool {
+        !self.method.is_exact()
+    }
+
+    /// Whether the moments and extremes are approximate: the mean, the
+    /// standard deviation, variance, and error, the min, max, and range. The
+    /// size never is; it is that of the data summarized. Sketches compute
+    /// these exactly, in one pass.
+    pub fn moments_are_approximate(&self) -> bool {
+        match self.method {
+            Method::Exact | Method::Sketch => false,
+            Method::Bootstrap { .. } | Method::Subsample { .. } => true,
+        }
+    }
+
+    /// Whether any statistic is approximate.
+    pub fn is_approximate(&self) -> bool {
+        self.quantiles_are_approximate() || self.moments_are_approximate()
+    }
 }
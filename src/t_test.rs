@@ -1,3 +1,5 @@
+use std::fmt;
+
 use error::Error;
 use summary::Summary;
 
@@ -9,7 +11,77 @@ pub struct TTest {
     pub df: f64,
 }
 
-fn t_test_2_sided(t: f64, df: f64) -> Result<TTest, Error> {
+impl TTest {
+    /// Decide whether to reject the null hypothesis of equal means at the
+    /// significance `level`.
+    pub fn verdict(&self, level: SigLevel) -> Verdict {
+        level.verdict(self.p)
+    }
+
+    /// The critical value of `|t|` at the significance `level`, given the
+    /// degrees of freedom of this test.
+    pub fn critical_value(&self, level: SigLevel) -> Result<f64, Error> {
+        level.critical_value(self.df)
+    }
+}
+
+/// The significance level of a hypothesis test, the probability `α` of
+/// rejecting a true null hypothesis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SigLevel {
+    Alpha010,
+    Alpha005,
+    Alpha001,
+
+    /// An arbitrary level, which must be in (0, 1).
+    Alpha(f64),
+}
+
+impl SigLevel {
+    /// The level as a probability.
+    pub fn alpha(&self) -> f64 {
+        match *self {
+            SigLevel::Alpha010 => 0.10,
+            SigLevel::Alpha005 => 0.05,
+            SigLevel::Alpha001 => 0.01,
+            SigLevel::Alpha(a) => a,
+        }
+    }
+
+    /// Decide whether a test with p-value `p` rejects its null hypothesis at
+    /// this level.
+    pub fn verdict(&self, p: f64) -> Verdict {
+        if p < self.alpha() {
+            Verdict::Reject
+        } else {
+            Verdict::FailToReject
+        }
+    }
+
+    /// The two-sided critical value of Student's t-distribution with `df`
+    /// degrees of freedom at this level.
+    pub fn critical_value(&self, df: f64) -> Result<f64, Error> {
+        t_critical_value(1.0 - self.alpha(), df)
+    }
+}
+
+/// The outcome of a hypothesis test at some `SigLevel`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+    Reject,
+    FailToReject,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Verdict::Reject => write!(f, "reject the null hypothesis"),
+            Verdict::FailToReject => write!(f, "fail to reject the null hypothesis"),
+        }
+    }
+}
+
+pub(crate) fn t_test_2_sided(t: f64, df: f64) -> Result<TTest, Error> {
     let p = 1.0 - t_atv(t.abs(), df as f64)?;
 
     Ok(TTest { df, p, t })
@@ -22,15 +94,82 @@ pub fn welch_t_test(s1: &Summary, s2: &Summary) -> Result<TTest, Error> {
     t_test_2_sided(t, df)
 }
 
-fn welch_t_statistic(s1: &Summary, s2: &Summary) -> (f64, f64) {
-    let n1 = s1.size();
-    let m1 = s1.mean();
-    let var1 = s1.unbiased_variance();
+/// Conduct a two-sided, unequal-variances t-test from the size, mean, and
+/// standard deviation of each sample, as when only published aggregates are
+/// available. Each sample must have at least two points.
+pub fn welch_t_test_from_stats(
+    n1: f64,
+    m1: f64,
+    sd1: f64,
+    n2: f64,
+    m2: f64,
+    sd2: f64,
+) -> Result<TTest, Error> {
+    let valid_n = |n: f64| n >= 2.0 && n.is_finite();
+    let valid_sd = |sd: f64| sd >= 0.0 && sd.is_finite();
+
+    if !(valid_n(n1) && valid_n(n2) && valid_sd(sd1) && valid_sd(sd2)) {
+        return Err(Error::BadParameter);
+    }
+
+    if !(m1.is_finite() && m2.is_finite()) {
+        return Err(Error::BadParameter);
+    }
+
+    let (t, df) = welch_t_statistic_from_stats(n1, m1, sd1.powi(2), n2, m2, sd2.powi(2));
+
+    t_test_2_sided(t, df)
+}
 
+/// Conduct a two-sided, equal-variances t-test (Student's), pooling the
+/// variances of the samples. Prefer `welch_t_test` unless the population
+/// variances are believed to be equal.
+pub fn student_t_test(s1: &Summary, s2: &Summary) -> Result<TTest, Error> {
+    let n1 = s1.size();
     let n2 = s2.size();
-    let m2 = s2.mean();
-    let var2 = s2.unbiased_variance();
+    let df = n1 + n2 - 2.0;
+
+    let pooled = ((n1 - 1.0) * s1.unbiased_variance() + (n2 - 1.0) * s2.unbiased_variance()) / df;
+    let t = (s1.mean() - s2.mean()) / (pooled * (1.0 / n1 + 1.0 / n2)).sqrt();
+
+    t_test_2_sided(t, df)
+}
 
+/// Adjust the p-values of a family of tests for multiple comparisons by
+/// Holm's step-down method, which bounds the probability of any false
+/// rejection at the significance level, whether or not the tests are
+/// independent. Adjusted values are in the order of `p`, and at most 1. A
+/// NaN p-value is left as is, and not counted among the tests.
+pub fn holm_adjust(p: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..p.len()).filter(|&i| !p[i].is_nan()).collect();
+    order.sort_by(|&i, &j| p[i].total_cmp(&p[j]));
+
+    let m = order.len();
+    let mut adjusted = p.to_vec();
+    let mut max: f64 = 0.0;
+
+    // Adjusted values never decrease with the rank of the raw value.
+    for (rank, &i) in order.iter().enumerate() {
+        max = max.max(((m - rank) as f64 * p[i]).min(1.0));
+        adjusted[i] = max;
+    }
+
+    adjusted
+}
+
+fn welch_t_statistic(s1: &Summary, s2: &Summary) -> (f64, f64) {
+    welch_t_statistic_from_stats(
+        s1.size(),
+        s1.mean(),
+        s1.unbiased_variance(),
+        s2.size(),
+        s2.mean(),
+        s2.unbiased_variance(),
+    )
+}
+
+fn welch_t_statistic_from_stats(n1: f64, m1: f64, var1: f64, n2: f64, m2: f64, var2: f64)
+                                -> (f64, f64) {
     let s_delta_bar = ((var1 / n1) + (var2 / n2)).sqrt();
     let t = (m1 - m2) / s_delta_bar;
 
@@ -53,6 +192,65 @@ fn welch_satterthwaite_df(var1: f64, n1: f64, var2: f64, n2: f64) -> f64 {
     appx
 }
 
+const T_QUANTILE_TOLERANCE: f64 = 1e-12;
+const T_QUANTILE_MAX_ITER: usize = 200;
+
+/// The critical value `t` such that the central interval [-t, t] of Student's
+/// t-distribution with `df` degrees of freedom has probability `confidence`.
+///
+/// Computed by bisection on `t_atv`, which is monotone in `t`.
+pub fn t_critical_value(confidence: f64, df: f64) -> Result<f64, Error> {
+    if !(0.0 < confidence && confidence < 1.0) { return Err(Error::Undefined); }
+    if df.is_nan() || df <= 0.0 { return Err(Error::Undefined); }
+
+    // Grow the bracket until it contains the critical value.
+    let mut lo = 0.0;
+    let mut hi = 1.0;
+    while t_atv(hi, df)? < confidence {
+        lo = hi;
+        hi *= 2.0;
+
+        if !hi.is_finite() { return Err(Error::Diverged); }
+    }
+
+    for _ in 0..T_QUANTILE_MAX_ITER {
+        let mid = 0.5 * (lo + hi);
+
+        if t_atv(mid, df)? < confidence {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+
+        if hi - lo < T_QUANTILE_TOLERANCE * hi.max(1.0) {
+            return Ok(0.5 * (lo + hi));
+        }
+    }
+
+    Err(Error::Diverged)
+}
+
+/// The `p`th quantile of Student's t-distribution with `df` degrees of
+/// freedom, for `p` in (0, 1).
+pub fn t_quantile(p: f64, df: f64) -> Result<f64, Error> {
+    if !(0.0 < p && p < 1.0) { return Err(Error::Undefined); }
+
+    // The distribution is symmetric about 0, and the central interval
+    // [-t, t] with probability `2p - 1` has upper end the `p`th quantile.
+    if p == 0.5 {
+        Ok(0.0)
+    } else if p > 0.5 {
+        t_critical_value(2.0 * p - 1.0, df)
+    } else {
+        Ok(-t_critical_value(1.0 - 2.0 * p, df)?)
+    }
+}
+
+/// Degrees of freedom above which `t_atv` is approximated via the normal
+/// distribution. Beyond this, the continued fraction of `inc_beta` loses more
+/// precision than the approximation, and is slower to converge.
+const LARGE_DF: f64 = 1e6;
+
 /// The definite integral of the density function of Student's t-distribution
 /// over an interval [-t, t]. Also called the A(t|ν) function.
 ///
@@ -60,12 +258,31 @@ fn welch_satterthwaite_df(var1: f64, n1: f64, var2: f64, n2: f64) -> f64 {
 ///
 /// [1]: "Numerical Recipes in C", 2nd Ed., p. 228
 fn t_atv(t: f64, df: f64) -> Result<f64, Error> {
-    use num;
+    use special;
+
+    if df > LARGE_DF {
+        return Ok(t_atv_large_df(t, df));
+    }
 
     let x = df / (df + t.powi(2));
     let a = 0.5 * df;
     let b = 0.5;
-    let ib = num::inc_beta(x, a, b)?;
+    let ib = special::inc_beta(x, a, b)?;
 
     Ok(1.0 - ib)
 }
+
+/// The A(t|ν) function for large `df`, by the normal approximation with the
+/// first correction term of its asymptotic expansion in 1/ν [1]. The error
+/// is O(1/ν²), below 1e-12 when `df` exceeds `LARGE_DF`.
+///
+/// [1]: R. A. Fisher, "Expansion of 'Student's' Integral in Powers of n⁻¹",
+///      Metron 5, 1926.
+fn t_atv_large_df(t: f64, df: f64) -> f64 {
+    use special;
+    use std::f64::consts::{PI, SQRT_2};
+
+    let density = (-0.5 * t.powi(2)).exp() / (2.0 * PI).sqrt();
+
+    special::erf(t / SQRT_2) - density * (t.powi(3) + t) / (2.0 * df)
+}
@@ -0,0 +1,70 @@
+use error::Error;
+
+
+/// The number of values in each tail of `n`, for a fraction `p` of values at
+/// each end, which must be in [0, 0.5).
+fn tail_size(n: usize, p: f64) -> Result<usize, Error> {
+    if !(0.0..0.5).contains(&p) {
+        return Err(Error::BadParameter);
+    }
+
+    Ok((p * n as f64).floor() as usize)
+}
+
+/// Indices of the values of `data` which are not NaN, in ascending order of
+/// value, and of equal values in the order given.
+fn ranked(data: &[f64]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..data.len()).filter(|&i| !data[i].is_nan()).collect();
+    order.sort_by(|&i, &j| data[i].total_cmp(&data[j]));
+
+    order
+}
+
+/// Winsorize `data` in place, replacing the lowest and highest fraction `p`
+/// of values with the nearest value not among them, which must be in
+/// [0, 0.5). NaN values are left alone, and not counted.
+///
+/// Returns the number of values raised and lowered, which, for values equal
+/// to those they are replaced by, may be fewer than are in each tail.
+pub fn winsorize(data: &mut [f64], p: f64) -> Result<(usize, usize), Error> {
+    let order = ranked(data);
+    let k = tail_size(order.len(), p)?;
+
+    if k == 0 {
+        return Ok((0, 0));
+    }
+
+    let lo = data[order[k]];
+    let hi = data[order[order.len() - 1 - k]];
+
+    let mut changed = (0, 0);
+
+    for x in data.iter_mut() {
+        if *x < lo {
+            *x = lo;
+            changed.0 += 1;
+        } else if *x > hi {
+            *x = hi;
+            changed.1 += 1;
+        }
+    }
+
+    Ok(changed)
+}
+
+/// Indices of the values of `data` kept by trimming the lowest and highest
+/// fraction `p` of values, which must be in [0, 0.5), in the order given. Of
+/// equal values in a tail, the first given are trimmed from the low end, and
+/// the last from the high. NaN values are kept, and not counted.
+pub fn trim(data: &[f64], p: f64) -> Result<Vec<usize>, Error> {
+    let order = ranked(data);
+    let k = tail_size(order.len(), p)?;
+
+    let mut keep = vec![true; data.len()];
+
+    for &i in order[..k].iter().chain(&order[order.len() - k..]) {
+        keep[i] = false;
+    }
+
+    Ok((0..data.len()).filter(|&i| keep[i]).collect())
+}
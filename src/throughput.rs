@@ -0,0 +1,52 @@
+use error::Error;
+use summary::Summary;
+
+
+/// Rate statistics of a sample of operation durations, in operations per unit
+/// of time.
+///
+/// The mean rate is the reciprocal of the mean duration, which is the harmonic
+/// mean of the per-operation rates. The arithmetic mean of the per-operation
+/// rates overstates throughput whenever durations vary, so it is not used.
+#[derive(Debug)]
+pub struct Throughput {
+    /// Operations per unit of time over the whole sample, `1 / mean`.
+    pub rate: f64,
+
+    /// Standard error of `rate`, propagated from that of the mean duration by
+    /// the delta method.
+    pub standard_error: f64,
+
+    /// Confidence interval of `rate`, found by inverting the endpoints of the
+    /// confidence interval of the mean duration. If that interval reaches
+    /// zero, the upper bound is infinite.
+    pub interval: (f64, f64),
+
+    /// The reciprocal of the median duration, which is the median of the
+    /// per-operation rates.
+    pub median_rate: f64,
+}
+
+impl Throughput {
+    /// Compute rate statistics from a summary of positive durations, with an
+    /// interval at the given `confidence` level.
+    pub fn new(durations: &Summary, confidence: f64) -> Result<Self, Error> {
+        if durations.min() <= 0.0 {
+            return Err(Error::Undefined);
+        }
+
+        let mean = durations.mean();
+        let rate = 1.0 / mean;
+        let standard_error = durations.standard_error() / mean.powi(2);
+
+        let (lo, hi) = durations.mean_confidence_interval(confidence)?;
+        let upper = if lo > 0.0 { 1.0 / lo } else { f64::INFINITY };
+
+        Ok(Throughput {
+            rate,
+            standard_error,
+            interval: (1.0 / hi, upper),
+            median_rate: 1.0 / durations.median(),
+        })
+    }
+}
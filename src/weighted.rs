@@ -0,0 +1,217 @@
+use error::Error;
+
+
+/// Wraps sample data with a non-negative weight per observation, sorted by
+/// value, and provides methods for computing weighted summary statistics.
+///
+/// Weights are interpreted as reliability weights, so the variance is an
+/// unbiased estimate in the sense of [1]. Observations with zero weight are
+/// discarded on construction.
+///
+/// [1]: https://en.wikipedia.org/wiki/Weighted_arithmetic_mean#Reliability_weights
+#[derive(Debug)]
+pub struct WeightedSummarizer {
+    values: Vec<f64>,
+    weights: Vec<f64>,
+
+    // Plotting position of each value, used to interpolate percentiles.
+    positions: Vec<f64>,
+    total_weight: f64,
+}
+
+impl WeightedSummarizer {
+    /// Construct a `WeightedSummarizer` from `(value, weight)` pairs.
+    ///
+    /// This constructor is partial, and we obtain the following guarantees
+    /// about the resulting sample data:
+    ///
+    ///   - There is at least one observation with positive weight
+    ///   - All values and weights are finite
+    ///   - All weights are positive
+    ///   - The data are sorted by value
+    ///
+    pub fn new(data: &[(f64, f64)]) -> Result<Self, Error> {
+        if data.iter().any(|&(x, w)| !x.is_finite() || !w.is_finite()) {
+            return Err(Error::BadSample);
+        }
+
+        if data.iter().any(|&(_, w)| w < 0.0) {
+            return Err(Error::BadParameter);
+        }
+
+        let mut data: Vec<(f64, f64)> = data
+            .iter()
+            .cloned()
+            .filter(|&(_, w)| w > 0.0)
+            .collect();
+
+        if data.is_empty() {
+            return Err(Error::EmptySample);
+        }
+
+        // Won't panic: we have checked that each float is finite.
+        data.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or_else(|| unreachable!()));
+
+        let (values, weights): (Vec<f64>, Vec<f64>) = data.into_iter().unzip();
+        let total_weight: f64 = weights.iter().sum();
+
+        // Position the `k`th value at `(S_k - w_k) / (S_n - w_k)`, where `S_k`
+        // is the cumulative weight. With equal weights, this reduces to the
+        // closest-ranks definition used by `Summarizer::percentile`, and it is
+        // always nondecreasing in `k`.
+        let mut cumulative = 0.0;
+        let positions = weights
+            .iter()
+            .map(|w| {
+                let below = cumulative;
+                cumulative += w;
+
+                if total_weight == *w { 0.0 } else { below / (total_weight - w) }
+            })
+            .collect();
+
+        Ok(WeightedSummarizer { values, weights, positions, total_weight })
+    }
+
+    /// Number of observations with positive weight, as a floating point value.
+    pub fn size(&self) -> f64 {
+        self.values.len() as f64
+    }
+
+    /// Sum of all weights.
+    pub fn total_weight(&self) -> f64 {
+        self.total_weight
+    }
+
+    /// Kish's effective sample size, `(Σw)² / Σw²`.
+    pub fn effective_size(&self) -> f64 {
+        let sum_sq: f64 = self.weights.iter().map(|w| w * w).sum();
+
+        self.total_weight.powi(2) / sum_sq
+    }
+
+    /// The minimum value in the data set.
+    pub fn min(&self) -> f64 {
+        self.values[0]
+    }
+
+    /// The maximum value in the data set.
+    pub fn max(&self) -> f64 {
+        self.values[self.values.len() - 1]
+    }
+
+    /// The weighted arithmetic mean.
+    pub fn mean(&self) -> f64 {
+        let t: f64 = self.values
+            .iter()
+            .zip(&self.weights)
+            .map(|(x, w)| x * w)
+            .sum();
+
+        t / self.total_weight
+    }
+
+    /// Weighted sample variance, corrected for bias assuming reliability
+    /// weights. Undefined (NaN) if only one observation has positive weight.
+    pub fn unbiased_variance(&self) -> f64 {
+        let m = self.mean();
+        let v1 = self.total_weight;
+        let v2: f64 = self.weights.iter().map(|w| w * w).sum();
+
+        let sum_sq_diff: f64 = self.values
+            .iter()
+            .zip(&self.weights)
+            .map(|(x, w)| w * (x - m).powi(2))
+            .sum();
+
+        sum_sq_diff / (v1 - v2 / v1)
+    }
+
+    /// Weighted standard deviation.
+    pub fn standard_deviation(&self) -> f64 {
+        self.unbiased_variance().sqrt()
+    }
+
+    /// Standard error of the weighted mean, using the effective sample size.
+    pub fn standard_error(&self) -> f64 {
+        self.standard_deviation() / self.effective_size().sqrt()
+    }
+
+    /// Weighted percentile, computed via linear interpolation between the
+    /// plotting positions of adjacent values. With equal weights, this agrees
+    /// with `Summarizer::percentile`.
+    pub fn percentile(&self, p: f64) -> Result<f64, Error> {
+        if !p.is_finite() { return Err(Error::Undefined); }
+        if !(0.0..=1.0).contains(&p) {
+            return Err(Error::Undefined);
+        }
+
+        // Index of the first position at or above `p`.
+        let j = self.positions.iter().position(|&q| p <= q).unwrap_or(self.values.len() - 1);
+
+        if j == 0 {
+            return Ok(self.values[0]);
+        }
+
+        let i = j - 1;
+        let (pi, pj) = (self.positions[i], self.positions[j]);
+        let (xi, xj) = (self.values[i], self.values[j]);
+
+        if pj == pi {
+            return Ok(xj);
+        }
+
+        Ok(xi + (p - pi) / (pj - pi) * (xj - xi))
+    }
+
+    /// The weighted 25th percentile.
+    pub fn lower_quartile(&self) -> f64 {
+        // Statically known to be defined.
+        self.percentile(0.25).unwrap_or_else(|_| unreachable!())
+    }
+
+    /// The weighted 50th percentile.
+    pub fn median(&self) -> f64 {
+        // Statically known to be defined.
+        self.percentile(0.5).unwrap_or_else(|_| unreachable!())
+    }
+
+    /// The weighted 75th percentile.
+    pub fn upper_quartile(&self) -> f64 {
+        // Statically known to be defined.
+        self.percentile(0.75).unwrap_or_else(|_| unreachable!())
+    }
+
+    /// Difference between the upper and lower quartiles.
+    pub fn iqr(&self) -> f64 {
+        self.upper_quartile() - self.lower_quartile()
+    }
+
+    /// The difference between the minimum and maximum value.
+    pub fn range(&self) -> f64 {
+        self.max() - self.min()
+    }
+
+    /// The minimum non-outlier value in the data set.
+    pub fn min_adjacent(&self) -> f64 {
+        let lower_outlier_bound = self.lower_quartile() - 1.5 * self.iqr();
+
+        self.values
+            .iter()
+            .cloned()
+            .find(|&x| lower_outlier_bound <= x)
+            .unwrap_or_else(|| unreachable!())  // By definition of quartile.
+    }
+
+    /// The maximum non-outlier value in the data set.
+    pub fn max_adjacent(&self) -> f64 {
+        let upper_outlier_bound = self.upper_quartile() + 1.5 * self.iqr();
+
+        self.values
+            .iter()
+            .cloned()
+            .rev()
+            .find(|&x| x <= upper_outlier_bound)
+            .unwrap_or_else(|| unreachable!())  // By definition of quartile.
+    }
+}
@@ -72,6 +72,12 @@ fn test_comparison_plot() {
     assert::exit_ok(&out);
     assert::stderr_is_empty(&out);
     assert::stdout_eq_file(&out, "comparison_plot.out");
+
+    // The width is only checked if there is a plot.
+    let out = exe::run(&["-p", "-w", "0", path1, path2]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "Plot width must be positive");
+    assert::exit_ok(&exe::run(&["-w", "0", path1, path2]));
 }
 
 #[test]
@@ -229,3 +235,1718 @@ fn test_tsv_3() {
     assert::stderr_is_empty(&out);
     assert::stdout_eq_file(&out, "tsv_3.out");
 }
+
+#[test]
+fn test_comparison_plot_style() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+    let out = exe::run(&["-p", "-w", "90", "--plot-style", "heavy", path1, path2]);
+
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+    assert::stdout_eq_file(&out, "comparison_plot_heavy.out");
+
+    let out = exe::run(&["-p", "-w", "90", "--plot-style", "unicode", path1, path2]);
+
+    assert::exit_ok(&out);
+    assert::stdout_eq_file(&out, "comparison_plot.out");
+
+    assert::exit_fail(&exe::run(&["-p", "--plot-style", "nonexistent", path1]));
+    assert::exit_fail(&exe::run(&["-p", "--ascii", "--plot-style", "heavy", path1]));
+}
+
+#[test]
+fn test_comparison_plot_subcell() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+    let out = exe::run(&["-p", "-w", "90", "--subcell", path1, path2]);
+
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+    assert::stdout_eq_file(&out, "comparison_plot_subcell.out");
+
+    assert::exit_fail(&exe::run(&["-p", "--subcell", "--ascii", path1]));
+}
+
+#[test]
+fn test_comparison_plot_ci_band() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+    let out = exe::run(&["-p", "-w", "90", "--ci-band", path1, path2]);
+
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+    assert::stdout_eq_file(&out, "comparison_plot_ci_band.out");
+
+    assert::exit_fail(&exe::run(&["-p", "--ci-band", "--confidence", "1.5", path1]));
+}
+
+#[test]
+fn test_categorical() {
+    let path = &fixture::path("http_methods");
+
+    let out = exe::run(&["--categorical", "-w", "60", path]);
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+    assert::stdout_eq_file(&out, "categorical.out");
+
+    let out = exe::run(&["--categorical", "--ascii", "--confidence", "0.9", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "GET |#");
+    assert::stdout_includes(&out, "90% Lower");
+
+    let args = ["--categorical", "--diversity", "--seed", "3", path];
+    let out = exe::run(&args);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "H (bits) = 1.75, 95% CI [");
+    assert::stdout_includes(&out, "Simpson's D = 0.25, 95% CI [");
+    assert_eq!(out.stdout, exe::run(&args).stdout);
+
+    assert::exit_fail(&exe::run(&["--categorical", "--freq", path]));
+    assert::exit_fail(&exe::run(&["--diversity", path]));
+}
+
+#[test]
+fn test_gen() {
+    let out = exe::run(&["gen", "normal", "0", "1", "-n", "20", "--seed", "7"]);
+
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+    assert::stdout_eq_file(&out, "gen_normal_0_1_seed_7.out");
+
+    assert::exit_ok(&exe::run(&["gen", "normal", "-5", "2"]));
+    assert::exit_ok(&exe::run(&["gen", "exponential", "2", "-n", "5"]));
+    assert::exit_ok(&exe::run(&["gen", "gamma", "0.5", "2", "-n", "5"]));
+    assert::exit_fail(&exe::run(&["gen", "normal", "0"]));
+    assert::exit_fail(&exe::run(&["gen", "normal", "0", "-1"]));
+    assert::exit_fail(&exe::run(&["gen", "cauchy", "0", "1"]));
+}
+
+#[test]
+fn test_stdin_pseudo_file() {
+    let path = &fixture::path("all_numeric_lines");
+
+    let file = fixture::file("all_numeric_lines");
+    let out = exe::run_with_stdin(file, &[path, "-"]);
+
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+    assert::stdout_eq_file(&out, "comparison.out");
+
+    let file = fixture::file("all_numeric_lines");
+    let out = exe::run_with_stdin(file, &[path, "-s"]);
+
+    assert::exit_ok(&out);
+    assert::stdout_eq_file(&out, "comparison.out");
+
+    let file = fixture::file("all_numeric_lines");
+    assert::exit_fail(&exe::run_with_stdin(file, &["-", "-s"]));
+}
+
+#[test]
+fn test_report_skipped() {
+    let path = &fixture::path("bad_lines");
+
+    let out = exe::run(&[path, "--lax", "--report-skipped"]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "Skipped 1 non-numeric line in");
+    assert::stderr_includes(&out, "line 3: \"not numeric\"");
+
+    let out = exe::run(&[path, "--lax"]);
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+
+    assert::exit_fail(&exe::run(&[path, "--report-skipped"]));
+}
+
+#[test]
+fn test_json_errors() {
+    let path = &fixture::path("bad_lines");
+
+    let out = exe::run(&["--json", path]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, &format!(
+        "{{\"level\":\"error\",\"code\":\"malformed_line\",\
+         \"message\":\"Input line has no valid value\",\"input\":\"{}\",\"line\":3}}",
+        path,
+    ));
+
+    let out = exe::run(&["--json", "--lax", "--report-skipped", path]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, &format!(
+        "{{\"level\":\"warning\",\"code\":\"skipped_line\",\"message\":\"\\\"not numeric\\\"\",\
+         \"input\":\"{}\",\"line\":3}}",
+        path,
+    ));
+
+    let out = exe::run(&["--json", "--diff", path]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "\"code\":\"usage\"");
+
+    let out = exe::run(&["--json", "--stats", "1,0,1", "--stats", "1,0,1"]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "\"code\":\"bad_parameter\"");
+
+    // Errors in the arguments are found by clap before the rest are read.
+    let out = exe::run(&["--nope", "--json", path]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "{\"level\":\"error\",\"code\":\"usage\",\"message\":\"Found argument '--nope'");
+}
+
+#[test]
+fn test_quick() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+
+    let args = ["--quick", "20", "--seed", "3", path1, path2];
+    let out = exe::run(&args);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "\n    20  ");
+    assert::stdout_includes(&out, "~ x₂ approximate: subsample of 20 of 100 values\n");
+    assert::stderr_includes(&out, "normal_5_2: estimated from a random subsample of 20 of 100 values");
+    assert::stderr_includes(&out, "so the mean is within ± 0.8194 of that of all values");
+
+    // Seeded subsamples are reproducible.
+    assert_eq!(out.stdout, exe::run(&args).stdout);
+
+    // Machine-readable output labels approximate values with their method.
+    let out = exe::run(&["--quick", "20", "--seed", "3", "--tsv", path1]);
+    assert::stdout_includes(&out, "\tUnit\tMethod\n");
+    assert::stdout_includes(&out, "\tsubsample:20/100\n");
+
+    let out = exe::run(&["--quick", "20", "--seed", "3", "--format", "prometheus", path1]);
+    assert::stdout_includes(&out, ",method=\"subsample:20/100\"} ");
+
+    // Inputs no larger than the subsample are summarized whole.
+    let path = &fixture::path("all_numeric_lines");
+    let out = exe::run(&["--quick", "100", path, path]);
+    assert::exit_ok(&out);
+    assert::stdout_eq_file(&out, "comparison.out");
+    assert::stderr_is_empty(&out);
+
+    assert::exit_fail(&exe::run(&["--quick", "0", path1]));
+}
+
+#[test]
+fn test_stable_output() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+
+    // Bootstrap intervals and generated data are seeded the same every run.
+    let args = ["--stable-output", "--shift", path1, path2];
+    let out = exe::run(&args);
+    assert::exit_ok(&out);
+    assert_eq!(out.stdout, exe::run(&args).stdout);
+
+    let args = ["gen", "normal", "0", "1", "--stable-output"];
+    let out = exe::run(&args);
+    assert::exit_ok(&out);
+    assert_eq!(out.stdout, exe::run(&args).stdout);
+
+    // An explicit seed still takes precedence.
+    let seeded = exe::run(&["gen", "normal", "0", "1", "-n", "20", "--seed", "7", "--stable-output"]);
+    assert::stdout_eq_file(&seeded, "gen_normal_0_1_seed_7.out");
+
+    // Neither the config file nor the environment changes it.
+    let config = &fixture::path("dent_config.toml");
+    let env = [("DENT_CONFIG", config.as_str()), ("DENT_PRECISION", "2"), ("DENT_WIDTH", "40")];
+    let args = ["--stable-output", "--plot", path1, path2];
+    let out = exe::run_with_env(&env, &args);
+    assert::exit_ok(&out);
+    assert_eq!(out.stdout, exe::run(&args).stdout);
+}
+
+#[test]
+fn test_verbose_value_counts() {
+    let path = &fixture::path("heavy_ties");
+
+    let out = exe::run(&["--verbose", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Zeros  Negatives  Duplicates  Distinct");
+    assert::stdout_includes(&out, "0          0           4         3");
+
+    let out = exe::run(&["-v", "--robust", path, &fixture::path("normal_0_1")]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "0         49           0       100");
+
+    assert::exit_fail(&exe::run(&["-v", "--tsv", path]));
+}
+
+#[test]
+fn test_diagnostics() {
+    let constant = &fixture::path("constant");
+    let ties = &fixture::path("heavy_ties");
+
+    let out = exe::run(&[constant, constant]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "All values are identical (3)");
+    assert::stderr_includes(&out, "t-test is undefined");
+    assert!(!String::from_utf8_lossy(&out.stdout).contains("p ="));
+
+    let out = exe::run(&[ties]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "only 3 distinct values in 7 observations");
+
+    let out = exe::run(&[&fixture::path("drifting")]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "Non-random order: 4 runs above and below the median, where 31.0 are expected");
+    assert::stderr_includes(&out, "suggesting a trend");
+
+    let out = exe::run(&[&fixture::path("normal_0_1")]);
+    assert::stderr_is_empty(&out);
+
+    let out = exe::run(&["lr", &fixture::path("lr_quadratic")]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "lr_quadratic: residuals: Non-random order: 3 runs");
+
+    let out = exe::run(&["lr", &fixture::path("lr_heteroscedastic")]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "residuals: Non-constant variance: squared residuals grow");
+
+    let out = exe::run(&["lr", &fixture::path("lr_group_1")]);
+    assert::stderr_is_empty(&out);
+}
+
+#[test]
+fn test_drop_nonfinite() {
+    let path = &fixture::path("nonfinite");
+
+    assert::exit_fail(&exe::run(&[path]));
+
+    let out = exe::run(&["--drop-nonfinite", path]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "Dropped 4 non-finite values (1 NaN, 2 inf, 1 -inf)");
+    assert::stdout_includes(&out, "     3           1         1.5           2");
+
+    let out = exe::run(&["--drop-nonfinite", "--threshold", "2", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "> 2       1  0.33333333");
+
+    let out = exe::run(&["--drop-nonfinite", "--threshold", "2", "--exceedance-test", path, path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "           Δ = 0, 95% CI ");
+
+    let out = exe::run(&["--drop-nonfinite", "--freq-table", "--bins", "2", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "[1.00, 2.00)       1");
+
+    assert::exit_ok(&exe::run(&["--drop-nonfinite", "--histogram", "--bins", "log:2", path]));
+}
+
+#[test]
+fn test_weighted() {
+    let path = &fixture::path("weighted");
+
+    assert::exit_fail(&exe::run(&[path]));
+
+    let out = exe::run(&["--weighted", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "     5           1       1.625         2.5        3.75           5  2.66666667");
+
+    let bad = &fixture::path("all_numeric_lines");
+    let out = exe::run(&["--weighted", bad]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "Line 1: expected a value and a weight");
+}
+
+#[test]
+fn test_freq() {
+    let path = &fixture::path("frequencies");
+
+    let out = exe::run(&["--freq", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "     6           1       1.125         1.5         1.5         1.5  1.58333333");
+
+    let out = exe::run(&["--freq", &fixture::path("weighted_fractional")]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "Line 2: expected a value and a non-negative integer count");
+}
+
+#[test]
+fn test_paired_diff() {
+    let before = &fixture::path("paired_before");
+    let after = &fixture::path("paired_after");
+
+    let out = exe::run(&["--diff", before, after]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "     4          -1         0.5           2        3.25           4        1.75");
+
+    let out = exe::run(&["--ratio", "--tsv", before, after]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "paired_after / tests/support/fixture/paired_before\t4\t1.0625\t");
+
+    let out = exe::run(&["--diff", "--robust", before, after]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "+ / − / ties = 3 / 1 / 0");
+    assert::stdout_includes(&out, "sign test p = 0.625");
+
+    // With every pair tied, there is no sign test, but the rest is reported.
+    let out = exe::run(&["--diff", "--robust", before, before]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "Skipped the sign test, as no pairs are untied");
+    assert!(!String::from_utf8_lossy(&out.stdout).contains("sign test"));
+
+    // Unpaired robust comparisons are tested by Mann–Whitney U alone.
+    let out = exe::run(&["--robust", before, after]);
+    assert::exit_ok(&out);
+    assert!(!String::from_utf8_lossy(&out.stdout).contains("sign test"));
+
+    assert::exit_fail(&exe::run(&["--diff", before]));
+
+    let out = exe::run(&["--diff", before, &fixture::path("all_numeric_lines")]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "Paired samples must have equal size");
+}
+
+#[test]
+fn test_quantile_comparison() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+
+    let out = exe::run(&["--quantiles", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_eq_file(&out, "quantile_comparison.out");
+
+    assert::exit_fail(&exe::run(&["--quantiles", path1]));
+}
+
+#[test]
+fn test_shift_plot() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+
+    let out = exe::run(&["--shift", "--seed", "3", "-w", "70", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_eq_file(&out, "shift_plot.out");
+
+    assert::exit_fail(&exe::run(&["--shift", path1]));
+
+    // Nothing is resampled without --shift, or another which resamples.
+    let out = exe::run(&["--seed", "3", path1, path2]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "--seed requires --shift, --speedup, --diversity, or --quick");
+    assert::exit_fail(&exe::run(&["--resamples", "100", "--quick", "20", path1, path2]));
+}
+
+#[test]
+fn test_from_summary() {
+    let table = &fixture::path("tsv_2.out");
+
+    let out = exe::run(&["--from-summary", "--tsv", table]);
+    assert::exit_ok(&out);
+    assert::stdout_eq_file(&out, "tsv_2.out");
+
+    let out = exe::run(&["--from-summary", table]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "p = 0");
+
+    let out = exe::run(&["--from-summary", &fixture::path("normal_0_1")]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "Malformed summary table record");
+}
+
+#[test]
+fn test_unit() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+
+    let out = exe::run(&["--unit", "ms", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Mean     Std Dev  Unit\n");
+    assert::stdout_includes(&out, "0.92541938    ms\n");
+
+    let out = exe::run(&["--unit", "ms", "--robust", path1]);
+    assert::stdout_includes(&out, "MAD  Unit\n");
+
+    let out = exe::run(&["--unit", "µs", "--shift", "--seed", "3", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "7.71 µs\n");
+    assert::stdout_includes(&out, "Quantile  x₂ - x₁ (µs)");
+
+    // Plots label their value axes with the unit.
+    let out = exe::run(&["--unit", "ms", "--plot", path1, path2]);
+    assert::stdout_includes(&out, "─ ms ─┘\n");
+
+    let out = exe::run(&["--unit", "ms", "--histogram", "--overlay", path1, path2]);
+    assert::stdout_includes(&out, "9.73 ms\n");
+
+    let out = exe::run(&["--unit", "ms", "--histogram", "--bins", "3", path1]);
+    assert::stdout_includes(&out, "[0.745, 2.42] ms │");
+
+    let out = exe::run(&["--unit", "ms", "--histogram=vertical", path1]);
+    assert::stdout_includes(&out, "2.42 ms\n");
+
+    let methods = [&fixture::path("method_a"), &fixture::path("method_b")];
+    let out = exe::run(&["--unit", "ms", "--bland-altman", "-p", methods[0], methods[1]]);
+    assert::stdout_includes(&out, "\n1.93 ms │");
+    assert::stdout_includes(&out, "61.20 ms\n");
+
+    // Units are written to and read back from summary tables.
+    let out = exe::run(&["--unit", "ms", "--tsv", path1]);
+    assert::exit_ok(&out);
+    assert::stdout_eq_file(&out, "tsv_unit.out");
+
+    let table = &fixture::path("tsv_unit.out");
+
+    let out = exe::run(&["--from-summary", table]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "0.92541938    ms\n");
+
+    let out = exe::run(&["--from-summary", "--unit", "s", table]);
+    assert::stdout_includes(&out, "0.92541938     s\n");
+
+    // Without units, summary tables are as before.
+    let out = exe::run(&["--tsv", path1]);
+    assert::stdout_eq_file(&out, "tsv_1.out");
+}
+
+#[test]
+fn test_prometheus() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+
+    let out = exe::run(&["--format", "prometheus", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_eq_file(&out, "prometheus.out");
+
+    let out = exe::run(&["--format", "prometheus", "--unit", "ms", path1]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "normal_0_1\",unit=\"ms\"} 100\n");
+    assert::stdout_includes(&out, ",quantile=\"0.75\"} ");
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_results_db() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+
+    let db_path = std::env::temp_dir().join(format!("dent-test-{}.sqlite", std::process::id()));
+    let _ = std::fs::remove_file(&db_path);
+    let db = db_path.to_str().unwrap();
+
+    let out = exe::run(&["--db", db, "--meta", "label=base", "--meta", "sha=abc", path1, path2]);
+    assert::exit_ok(&out);
+
+    let out = exe::run(&["--db", db, "--meta", "label=new", "--unit", "ms", path1]);
+    assert::exit_ok(&out);
+
+    let out = exe::run(&["query", db]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Run\tTime\tSource\tSize\tMean\t");
+    assert::stdout_includes(&out, "normal_5_2\t100\t5.1926899330000005\t");
+    assert::stdout_includes(&out, "\tlabel=base,sha=abc\n");
+    assert::stdout_includes(&out, "\tms\tlabel=new\n");
+
+    let out = exe::run(&["query", db, "--meta", "label=new", "--source", path2]);
+    assert::exit_ok(&out);
+    assert_eq!(String::from_utf8_lossy(&out.stdout).lines().count(), 1);
+
+    let out = exe::run(&["query", db, "--tests"]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "normal_5_2\t-23.70694346195494\t140.1261935028268\t");
+
+    let out = exe::run(&["query", db, "--last", "1"]);
+    assert::stdout_includes(&out, "label=new");
+    assert!(!String::from_utf8_lossy(&out.stdout).contains("label=base"));
+
+    assert::exit_fail(&exe::run(&["--meta", "label", "--db", db, path1]));
+    assert::exit_fail(&exe::run(&["--meta", "label=base", path1]));
+
+    std::fs::remove_file(&db_path).unwrap();
+    assert::exit_fail(&exe::run(&["query", db]));
+}
+
+#[cfg(feature = "net")]
+#[test]
+fn test_url_input() {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/normal_0_1", listener.local_addr().unwrap());
+
+    // Serve the fixture once, then a 404.
+    let server = thread::spawn(move || {
+        let statuses = [("200 OK", fixture::read("normal_0_1")), ("404 Not Found", String::new())];
+
+        for ((status, body), stream) in statuses.iter().zip(listener.incoming()) {
+            let mut stream = stream.unwrap();
+            let _ = stream.read(&mut [0; 4096]).unwrap();
+
+            write!(stream, "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                   status, body.len(), body).unwrap();
+        }
+    });
+
+    let out = exe::run(&["--tsv", &url]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, &format!("{}\t100\t0.0024382313218000596\t", url));
+
+    let out = exe::run(&[&url]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "Could not fetch URL");
+
+    server.join().unwrap();
+}
+
+#[cfg(not(feature = "net"))]
+#[test]
+fn test_url_input_without_net() {
+    let out = exe::run(&["https://example.com/run1.txt"]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "built without the `net` feature");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_fifo_inputs() {
+    use std::fs;
+    use std::process::Command;
+    use std::thread;
+    use std::time::Duration;
+
+    let dir = std::env::temp_dir().join(format!("dent-test-fifo-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let fifos = [dir.join("a"), dir.join("b")];
+    for f in &fifos {
+        assert!(Command::new("mkfifo").arg(f).status().unwrap().success());
+    }
+
+    // The second input is written first, after a delay, which would block
+    // forever if the inputs were read in order.
+    let writer = {
+        let fifos = fifos.clone();
+
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(2500));
+            fs::write(&fifos[1], fixture::read("normal_5_2")).unwrap();
+            fs::write(&fifos[0], fixture::read("normal_0_1")).unwrap();
+        })
+    };
+
+    let out = exe::run(&["--tsv", fifos[0].to_str().unwrap(), fifos[1].to_str().unwrap()]);
+    writer.join().unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "/a\t100\t0.0024382313218000596\t");
+    assert::stdout_includes(&out, "/b\t100\t5.1926899330000005\t");
+    assert::stderr_includes(&out, "Waiting for input from ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_bench_compare() {
+    let out = exe::run(&["bench", "--compare", "true", "sleep 0.01", "-n", "3", "--interleave"]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Ran each command 3 times, interleaved:\n");
+    assert::stdout_includes(&out, "x₁ = true\n");
+    assert::stdout_includes(&out, "x₂ = sleep 0.01\n");
+    assert::stdout_includes(&out, "Wall time (ms):\n");
+    assert::stdout_includes(&out, "Std Dev  Unit\n");
+    assert::stdout_includes(&out, "m₂ - m₁ ± SE = ");
+    assert::stdout_includes(&out, "User CPU time (ms):\n");
+    assert::stdout_includes(&out, "System CPU time (ms):\n");
+    assert::stdout_includes(&out, "Max RSS (KiB):\n");
+    assert::stdout_includes(&out, "KiB\n");
+
+    let out = exe::run(&["bench", "--compare", "true", "exit 3"]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "Command \"exit 3\" failed: exit status: 3");
+
+    let out = exe::run(&["bench", "--compare", "true", "exit 3", "-n", "2", "--allow-failure"]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "exit 3: 2 of 2 runs failed");
+
+    // Runs are added to the first command until its mean is precise enough,
+    // up to a limit.
+    let out = exe::run(&[
+        "bench", "--compare", "true", "sleep 0.01", "-n", "2",
+        "--target-precision", "0.01%", "--max-runs", "4", "--rerun-outliers",
+    ]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Ran each command 4 times, in sequence:\n");
+    assert::stderr_includes(&out, "wider than the target of 0.01%");
+
+    let out = exe::run(&["bench", "--compare", "true", "true", "--target-precision", "1000%"]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Ran each command 10 times, in sequence:\n");
+
+    let out = exe::run(&["bench", "--compare", "true", "true", "--target-precision", "-1"]);
+    assert::exit_fail(&out);
+    assert::exit_fail(&exe::run(&["bench", "--compare", "true", "true", "-n", "1"]));
+    assert::exit_fail(&exe::run(&["bench", "--compare", "true"]));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_bench_hooks() {
+    let dir = std::env::temp_dir().join(format!("dent-test-hooks-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let log = dir.join("log");
+    let prepare = format!("echo p >> {}", log.display());
+    let cleanup = format!("echo c >> {}", log.display());
+
+    let args = [
+        "bench", "--compare", "true", "true", "-n", "2", "--prepare", &prepare, "--cleanup", &cleanup,
+    ];
+
+    let out = exe::run(&args);
+    assert::exit_ok(&out);
+    assert_eq!(std::fs::read_to_string(&log).unwrap(), "p\nc\n");
+
+    std::fs::remove_file(&log).unwrap();
+
+    let out = exe::run(&[&args[..], &["--cold"]].concat());
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Ran each command 2 times, in sequence, with cold caches:\n");
+    assert_eq!(std::fs::read_to_string(&log).unwrap(), "p\nc\n".repeat(4));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let out = exe::run(&["bench", "--compare", "true", "true", "--prepare", "exit 2"]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "failed as the prepare command: exit status: 2");
+
+    assert::exit_fail(&exe::run(&["bench", "--compare", "true", "true", "--cold"]));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_bench_rerun_outliers() {
+    let dir = std::env::temp_dir().join(format!("dent-test-rerun-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    // Only the first run is slow.
+    let seen = dir.join("seen");
+    let slow = format!("[ -e {0} ] || {{ touch {0}; sleep 0.3; }}", seen.display());
+    let raw = dir.join("runs.csv");
+
+    let out = exe::run(&[
+        "bench", "--compare", &slow, "true", "-n", "6", "--interleave", "--rerun-outliers",
+        "--save-raw", raw.to_str().unwrap(),
+    ]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Ran each command 6 times, interleaved:\n");
+    assert::stdout_includes(&out, " of x₁ again, in ms: ");
+
+    // The outlying run is kept, and its rerun recorded apart from it.
+    let text = std::fs::read_to_string(&raw).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines[0].ends_with(",max_rss_kib,rerun_of"));
+    let first: Vec<&str> = lines[1].rsplitn(6, ',').collect();
+    assert!(lines[1].starts_with(&format!("0,{},1,0,", slow)));
+    assert!(first[4].parse::<f64>().unwrap() >= 300.0);
+    assert!(lines.iter().any(|l| l.starts_with(&format!("12,{},,0,", slow)) && l.ends_with(",1")));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_bench_save_raw() {
+    let dir = std::env::temp_dir().join(format!("dent-test-raw-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let csv = dir.join("runs.csv");
+    let args = ["bench", "--compare", "true", "echo a, b", "-n", "2", "--interleave"];
+
+    let out = exe::run(&[&args[..], &["--save-raw", csv.to_str().unwrap()]].concat());
+    assert::exit_ok(&out);
+
+    let text = std::fs::read_to_string(&csv).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], "sequence,command,run,status,wall_ms,user_ms,system_ms,max_rss_kib,rerun_of");
+    assert!(lines[1].starts_with("0,true,1,0,"));
+    assert!(lines[1].ends_with(","));
+    assert!(lines[2].starts_with("1,\"echo a, b\",1,0,"));
+    assert!(lines[4].starts_with("3,\"echo a, b\",2,0,"));
+
+    let json = dir.join("runs.json");
+    let out = exe::run(&[&args[..], &["--save-raw", json.to_str().unwrap()]].concat());
+    assert::exit_ok(&out);
+
+    let text = std::fs::read_to_string(&json).unwrap();
+    assert!(text.starts_with("[\n{\"sequence\":0,\"command\":\"true\",\"run\":1,\"status\":0,"));
+    assert!(text.ends_with("}\n]\n"));
+    assert_eq!(text.matches("\"wall_ms\":").count(), 8);
+
+    // Each run maps its measurements to their units.
+    let units = r#","rerun_of":null,"units":{"wall_ms":"ms","user_ms":"ms","system_ms":"ms","max_rss_kib":"KiB"}}"#;
+    assert_eq!(text.matches(units).count(), 4);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let missing = dir.join("runs.csv");
+    let out = exe::run(&[&args[..], &["--save-raw", missing.to_str().unwrap()]].concat());
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "Could not write");
+}
+
+#[test]
+fn test_stats_t_test() {
+    let out = exe::run(&["--stats", "10,1.0,2.0", "--stats", "20,3.0,1.5"]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "m₂ - m₁ ± SE = 2 ± ");
+    assert::stdout_includes(&out, "DF = ");
+
+    assert::exit_fail(&exe::run(&["--stats", "10,1.0,2.0"]));
+    assert::exit_fail(&exe::run(&["--stats", "10,1.0", "--stats", "20,3.0,1.5"]));
+    assert::exit_fail(&exe::run(&["--stats", "1,1.0,2.0", "--stats", "20,3.0,1.5"]));
+}
+
+#[test]
+fn test_lr_compare() {
+    let path1 = &fixture::path("lr_group_1");
+    let path2 = &fixture::path("lr_group_2");
+
+    let out = exe::run(&["lr", path1]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "slope = 2.02");
+
+    let out = exe::run(&["lr", "--compare", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_eq_file(&out, "lr_compare.out");
+
+    assert::exit_fail(&exe::run(&["lr", "--compare", path1]));
+    assert::exit_fail(&exe::run(&["lr", &fixture::path("normal_0_1")]));
+}
+
+#[test]
+fn test_lr_segmented() {
+    let path = &fixture::path("lr_knee");
+
+    let out = exe::run(&["lr", "--segmented", path]);
+    assert::exit_ok(&out);
+    assert::stdout_eq_file(&out, "lr_segmented.out");
+
+    let out = exe::run(&["lr", "--segmented", "--confidence", "0.9", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "90% CI = ");
+
+    assert::exit_fail(&exe::run(&["lr", "--confidence", "0.9", path]));
+    assert::exit_fail(&exe::run(&["lr", "--segmented", "--compare", path, path]));
+}
+
+#[test]
+fn test_lr_model() {
+    let path = &fixture::path("lr_growth");
+
+    let out = exe::run(&["lr", "--model", "exp", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "model = y = a·e^(b·x)");
+    assert::stdout_includes(&out, "b = 0.298");
+
+    let out = exe::run(&["lr", "--model", "power", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "model = y = a·x^b");
+
+    // The power model is undefined at `x = 0`.
+    assert::exit_fail(&exe::run(&["lr", "--model", "power", &fixture::path("lr_group_1")]));
+    assert::exit_fail(&exe::run(&["lr", "--model", "cubic", path]));
+}
+
+#[test]
+fn test_lr_deming() {
+    let path = &fixture::path("lr_methods");
+
+    let out = exe::run(&["lr", "--deming", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "error ratio = 1\n");
+    assert::stdout_includes(&out, "slope ± SE = 1.01957981");
+    assert::stdout_includes(&out, "intercept ± SE = 0.73106455");
+
+    let out = exe::run(&["lr", "--deming", "--error-ratio", "4", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "error ratio = 4\n");
+
+    assert::exit_fail(&exe::run(&["lr", "--deming", "--error-ratio", "-1", path]));
+    assert::exit_fail(&exe::run(&["lr", "--error-ratio", "2", path]));
+}
+
+#[test]
+fn test_lr_cross_validation() {
+    let path = &fixture::path("lr_growth");
+
+    let out = exe::run(&["lr", "--model", "exp", "--cv", "loo", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "CV folds = 12");
+    assert::stdout_includes(&out, "CV RMSE = 1.28075187");
+
+    let out = exe::run(&["lr", "--cv", "5", "--seed", "1", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "CV folds = 5");
+    assert::stdout_includes(&out, "CV RMSE = 11.08912002");
+
+    assert::exit_fail(&exe::run(&["lr", "--cv", "1", path]));
+    assert::exit_fail(&exe::run(&["lr", "--cv", "13", path]));
+}
+
+#[test]
+fn test_lr_influence() {
+    let out = exe::run(&["lr", "--influence", &fixture::path("lr_outlier")]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "  Line           X           Y    Residual    Leverage    Cook's D");
+    assert::stdout_includes(&out, "    10          20          10  -6.3640000  0.79428571  1.543132e1  *");
+    assert::stdout_includes(&out, "* Cook's distance above 4/n = 0.400");
+
+    assert::exit_fail(&exe::run(&["lr", "--influence", "--segmented", &fixture::path("lr_knee")]));
+}
+
+#[test]
+fn test_fit() {
+    let path = &fixture::path("fit_lognormal");
+
+    let out = exe::run(&["fit", "--dist", "lognormal", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "distribution = lognormal");
+    assert::stdout_includes(&out, "mu ± SE = 0.1085");
+    assert::stdout_includes(&out, "sigma ± SE = 0.4783");
+    assert::stdout_includes(&out, "K-S statistic = 0.0849");
+
+    let out = exe::run(&["fit", "--dist", "gamma", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "shape ± SE = 4.4929");
+    assert::stdout_includes(&out, "rate ± SE = 3.5914");
+
+    let out = exe::run(&["fit", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "distribution = normal");
+
+    // The log-normal distribution is undefined for non-positive values.
+    assert::exit_fail(&exe::run(&["fit", "--dist", "lognormal", &fixture::path("normal_0_1")]));
+    assert::exit_fail(&exe::run(&["fit", "--dist", "cauchy", path]));
+}
+
+#[test]
+fn test_fit_rank() {
+    let path = &fixture::path("fit_lognormal");
+
+    let out = exe::run(&["fit", "--rank", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "ΔAIC");
+    assert::stdout_includes(&out, "Best fit by AIC: lognormal");
+
+    let out = exe::run(&["fit", "--rank", "--criterion", "bic", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Best fit by BIC: lognormal");
+
+    // Only the normal distribution supports negative values.
+    let out = exe::run(&["fit", "--rank", &fixture::path("normal_0_1")]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Best fit by AIC: normal");
+
+    assert::exit_fail(&exe::run(&["fit", "--rank", "--dist", "gamma", path]));
+    assert::exit_fail(&exe::run(&["fit", "--criterion", "bic", path]));
+}
+
+#[test]
+fn test_fit_simulate() {
+    let path = &fixture::path("fit_lognormal");
+
+    let args = ["fit", "--dist", "gamma", "--simulate", "1000", "--seed", "7", path];
+    let out = exe::run(&args);
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+    assert_eq!(out.stdout, exe::run(&args).stdout);
+
+    let values: Vec<f64> = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(|l| l.parse().unwrap())
+        .collect();
+    assert_eq!(values.len(), 1000);
+    assert!(values.iter().all(|&x| x > 0.0));
+
+    assert::exit_fail(&exe::run(&["fit", "--simulate", "10", path, path]));
+    assert::exit_fail(&exe::run(&["fit", "--simulate", "10", "--rank", path]));
+    assert::exit_fail(&exe::run(&["fit", "--seed", "7", path]));
+}
+
+#[test]
+fn test_table() {
+    let out = exe::run(&["table", "--table", "12,388,3,397"]);
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+    assert::stdout_includes(&out, "odds ratio = 4.0927");
+    assert::stdout_includes(&out, "relative risk = 4");
+    assert::stdout_includes(&out, "Fisher exact p = 0.0335");
+
+    let out = exe::run(&["table", &fixture::path("table_failures.csv")]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "odds ratio = 4.0927");
+
+    let out = exe::run(&["table", "--table", "0,10,5,5", "--confidence", "0.9"]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "90% CI");
+    assert::stderr_includes(&out, "prefer Fisher's exact test");
+
+    assert::exit_fail(&exe::run(&["table", "--table", "1,2,3"]));
+    assert::exit_fail(&exe::run(&["table"]));
+}
+
+#[test]
+fn test_stratify() {
+    let before = &fixture::path("strata_before");
+    let after = &fixture::path("strata_after");
+
+    let out = exe::run(&["--stratify", "1", before, after]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "/health      12       3  -4.9833333");
+    assert::stdout_includes(&out, "Pooled ± SE = -5.000385");
+    assert::stdout_includes(&out, "Aggregate = 47.74625");
+    assert::stderr_includes(&out, "Skipped strata without two varying values in each input: /admin");
+    assert::stderr_includes(&out, "Simpson's paradox");
+
+    let out = exe::run(&["--stratify", "1", before, before]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Pooled ± SE = 0 ±");
+
+    assert::exit_fail(&exe::run(&["--stratify", "2", before, after]));
+    assert::exit_fail(&exe::run(&["--stratify", "0", before, after]));
+    assert::exit_fail(&exe::run(&["--stratify", "1", before]));
+    assert::exit_fail(&exe::run(&["--stratify", "1", "--diff", before, after]));
+}
+
+#[test]
+fn test_repeated() {
+    let inputs = [fixture::path("repeated_a"), fixture::path("repeated_b"), fixture::path("repeated_c")];
+
+    let out = exe::run(&["--repeated", &inputs[0], &inputs[1], &inputs[2]]);
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+    assert::stdout_includes(&out, "F = 27.5423728");
+    assert::stdout_includes(&out, "DF = 2, 10");
+    assert::stdout_includes(&out, "p (GG) = 0.000374523");
+    assert::stdout_includes(&out, "Friedman χ² = 9.3333");
+
+    assert::exit_fail(&exe::run(&["--repeated", &inputs[0]]));
+    assert::exit_fail(&exe::run(&["--repeated", &inputs[0], &fixture::path("normal_0_1")]));
+    assert::exit_fail(&exe::run(&["--repeated", "--diff", &inputs[0], &inputs[1]]));
+}
+
+#[test]
+fn test_anova() {
+    let out = exe::run(&["anova", &fixture::path("anova_compilers")]);
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+    assert::stdout_includes(&out, "compiler          O0          O2");
+    assert::stdout_includes(&out, "compiler × opt  1.950310e1       2  5.064277e1  2.13423e-7");
+    assert::stdout_includes(&out, "Residual  2.88833333      15");
+
+    assert::exit_fail(&exe::run(&["anova", &fixture::path("normal_0_1")]));
+    assert::exit_fail(&exe::run(&["anova"]));
+}
+
+#[test]
+fn test_meta() {
+    let out = exe::run(&["meta", &fixture::path("meta_machines.tsv")]);
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+    assert::stdout_includes(&out, "x86-b        -3.9         0.8  0.44290618  0.27941202");
+    assert::stdout_includes(&out, "fixed effect = -4.2006068");
+    assert::stdout_includes(&out, "random effects = -4.5058571");
+    assert::stdout_includes(&out, "I² = 0.8115");
+
+    let out = exe::run(&["meta", &fixture::path("meta_machines.tsv"), "--confidence", "0.9"]);
+    assert::stdout_includes(&out, "90% CI");
+
+    assert::exit_fail(&exe::run(&["meta", &fixture::path("normal_0_1")]));
+    assert::exit_fail(&exe::run(&["meta"]));
+}
+
+#[test]
+fn test_drift() {
+    let baseline = &fixture::path("normal_0_1");
+
+    let out = exe::run(&["drift", baseline, baseline]);
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+    assert::stdout_includes(&out, "PSI = 0");
+    assert::stdout_includes(&out, "Drift = none");
+
+    // Drift exits with its own status, so that CI can gate on it.
+    let out = exe::run(&["drift", baseline, &fixture::path("normal_5_2")]);
+    assert_eq!(out.status.code(), Some(2));
+    assert::stdout_includes(&out, "Drift = detected");
+    assert::stderr_includes(&out, "Drift exceeds threshold: PSI");
+
+    let out = exe::run(&["drift", baseline, &fixture::path("normal_5_2"), "--max-psi", "100"]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Drift = none");
+
+    assert::exit_fail(&exe::run(&["drift", baseline, baseline, "--bins", "1"]));
+    assert::exit_fail(&exe::run(&["drift", baseline, baseline, "--max-js", "-1"]));
+    assert::exit_fail(&exe::run(&["drift", "-", "-"]));
+}
+
+#[test]
+fn test_benford() {
+    let out = exe::run(&["--benford", &fixture::path("benford_ledger")]);
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+    assert::stdout_includes(&out, "Digit   Count    Observed    Expected");
+    assert::stdout_includes(&out, "Conformity = acceptable conformity");
+
+    // Uniform over a single order of magnitude.
+    let out = exe::run(&["--benford", &fixture::path("uniform_1_10")]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Conformity = nonconformity");
+    assert::stderr_includes(&out, "so Benford's law may not apply");
+
+    assert::exit_fail(&exe::run(&["--benford", "--categorical", &fixture::path("benford_ledger")]));
+}
+
+#[test]
+fn test_lr_scatter_plot() {
+    let path = &fixture::path("lr_knee");
+
+    let out = exe::run(&["lr", "-p", "--smooth", "--span", "0.3", "--ascii", "-w", "50", path]);
+    assert::exit_ok(&out);
+    assert::stdout_eq_file(&out, "lr_scatter_smooth.out");
+
+    assert::exit_fail(&exe::run(&["lr", "--smooth", path]));
+    assert::exit_fail(&exe::run(&["lr", "-p", "--smooth", "--span", "2", path]));
+}
+
+#[test]
+fn test_series() {
+    let path = &fixture::path("series_requests");
+
+    let out = exe::run(&["series", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "2019-03-01T12:00:10Z 100\n2019-03-01T12:00:45Z 80");
+
+    let out = exe::run(&["series", "--resample", "1m", "mean", path]);
+    assert::exit_ok(&out);
+    assert::stdout_eq_file(&out, "series_resample_1m_mean.out");
+
+    let out = exe::run(&["series", "--resample", "1m", "count", "--aggregate", "2", "mean", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "2019-03-01T12:00:00Z 2\n2019-03-01T12:02:00Z 1\n");
+
+    assert::exit_fail(&exe::run(&["series", "--aggregate", "0", "mean", path]));
+    assert::exit_fail(&exe::run(&["series", "--resample", "1m", "mode", path]));
+    assert::exit_fail(&exe::run(&["series", "--resample", "soon", "mean", path]));
+    assert::exit_fail(&exe::run(&["series", &fixture::path("bad_lines")]));
+}
+
+#[test]
+fn test_series_time_checks() {
+    let path = &fixture::path("series_requests");
+
+    // Times out of order are sorted, with a warning unless it is expected.
+    let out = exe::run(&["series", path]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "2 observations are earlier than the one before, and sorted");
+    assert::stderr_includes(&out, "    line 3: time 2019-03-01T12:00:10Z is earlier than that of the line before, in ");
+
+    let out = exe::run(&["series", "--sort-time", path]);
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+
+    // Files are only checked for order within themselves, so a file may
+    // begin earlier than the one before ends.
+    let out = exe::run(&["series", path, &fixture::path("series_duplicates")]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "2 observations are earlier than the one before, and sorted");
+
+    let out = exe::run(&["series", "--sort-time", "--max-gap", "40s", path]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "2 gaps of more than 40s between observations\n");
+    assert::stderr_includes(&out, "    line 4: 45.5s gap before time 2019-03-01T12:01:30.500Z, in ");
+
+    let out = exe::run_with_stdin(fixture::file("series_duplicates"), &["series", "-"]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "1 observation is at the same time as an earlier one\n");
+    assert::stderr_includes(&out, "    line 4: time 2 again, in stdin\n");
+
+    assert::exit_fail(&exe::run(&["series", "--max-gap", "soon", path]));
+}
+
+#[test]
+fn test_series_summary() {
+    let path = &fixture::path("series_streaks");
+
+    let out = exe::run(&["series", "--summary", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Longest rise = 3 steps, from 0 to 3");
+    assert::stdout_includes(&out, "Max drawdown = 6, from 7 to 9");
+    assert::stdout_includes(&out, "Threshold = 2.5");
+    assert::stdout_includes(&out, "Crossings = 4 (2 up, 2 down)");
+
+    let out = exe::run(&["series", "--summary", "--threshold", "-1", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Crossings = 0 (0 up, 0 down)");
+
+    let out = exe::run(&["series", "--summary", &fixture::path("series_requests")]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Longest rise = ");
+
+    assert::exit_fail(&exe::run(&["series", "--summary", "--threshold", "high", path]));
+    assert::exit_fail(&exe::run(&["series", "--threshold", "1", path]));
+}
+
+#[test]
+fn test_throughput() {
+    let path1 = &fixture::path("normal_5_2");
+    let path2 = &fixture::path("normal_3_1");
+
+    let out = exe::run(&["--throughput", path1]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "ops/s ± SE = 0.192578");
+    assert::stdout_includes(&out, "95% CI = [0.179006");
+
+    // The inputs are labeled without their common directories.
+    let out = exe::run(&["--throughput", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "\nnormal_3_1");
+
+    // Durations must be positive.
+    assert::exit_fail(&exe::run(&["--throughput", &fixture::path("normal_0_1")]));
+}
+
+#[test]
+fn test_speedup() {
+    let path1 = &fixture::path("normal_5_2");
+    let path2 = &fixture::path("normal_3_1");
+
+    let out = exe::run(&["--speedup", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "speedup = 1.7617× [1.5935, 1.9448] (95% CI, Fieller)");
+
+    let out = exe::run(&["--speedup", "--seed", "3", path1, &fixture::path("normal_0_1")]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "(95% CI, approximate: bootstrap of 1000 resamples, ± ");
+
+    assert::exit_fail(&exe::run(&["--speedup", path1]));
+}
+
+#[test]
+fn test_explain() {
+    let path1 = &fixture::path("normal_5_2");
+    let path2 = &fixture::path("normal_3_1");
+
+    let out = exe::run(&["--explain", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "mean is 43.2% lower than");
+    assert::stdout_includes(&out, "the difference is statistically significant at α = 0.05");
+    assert::stdout_includes(&out, "with a large effect size (d = 1.44).");
+
+    let out = exe::run(&["--explain", "--alpha", "0.01", path1, path1]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "not statistically significant at α = 0.01");
+
+    assert::exit_fail(&exe::run(&["--explain", path1]));
+}
+
+#[test]
+fn test_robust() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_0_1_ext_outlier");
+
+    let out = exe::run(&["--robust", "--alpha", "0.05", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "MAD");
+    assert::stdout_includes(&out, "U = 5100");
+    assert::stdout_includes(&out, "Verdict = fail to reject the null hypothesis");
+
+    let out = exe::run(&["--robust", path1]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "MAD");
+
+    assert::exit_fail(&exe::run(&["--robust", "--tsv", path1]));
+}
+
+#[test]
+fn test_auto_test_selection() {
+    let skewed = &fixture::path("skewed_small");
+    let normal_0_1 = &fixture::path("normal_0_1");
+    let normal_3_1 = &fixture::path("normal_3_1");
+    let normal_5_2 = &fixture::path("normal_5_2");
+
+    let out = exe::run(&["--auto", skewed, normal_3_1]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "sample 1 is normal: Jarque–Bera = ");
+    assert::stdout_includes(&out, "Chose Mann–Whitney U test: ");
+
+    let out = exe::run(&["--auto", normal_0_1, normal_5_2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "variances are equal: Brown–Forsythe = ");
+    assert::stdout_includes(&out, "Chose Welch's t-test: ");
+
+    let out = exe::run(&["--auto", "--alpha", "0.01", normal_0_1, normal_3_1]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Assumption checks (α = 0.01):");
+    assert::stdout_includes(&out, "Chose Student's t-test: ");
+    assert::stdout_includes(&out, "Verdict = reject the null hypothesis");
+
+    assert::exit_fail(&exe::run(&["--auto", normal_0_1]));
+}
+
+#[test]
+fn test_alpha_verdict() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_3_1");
+
+    let out = exe::run(&["--alpha", "0.05", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Verdict = reject the null hypothesis");
+
+    let out = exe::run(&["--alpha", "0.01", "--stats", "10,1,1", "--stats", "10,1.5,1"]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Verdict = fail to reject the null hypothesis");
+
+    assert::exit_fail(&exe::run(&["--alpha", "5", path1, path2]));
+}
+
+#[test]
+fn test_pairwise() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+    let path3 = &fixture::path("normal_0_1_mod_outlier");
+
+    let out = exe::run(&["--alpha", "0.05", path1, path2, path3]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Pairwise t-tests (difference of means, Holm-adjusted p, α = 0.05):\n");
+    assert::stdout_includes(&out, "x₃ = normal_0_1_mod_outlier\n");
+    assert::stdout_includes(&out, "   Pair  Difference                          p  p < α\n");
+    assert::stdout_includes(&out, "x₂ - x₁  5.19025170                          0      ✓\n");
+    assert::stdout_includes(&out, "x₃ - x₁  -0.9901232        0.32381267856360485      ✗\n");
+
+    let out = exe::run(&["--alpha", "0.05", "--precision", "3", path1, path2, path3]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "x₃ - x₂  -6.1803749     3.44e-8      ✓\n");
+
+    // Without a significance level, there are no tests.
+    let out = exe::run(&[path1, path2, path3]);
+    assert::exit_ok(&out);
+    assert!(!String::from_utf8_lossy(&out.stdout).contains("Pairwise"));
+}
+
+#[test]
+fn test_reference() {
+    let path1 = &fixture::path("normal_5_2");
+    let path2 = &fixture::path("normal_3_1");
+
+    let out = exe::run(&["--reference", "1", "--alpha", "0.01", path1, path2, path1]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Compared against x₁ (Holm-adjusted p, α = 0.01):\n");
+    assert::stdout_includes(&out, "x₃ = normal_5_2\n");
+    assert::stdout_includes(&out, "Mean      m - m₁      Change           p  p < α\n");
+    assert::stdout_includes(&out, "x₁  5.19268993\n");
+    assert::stdout_includes(&out, "x₂  2.94746720  -2.2452227      -43.2%           0      ✓\n");
+    assert::stdout_includes(&out, "x₃  5.19268993           0       +0.0%           1      ✗\n");
+
+    // The reference may be any input, given by its path.
+    let out = exe::run(&["--reference", path2, path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Compared against x₂ (Holm-adjusted p, α = 0.05):\n");
+    assert::stdout_includes(&out, "Mean      m - m₂      Change           p  p < α\n");
+    assert::stdout_includes(&out, "x₁  5.19268993  2.24522273      +76.2%           0      ✓\n");
+
+    let (path1, path2) = (&fixture::path("normal_0_1"), &fixture::path("normal_0_1_mod_outlier"));
+    let out = exe::run(&["--reference", "1", "--precision", "2", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "x₂  -0.9876849  -0.9901232   -40608.3%        0.32      ✗\n");
+
+    let out = exe::run(&["--reference", "3", path1, path2]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "No input to compare against: \"3\"");
+
+    assert::exit_fail(&exe::run(&["--reference", "1", path1]));
+}
+
+#[test]
+fn test_show_extremes() {
+    let path = &fixture::path("normal_0_1_mod_outlier");
+
+    let out = exe::run(&["--show-extremes", "2", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Extremes of tests/support/fixture/normal_0_1_mod_outlier:\n");
+    assert::stdout_includes(&out, "  Rank    Smallest    Line     Largest    Line\n");
+    assert::stdout_includes(&out, "     1        -100       1  2.41686649      62\n");
+    assert::stdout_includes(&out, "     2  -2.5985268      50  2.01455637      70\n");
+    assert!(!String::from_utf8_lossy(&out.stdout).contains("     3  "));
+
+    // Blank lines are counted, though they hold no value.
+    let out = exe::run(&["--show-extremes", "1", &fixture::path("empty_lines")]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "     1           1       1           5       9\n");
+
+    assert::exit_fail(&exe::run(&["--show-extremes", "x", path]));
+}
+
+#[test]
+fn test_quantize() {
+    let path = &fixture::path("near_resolution");
+
+    let out = exe::run(&["--round", "0.1", "-v", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "     5        -0.1         0.1         0.3         0.3         0.3        0.18");
+    assert::stdout_includes(&out, "           2         3\n");
+
+    let out = exe::run(&["--bucket", "0.25", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "     5       -0.25           0        0.25        0.25        0.25         0.1");
+
+    assert::exit_fail(&exe::run(&["--round", "0", path]));
+    assert::exit_fail(&exe::run(&["--bucket", "wide", path]));
+    assert::exit_fail(&exe::run(&["--round", "1", "--bucket", "1", path]));
+}
+
+#[test]
+fn test_tails() {
+    let path = &fixture::path("normal_0_1_mod_outlier");
+
+    let out = exe::run(&["--winsorize", "0.05", path]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "normal_0_1_mod_outlier: winsorized 5 low and 5 high values");
+    assert::stdout_includes(&out, "   101  -1.5516729  -0.6813048");
+
+    let out = exe::run(&["--trim-tails", "0.05", path]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "normal_0_1_mod_outlier: trimmed 10 of 101 values");
+    assert::stdout_includes(&out, "    91  -1.5516729  -0.5834491");
+
+    let out = exe::run(&["--trim-tails", "0.5", path]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "Invalid --trim-tails fraction: \"0.5\"");
+
+    // Too small a fraction to cut any value.
+    assert::stderr_is_empty(&exe::run(&["--winsorize", "0.005", path]));
+    assert::stderr_is_empty(&exe::run(&["--trim-tails", "0.005", path]));
+
+    assert::exit_fail(&exe::run(&["--winsorize", "0.1", "--trim-tails", "0.1", path]));
+}
+
+#[test]
+fn test_dedup() {
+    let path = &fixture::path("duplicates");
+
+    let out = exe::run(&[path, "--dedup"]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "duplicates: dropped 3 duplicate values of 6");
+    assert::stdout_includes(&out, "     3           1         1.5           2");
+
+    let out = exe::run(&["--dedup=consecutive", path]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "duplicates: dropped 2 duplicate values of 6");
+    assert::stdout_includes(&out, "     4           1           1         1.5");
+
+    let out = exe::run(&["--dedup", &fixture::path("normal_0_1")]);
+    assert::exit_ok(&out);
+    assert::stderr_is_empty(&out);
+
+    assert::exit_fail(&exe::run(&["--dedup=some", path]));
+}
+
+#[test]
+fn test_derive() {
+    let path = &fixture::path("estimates");
+
+    let out = exe::run(&["--derive", "(predicted - actual) / actual", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "     4        -0.1  -2.5000e-2        0.05         0.1         0.1       0.025");
+
+    let out = exe::run(&["--derive", "predicted - guess", path]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "No column named \"guess\"");
+
+    assert::exit_fail(&exe::run(&["--derive", "(actual", path]));
+}
+
+#[test]
+fn test_method_comparison() {
+    let path1 = &fixture::path("method_a");
+    let path2 = &fixture::path("method_b");
+
+    let out = exe::run(&["--bland-altman", "--passing-bablok", "-p", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "method_b - method_a");
+    assert::stdout_includes(&out, "Bias = 1.041666");
+    assert::stdout_includes(&out, "95% LoA = [0.155387");
+    assert::stdout_includes(&out, "Slope = 1.020061");
+    assert::stdout_includes(&out, "──────");
+
+    let out = exe::run(&["--bland-altman", path1]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "Method comparison requires exactly two inputs");
+
+    assert::exit_fail(&exe::run(&["--passing-bablok", path1, &fixture::path("normal_0_1")]));
+}
+
+#[test]
+fn test_rank_of() {
+    let path = &fixture::path("normal_0_1");
+
+    let out = exe::run(&["--rank-of", "0", "--rank-of", "-1.5", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Input       Value  Percentile        Rank    Size");
+    assert::stdout_includes(&out, "normal_0_1           0  4.943091e1  4.993660e1     100");
+    assert::stdout_includes(&out, "normal_0_1        -1.5");
+
+    assert::exit_fail(&exe::run(&["--rank-of", "slow", path]));
+}
+
+#[test]
+fn test_threshold() {
+    let path = &fixture::path("normal_0_1");
+
+    let out = exe::run(&["--threshold", "1.5", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Values   Count  Proportion                    95% CI        Mean");
+    assert::stdout_includes(&out, "> 1.5       5        0.05  [2.15437e-2, 0.11175047]  1.93584917");
+    assert::stdout_includes(&out, "≤ 1.5      95        0.95");
+
+    assert::exit_fail(&exe::run(&["--threshold", "NaN", path]));
+}
+
+#[test]
+fn test_exceedance_test() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+
+    let out = exe::run(&["--threshold", "1", "--exceedance-test", "--alpha", "0.05", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Proportion > 1: normal_5_2 - normal_0_1");
+    assert::stdout_includes(&out, "Δ = 0.85, 95% CI [0.75582048");
+    assert::stdout_includes(&out, "Verdict = reject the null hypothesis");
+
+    let out = exe::run(&["--threshold", "1", "--exceedance-test", path1]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "Comparing exceedances requires exactly two inputs");
+
+    assert::exit_fail(&exe::run(&["--exceedance-test", path1, path2]));
+}
+
+#[test]
+fn test_histogram() {
+    let path = &fixture::path("fit_lognormal");
+
+    let out = exe::run(&["--histogram", "--bins", "quantile:4", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "[0.369, 0.801) │");
+    assert::stdout_includes(&out, "[1.47, 3.06] │");
+
+    let out = exe::run(&["--histogram", "--bins", "log:5", "--ascii", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "[0.563, 0.860) |");
+
+    let out = exe::run(&["--histogram", "--bins", "log:5", &fixture::path("normal_0_1")]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "Log-spaced bins require positive values");
+
+    // Both inputs share bins, which span them both, and bars scaled by the
+    // proportion of each input in them.
+    let out = exe::run(&["--histogram", "--bins", "4", &fixture::path("normal_0_1"), &fixture::path("normal_5_2")]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "[6.65, 9.73] │ 0 (0.0%)\n");
+    assert::stdout_includes(&out, "[-2.60, 0.485) │█ 1 (1.0%)\n");
+
+    let out = exe::run(&["--histogram", "--overlay", "--ascii", "-w", "40", &fixture::path("normal_0_1"), &fixture::path("normal_5_2")]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "+ normal_5_2");
+    assert::stdout_includes(&out, "# both");
+
+    let out = exe::run(&["--histogram", "--overlay", path]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "Overlaid histograms require exactly two inputs");
+
+    let out = exe::run(&["--histogram=vertical", "--bins", "20", "--ascii", "-w", "60", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "9 |          ##                                            |\n");
+    assert::stdout_includes(&out, "    0.37                                              3.06\n");
+
+    assert::exit_fail(&exe::run(&["--histogram=sideways", path]));
+    // `--hist` is short for `--histogram=vertical`, and can't be overlaid.
+    let out = exe::run(&["--hist", "--bins", "20", "--ascii", "-w", "60", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "9 |          ##                                            |\n");
+
+    let out = exe::run(&["--histogram=vertical", "--overlay", path, path]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "--overlay requires horizontal histograms");
+    assert::exit_fail(&exe::run(&["--hist", "--overlay", path, path]));
+
+    let out = exe::run(&["--histogram", "--overlay", "--bins", "quantile:4", path, path]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "--overlay requires bins of equal width");
+    assert::exit_fail(&exe::run(&["--histogram", "--bins", "log:x", path]));
+    assert::exit_fail(&exe::run(&["--bins", "5", path]));
+}
+
+#[test]
+fn test_freq_table() {
+    let path = &fixture::path("normal_0_1");
+
+    let out = exe::run(&["--freq-table", "--bins", "3", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Bin   Count  Proportion  Cumulative %");
+    assert::stdout_includes(&out, "[-0.927, 0.745)      61        0.61            80");
+
+    let out = exe::run(&["--freq-table", "--bins", "3", "--format", "tsv", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Source\tLower\tUpper\tCount\tProportion\tCumulativePercent\n");
+    assert::stdout_includes(&out, "\t2.41686649\t20\t0.2\t100\n");
+
+    let out = exe::run(&["--freq-table", "--bins", "3", "--format", "prometheus", path]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "# TYPE dent_histogram histogram\n");
+    assert::stdout_includes(&out, "le=\"+Inf\"} 100\n");
+
+    let out = exe::run(&["--bins", "3", path]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "--bins requires --histogram, --hist, --freq-table, or --sparkline");
+}
+
+#[test]
+fn test_streaming() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+
+    // The moments are exact, and the quartiles and adjacent values are
+    // estimates, marked as such.
+    let out = exe::run(&["--streaming", path1]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "   100  ~-2.5985268  ~-0.7385331  ~8.95706e-2  ~0.63409225  ~2.41686649  2.43823e-3  0.92541938");
+    assert::stdout_includes(&out, "~ approximate: P² quantiles, with the fences as adjacent values\n");
+
+    let out = exe::run(&["--streaming", "--brief", path1]);
+    assert::stdout_includes(&out, "n=100 min=-2.60 q1~-0.739 med~0.0896 q3~0.634 max=2.42 mean=");
+
+    let out = exe::run(&["--streaming", "--tsv", path1]);
+    assert::stdout_includes(&out, "\tUnit\tMethod\n");
+    assert::stdout_includes(&out, "\t\tsketch\n");
+
+    let out = exe::run(&["--streaming", "--format", "prometheus", path1]);
+    assert::stdout_includes(&out, "dent_median{source=\"tests/support/fixture/normal_0_1\",method=\"sketch\"} ");
+    assert::stdout_includes(&out, "dent_mean{source=\"tests/support/fixture/normal_0_1\"} ");
+
+    let out = exe::run(&["--streaming", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "DF = 140.126193502826");
+
+    let nonfinite = &fixture::path("nonfinite");
+    assert::exit_fail(&exe::run(&["--streaming", nonfinite]));
+
+    let out = exe::run(&["--streaming", "--drop-nonfinite", nonfinite]);
+    assert::exit_ok(&out);
+    assert::stderr_includes(&out, "Dropped 4 non-finite values");
+
+    assert::exit_fail(&exe::run(&["--streaming", "--robust", path1, path2]));
+}
+
+#[test]
+fn test_vertical() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+
+    let out = exe::run(&["--vertical", path1]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "Size            100\nMin Adj  -1.9126341\nQ1       -0.6643476\n");
+
+    // Each input has a column, headed by its label, after the source it labels.
+    let out = exe::run(&["--vertical", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "          x₁ = normal_0_1\n          x₂ = normal_5_2\n");
+    assert::stdout_includes(&out, "                 x₁          x₂\nSize            100         100\n");
+    assert::stdout_includes(&out, "m₂ - m₁ ± SE = ");
+
+    assert::exit_fail(&exe::run(&["--vertical", "--tsv", path1]));
+}
+
+#[test]
+fn test_brief() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+
+    let out = exe::run(&["--brief", path1]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "n=100 min=-2.60 q1=-0.664 med=0.00178 q3=0.584 max=2.42 mean=0.00244±0.0925\n");
+
+    let out = exe::run(&["--brief", "--precision", "2", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "normal_5_2: n=100 min=0.17 q1=4.0 med=5.1 q3=6.2 max=9.7 mean=5.2±0.20\n");
+
+    assert::exit_fail(&exe::run(&["--brief", "--tsv", path1]));
+
+    // The inputs share bins, so their sparklines can be compared.
+    let out = exe::run(&["--brief", "--sparkline", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "mean=0.00244±0.0925 ▂▆█▂▁     \n");
+    assert::stdout_includes(&out, "mean=5.19±0.198   ▁▂▃█▇▄▃▂\n");
+
+    assert::exit_fail(&exe::run(&["--sparkline", path1]));
+}
+
+#[test]
+fn test_no_pager() {
+    let path1 = &fixture::path("normal_0_1");
+    let path2 = &fixture::path("normal_5_2");
+
+    // Redirected output is never paged, and is the same either way.
+    let out = exe::run(&["--no-pager", "-p", "-w", "90", path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_eq_file(&out, "comparison_plot.out");
+
+    let out = exe::run(&["lr", "--no-pager", &fixture::path("lr_group_1")]);
+    assert::exit_ok(&out);
+}
+
+#[test]
+fn test_config_defaults() {
+    let config = &fixture::path("dent_config.toml");
+    let path1 = &fixture::path("normal_5_2");
+    let path2 = &fixture::path("normal_3_1");
+
+    let out = exe::run_with_env(&[("DENT_CONFIG", config)], &[path1, path2]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, "m₁ ± SE = 5.19 ± 0.198");
+    assert::stdout_includes(&out, "α = 0.01");
+
+    // The environment overrides the file, and flags override both.
+    let env = [("DENT_CONFIG", config.as_str()), ("DENT_PRECISION", "2")];
+    let out = exe::run_with_env(&env, &[path1, path2]);
+    assert::stdout_includes(&out, "m₁ ± SE = 5.2 ± 0.20");
+
+    let out = exe::run_with_env(&env, &["--precision", "4", "--alpha", "0.05", path1, path2]);
+    assert::stdout_includes(&out, "m₁ ± SE = 5.193 ± 0.1984");
+    assert::stdout_includes(&out, "α = 0.05");
+
+    let out = exe::run_with_env(&[("DENT_CONFIG", config), ("DENT_FORMAT", "tsv")], &[path1]);
+    assert::stdout_includes(&out, "Source\tSize");
+
+    let out = exe::run_with_env(&[("DENT_CONFIG", config), ("DENT_ALPHA", "2")], &[path1]);
+    assert::exit_fail(&out);
+    assert::stderr_includes(&out, "DENT_ALPHA: invalid value for `alpha`");
+}
+
+#[test]
+fn test_completions() {
+    for shell in &["bash", "zsh", "fish"] {
+        let out = exe::run(&["completions", shell]);
+        assert::exit_ok(&out);
+        assert::stdout_includes(&out, "dent");
+        assert::stdout_includes(&out, "plot-style");
+        assert::stderr_is_empty(&out);
+    }
+
+    let out = exe::run(&["completions", "tcsh"]);
+    assert::exit_fail(&out);
+}
+
+#[test]
+fn test_man() {
+    // A broken config must not keep the manual from being printed.
+    let out = exe::run_with_env(&[("DENT_ALPHA", "2")], &["man"]);
+    assert::exit_ok(&out);
+    assert::stdout_includes(&out, ".TH DENT 1");
+    assert::stdout_includes(&out, "dent \\- A tiny tool for t\\-tests &c.");
+    assert::stdout_includes(&out, "\\fB\\-p, \\-\\-plot\\fR");
+    assert::stdout_includes(&out, ".SH \"DENT LR\"");
+    assert::stdout_includes(&out, "dent lr [FLAGS] [OPTIONS] <FILES>...");
+}
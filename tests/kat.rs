@@ -86,3 +86,1797 @@ t_test_kat!(t_test_kat66, "ttest-1_1_1000-1_1_100");
 lr_kat!(lr_test_0_1_100, "lr-0_1_100");
 lr_kat!(lr_test_0_1_1000, "lr-0_1_1000");
 lr_kat!(lr_test_1_5_1000, "lr-1_5_1000");
+
+#[test]
+fn mean_confidence_interval_kat() {
+    use dent::summary::Summary;
+
+    let summary = Summary::new(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+    let (lo, hi) = summary.mean_confidence_interval(0.95).unwrap();
+
+    // Student's t quantile t(0.975, 4) = 2.7764451051977987.
+    assert_appx_eq!("Lower bound", 1e-9, 1.0367568385224, lo);
+    assert_appx_eq!("Upper bound", 1e-9, 4.9632431614776, hi);
+
+    assert!(Summary::new(&[1.0]).unwrap().mean_confidence_interval(0.95).is_err());
+    assert!(summary.mean_confidence_interval(1.0).is_err());
+}
+
+#[test]
+fn weighted_summary_kat() {
+    use dent::summary::Summarizer;
+    use dent::weighted::WeightedSummarizer;
+
+    let data = [2.0, 4.0, 4.0, 5.0, 7.0, 9.0];
+    let pairs: Vec<_> = data.iter().map(|&x| (x, 1.0)).collect();
+
+    let plain = Summarizer::new(&data).unwrap();
+    let weighted = WeightedSummarizer::new(&pairs).unwrap();
+
+    let precision = 1e-12;
+
+    assert_appx_eq!("Mean", precision, plain.mean(), weighted.mean());
+    assert_appx_eq!("Variance", precision,
+                    plain.unbiased_variance(), weighted.unbiased_variance());
+    assert_appx_eq!("Lower quartile", precision,
+                    plain.lower_quartile(), weighted.lower_quartile());
+    assert_appx_eq!("Median", precision, plain.median(), weighted.median());
+    assert_appx_eq!("Upper quartile", precision,
+                    plain.upper_quartile(), weighted.upper_quartile());
+
+    let repeated = WeightedSummarizer::new(&[(1.0, 2.0), (3.0, 1.0)]).unwrap();
+    assert_appx_eq!("Weighted mean", precision, 5.0 / 3.0, repeated.mean());
+}
+
+#[test]
+fn frequency_summary_kat() {
+    use dent::frequency::FrequencySummarizer;
+    use dent::summary::Summarizer;
+
+    let pairs = [(4.0, 3), (-1.0, 1), (2.5, 0), (7.0, 2), (4.0, 1), (10.0, 5)];
+    let mut expanded = vec![];
+    for &(x, c) in &pairs {
+        for _ in 0..c {
+            expanded.push(x);
+        }
+    }
+
+    let plain = Summarizer::new(&expanded).unwrap();
+    let freq = FrequencySummarizer::new(&pairs).unwrap();
+
+    let precision = 1e-12;
+
+    assert_eq!(freq.distinct(), 4);
+    assert_appx_eq!("Size", precision, plain.size(), freq.size());
+    assert_appx_eq!("Mean", precision, plain.mean(), freq.mean());
+    assert_appx_eq!("Median", precision, plain.median(), freq.median());
+    assert_appx_eq!("Variance", precision,
+                    plain.unbiased_variance(), freq.unbiased_variance());
+    assert_appx_eq!("Min adjacent", precision, plain.min_adjacent(), freq.min_adjacent());
+    assert_appx_eq!("Max adjacent", precision, plain.max_adjacent(), freq.max_adjacent());
+
+    for i in 0..=20 {
+        let p = i as f64 / 20.0;
+        assert_appx_eq!("Percentile", precision,
+                        plain.percentile(p).unwrap(), freq.percentile(p).unwrap());
+    }
+}
+
+#[test]
+fn shift_function_of_translate() {
+    use dent::rng::Rng;
+    use dent::shift::{DECILES, ShiftFunction};
+    use dent::summary::Summarizer;
+
+    let data: Vec<f64> = (0..50).map(|i| (i * i % 17) as f64).collect();
+    let shifted: Vec<f64> = data.iter().map(|x| x + 3.0).collect();
+
+    let x = Summarizer::new(&data).unwrap();
+    let y = Summarizer::new(&shifted).unwrap();
+
+    let mut rng = Rng::new(1);
+    let f = ShiftFunction::new(&x, &y, &DECILES, 0.95, 200, &mut rng).unwrap();
+
+    assert_eq!(f.points.len(), DECILES.len());
+
+    for p in &f.points {
+        assert_appx_eq!("Difference", 1e-12, 3.0, p.difference);
+        assert!(p.interval.value.0 <= p.interval.value.1);
+    }
+
+    assert!(ShiftFunction::new(&x, &y, &DECILES, 0.95, 0, &mut rng).is_err());
+}
+
+#[test]
+fn summary_percentiles_snapshot() {
+    use dent::summary::{Percentiles, Summarizer, Summary};
+    use support::fs::read_data;
+
+    let data = read_data("support/data/1.1_1_100");
+    let summarizer = Summarizer::new(&data).unwrap();
+    let summary = Summary::new_with_percentiles(&data, &Percentiles::evenly_spaced(100)).unwrap();
+
+    let precision = 1e-12;
+
+    for &p in &[0.0, 0.01, 0.5, 0.95, 0.99, 1.0] {
+        assert_appx_eq!("Recorded percentile", precision,
+                        summarizer.percentile(p).unwrap(), summary.percentile(p).unwrap());
+    }
+
+    // Between recorded percentiles, we interpolate.
+    let (p95, p96) = (summary.percentile(0.95).unwrap(), summary.percentile(0.96).unwrap());
+    assert_appx_eq!("Interpolated percentile", precision,
+                    (p95 + p96) / 2.0, summary.percentile(0.955).unwrap());
+
+    let partial = Summary::new_with_percentiles(&data, &[0.99, 0.5]).unwrap();
+    assert_eq!(partial.percentiles().unwrap().as_slice().len(), 2);
+    assert!(partial.percentile(0.25).is_err());
+    assert!(partial.percentile(0.75).is_ok());
+
+    assert!(Summary::new(&data).unwrap().percentile(0.5).is_err());
+    assert!(Summary::new_with_percentiles(&data, &[]).is_err());
+    assert!(Summary::new_with_percentiles(&data, &[1.5]).is_err());
+}
+
+#[test]
+fn percentile_of_unsorted_kat() {
+    use dent::summary::{percentile_of_unsorted, Summarizer};
+    use support::fs::read_data;
+
+    let data = read_data("support/data/1.1_1_1000");
+    let summarizer = Summarizer::new(&data).unwrap();
+
+    let precision = 1e-14;
+
+    for &p in &[0.0, 0.001, 0.25, 0.5, 0.95, 0.99, 0.9995, 1.0] {
+        let mut scratch = data.clone();
+        assert_appx_eq!("Percentile", precision,
+                        summarizer.percentile(p).unwrap(),
+                        percentile_of_unsorted(&mut scratch, p).unwrap());
+    }
+
+    assert!(percentile_of_unsorted(&mut [], 0.5).is_err());
+    assert!(percentile_of_unsorted(&mut [1.0, f64::NAN], 0.5).is_err());
+    assert!(percentile_of_unsorted(&mut [1.0, 2.0], 1.5).is_err());
+}
+
+#[test]
+fn rank_of_kat() {
+    use dent::summary::Summarizer;
+
+    let s = Summarizer::new(&[4.0, 2.0, 1.0, 2.0]).unwrap();
+
+    // Tied values take their mean rank, and others are interpolated.
+    assert_eq!(s.rank_of(2.0).unwrap(), 1.5);
+    assert_eq!(s.rank_of(3.0).unwrap(), 2.5);
+    assert_eq!(s.rank_of(0.0).unwrap(), 0.0);
+    assert_eq!(s.rank_of(5.0).unwrap(), 3.0);
+    assert_eq!(s.cdf_at(2.0).unwrap(), 0.5);
+    assert_eq!(s.cdf_at(3.0).unwrap(), 2.5 / 3.0);
+    assert_eq!(s.cdf_at(4.0).unwrap(), 1.0);
+
+    let s = Summarizer::new(&[1.0, 2.0, 3.0, 5.0, 8.0]).unwrap();
+
+    for &p in &[0.0, 0.1, 0.25, 0.6, 0.9, 1.0] {
+        assert_appx_eq!("CDF", 1e-12, p, s.cdf_at(s.percentile(p).unwrap()).unwrap());
+    }
+
+    let s = Summarizer::new(&[7.0]).unwrap();
+    assert_eq!(s.cdf_at(7.0).unwrap(), 0.5);
+    assert_eq!(s.cdf_at(6.0).unwrap(), 0.0);
+    assert!(s.rank_of(f64::NAN).is_err());
+}
+
+#[test]
+fn summarizer_from_vec() {
+    use dent::summary::{NonFinitePolicy, Summarizer};
+    use support::fs::read_data;
+
+    let data = read_data("support/data/1.1_1_100");
+    let borrowed = Summarizer::new(&data).unwrap();
+    let owned = Summarizer::from_vec(data.clone()).unwrap();
+
+    assert_eq!(borrowed.as_slice(), owned.as_slice());
+    assert_eq!(borrowed.mean(), owned.mean());
+    assert_eq!(borrowed.unbiased_variance(), owned.unbiased_variance());
+
+    assert!(Summarizer::from_vec(vec![]).is_err());
+    assert!(Summarizer::from_vec(vec![1.0, f64::INFINITY]).is_err());
+
+    let dropped = Summarizer::from_vec_with_policy(
+        vec![3.0, f64::NAN, 1.0, f64::NEG_INFINITY],
+        NonFinitePolicy::DropWithReport,
+    ).unwrap();
+    assert_eq!(dropped.as_slice(), &[1.0, 3.0]);
+    assert_eq!(dropped.nonfinite_report().unwrap().total(), 2);
+}
+
+#[test]
+fn variance_with_large_offset_kat() {
+    use dent::summary::Summarizer;
+    use support::fs::read_data;
+
+    // Exact in floating point, for any offset below 2^53.
+    for &offset in &[0.0, 1e9, 1e15] {
+        let data: Vec<f64> = [4.0, 7.0, 13.0, 16.0].iter().map(|x| offset + x).collect();
+        let s = Summarizer::new(&data).unwrap();
+
+        assert_eq!(s.unbiased_variance(), 30.0);
+    }
+
+    // Known values from exact rational arithmetic on the offset data.
+    let data = read_data("support/data/1_1_100");
+
+    for &(offset, known) in &[(1e6, 1.0088566229610938), (1e9, 1.0088566220213908)] {
+        let shifted: Vec<f64> = data.iter().map(|x| offset + x).collect();
+        let s = Summarizer::new(&shifted).unwrap();
+
+        assert_appx_eq!("Variance", 1e-12, known, s.unbiased_variance());
+    }
+}
+
+#[test]
+fn inc_beta_at_symmetry_bound() {
+    use dent::special::inc_beta;
+
+    // By symmetry, `I_{1/2}(a, a) = 1/2`. This point is also the bound at
+    // which `inc_beta` switches evaluation strategy.
+    for &a in &[1.0, 7.5, 500.0] {
+        assert_appx_eq!("I_0.5(a, a)", 1e-12, 0.5, inc_beta(0.5, a, a).unwrap());
+    }
+}
+
+#[test]
+fn special_functions_kat() {
+    use dent::special::{
+        beta, digamma, erf, inc_beta, inc_gamma, ln_gamma, normal_cdf, normal_quantile, trigamma,
+    };
+    use std::f64::consts::PI;
+
+    let precision = 1e-12;
+
+    assert_eq!(ln_gamma(1.0).unwrap(), 0.0);
+    assert_appx_eq!("ln Γ(1/2)", precision, 0.5 * PI.ln(), ln_gamma(0.5).unwrap());
+    assert_appx_eq!("ln Γ(10)", precision, 362880f64.ln(), ln_gamma(10.0).unwrap());
+    assert_appx_eq!("ln |Γ(-1/2)|", precision, (2.0 * PI.sqrt()).ln(), ln_gamma(-0.5).unwrap());
+    assert!(ln_gamma(0.0).is_err());
+    assert!(ln_gamma(-3.0).is_err());
+    assert!(ln_gamma(f64::NAN).is_err());
+
+    assert_appx_eq!("B(2, 3)", precision, 1.0 / 12.0, beta(2.0, 3.0).unwrap());
+    assert_appx_eq!("B(1/2, 1/2)", precision, PI, beta(0.5, 0.5).unwrap());
+    assert!(beta(0.0, 1.0).is_err());
+    assert!(beta(1.0, -1.0).is_err());
+
+    assert_eq!(inc_beta(0.0, 2.0, 3.0).unwrap(), 0.0);
+    assert_appx_eq!("I_1(2, 3)", precision, 1.0, inc_beta(1.0, 2.0, 3.0).unwrap());
+    assert_appx_eq!("I_x(1, 1)", precision, 0.3, inc_beta(0.3, 1.0, 1.0).unwrap());
+    assert_appx_eq!("I_0.3(2, 3)", precision, 0.3483, inc_beta(0.3, 2.0, 3.0).unwrap());
+    assert_appx_eq!("I_0.9(2, 3)", precision, 0.9963, inc_beta(0.9, 2.0, 3.0).unwrap());
+    assert!(inc_beta(-0.1, 2.0, 3.0).is_err());
+    assert!(inc_beta(1.1, 2.0, 3.0).is_err());
+    assert!(inc_beta(0.5, 0.0, 3.0).is_err());
+
+    assert_eq!(erf(0.0), 0.0);
+    assert_appx_eq!("erf(0.5)", 1e-15, 0.5204998778130465, erf(0.5));
+    assert_appx_eq!("erf(1)", 1e-15, 0.8427007929497149, erf(1.0));
+    assert_appx_eq!("erf(-1)", 1e-15, -0.8427007929497149, erf(-1.0));
+
+    assert_eq!(normal_cdf(0.0), 0.5);
+    assert_appx_eq!("Φ(1.96)", precision, 0.9750021048517795, normal_cdf(1.96));
+    assert_appx_eq!("Φ(-1.96)", precision, 1.0 - 0.9750021048517795, normal_cdf(-1.96));
+
+    assert_eq!(normal_quantile(0.5).unwrap(), 0.0);
+    assert_appx_eq!("Φ⁻¹(0.975)", precision, 1.95996398454005, normal_quantile(0.975).unwrap());
+    assert_appx_eq!("Φ⁻¹(0.01)", precision, -2.32634787404084, normal_quantile(0.01).unwrap());
+    assert_appx_eq!("Φ⁻¹(1e-10)", precision, -6.36134090240406, normal_quantile(1e-10).unwrap());
+    assert!(normal_quantile(0.0).is_err());
+    assert!(normal_quantile(1.0).is_err());
+
+    assert_appx_eq!("ψ(1/2)", precision, -1.96351002602142, digamma(0.5).unwrap());
+    assert_appx_eq!("ψ(3.7)", precision, 1.16715353936151, digamma(3.7).unwrap());
+    assert_appx_eq!("ψ'(1/2)", precision, PI * PI / 2.0, trigamma(0.5).unwrap());
+    assert_appx_eq!("ψ'(3.7)", precision, 0.310037857670038, trigamma(3.7).unwrap());
+    assert!(digamma(0.0).is_err());
+    assert!(trigamma(-1.5).is_err());
+
+    assert_eq!(inc_gamma(0.0, 2.5).unwrap(), 0.0);
+    assert_appx_eq!("P(1, x)", precision, 1.0 - (-0.7f64).exp(), inc_gamma(0.7, 1.0).unwrap());
+    assert_appx_eq!("P(2.5, 1.3)", precision, 0.238634732154986, inc_gamma(1.3, 2.5).unwrap());
+    assert_appx_eq!("P(2.5, 7)", precision, 0.984390583899733, inc_gamma(7.0, 2.5).unwrap());
+    assert_appx_eq!("P(100, 90)", 1e-10, 0.15822098918643, inc_gamma(90.0, 100.0).unwrap());
+    assert!(inc_gamma(-1.0, 2.0).is_err());
+    assert!(inc_gamma(1.0, 0.0).is_err());
+}
+
+#[test]
+fn t_quantile_large_df_kat() {
+    use dent::t_test::t_quantile;
+
+    // Known values from arbitrary-precision evaluation of the incomplete
+    // beta function, either side of the switch to a normal approximation.
+    let known = [
+        (1e6, 1.959966356814107),
+        (2e6, 1.959965170676375),
+        (1e9, 1.9599639869123255),
+    ];
+
+    for &(df, q) in &known {
+        assert_appx_eq!("t(0.975, df)", 1e-10, q, t_quantile(0.975, df).unwrap());
+    }
+
+    // The limit is the normal quantile.
+    assert_appx_eq!("t(0.975, 1e15)", 1e-10, 1.959963984540054, t_quantile(0.975, 1e15).unwrap());
+}
+
+#[test]
+fn summary_tsv_round_trip() {
+    use dent::summary::{parse_summary_table, Summary, TSV_COLUMNS};
+    use support::fs::read_data;
+
+    let data = read_data("support/data/1.1_1_100");
+    let summary = Summary::new(&data).unwrap();
+
+    let record = summary.to_tsv_record("src");
+    let (src, parsed) = Summary::from_tsv_record(&record).unwrap();
+
+    assert_eq!(src, "src");
+    assert_eq!(parsed.to_tsv_record("src"), record);
+    assert_eq!(parsed.mean(), summary.mean());
+    assert_eq!(parsed.standard_error(), summary.standard_error());
+
+    let table = format!("{}\n{}\n{}\n", TSV_COLUMNS.join("\t"), record, record);
+    assert_eq!(parse_summary_table(&table).unwrap().len(), 2);
+
+    assert!(parse_summary_table(&record).is_err());
+    assert!(parse_summary_table(&TSV_COLUMNS.join("\t")).is_err());
+    assert!(Summary::from_tsv_record("src\t1\t2").is_err());
+
+    // Swapping the min and max gives an inconsistent summary.
+    let fields: Vec<&str> = record.split('\t').collect();
+    let mut swapped = fields.clone();
+    swapped.swap(7, 8);
+    assert!(Summary::from_tsv_record(&swapped.join("\t")).is_err());
+}
+
+#[test]
+fn summary_unit_round_trip() {
+    use dent::summary::{parse_summary_table, Summary, TSV_COLUMNS, TSV_UNIT_COLUMN};
+
+    let summary = Summary::new(&[1.0, 2.0, 4.0]).unwrap();
+    assert_eq!(summary.unit(), None);
+
+    let summary = summary.with_unit("ms");
+    assert_eq!(summary.unit(), Some("ms"));
+
+    let record = summary.to_tsv_record_with_unit("src");
+    assert!(record.ends_with("\tms"));
+
+    let (_, parsed) = Summary::from_tsv_record(&record).unwrap();
+    assert_eq!(parsed.unit(), Some("ms"));
+
+    // An empty unit is none.
+    let unitless = Summary::new(&[1.0, 2.0, 4.0]).unwrap().to_tsv_record_with_unit("src");
+    assert_eq!(Summary::from_tsv_record(&unitless).unwrap().1.unit(), None);
+
+    let header = format!("{}\t{}", TSV_COLUMNS.join("\t"), TSV_UNIT_COLUMN);
+    let table = format!("{}\n{}\n{}\n", header, record, unitless);
+    let parsed = parse_summary_table(&table).unwrap();
+    assert_eq!(parsed[0].1.unit(), Some("ms"));
+    assert_eq!(parsed[1].1.unit(), None);
+
+    let header = format!("{}\tUnits", TSV_COLUMNS.join("\t"));
+    assert!(parse_summary_table(&format!("{}\n{}\n", header, record)).is_err());
+    assert!(Summary::from_tsv_record(&format!("{}\tms", record)).is_err());
+}
+
+#[test]
+fn t_test_from_stats_kat() {
+    use dent::summary::Summary;
+    use dent::t_test::{welch_t_test, welch_t_test_from_stats};
+    use support::fs::read_data;
+
+    let s1 = Summary::new(&read_data("support/data/1_1_100")).unwrap();
+    let s2 = Summary::new(&read_data("support/data/1.1_1_100")).unwrap();
+
+    let known = welch_t_test(&s1, &s2).unwrap();
+    let t_test = welch_t_test_from_stats(
+        s1.size(), s1.mean(), s1.standard_deviation(),
+        s2.size(), s2.mean(), s2.standard_deviation(),
+    ).unwrap();
+
+    let precision = 1e-11;
+
+    assert_appx_eq!("T statistic", precision, known.t, t_test.t);
+    assert_appx_eq!("P value", precision, known.p, t_test.p);
+    assert_appx_eq!("DF", precision, known.df, t_test.df);
+
+    assert!(welch_t_test_from_stats(1.0, 0.0, 1.0, 10.0, 0.0, 1.0).is_err());
+    assert!(welch_t_test_from_stats(10.0, 0.0, -1.0, 10.0, 0.0, 1.0).is_err());
+}
+
+#[test]
+fn sig_level_critical_values() {
+    use dent::t_test::{t_quantile, SigLevel, TTest, Verdict};
+
+    // Known values of the t quantile function.
+    let precision = 1e-9;
+
+    assert_appx_eq!("t(0.975, 10)", precision,
+                    2.2281388519649385, SigLevel::Alpha005.critical_value(10.0).unwrap());
+    assert_appx_eq!("t(0.95, 10)", precision,
+                    1.8124611228107341, SigLevel::Alpha010.critical_value(10.0).unwrap());
+    assert_appx_eq!("t(0.995, 30)", precision,
+                    2.7499956535670305, SigLevel::Alpha001.critical_value(30.0).unwrap());
+    assert_appx_eq!("t(0.9, 5)", precision,
+                    1.4758840488244813, SigLevel::Alpha(0.2).critical_value(5.0).unwrap());
+
+    assert_appx_eq!("t(0.975, 10)", precision, 2.2281388519649385, t_quantile(0.975, 10.0).unwrap());
+    assert_appx_eq!("t(0.025, 10)", precision, -2.2281388519649385, t_quantile(0.025, 10.0).unwrap());
+    assert_eq!(t_quantile(0.5, 3.0).unwrap(), 0.0);
+    assert!(t_quantile(1.0, 3.0).is_err());
+
+    let t_test = TTest { p: 0.03, t: 2.2, df: 40.0 };
+    assert_eq!(t_test.verdict(SigLevel::Alpha005), Verdict::Reject);
+    assert_eq!(t_test.verdict(SigLevel::Alpha001), Verdict::FailToReject);
+}
+
+#[test]
+fn influence_kat() {
+    use dent::lr::LinearRegression;
+
+    let data = [(1.0, 1.0), (2.0, 3.0), (3.0, 2.0), (4.0, 5.0)];
+    let lr = LinearRegression::new(&data).unwrap();
+    let influence = lr.influence(&data).unwrap();
+    let precision = 1e-12;
+
+    let residuals = [-0.1, 0.8, -1.3, 0.6];
+    let leverages = [0.7, 0.3, 0.3, 0.7];
+    let distances = [0.028806584362139964, 0.14512471655328796, 0.38321995464852626, 28.0 / 27.0];
+
+    for (i, inf) in influence.iter().enumerate() {
+        assert_appx_eq!("Residual", precision, residuals[i], inf.residual);
+        assert_appx_eq!("Leverage", precision, leverages[i], inf.leverage);
+        assert_appx_eq!("Cook's distance", precision, distances[i], inf.cooks_distance);
+    }
+
+    assert!(lr.influence(&data[..2]).is_err());
+
+    // An exact fit has no residual variance to measure influence against.
+    let data = [(1.0, 3.0), (2.0, 5.0), (3.0, 7.0), (4.0, 9.0)];
+    let lr = LinearRegression::new(&data).unwrap();
+    assert!(lr.influence(&data).is_err());
+}
+
+#[test]
+fn cross_validation_kat() {
+    use dent::lr::{cross_validate, Model};
+    use dent::rng::Rng;
+
+    let mut rng = Rng::new(0);
+
+    // Each point left out is predicted by the line through the other two.
+    let data = [(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)];
+    let rmse = cross_validate(&data, Model::Linear, 3, &mut rng).unwrap();
+    assert_appx_eq!("LOO RMSE", 1e-12, 3f64.sqrt(), rmse);
+
+    let line: Vec<(f64, f64)> = (0..10).map(|i| (i as f64, 2.0 * i as f64 + 1.0)).collect();
+    let rmse = cross_validate(&line, Model::Linear, 5, &mut rng).unwrap();
+    assert_appx_eq!("Exact RMSE", 1e-12, 0.0, rmse);
+
+    assert!(cross_validate(&data, Model::Linear, 1, &mut rng).is_err());
+    assert!(cross_validate(&data, Model::Linear, 4, &mut rng).is_err());
+    assert!(cross_validate(&[(1.0, -1.0), (2.0, 1.0)], Model::Power, 2, &mut rng).is_err());
+}
+
+#[test]
+fn deming_regression_kat() {
+    use dent::lr::DemingRegression;
+
+    let data = [(0.0, 0.0), (1.0, 1.0), (2.0, 3.0), (3.0, 3.0)];
+    let precision = 1e-12;
+
+    // Orthogonal regression, steeper than the least-squares slope of 1.1.
+    let fit = DemingRegression::new(&data, 1.0).unwrap();
+    assert_appx_eq!("Slope", precision, 1.1716667913611574, fit.slope);
+    assert_appx_eq!("Intercept", precision, -0.007500187041736162, fit.intercept);
+    assert_appx_eq!("Slope SE", precision, 0.34373857793565876, fit.slope_standard_error);
+    assert_appx_eq!("Intercept SE", precision, 0.18890261322524252, fit.intercept_standard_error);
+
+    let fit = DemingRegression::new(&data, 2.0).unwrap();
+    assert_appx_eq!("Slope, ratio 2", precision, 1.1492922795738925, fit.slope);
+    assert_appx_eq!("Intercept, ratio 2", precision, 0.026061580639161308, fit.intercept);
+
+    // Points on a line are fit exactly, whatever the ratio.
+    let line: Vec<(f64, f64)> = (0..5).map(|i| (i as f64, 2.0 * i as f64 + 1.0)).collect();
+    let fit = DemingRegression::new(&line, 0.5).unwrap();
+    assert_appx_eq!("Exact slope", precision, 2.0, fit.slope);
+    assert_appx_eq!("Exact intercept", precision, 1.0, fit.intercept);
+    assert_appx_eq!("Exact slope SE", precision, 0.0, fit.slope_standard_error);
+
+    assert!(DemingRegression::new(&data, 0.0).is_err());
+    assert!(DemingRegression::new(&data[..2], 1.0).is_err());
+    assert!(DemingRegression::new(&[(0.0, 1.0), (1.0, 0.0), (2.0, 1.0)], 1.0).is_err());
+}
+
+#[test]
+fn agreement_kat() {
+    use dent::agreement::{BlandAltman, PassingBablok};
+
+    let precision = 1e-12;
+
+    let ba = BlandAltman::new(&[1.0, 2.0, 3.0, 4.0], &[2.0, 2.0, 5.0, 5.0], 0.95).unwrap();
+    let sd = (2.0f64 / 3.0).sqrt();
+    let margin = 3.182446305284263 * sd / 2.0;
+    assert_eq!(ba.size, 4);
+    assert_appx_eq!("Bias", precision, 1.0, ba.bias);
+    assert_appx_eq!("SD", precision, sd, ba.standard_deviation);
+    assert_appx_eq!("Bias lower", 1e-9, 1.0 - margin, ba.bias_interval.0);
+    assert_appx_eq!("Lower LoA", precision, 1.0 - 1.96 * sd, ba.limits.0);
+    assert_appx_eq!("Upper LoA", precision, 1.0 + 1.96 * sd, ba.limits.1);
+    assert_eq!(ba.points[2], (4.0, 2.0));
+    assert!(BlandAltman::new(&[1.0, 2.0], &[1.0], 0.95).is_err());
+
+    // Pairwise slopes are 0, 1, 1, 1, 1.5, and 2.
+    let data = [(1.0, 1.0), (2.0, 2.0), (3.0, 4.0), (4.0, 4.0)];
+    let pb = PassingBablok::new(&data, 0.5).unwrap();
+    assert_appx_eq!("Slope", precision, 1.0, pb.slope);
+    assert_appx_eq!("Intercept", precision, 0.0, pb.intercept);
+    assert_eq!(pb.slope_interval, (1.0, 1.5));
+    assert_eq!(pb.intercept_interval, (-0.75, 0.0));
+
+    // Too few points for an interval at the usual level.
+    assert!(PassingBablok::new(&data, 0.95).is_err());
+    assert!(PassingBablok::new(&data[..2], 0.5).is_err());
+}
+
+#[test]
+fn regression_comparison_kat() {
+    use dent::lr::RegressionComparison;
+
+    // Residuals alternate in sign, so the fit has nonzero error.
+    let data1: Vec<(f64, f64)> = (0..10)
+        .map(|i| (i as f64, 2.0 * i as f64 + 1.0 + 0.5 * (-1.0f64).powi(i)))
+        .collect();
+
+    // A translate of `data1` along the response axis.
+    let data2: Vec<(f64, f64)> = data1.iter().map(|&(x, y)| (x, y + 3.0)).collect();
+
+    let cmp = RegressionComparison::new(&data1, &data2).unwrap();
+    let precision = 1e-9;
+
+    assert_appx_eq!("Slope difference", precision, 0.0, cmp.slope_difference.estimate);
+    assert_appx_eq!("Slope difference p", precision, 1.0, cmp.slope_difference.test.p);
+    assert_appx_eq!("Intercept difference", precision, 3.0, cmp.intercept_difference.estimate);
+    assert_appx_eq!("DF", precision, 16.0, cmp.df);
+    assert!(cmp.intercept_difference.test.p < 0.001);
+
+    assert!(RegressionComparison::new(&data1[..1], &data2).is_err());
+    assert!(RegressionComparison::new(&[(1.0, 1.0), (1.0, 2.0)], &data2).is_err());
+}
+
+#[test]
+fn segmented_regression_kat() {
+    use dent::lr::SegmentedRegression;
+
+    // Exact lines with slopes 1 and 3, with a jump between `x = 5` and `x = 6`
+    // so that the split is unique.
+    let data: Vec<(f64, f64)> = (0..12)
+        .map(|i| i as f64)
+        .map(|x| (x, if x <= 5.0 { x } else { 3.0 * x - 8.0 }))
+        .collect();
+
+    let seg = SegmentedRegression::new(&data, 0.95).unwrap();
+    let precision = 1e-9;
+
+    assert_appx_eq!("Breakpoint", precision, 5.5, seg.breakpoint);
+    assert_appx_eq!("Left slope", precision, 1.0, seg.left.slope);
+    assert_appx_eq!("Right slope", precision, 3.0, seg.right.slope);
+    assert_appx_eq!("Right intercept", precision, -8.0, seg.right.intercept);
+    assert_appx_eq!("SSE", precision, 0.0, seg.sse);
+    assert_eq!(seg.left.size, 6);
+
+    assert!(SegmentedRegression::new(&data[..5], 0.95).is_err());
+}
+
+#[test]
+fn model_fit_kat() {
+    use dent::lr::{Model, ModelFit};
+
+    let xs: Vec<f64> = (1..10).map(|i| i as f64).collect();
+    let precision = 1e-9;
+
+    let data: Vec<(f64, f64)> = xs.iter().map(|&x| (x, 3.0 * (0.5 * x).exp())).collect();
+    let fit = ModelFit::new(&data, Model::Exponential).unwrap();
+    assert_appx_eq!("Exponential a", precision, 3.0, fit.a);
+    assert_appx_eq!("Exponential b", precision, 0.5, fit.b);
+    assert_appx_eq!("Exponential R²", precision, 1.0, fit.r_squared);
+
+    let data: Vec<(f64, f64)> = xs.iter().map(|&x| (x, 2.0 * x.powf(1.5))).collect();
+    let fit = ModelFit::new(&data, Model::Power).unwrap();
+    assert_appx_eq!("Power a", precision, 2.0, fit.a);
+    assert_appx_eq!("Power b", precision, 1.5, fit.b);
+    assert_appx_eq!("Power prediction", precision, 16.0, fit.predict(4.0));
+
+    assert!(ModelFit::new(&[(0.0, 1.0), (1.0, 2.0)], Model::Power).is_err());
+    assert!(ModelFit::new(&[(0.0, -1.0), (1.0, 2.0)], Model::Exponential).is_err());
+}
+
+#[test]
+fn distribution_fit_kat() {
+    use dent::fit::{DistributionFit, Family};
+
+    let data = [0.5, 1.2, 1.9, 2.4, 3.3, 4.1, 6.0];
+
+    // Closed forms of the maximum-likelihood estimates.
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let sd = (data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n).sqrt();
+    let precision = 1e-12;
+
+    let fit = DistributionFit::new(&data, Family::Normal).unwrap();
+    assert_appx_eq!("Normal mean", precision, mean, fit.parameters[0].estimate);
+    assert_appx_eq!("Normal SE(mean)", precision, sd / n.sqrt(), fit.parameters[0].standard_error);
+    assert_appx_eq!("Normal sd", precision, sd, fit.parameters[1].estimate);
+
+    let fit = DistributionFit::new(&data, Family::Exponential).unwrap();
+    assert_appx_eq!("Exponential rate", precision, 1.0 / mean, fit.parameters[0].estimate);
+    assert_appx_eq!("Exponential log-likelihood", precision, -n * (mean.ln() + 1.0), fit.log_likelihood);
+
+    // Known values from arbitrary-precision evaluation with `mpmath`.
+    let precision = 1e-9;
+
+    let fit = DistributionFit::new(&data, Family::Gamma).unwrap();
+    assert_appx_eq!("Gamma shape", precision, 2.1723453016, fit.parameters[0].estimate);
+    assert_appx_eq!("Gamma rate", precision, 0.7838359336, fit.parameters[1].estimate);
+    assert_appx_eq!("Gamma SE(shape)", precision, 1.0838691497, fit.parameters[0].standard_error);
+    assert_appx_eq!("Gamma log-likelihood", precision, -13.1504561431, fit.log_likelihood);
+    assert_appx_eq!("Gamma K-S", precision, 0.1164587708, fit.ks_statistic);
+
+    let fit = DistributionFit::new(&data, Family::LogNormal).unwrap();
+    assert_appx_eq!("Log-normal K-S", precision, 0.1472202802, fit.ks_statistic);
+
+    assert!(fit.distribution().is_ok());
+
+    let k = fit.parameters.len() as f64;
+    assert_appx_eq!("Log-normal AIC", 1e-12, 2.0 * k - 2.0 * fit.log_likelihood, fit.aic());
+    assert_appx_eq!("Log-normal BIC", 1e-12, k * n.ln() - 2.0 * fit.log_likelihood, fit.bic());
+
+    assert!(DistributionFit::new(&[1.0], Family::Normal).is_err());
+    assert!(DistributionFit::new(&[2.0, 2.0, 2.0], Family::Gamma).is_err());
+    assert!(DistributionFit::new(&[-1.0, 2.0], Family::LogNormal).is_err());
+    assert!(DistributionFit::new(&[-1.0, 2.0], Family::Exponential).is_err());
+}
+
+#[test]
+fn distribution_ranking_kat() {
+    use dent::fit::{Criterion, Family, rank};
+
+    let data = [0.5, 1.2, 1.9, 2.4, 3.3, 4.1, 6.0];
+
+    let ranked = rank(&data, Criterion::Aic).unwrap();
+    assert_eq!(ranked.len(), 4);
+    assert_eq!(ranked[0].delta, 0.0);
+    assert_appx_eq!("Sum of weights", 1e-12, 1.0, ranked.iter().map(|r| r.weight).sum::<f64>());
+
+    for pair in ranked.windows(2) {
+        assert!(pair[0].score <= pair[1].score);
+        assert!(pair[0].weight >= pair[1].weight);
+        assert_appx_eq!("Weight ratio", 1e-12, (-0.5 * (pair[1].delta - pair[0].delta)).exp(), pair[1].weight / pair[0].weight);
+    }
+
+    // Data with negative values can only be normal.
+    let ranked = rank(&[-1.0, 0.5, 2.0], Criterion::Bic).unwrap();
+    assert_eq!(ranked.len(), 1);
+    assert_eq!(ranked[0].fit.family, Family::Normal);
+    assert_eq!(ranked[0].weight, 1.0);
+
+    assert!(rank(&[-1.0, -1.0], Criterion::Aic).is_err());
+}
+
+#[test]
+fn contingency_kat() {
+    use dent::contingency::TwoByTwo;
+
+    // Known values from arbitrary-precision evaluation with `mpmath`.
+    let precision = 1e-12;
+    let table = TwoByTwo::new(12, 388, 3, 397).unwrap();
+
+    let or = table.odds_ratio(0.95).unwrap();
+    assert_appx_eq!("Odds ratio", precision, 4.092783505154639, or.estimate);
+    assert_appx_eq!("Odds ratio lower", precision, 1.14609502100575, or.interval.0);
+    assert_appx_eq!("Odds ratio upper", 1e-11, 14.6156091013869, or.interval.1);
+
+    let rr = table.relative_risk(0.95).unwrap();
+    assert_appx_eq!("Relative risk", precision, 4.0, rr.estimate);
+
+    let chi_square = table.chi_square();
+    assert_appx_eq!("χ²", precision, 5.503184713375796, chi_square.statistic);
+    assert_appx_eq!("χ² p", precision, 0.0189818733073342, chi_square.p);
+    assert_appx_eq!("Fisher exact p", precision, 0.0335006093001258, table.fisher_exact().unwrap());
+
+    // Tables with a zero cell are corrected by adding 0.5 to every cell.
+    let table = TwoByTwo::new(0, 10, 5, 5).unwrap();
+    assert_appx_eq!("Corrected odds ratio", precision, (0.5 * 5.5) / (10.5 * 5.5), table.odds_ratio(0.95).unwrap().estimate);
+    assert!(table.min_expected() < 5.0);
+
+    assert_eq!(TwoByTwo::parse("12, 388, 3, 397").unwrap(), TwoByTwo::new(12, 388, 3, 397).unwrap());
+    assert_eq!(TwoByTwo::parse_csv("x,y,z\na,1,2\nb,3,4\n").unwrap(), TwoByTwo::new(1, 2, 3, 4).unwrap());
+    assert_eq!(TwoByTwo::parse_csv("1,2\n3,4").unwrap(), TwoByTwo::new(1, 2, 3, 4).unwrap());
+    assert!(TwoByTwo::parse("1,2,3").is_err());
+    assert!(TwoByTwo::parse_csv("1,2,3\n4,5,6").is_err());
+    assert!(TwoByTwo::new(0, 0, 3, 4).is_err());
+}
+
+#[test]
+fn categorical_summary_kat() {
+    use dent::categorical::CategoricalSummary;
+    use dent::rng::Rng;
+
+    let labels = ["b", "a", "b", "c", "b", "a", "b", "d"];
+    let summary = CategoricalSummary::new(labels.iter().cloned(), 0.95).unwrap();
+
+    assert_eq!(summary.size, 8);
+
+    let order: Vec<&str> = summary.categories.iter().map(|c| c.label.as_str()).collect();
+    assert_eq!(order, ["b", "a", "c", "d"]);
+
+    // Known values of the Wilson score interval, from its closed form with
+    // `z = 1.959963984540054`.
+    let b = &summary.categories[0];
+    assert_eq!(b.count, 4);
+    assert_eq!(b.proportion, 0.5);
+    assert_appx_eq!("Wilson lower", 1e-8, 0.2152160622, b.interval.0);
+    assert_appx_eq!("Wilson upper", 1e-8, 0.7847839378, b.interval.1);
+
+    let d = &summary.categories[3];
+    assert_appx_eq!("Wilson lower", 1e-8, 0.0224174915, d.interval.0);
+    assert_appx_eq!("Wilson upper", 1e-8, 0.4708881822, d.interval.1);
+
+    // Known values from the closed forms, with counts 4, 2, 1, 1.
+    let mut rng = Rng::new(7);
+    let d = summary.diversity(1000, &mut rng).unwrap();
+    assert_appx_eq!("Shannon entropy", 1e-12, 1.75, d.entropy.value);
+    assert_appx_eq!("Gini impurity", 1e-12, 1.0 - 22.0 / 64.0, d.gini_impurity.value);
+    assert_appx_eq!("Simpson's index", 1e-12, 14.0 / 56.0, d.simpson.value);
+    assert!(d.entropy.interval.value.0 <= d.entropy.interval.value.1);
+    assert!(d.entropy.interval.value.1 <= 2.0);
+
+    let single = CategoricalSummary::new(vec!["a", "a", "a"], 0.95).unwrap();
+    let d = single.diversity(10, &mut rng).unwrap();
+    assert_eq!(d.entropy.value, 0.0);
+    assert_eq!(d.gini_impurity.value, 0.0);
+    assert_eq!(d.simpson.value, 1.0);
+    assert!(CategoricalSummary::new(vec!["a"], 0.95).unwrap().diversity(10, &mut rng).is_err());
+    assert!(single.diversity(0, &mut rng).is_err());
+
+    assert!(CategoricalSummary::new(vec![], 0.95).is_err());
+    assert!(CategoricalSummary::new(vec!["a"], 1.0).is_err());
+}
+
+#[test]
+fn loess_kat() {
+    use dent::smooth;
+
+    // Local linear fits reproduce a line exactly, at any span.
+    let data: Vec<(f64, f64)> = (0..20).rev().map(|i| (i as f64, 2.0 * i as f64 - 3.0)).collect();
+    let precision = 1e-9;
+
+    for &span in &[0.2, 0.5, 1.0] {
+        let smoothed = smooth::loess(&data, span).unwrap();
+        assert_eq!(smoothed.len(), 20);
+
+        for (i, &(x, y)) in smoothed.iter().enumerate() {
+            assert_eq!(x, i as f64);
+            assert_appx_eq!("Smoothed line", precision, 2.0 * x - 3.0, y);
+        }
+    }
+
+    // Repeated `x` values are smoothed once.
+    let smoothed = smooth::loess(&[(1.0, 1.0), (1.0, 3.0), (2.0, 2.0)], 1.0).unwrap();
+    assert_eq!(smoothed.len(), 2);
+
+    assert!(smooth::loess(&data, 0.0).is_err());
+    assert!(smooth::loess(&data, 1.5).is_err());
+}
+
+#[test]
+fn series_timestamps_kat() {
+    use dent::series::{self, Series, Statistic, TimeFormat};
+
+    assert_eq!(series::parse_timestamp("2019-03-01T12:00:00Z").unwrap(),
+               (1551441600.0, TimeFormat::Rfc3339));
+    assert_eq!(series::parse_timestamp("2019-03-01t13:30:00.25+01:30").unwrap().0, 1551441600.25);
+    assert_eq!(series::parse_timestamp("1970-01-01T00:00:00Z").unwrap().0, 0.0);
+    assert_eq!(series::parse_timestamp("2000-02-29T00:00:00Z").unwrap().0, 951782400.0);
+    assert_eq!(series::parse_timestamp("1551441600.5").unwrap(), (1551441600.5, TimeFormat::Epoch));
+
+    assert!(series::parse_timestamp("2019-02-29T00:00:00Z").is_err());
+    assert!(series::parse_timestamp("2019-03-01T12:00:00").is_err());
+    assert!(series::parse_timestamp("2019-03-01T12:00:00+1:00").is_err());
+
+    assert_eq!(series::format_rfc3339(1551441600.0), "2019-03-01T12:00:00Z");
+    assert_eq!(series::format_rfc3339(-0.5), "1969-12-31T23:59:59.500Z");
+
+    assert_eq!(series::parse_duration("250ms").unwrap(), 0.25);
+    assert_eq!(series::parse_duration("1m").unwrap(), 60.0);
+    assert_eq!(series::parse_duration("2h").unwrap(), 7200.0);
+    assert_eq!(series::parse_duration("15").unwrap(), 15.0);
+    assert!(series::parse_duration("0s").is_err());
+    assert!(series::parse_duration("1w").is_err());
+
+    let series = Series::new(&[(61.0, 4.0), (5.0, 1.0), (59.0, 3.0), (125.0, 8.0)]).unwrap();
+    let resampled = series.resample(60.0, Statistic::Mean).unwrap();
+    assert_eq!(resampled.points(), &[(0.0, 2.0), (60.0, 4.0), (120.0, 8.0)]);
+
+    let counts = series.resample(120.0, Statistic::Count).unwrap();
+    assert_eq!(counts.points(), &[(0.0, 3.0), (120.0, 1.0)]);
+
+    let aggregated = series.aggregate(3, Statistic::Max).unwrap();
+    assert_eq!(aggregated.points(), &[(5.0, 4.0), (125.0, 8.0)]);
+
+    let medians = series.aggregate(2, Statistic::Median).unwrap();
+    assert_eq!(medians.points(), &[(5.0, 2.0), (61.0, 6.0)]);
+
+    assert!(series.aggregate(0, Statistic::Sum).is_err());
+}
+
+#[test]
+fn series_streaks_kat() {
+    use dent::series::{Crossings, Excursion, Series, Streak};
+
+    let values = [1.0, 2.0, 3.0, 5.0, 4.0, 1.0, 2.0, 6.0, 6.0, 0.0];
+    let points: Vec<(f64, f64)> = values.iter().enumerate().map(|(i, &x)| (i as f64, x)).collect();
+    let series = Series::new(&points).unwrap();
+
+    assert_eq!(series.longest_rise(), Some(Streak { steps: 3, start: 0.0, end: 3.0 }));
+    assert_eq!(series.longest_fall(), Some(Streak { steps: 2, start: 3.0, end: 5.0 }));
+    assert_eq!(series.max_drawup(), Some(Excursion { size: 5.0, start: 0.0, end: 7.0 }));
+    assert_eq!(series.max_drawdown(), Some(Excursion { size: 6.0, start: 7.0, end: 9.0 }));
+
+    let crossings = series.crossings(2.5).unwrap();
+    assert_eq!(crossings, Crossings { threshold: 2.5, up: 2, down: 2 });
+
+    // Touching the threshold is not crossing it.
+    assert_eq!(series.crossings(6.0).unwrap().total(), 0);
+    assert_eq!(series.crossings(2.0).unwrap().total(), 4);
+
+    let flat = Series::new(&[(0.0, 1.0), (1.0, 1.0)]).unwrap();
+    assert_eq!(flat.longest_rise(), None);
+    assert_eq!(flat.max_drawdown(), None);
+    assert!(flat.crossings(f64::NAN).is_err());
+}
+
+#[test]
+fn series_time_checks_kat() {
+    use dent::series::{check_times, TimeIssues};
+
+    let issues = check_times(&[&[0.0, 10.0, 5.0, 10.0, 60.0, 5.0]], Some(30.0));
+    assert_eq!(issues.out_of_order, vec![2, 5]);
+    assert_eq!(issues.duplicates, vec![3, 5]);
+    assert_eq!(issues.gaps, vec![(4, 50.0)]);
+
+    // Runs are only out of order within themselves.
+    let issues = check_times(&[&[0.0, 10.0, 60.0], &[5.0, 4.0, 10.0]], Some(30.0));
+    assert_eq!(issues.out_of_order, vec![4]);
+    assert_eq!(issues.duplicates, vec![5]);
+    assert_eq!(issues.gaps, vec![(2, 50.0)]);
+
+    // Gaps are only found if a greatest gap is given.
+    assert!(check_times(&[&[0.0, 100.0]], None).is_empty());
+    assert_eq!(check_times(&[], Some(1.0)), TimeIssues::default());
+}
+
+#[test]
+fn quantize_kat() {
+    use dent::quantize::Quantizer;
+
+    let round = Quantizer::round(0.1).unwrap();
+    assert_eq!(round.apply(0.26), 0.3);
+    assert_eq!(round.apply(0.31), 0.3);
+    assert_eq!(round.apply(-0.05), -0.1);
+
+    let round = Quantizer::round(2.5).unwrap();
+    assert_eq!(round.apply(6.0), 5.0);
+    assert_eq!(round.apply(6.25), 7.5);
+
+    let bucket = Quantizer::bucket(0.25).unwrap();
+    assert_eq!(bucket.apply(0.26), 0.25);
+    assert_eq!(bucket.apply(0.25), 0.25);
+    assert_eq!(bucket.apply(-0.05), -0.25);
+    assert!(bucket.apply(f64::NAN).is_nan());
+    assert_eq!(bucket.apply(f64::INFINITY), f64::INFINITY);
+
+    assert!(Quantizer::round(0.0).is_err());
+    assert!(Quantizer::bucket(-1.0).is_err());
+    assert!(Quantizer::bucket(f64::NAN).is_err());
+}
+
+#[test]
+fn tails_kat() {
+    use dent::tails::{trim, winsorize};
+
+    let data = [5.0, 1.0, 9.0, 3.0, 100.0, 4.0, 2.0, 8.0, 7.0, 6.0, -50.0, f64::NAN];
+
+    // One value in each tail of 11 which are not NaN.
+    let mut winsorized = data;
+    assert_eq!(winsorize(&mut winsorized, 0.1).unwrap(), (1, 1));
+    assert_eq!(&winsorized[..11], &[5.0, 1.0, 9.0, 3.0, 9.0, 4.0, 2.0, 8.0, 7.0, 6.0, 1.0]);
+    assert!(winsorized[11].is_nan());
+
+    assert_eq!(trim(&data, 0.1).unwrap(), vec![0, 1, 2, 3, 5, 6, 7, 8, 9, 11]);
+    assert_eq!(trim(&data, 0.2).unwrap(), vec![0, 3, 5, 6, 7, 8, 9, 11]);
+
+    // Of equal values in a tail, only as many as are in it are trimmed.
+    assert_eq!(trim(&[1.0, 1.0, 1.0, 2.0], 0.25).unwrap(), vec![1, 2]);
+
+    let mut ties = [1.0, 1.0, 1.0, 2.0];
+    assert_eq!(winsorize(&mut ties, 0.25).unwrap(), (0, 1));
+    assert_eq!(ties, [1.0, 1.0, 1.0, 1.0]);
+
+    assert_eq!(trim(&data, 0.0).unwrap().len(), data.len());
+    assert!(trim(&data, 0.5).is_err());
+    assert!(winsorize(&mut [1.0], -0.1).is_err());
+}
+
+#[test]
+fn dedup_kat() {
+    use dent::dedup::Dedup;
+
+    let data = [1.0, 1.0, 2.0, 1.0, 0.0, -0.0, f64::NAN, f64::NAN];
+
+    assert_eq!(Dedup::All.kept(&data), vec![0, 2, 4, 6]);
+    assert_eq!(Dedup::Consecutive.kept(&data), vec![0, 2, 3, 4, 6]);
+    assert!(Dedup::All.kept(&[]).is_empty());
+
+    assert_eq!(Dedup::from_name("consecutive"), Some(Dedup::Consecutive));
+    assert_eq!(Dedup::from_name("some"), None);
+}
+
+#[test]
+fn expr_kat() {
+    use dent::expr::{Expr, ExprError};
+
+    let e = Expr::parse("(a - b) / b").unwrap();
+    let row = |name: &str| match name {
+        "a" => Some(3.0),
+        "b" => Some(2.0),
+        _ => None,
+    };
+
+    assert_eq!(e.eval(&row), Ok(0.5));
+    assert_eq!(e.columns(), vec!["a", "b", "b"]);
+    assert_eq!(Expr::parse("-a * 2 + b / 4e-1").unwrap().eval(&row), Ok(-1.0));
+    assert_eq!(Expr::parse("1 - 2 - 3").unwrap().eval(&row), Ok(-4.0));
+
+    assert_eq!(e.check_columns(&["a"]), Err(ExprError::UnknownColumn("b".to_string())));
+    assert_eq!(Expr::parse("a +"), Err(ExprError::UnexpectedEnd));
+    assert_eq!(Expr::parse("a $ b"), Err(ExprError::Unexpected { position: 3 }));
+    assert_eq!(Expr::parse("(a"), Err(ExprError::UnexpectedEnd));
+}
+
+#[test]
+fn exceedance_kat() {
+    use dent::exceedance::Exceedance;
+
+    let data = [100.0, 200.0, 300.0, 400.0, 600.0];
+    let e = Exceedance::new(&data, 300.0, 0.95).unwrap();
+
+    // A value equal to the threshold does not exceed it.
+    assert_eq!((e.above.count, e.below.count), (2, 3));
+    assert_eq!(e.above.proportion, 0.4);
+    assert_eq!(e.above.mean, Some(500.0));
+    assert_eq!(e.below.mean, Some(200.0));
+
+    // Wilson interval of 2 of 5.
+    assert_appx_eq!("Lower", 1e-9, 0.11762077423264793, e.above.interval.0);
+    assert_appx_eq!("Upper", 1e-9, 0.7692757187239873, e.above.interval.1);
+
+    let e = Exceedance::new(&data, 1000.0, 0.95).unwrap();
+    assert_eq!((e.above.count, e.above.mean), (0, None));
+
+    assert!(Exceedance::new(&[], 1.0, 0.95).is_err());
+    assert!(Exceedance::new(&data, f64::NAN, 0.95).is_err());
+}
+
+#[test]
+fn exceedance_change_kat() {
+    use dent::exceedance::{Exceedance, ExceedanceChange};
+
+    let before: Vec<f64> = (0..10).map(|i| if i < 2 { 400.0 } else { 100.0 }).collect();
+    let after: Vec<f64> = (0..10).map(|i| if i < 6 { 400.0 } else { 100.0 }).collect();
+
+    let first = Exceedance::new(&before, 300.0, 0.95).unwrap();
+    let second = Exceedance::new(&after, 300.0, 0.95).unwrap();
+    let change = ExceedanceChange::new(&first, &second).unwrap();
+    let precision = 1e-9;
+
+    assert_appx_eq!("Difference", precision, 0.4, change.difference);
+    assert_appx_eq!("Lower", precision, -0.02255846535520878, change.interval.0);
+    assert_appx_eq!("Upper", precision, 0.6725442445674756, change.interval.1);
+    assert_appx_eq!("z", precision, 1.8257418583505536, change.z);
+    assert_appx_eq!("p", precision, 0.06788915486182907, change.p);
+
+    // Neither sample exceeds the threshold.
+    let none = Exceedance::new(&before, 500.0, 0.95).unwrap();
+    let change = ExceedanceChange::new(&none, &none).unwrap();
+    assert_eq!((change.difference, change.p), (0.0, 1.0));
+
+    assert!(ExceedanceChange::new(&first, &none).is_err());
+}
+
+#[test]
+fn histogram_kat() {
+    use dent::histogram::{self, Binning, Histogram};
+
+    assert_eq!(Binning::parse("20").unwrap(), Binning::Width(20));
+    assert_eq!(Binning::parse("log:20").unwrap(), Binning::Log(20));
+    assert_eq!(Binning::parse("quantile:10").unwrap(), Binning::Quantile(10));
+    assert!(Binning::parse("log:0").is_err());
+    assert!(Binning::parse("cubic:3").is_err());
+
+    let data: Vec<f64> = (1..=10).map(f64::from).collect();
+
+    let h = Histogram::new(&data, Binning::Width(3)).unwrap();
+    assert_eq!(h.edges, vec![1.0, 4.0, 7.0, 10.0]);
+    assert_eq!(h.counts, vec![3, 3, 4]);
+
+    let h = Histogram::new(&data, Binning::Quantile(2)).unwrap();
+    assert_eq!(h.edges, vec![1.0, 5.5, 10.0]);
+    assert_eq!(h.counts, vec![5, 5]);
+
+    let h = Histogram::new(&[1.0, 10.0, 100.0, 1000.0], Binning::Log(2)).unwrap();
+    assert_appx_eq!("Middle edge", 1e-9, 1000f64.sqrt(), h.edges[1]);
+    assert_eq!(h.counts, vec![2, 2]);
+
+    // Tied quantiles are merged.
+    let h = Histogram::new(&[1.0, 1.0, 1.0, 1.0, 2.0], Binning::Quantile(4)).unwrap();
+    assert_eq!(h.edges, vec![1.0, 2.0]);
+    assert_eq!(h.counts, vec![5]);
+
+    let h = Histogram::new(&[3.0, 3.0], Binning::Width(5)).unwrap();
+    assert_eq!(h.counts, vec![2]);
+
+    // Bins shared by two samples span both.
+    let hs = Histogram::shared(&[&[1.0, 2.0, 3.0], &[3.0, 4.0, 5.0]], Binning::Width(2)).unwrap();
+    assert_eq!(hs[0].edges, vec![1.0, 3.0, 5.0]);
+    assert_eq!((hs[0].counts.clone(), hs[1].counts.clone()), (vec![2, 1], vec![0, 3]));
+
+    let h = Histogram::with_edges(&[0.0, 1.0, 2.0, 9.0], vec![1.0, 2.0, 3.0]).unwrap();
+    assert_eq!(h.counts, vec![1, 1]);
+    assert!(Histogram::with_edges(&data, vec![2.0, 1.0]).is_err());
+
+    let h = Histogram::new(&data, Binning::Width(3)).unwrap();
+    let f = h.frequencies();
+    assert_eq!(h.total(), 10);
+    assert_eq!((f[1].lower, f[1].upper, f[1].count), (4.0, 7.0, 3));
+    assert_appx_eq!("Proportion", 1e-12, 0.3, f[1].proportion);
+    assert_appx_eq!("Cumulative", 1e-12, 60.0, f[1].cumulative_percent);
+    assert_appx_eq!("Cumulative", 1e-12, 100.0, f[2].cumulative_percent);
+
+    let h = Histogram::new(&[1.0, 2.0, 2.0, 3.0], Binning::Width(2)).unwrap();
+    assert_eq!(histogram::to_tsv(std::slice::from_ref(&h), &["a"]), "Source\tLower\tUpper\tCount\tProportion\tCumulativePercent\na\t1\t2\t1\t0.25\t25\na\t2\t3\t3\t0.75\t100\n");
+    assert_eq!(
+        histogram::to_prometheus(&[h], &["a"]),
+        "# HELP dent_histogram Counts of the sample in bins.\n\
+         # TYPE dent_histogram histogram\n\
+         dent_histogram_bucket{source=\"a\",le=\"2\"} 1\n\
+         dent_histogram_bucket{source=\"a\",le=\"3\"} 4\n\
+         dent_histogram_bucket{source=\"a\",le=\"+Inf\"} 4\n\
+         dent_histogram_count{source=\"a\"} 4\n",
+    );
+
+    assert!(Histogram::new(&[0.0, 1.0], Binning::Log(2)).is_err());
+    assert!(Histogram::new(&[], Binning::Width(2)).is_err());
+}
+
+#[test]
+fn streaming_summarizer_kat() {
+    use dent::summary::{NonFinitePolicy, StreamingSummarizer, Summary};
+
+    // At most five values, the quartiles are exact.
+    let data = [4.0, 1.0, 5.0, 2.0, 3.0];
+    let mut streaming = StreamingSummarizer::new();
+
+    for &x in &data {
+        streaming.push(x);
+    }
+
+    let summary = streaming.summary().unwrap();
+    let exact = Summary::new(&data).unwrap();
+    assert_eq!(summary.lower_quartile(), exact.lower_quartile());
+    assert_eq!(summary.median(), exact.median());
+    assert_eq!(summary.upper_quartile(), exact.upper_quartile());
+    assert_appx_eq!("Variance", 1e-12, exact.unbiased_variance(), summary.unbiased_variance());
+
+    // A permutation of 0 through 10006, whose quartiles are estimated.
+    let data: Vec<f64> = (0..10007).map(|i| ((i * 7919) % 10007) as f64).collect();
+    let mut streaming = StreamingSummarizer::new();
+
+    for &x in &data {
+        streaming.push(1e9 + x);
+    }
+
+    let summary = streaming.summary().unwrap();
+    assert_eq!((summary.size(), summary.min(), summary.max()), (10007.0, 1e9, 1e9 + 10006.0));
+    assert_appx_eq!("Mean", 1e-6, 1e9 + 5003.0, summary.mean());
+    assert_appx_eq!("Variance", 1e-3, 10007.0 * 10008.0 / 12.0, summary.unbiased_variance());
+    assert_appx_eq!("Q1", 50.0, 1e9 + 2501.5, summary.lower_quartile());
+    assert_appx_eq!("Median", 50.0, 1e9 + 5003.0, summary.median());
+    assert_appx_eq!("Q3", 50.0, 1e9 + 7504.5, summary.upper_quartile());
+    assert!(summary.debug_validate().is_ok());
+
+    // Only the quantiles, and the adjacent values taken from them, are
+    // marked approximate.
+    assert_eq!(summary.method(), dent::estimate::Method::Sketch);
+    assert!(summary.quantiles_are_approximate() && !summary.moments_are_approximate());
+
+    let mut streaming = StreamingSummarizer::new();
+    assert!(streaming.summary().is_err());
+    streaming.push(1.0);
+    streaming.push(f64::NAN);
+    assert!(streaming.summary().is_err());
+
+    let mut streaming = StreamingSummarizer::with_policy(NonFinitePolicy::DropWithReport);
+    streaming.push(1.0);
+    streaming.push(f64::NEG_INFINITY);
+    assert_eq!(streaming.summary().unwrap().size(), 1.0);
+    assert_eq!(streaming.nonfinite_report().unwrap().neg_infinite, 1);
+}
+
+#[test]
+fn throughput_kat() {
+    use dent::summary::Summary;
+    use dent::throughput::Throughput;
+
+    let durations = Summary::new(&[1.0, 2.0, 4.0, 5.0]).unwrap();
+    let t = Throughput::new(&durations, 0.99).unwrap();
+    let precision = 1e-12;
+
+    // Harmonic mean of the rates 1, 1/2, 1/4, and 1/5, not their mean.
+    assert_appx_eq!("Rate", precision, 1.0 / 3.0, t.rate);
+    assert_appx_eq!("Median rate", precision, 1.0 / 3.0, t.median_rate);
+    assert_appx_eq!("Rate SE", precision, durations.standard_error() / 9.0, t.standard_error);
+
+    // The interval of the mean duration reaches zero, so the rate is unbounded.
+    let (lo, hi) = durations.mean_confidence_interval(0.99).unwrap();
+    assert!(lo < 0.0);
+    assert_appx_eq!("Rate CI lower", precision, 1.0 / hi, t.interval.0);
+    assert_eq!(t.interval.1, f64::INFINITY);
+
+    assert!(Throughput::new(&Summary::new(&[0.0, 1.0]).unwrap(), 0.95).is_err());
+}
+
+#[test]
+fn estimate_kat() {
+    use dent::estimate::{self, Method};
+    use dent::summary::Summarizer;
+
+    let sample = Summarizer::new(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]).unwrap();
+
+    // Computed with mpmath.
+    let mean = estimate::subsample_mean(&sample, 20).unwrap();
+    assert_eq!(mean.value, 4.5);
+    assert_eq!(mean.method, Method::Subsample { size: 8, population: 20 });
+    assert_appx_eq!("Error bound", 1e-12, 1.34893972761938, mean.error_bound.unwrap());
+
+    assert!(estimate::subsample_mean(&sample, 8).unwrap().is_exact());
+    assert!(estimate::subsample_mean(&sample, 7).is_err());
+
+    // The 2.5th percentile of 1..=101 is 3.5, but the resampled statistic at
+    // that rank could by chance be that at percentiles 2.5 ± 3.04, up to 6.54.
+    let boot = Summarizer::new(&(1..102).map(f64::from).collect::<Vec<f64>>()).unwrap();
+    let interval = estimate::bootstrap_interval(&boot, 0.95, 101).unwrap();
+    assert_appx_eq!("Lower", 1e-12, 3.5, interval.value.0);
+    assert_appx_eq!("Upper", 1e-12, 98.5, interval.value.1);
+    assert_eq!(interval.method, Method::Bootstrap { resamples: 101 });
+    assert!(!interval.is_exact());
+    assert_appx_eq!("Monte Carlo error", 1e-12, 3.04480662792034, interval.error_bound.unwrap());
+
+    // Methods are labeled compactly, and read back.
+    for m in &[Method::Exact, Method::Sketch, interval.method, mean.method] {
+        assert_eq!(Method::parse_label(&m.label()).unwrap(), *m);
+    }
+
+    assert_eq!(mean.method.label(), "subsample:8/20");
+    assert!(Method::parse_label("subsample:8").is_err());
+
+    // A summary of a subsample is marked approximate, and stays so in TSV.
+    let s = dent::summary::Summary::new(&[1.0, 2.0, 3.0]).unwrap().with_method(mean.method);
+    assert!(s.quantiles_are_approximate() && s.moments_are_approximate());
+
+    let record = s.to_tsv_record_with_method("x");
+    let (_, read) = dent::summary::Summary::from_tsv_record(&record).unwrap();
+    assert_eq!(read.method(), mean.method);
+}
+
+#[test]
+fn ratio_of_means_kat() {
+    use dent::ratio::{RatioMethod, RatioOfMeans};
+    use dent::rng::Rng;
+    use dent::summary::{Summarizer, Summary};
+    use dent::t_test::t_critical_value;
+
+    let xs = [10.0, 12.0, 11.0, 13.0, 12.5, 11.5];
+    let ys = [5.0, 6.5, 6.0, 5.5, 7.0, 6.0];
+    let num = Summary::new(&xs).unwrap();
+    let den = Summary::new(&ys).unwrap();
+
+    let r = RatioOfMeans::fieller(&num, &den, 0.95).unwrap();
+    assert_eq!(r.method, RatioMethod::Fieller);
+    assert_appx_eq!("Ratio", 1e-12, num.mean() / den.mean(), r.ratio);
+
+    // Fieller's bounds are the ratios `ρ` at which the t statistic of the
+    // mean of `num - ρ·den` is at the critical value.
+    let (a, b) = (num.mean(), den.mean());
+    let (va, vb) = (num.standard_error().powi(2), den.standard_error().powi(2));
+    let df = (va + vb).powi(2) / (va.powi(2) / 5.0 + vb.powi(2) / 5.0);
+    let t = t_critical_value(0.95, df).unwrap();
+
+    for &rho in &[r.interval.value.0, r.interval.value.1] {
+        let t_rho = (a - rho * b).abs() / (va + rho.powi(2) * vb).sqrt();
+        assert_appx_eq!("Fieller bound", 1e-9, t, t_rho);
+    }
+
+    // A denominator indistinguishable from zero gives an unbounded interval,
+    // so the estimate falls back to the bootstrap.
+    let zs = [-1.0, 1.5, -0.5, 0.5, 1.0, -1.0];
+    assert!(RatioOfMeans::fieller(&num, &Summary::new(&zs).unwrap(), 0.95).is_err());
+
+    let x = Summarizer::new(&xs).unwrap();
+    let z = Summarizer::new(&zs).unwrap();
+    let r1 = RatioOfMeans::new(&x, &z, 0.95, 200, &mut Rng::new(7)).unwrap();
+    let r2 = RatioOfMeans::new(&x, &z, 0.95, 200, &mut Rng::new(7)).unwrap();
+    assert_eq!(r1.method, RatioMethod::Bootstrap);
+    assert_eq!(r1.interval, r2.interval);
+}
+
+#[test]
+fn plot_dimensions_kat() {
+    use dent::plot::{self, PlotError, PlotHeight, PlotWidth};
+    use dent::summary::Summary;
+
+    assert_eq!(PlotWidth::new(0), Err(PlotError::ZeroWidth));
+    assert_eq!(PlotHeight::new(0), Err(PlotError::ZeroHeight));
+
+    let s = Summary::new(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+
+    // A border and its padding take four columns.
+    let narrow = PlotWidth::new(4).unwrap();
+    assert_eq!(plot::comparison_plot(&[&s], narrow, true, true, true), Err(PlotError::TooNarrow { min: 5 }));
+
+    let rendered = plot::comparison_plot(&[&s], PlotWidth::new(40).unwrap(), true, true, true).unwrap();
+    assert_eq!(rendered.width(), 40);
+    assert_eq!(rendered.height(), 7);
+
+    // The longest bar, with its label, axis, and count, spans the width.
+    let bars = [("long label", 120), ("short", 3)];
+    let rendered = plot::bar_chart(&bars, PlotWidth::new(40).unwrap(), true).unwrap();
+    assert_eq!(rendered.width(), 40);
+    assert_eq!(rendered.height(), 2);
+    assert_eq!(plot::bar_chart(&bars, narrow, true), Err(PlotError::TooNarrow { min: 8 }));
+
+    // Bars of proportions are scaled to the largest proportion given.
+    let rendered = plot::proportion_chart(&[("a", 1), ("b", 3)], 4, 1.0, PlotWidth::new(20).unwrap(), true).unwrap();
+    assert_eq!(rendered.to_string(), "a |## 1 (25.0%)\nb |##### 3 (75.0%)");
+    assert_eq!(plot::proportion_chart(&bars, 200, 0.5, narrow, true), Err(PlotError::BadData));
+    assert_eq!(plot::proportion_chart(&bars, 100, 1.0, narrow, true), Err(PlotError::BadData));
+
+    // One bar per bin, the tallest spanning the plot.
+    let data = [1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 3.0, 3.0, 3.0, 3.0, 3.0];
+    let rendered = plot::histogram::from_slice(&data, 2, PlotWidth::new(16).unwrap(), PlotHeight::new(2).unwrap(), true).unwrap();
+    assert_eq!(rendered.to_string(), "   +-----------+\n10 |      #### |\n 0 | ######### |\n   +-----------+\n     1.00 3.00");
+    assert_eq!(plot::histogram::sparkline(&[0, 1, 4, 8], false), " ▁▄█");
+    assert_eq!(plot::histogram::sparkline(&[0, 1, 4, 8], true), " _-#");
+
+    assert_eq!(plot::histogram::from_slice(&[], 2, PlotWidth::new(12).unwrap(), PlotHeight::new(2).unwrap(), true), Err(PlotError::Empty));
+    assert_eq!(plot::histogram::from_slice(&data, 20, PlotWidth::new(12).unwrap(), PlotHeight::new(2).unwrap(), true), Err(PlotError::TooNarrow { min: 26 }));
+
+    let height = PlotHeight::new(4).unwrap();
+    let rendered = plot::overlay_histogram(&[0.5, 0.5, 0.0], &[0.0, 0.5, 0.5], (0.0, 3.0), PlotWidth::new(20).unwrap(), height, true, None).unwrap();
+    assert_eq!(rendered.width(), 20);
+    assert_eq!(rendered.height(), 7);
+    assert!(rendered.to_string().ends_with("       0.00   3.00"));
+    assert_eq!(plot::overlay_histogram(&[0.5], &[0.5, 0.5], (0.0, 1.0), PlotWidth::new(20).unwrap(), height, true, None), Err(PlotError::BadData));
+
+    // The upper end of the value axis is labeled with the unit.
+    let rendered = plot::overlay_histogram(&[0.5, 0.5, 0.0], &[0.0, 0.5, 0.5], (0.0, 3.0), PlotWidth::new(24).unwrap(), height, true, Some("ms")).unwrap();
+    assert_eq!(rendered.width(), 24);
+    assert!(rendered.to_string().ends_with("       0.00    3.00 ms"));
+    assert_eq!(plot::overlay_histogram(&[0.5], &[0.5], (0.0, 1.0), PlotWidth::new(20).unwrap(), height, true, Some("ms")), Err(PlotError::TooNarrow { min: 21 }));
+}
+
+#[test]
+fn layout_kat() {
+    use dent::layout::{self, Layout, Table};
+    use dent::plot::{self, PlotWidth};
+    use dent::summary::Summary;
+
+    let mut table = Table::new(&[("a", 4), ("bb", 4), ("c", 4)]);
+    table.row(&["1".to_string(), "2".to_string(), "3".to_string()]);
+
+    let mut layout = Layout::new(None);
+    layout.table(table.clone());
+    layout.text(vec!["alpha beta gamma".to_string()], 2);
+    assert_eq!(layout.render(), "   a    bb     c\n   1     2     3\n\nalpha beta gamma");
+
+    // Columns which do not fit are wrapped into another table.
+    let mut layout = Layout::new(Some(PlotWidth::new(11).unwrap()));
+    layout.table(table);
+    layout.text(vec!["alpha beta gamma".to_string()], 2);
+    assert_eq!(layout.render(), "   a    bb\n   1     2\n\n   c\n   3\n\nalpha beta\n  gamma");
+
+    // Transposed, the old headers label the rows, and repeat when wrapped.
+    let mut table = Table::new(&[("a", 4), ("bb", 4)]);
+    table.row(&["1".to_string(), "2".to_string()]);
+    table.row(&["30".to_string(), "4".to_string()]);
+
+    let mut layout = Layout::new(None);
+    layout.table(table.transpose(&["x".to_string(), "y".to_string()]));
+    assert_eq!(layout.render(), "    x   y\na   1  30\nbb  2   4");
+
+    let mut layout = Layout::new(Some(PlotWidth::new(6).unwrap()));
+    layout.table(table.transpose(&[]));
+    assert_eq!(layout.render(), "a   1\nbb  2\n\na   30\nbb   4");
+
+    // Labels lose their common directories, and then their middles if too wide.
+    assert_eq!(layout::shorten_labels(&["runs/a/x.txt", "runs/b/x.txt"], None), ["a/x.txt", "b/x.txt"]);
+    assert_eq!(layout::shorten_labels(&["runs/x.txt", "runs/x.txt"], None), ["x.txt", "x.txt"]);
+    assert_eq!(layout::shorten_labels(&["runs/x.txt"], None), ["runs/x.txt"]);
+    assert_eq!(layout::shorten_labels(&["stdin", "runs/x.txt"], None), ["stdin", "runs/x.txt"]);
+    assert_eq!(layout::shorten_labels(&["a/long_label.txt", "a/b"], Some(9)), ["long….txt", "b"]);
+
+    // Plots are truncated.
+    let s = Summary::new(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+    let mut layout = Layout::new(Some(PlotWidth::new(10).unwrap()));
+    layout.plot(plot::summary_plot(&s, PlotWidth::new(20).unwrap(), true, true).unwrap());
+
+    let rendered = layout.render();
+    assert!(rendered.lines().all(|l| l.chars().count() == 10 && l.ends_with('…')));
+}
+
+#[test]
+fn effect_size_kat() {
+    use dent::effect::{cohens_d, Magnitude};
+    use dent::summary::Summary;
+
+    let x = Summary::new(&[1.0, 2.0, 3.0]).unwrap();
+    let y = Summary::new(&[4.0, 5.0, 6.0]).unwrap();
+
+    assert_eq!(cohens_d(&x, &y).unwrap(), 3.0);
+    assert_eq!(cohens_d(&y, &x).unwrap(), -3.0);
+    assert_eq!(Magnitude::of(-3.0), Magnitude::Large);
+    assert_eq!(Magnitude::of(0.5), Magnitude::Medium);
+    assert_eq!(Magnitude::of(0.3), Magnitude::Small);
+    assert_eq!(Magnitude::of(0.1), Magnitude::Negligible);
+
+    let constant = Summary::new(&[1.0, 1.0]).unwrap();
+    assert!(cohens_d(&constant, &constant).is_err());
+}
+
+#[test]
+fn robust_comparison_kat() {
+    use dent::robust::{hodges_lehmann_shift, mann_whitney_u_test};
+    use dent::summary::Summarizer;
+
+    let precision = 1e-12;
+
+    let x = Summarizer::new(&[3.0, 1.0, 2.0]).unwrap();
+    let y = Summarizer::new(&[4.0, 6.0, 5.0]).unwrap();
+    let test = mann_whitney_u_test(&x, &y).unwrap();
+
+    assert_eq!(test.u, 0.0);
+    assert_appx_eq!("z", precision, -1.7457431218879391, test.z);
+    assert_appx_eq!("p", precision, 0.0808555983700523, test.p);
+    assert_eq!(hodges_lehmann_shift(&x, &y), 3.0);
+
+    // Midranks and the tie correction.
+    let x = Summarizer::new(&[1.0, 2.0, 2.0]).unwrap();
+    let y = Summarizer::new(&[2.0, 3.0]).unwrap();
+    let test = mann_whitney_u_test(&x, &y).unwrap();
+
+    assert_eq!(test.u, 1.0);
+    assert_appx_eq!("Tied z", precision, -0.9682458365518541, test.z);
+    assert_appx_eq!("Tied p", precision, 0.33292160806556603, test.p);
+
+    let constant = Summarizer::new(&[1.0, 1.0]).unwrap();
+    assert!(mann_whitney_u_test(&constant, &constant).is_err());
+
+    let s = Summarizer::new(&[1.0, 2.0, 3.0, 4.0, 100.0]).unwrap();
+    assert_eq!(s.median_absolute_deviation(), 1.0);
+}
+
+#[test]
+fn assumption_checks_kat() {
+    use dent::assumptions::{brown_forsythe, jarque_bera, select_test, TestChoice};
+    use dent::summary::{Summarizer, Summary};
+    use dent::t_test::{student_t_test, welch_t_test, SigLevel};
+
+    let precision = 1e-12;
+
+    let primes = Summarizer::new(&[2.0, 3.0, 5.0, 7.0, 11.0, 13.0, 17.0, 19.0, 23.0]).unwrap();
+    let (jb, p) = jarque_bera(&primes).unwrap();
+    assert_appx_eq!("Jarque–Bera", precision, 0.7050843029569995, jb);
+    assert_appx_eq!("Jarque–Bera p", precision, 0.7028989409566753, p);
+
+    // With two groups, the Brown–Forsythe F is the square of the pooled t
+    // statistic of the absolute deviations from each median.
+    let x = Summarizer::new(&[1.0, 2.0, 4.0, 8.0, 9.0]).unwrap();
+    let y = Summarizer::new(&[3.0, 3.5, 4.0, 5.0, 5.5, 6.0]).unwrap();
+    let (f, p) = brown_forsythe(&x, &y).unwrap();
+
+    let zx = Summary::new(&[3.0, 2.0, 0.0, 4.0, 5.0]).unwrap();
+    let zy = Summary::new(&[1.5, 1.0, 0.5, 0.5, 1.0, 1.5]).unwrap();
+    let t_test = student_t_test(&zx, &zy).unwrap();
+    assert_appx_eq!("Brown–Forsythe F", 1e-9, t_test.t.powi(2), f);
+    assert_appx_eq!("Brown–Forsythe p", 1e-9, t_test.p, p);
+
+    // With equal sizes, Student's and Welch's t statistics agree.
+    let s1 = Summary::new(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+    let s2 = Summary::new(&[2.0, 4.0, 6.0, 9.0]).unwrap();
+    let student = student_t_test(&s1, &s2).unwrap();
+    assert_appx_eq!("Student t", precision, welch_t_test(&s1, &s2).unwrap().t, student.t);
+    assert_eq!(student.df, 6.0);
+
+    let selection = select_test(&primes, &x, SigLevel::Alpha005).unwrap();
+    assert_eq!(selection.checks.len(), 3);
+    assert_eq!(selection.choice, TestChoice::Student);
+}
+
+#[test]
+fn drift_kat() {
+    use dent::drift::Drift;
+
+    let precision = 1e-12;
+
+    let baseline: Vec<f64> = (1..101).map(f64::from).collect();
+    let drift = Drift::new(&baseline, &baseline, 10).unwrap();
+    assert_eq!(drift.bins.len(), 10);
+    assert_eq!(drift.psi, 0.0);
+    assert_eq!(drift.kl_divergence, 0.0);
+    assert_eq!(drift.js_divergence, 0.0);
+
+    // Halves of `[1, 2, 3, 4]` hold proportions `[0.75, 0.25]` of the current
+    // sample, so that PSI = `0.25 ln 3`.
+    let drift = Drift::new(&[1.0, 2.0, 3.0, 4.0], &[1.0, 1.0, 1.0, 4.0], 2).unwrap();
+    assert_eq!(drift.bins.len(), 2);
+    assert_appx_eq!("PSI", precision, 0.274653072167027, drift.psi);
+    assert_appx_eq!("KL", precision, 0.130812035941137, drift.kl_divergence);
+    assert_appx_eq!("JS", precision, 0.0338220755686052, drift.js_divergence);
+
+    // Empty bins are corrected, so every divergence stays finite.
+    let drift = Drift::new(&[1.0, 2.0, 3.0, 4.0], &[10.0, 11.0], 2).unwrap();
+    assert!(drift.psi.is_finite() && drift.kl_divergence.is_finite());
+
+    // Ties merge bins.
+    assert_eq!(Drift::new(&[1.0, 1.0, 1.0, 1.0, 2.0], &[1.0], 4).unwrap().bins.len(), 2);
+
+    assert!(Drift::new(&[1.0, 2.0], &[1.0], 1).is_err());
+    assert!(Drift::new(&[1.0, 2.0], &[], 2).is_err());
+    assert!(Drift::new(&[], &[1.0], 2).is_err());
+}
+
+#[test]
+fn stratified_comparison_kat() {
+    use dent::stratify::StratifiedComparison;
+
+    let precision = 1e-12;
+
+    let x = [("a", 1.0), ("a", 2.0), ("a", 3.0), ("b", 10.0), ("b", 12.0), ("c", 7.0)];
+    let y = [("b", 11.0), ("a", 2.0), ("a", 3.0), ("b", 15.0), ("a", 4.0), ("a", 5.0)];
+    let comparison = StratifiedComparison::new(&x, &y, 0.95).unwrap();
+
+    let labels: Vec<&str> = comparison.strata.iter().map(|s| s.label.as_str()).collect();
+    assert_eq!(labels, ["a", "b"]);
+    assert_eq!(comparison.skipped, ["c"]);
+
+    assert_appx_eq!("Stratum a", precision, 1.5, comparison.strata[0].difference);
+    assert_appx_eq!("Stratum a SE", precision, 0.866025403784439, comparison.strata[0].standard_error);
+    assert_appx_eq!("Stratum b", precision, 2.0, comparison.strata[1].difference);
+
+    // Known values from inverse-variance weighting, computed independently.
+    let pooled = comparison.pooled;
+    assert_appx_eq!("Pooled", precision, 1.56521739130435, pooled.estimate);
+    assert_appx_eq!("Pooled SE", precision, 0.807572853087248, pooled.standard_error);
+    assert_appx_eq!("Pooled p", 1e-9, 0.0526018943268765, pooled.p);
+    assert_eq!(pooled.heterogeneity, 0.0);
+    assert_appx_eq!("Aggregate", precision, 5.0 / 6.0, comparison.aggregate);
+    assert!(!comparison.is_reversed());
+
+    assert!(StratifiedComparison::new(&x, &[("c", 1.0), ("c", 2.0)], 0.95).is_err());
+    assert!(StratifiedComparison::new(&x, &[], 0.95).is_err());
+    assert!(StratifiedComparison::new(&x, &y, 1.0).is_err());
+}
+
+#[test]
+fn meta_analysis_kat() {
+    use dent::meta::{parse_studies, MetaAnalysis, Study};
+
+    let precision = 1e-12;
+
+    let studies = parse_studies(
+        "machine\testimate\tse\nx86-a\t-5.2\t1.1\nx86-b\t-3.9\t0.8\n\narm-a\t-8.4\t1.5\narm-b\t-1.0\t1.2\n",
+    ).unwrap();
+    assert_eq!(studies.len(), 4);
+    assert_eq!(studies[1], Study::new("x86-b", -3.9, 0.8).unwrap());
+
+    // Known values from the closed forms, computed independently.
+    let meta = MetaAnalysis::new(studies, 0.95).unwrap();
+    assert_appx_eq!("Fixed", precision, -4.20060680993672, meta.fixed.estimate);
+    assert_appx_eq!("Fixed SE", precision, 0.532409574880736, meta.fixed.standard_error);
+    assert_appx_eq!("Q", 1e-11, 15.9181810930894, meta.q);
+    assert_appx_eq!("I²", precision, 0.811536256406682, meta.heterogeneity);
+    assert_appx_eq!("τ²", precision, 5.27381236797683, meta.tau_squared);
+    assert_appx_eq!("Random", precision, -4.50585717184338, meta.random.estimate);
+    assert_appx_eq!("Random SE", precision, 1.28545331734455, meta.random.standard_error);
+    assert_appx_eq!("Random p", 1e-9, 0.000456149470246302, meta.random.p);
+    assert_appx_eq!("Weights", precision, 1.0, meta.random_weights().iter().sum::<f64>());
+
+    // Agreeing studies have no heterogeneity, so both models coincide.
+    let studies = vec![Study::new("a", 1.0, 0.5).unwrap(), Study::new("b", 1.0, 1.0).unwrap()];
+    let meta = MetaAnalysis::new(studies, 0.95).unwrap();
+    assert_eq!(meta.tau_squared, 0.0);
+    assert_eq!(meta.heterogeneity, 0.0);
+    assert_eq!(meta.fixed.estimate, meta.random.estimate);
+
+    let study = parse_studies("a\t10\t0.3\t12\t0.4").unwrap().remove(0);
+    assert_appx_eq!("Difference of means", precision, 2.0, study.estimate);
+    assert_appx_eq!("SE of difference", precision, 0.5, study.standard_error);
+
+    assert!(parse_studies("a\t1\t1\nb\t1").is_err());
+    assert!(parse_studies("a\t1\t1\nb\tx\t1").is_err());
+    assert!(Study::new("a", 1.0, 0.0).is_err());
+    assert!(MetaAnalysis::new(vec![], 0.95).is_err());
+}
+
+#[test]
+fn repeated_measures_kat() {
+    use dent::anova::{Friedman, RepeatedMeasuresAnova};
+
+    let a = [12.1, 14.3, 11.8, 15.2, 13.0, 12.7];
+    let b = [12.9, 15.1, 12.2, 16.4, 13.6, 13.9];
+    let c = [13.4, 15.0, 13.1, 16.9, 14.2, 13.5];
+    let conditions: [&[f64]; 3] = [&a, &b, &c];
+
+    // Known values from arbitrary-precision evaluation with `mpmath`.
+    let anova = RepeatedMeasuresAnova::new(&conditions).unwrap();
+    assert_appx_eq!("F", 1e-10, 27.542372881356, anova.f);
+    assert_eq!((anova.df_conditions, anova.df_error), (2.0, 10.0));
+    assert_appx_eq!("p", 1e-12, 8.56256608539052e-5, anova.p);
+    assert_appx_eq!("ε", 1e-12, 0.796567505720819, anova.epsilon);
+    assert_appx_eq!("Corrected p", 1e-12, 0.000374523706483054, anova.p_corrected);
+    assert_appx_eq!("Partial η²", 1e-12, 0.846354166666667, anova.partial_eta_squared);
+
+    let friedman = Friedman::new(&conditions).unwrap();
+    assert_eq!(friedman.mean_ranks, [1.0, 14.0 / 6.0, 16.0 / 6.0]);
+    assert_appx_eq!("Friedman χ²", 1e-12, 28.0 / 3.0, friedman.statistic);
+    assert_appx_eq!("Friedman p", 1e-12, (-14.0f64 / 3.0).exp(), friedman.p);
+
+    // Ties within subjects share their mean rank, and correct the statistic.
+    let conditions: [&[f64]; 3] = [&[1.0, 2.0, 3.0, 4.0], &[2.0, 2.0, 5.0, 3.0], &[3.0, 1.0, 4.0, 4.0]];
+    let friedman = Friedman::new(&conditions).unwrap();
+    assert_eq!(friedman.mean_ranks, [1.75, 2.125, 2.125]);
+    assert_appx_eq!("Tied χ²", 1e-12, 0.428571428571429, friedman.statistic);
+    assert_appx_eq!("Tied p", 1e-12, 0.807117747005389, friedman.p);
+    assert_appx_eq!("Kendall's W", 1e-12, 0.0535714285714286, friedman.kendall_w);
+
+    assert!(RepeatedMeasuresAnova::new(&[&a]).is_err());
+    assert!(RepeatedMeasuresAnova::new(&[&a, &b[1..]]).is_err());
+    assert!(RepeatedMeasuresAnova::new(&[&[1.0, 2.0], &[2.0, 3.0]]).is_err());
+    assert!(Friedman::new(&[&[1.0, 2.0], &[1.0, 2.0]]).is_err());
+}
+
+#[test]
+fn two_way_anova_kat() {
+    use dent::anova::{LongFormat, TwoWayAnova};
+
+    let text = "compiler opt time\n\
+                gcc11 O0 41.2\ngcc11 O0 40.8\ngcc11 O0 42.0\ngcc11 O0 41.5\n\
+                gcc11 O2 20.1\ngcc11 O2 19.8\ngcc11 O2 20.6\n\
+                gcc12 O0 39.9\ngcc12 O0 40.5\ngcc12 O0 40.1\n\
+                gcc12 O2 18.2\ngcc12 O2 18.9\ngcc12 O2 18.4\ngcc12 O2 18.0\n\
+                clang O0 43.0\nclang O0 42.1\nclang O0 42.7\nclang O0 43.4\n\
+                clang O2 17.1\nclang O2 17.6\nclang O2 16.9\n";
+    let data = LongFormat::parse(text).unwrap();
+    assert_eq!(data.factors, ("compiler".to_string(), "opt".to_string()));
+    assert_eq!(data.observations.len(), 21);
+
+    let observations: Vec<(&str, &str, f64)> = data.observations
+        .iter()
+        .map(|o| (o.0.as_str(), o.1.as_str(), o.2))
+        .collect();
+    let anova = TwoWayAnova::new(&observations).unwrap();
+
+    assert_eq!(anova.levels.0, ["gcc11", "gcc12", "clang"]);
+    assert_eq!(anova.levels.1, ["O0", "O2"]);
+    assert_eq!(anova.cell_sizes[0], [4, 3]);
+    assert_appx_eq!("Cell mean", 1e-12, 41.375, anova.cell_means[0][0]);
+
+    // Known values of Type III sums of squares for this unbalanced design,
+    // from the Wald tests of contrasts of cell means, with `mpmath`.
+    assert_appx_eq!("SS compiler", 1e-9, 7.71626984126984, anova.a.sum_sq);
+    assert_appx_eq!("F compiler", 1e-9, 20.036476794988, anova.a.f);
+    assert_appx_eq!("p compiler", 1e-12, 5.80292127670243e-5, anova.a.p);
+    assert_appx_eq!("SS opt", 1e-8, 2689.12, anova.b.sum_sq);
+    assert_eq!(anova.b.df, 1.0);
+    assert_appx_eq!("SS interaction", 1e-9, 19.5030952380952, anova.interaction.sum_sq);
+    assert_appx_eq!("p interaction", 1e-12, 2.13423051123942e-7, anova.interaction.p);
+    assert_appx_eq!("Partial η² interaction", 1e-9, 0.871007188124708, anova.interaction.partial_eta_squared);
+    assert_appx_eq!("Residual SS", 1e-9, 2.88833333333333, anova.residual_sum_sq);
+    assert_eq!(anova.df_residual, 15.0);
+
+    // In a balanced design, these are the classical sums of squares.
+    let balanced = [
+        ("x", "p", 1.0), ("x", "p", 3.0), ("x", "q", 5.0), ("x", "q", 7.0),
+        ("y", "p", 2.0), ("y", "p", 4.0), ("y", "q", 10.0), ("y", "q", 12.0),
+    ];
+    let anova = TwoWayAnova::new(&balanced).unwrap();
+    assert_appx_eq!("Balanced SS A", 1e-12, 18.0, anova.a.sum_sq);
+    assert_appx_eq!("Balanced SS B", 1e-12, 72.0, anova.b.sum_sq);
+    assert_appx_eq!("Balanced SS AB", 1e-12, 8.0, anova.interaction.sum_sq);
+    assert_appx_eq!("Balanced residual", 1e-12, 8.0, anova.residual_sum_sq);
+
+    assert!(LongFormat::parse("a b 1\na b x").is_err());
+    assert!(LongFormat::parse("a b 1 2").is_err());
+    assert!(TwoWayAnova::new(&balanced[..6]).is_err());
+    assert!(TwoWayAnova::new(&[("x", "p", 1.0), ("x", "q", 2.0), ("x", "p", 3.0)]).is_err());
+}
+
+#[test]
+fn sign_test_kat() {
+    use dent::paired::{sign_test, Operation};
+
+    let test = sign_test(&[1.0, 2.0, 0.5, 3.0, 1.0, 4.0, 2.0, 1.5, 0.1, -1.0], Operation::Difference).unwrap();
+    assert_eq!((test.above, test.below, test.ties), (9, 1, 0));
+    assert_appx_eq!("Sign test p", 1e-12, 22.0 / 1024.0, test.p);
+
+    // Ties are discarded, and ratios are centered on 1.
+    let test = sign_test(&[1.0, 1.0, 2.0, 0.5, 3.0], Operation::Ratio).unwrap();
+    assert_eq!((test.above, test.below, test.ties), (2, 1, 2));
+    assert_eq!(test.p, 1.0);
+
+    // Known value from arbitrary-precision evaluation with `mpmath`.
+    let derived: Vec<f64> = (0..1000).map(|i| if i < 600 { 1.0 } else { -1.0 }).collect();
+    let test = sign_test(&derived, Operation::Difference).unwrap();
+    assert_appx_eq!("Sign test p", 1e-20, 2.72846415606602e-10, test.p);
+
+    assert!(sign_test(&[0.0, 0.0], Operation::Difference).is_err());
+    assert!(sign_test(&[f64::NAN], Operation::Difference).is_err());
+}
+
+#[test]
+fn runs_test_kat() {
+    use dent::diagnostics::{check_order, runs_test};
+
+    // Both halves in one run each: the fewest possible.
+    let ascending: Vec<f64> = (1..11).map(f64::from).collect();
+    let test = runs_test(&ascending).unwrap();
+    assert_eq!((test.runs, test.above, test.below), (2, 5, 5));
+    assert_eq!(test.expected, 6.0);
+    assert_appx_eq!("z", 1e-12, -4.0 / (20.0f64 / 9.0).sqrt(), test.z);
+    assert_appx_eq!("p", 1e-12, 0.00729035809153564, test.p);
+
+    let alternating = [1.0, 10.0, 2.0, 9.0, 3.0, 8.0, 4.0, 7.0, 5.0, 6.0];
+    let test = runs_test(&alternating).unwrap();
+    assert_eq!(test.runs, 10);
+    assert_appx_eq!("Alternating p", 1e-12, 0.00729035809153564, test.p);
+
+    // Values equal to the median are discarded.
+    let test = runs_test(&[1.0, 2.0, 3.0, 2.0, 1.0, 3.0, 2.0]).unwrap();
+    assert_eq!((test.runs, test.above, test.below), (4, 2, 2));
+
+    // Too few values on each side of the median to warn.
+    assert!(check_order(&ascending).is_empty());
+
+    let ascending: Vec<f64> = (1..41).map(f64::from).collect();
+    assert_eq!(check_order(&ascending).len(), 1);
+
+    assert!(runs_test(&[1.0, 1.0, 1.0]).is_err());
+    assert!(runs_test(&[]).is_err());
+}
+
+#[test]
+fn breusch_pagan_kat() {
+    use dent::diagnostics::{breusch_pagan, check_variance};
+
+    let test = breusch_pagan(&[1.0, 2.0, 3.0, 4.0], &[1.0, -1.0, 2.0, -2.0]).unwrap();
+    assert_appx_eq!("LM", 1e-12, 3.2, test.statistic);
+    assert_appx_eq!("Slope", 1e-12, 1.2, test.slope);
+    assert_appx_eq!("p", 1e-12, 0.07363827012030266, test.p);
+
+    // Too few residuals to warn, however uneven.
+    assert!(check_variance(&[1.0, 2.0, 3.0, 4.0], &[1.0, -1.0, 2.0, -2.0]).is_empty());
+
+    let xs: Vec<f64> = (1..41).map(f64::from).collect();
+    let even: Vec<f64> = (1..41).map(|i| if i % 3 == 0 { 1.0 } else { -1.0 }).collect();
+    let spreading: Vec<f64> = xs.iter().zip(&even).map(|(x, e)| x * e).collect();
+    assert!(check_variance(&xs, &even).is_empty());
+    assert_eq!(check_variance(&xs, &spreading).len(), 1);
+
+    assert!(breusch_pagan(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0]).is_err());
+    assert!(breusch_pagan(&[1.0, 2.0], &[1.0]).is_err());
+}
+
+#[test]
+fn benford_kat() {
+    use dent::benford::{first_digit, Benford, Conformity};
+
+    assert_eq!(first_digit(0.0123), Some(1));
+    assert_eq!(first_digit(-950.0), Some(9));
+    assert_eq!(first_digit(1e-300), Some(1));
+    assert_eq!(first_digit(0.0), None);
+    assert_eq!(first_digit(f64::NAN), None);
+
+    let data = [1.5, -12.0, 0.0123, 2e5, 3.7, 9.99, 0.0];
+    let benford = Benford::new(&data).unwrap();
+
+    let counts: Vec<u64> = benford.digits.iter().map(|d| d.count).collect();
+    assert_eq!(counts, vec![3, 1, 1, 0, 0, 0, 0, 0, 1]);
+    assert_eq!((benford.size, benford.zeros), (6, 1));
+    assert_appx_eq!("Expected 1", 1e-15, 2f64.log10(), benford.digits[0].expected);
+
+    // Computed with mpmath.
+    assert_appx_eq!("χ²", 1e-12, 4.90574909128129, benford.chi_square);
+    assert_appx_eq!("p", 1e-12, 0.767600694791990, benford.p);
+    assert_appx_eq!("MAD", 1e-12, 0.0803571356667505, benford.mad);
+    assert_appx_eq!("Orders", 1e-12, (2e5f64 / 0.0123).log10(), benford.orders_of_magnitude);
+    assert_eq!(benford.conformity(), Conformity::Nonconformity);
+
+    assert!(Benford::new(&[0.0, 0.0]).is_err());
+    assert!(Benford::new(&[1.0, f64::INFINITY]).is_err());
+    assert!(Benford::new(&[]).is_err());
+}
+
+#[test]
+fn decode_kat() {
+    use dent::decode::{decode, Csv, InputDecoder, JsonLines, Pattern, PlainLines};
+
+    assert_eq!(decode(&PlainLines, "1.5\n\n -2 \n").unwrap(), vec![1.5, -2.0]);
+    assert_eq!(decode(&PlainLines, "1\nx\n").unwrap_err().line, 2);
+
+    let csv = "name,time\n\"a, b\",1.5\nc,\nd,\"2.5\"\n";
+    assert_eq!(decode(&Csv::new(1), csv).unwrap(), vec![1.5, 2.5]);
+    assert_eq!(Csv::new(0).fields("\"say \"\"hi\"\"\",2").unwrap(), vec!["say \"hi\"", "2"]);
+    assert!(Csv::new(2).decode_line("a,1").is_err());
+    assert!(Csv::new(0).decode_line("\"a,1").is_err());
+
+    let tsv = Csv { column: 0, delimiter: '\t', header: false };
+    assert_eq!(decode(&tsv, "3\tx\n4\ty\n").unwrap(), vec![3.0, 4.0]);
+
+    let json = "{\"name\": \"t\\\"ms\\\"\", \"ms\": 12.5}\n\
+                {\"tags\": {\"ms\": 1}, \"ms\": -3e2}\n\
+                {\"ms\": null}\n";
+    assert_eq!(decode(&JsonLines::new("ms"), json).unwrap(), vec![12.5, -300.0]);
+    assert!(JsonLines::new("ms").decode_line("{\"s\": 1}").is_err());
+    assert!(JsonLines::new("ms").decode_line("{\"ms\": \"1\"}").is_err());
+    assert!(JsonLines::new("ms").decode_line("[1]").is_err());
+
+    let log = "GET / took 12 ms\nstarting\nGET /a took 7.5 ms (cached)\n";
+    assert_eq!(decode(&Pattern::new(r"took (\S+) ms").unwrap(), log).unwrap(), vec![12.0, 7.5]);
+    assert_eq!(Pattern::new(r"\bt=(\S+)").unwrap().extract("n=1 t=0.25 u=3"), Some("0.25"));
+    assert_eq!(Pattern::new(r"x(\d+)y").unwrap().extract("xy x3y"), Some("3"));
+    assert_eq!(Pattern::new(r"a=(\d+)|b").unwrap().extract("b"), None);
+    assert!(Pattern::new(r"took (\S+) ms").unwrap().decode_line("took ? ms").is_err());
+    assert!(Pattern::new("no group").is_err());
+    assert!(Pattern::new("(a) and (b)").is_err());
+    assert!(Pattern::new("(unclosed").is_err());
+}
+
+#[test]
+fn holm_adjust_kat() {
+    use dent::t_test::holm_adjust;
+
+    // Known values from R's `p.adjust(p, "holm")`.
+    let adjusted = holm_adjust(&[0.01, 0.04, 0.03, 0.005]);
+    for (&x, &y) in [0.03, 0.06, 0.06, 0.02].iter().zip(&adjusted) {
+        assert_appx_eq!("Holm-adjusted p", 1e-15, x, y);
+    }
+
+    assert_eq!(holm_adjust(&[0.5, 0.9]), vec![1.0, 1.0]);
+
+    let adjusted = holm_adjust(&[0.01, f64::NAN, 0.02]);
+    assert_eq!((adjusted[0], adjusted[2]), (0.02, 0.02));
+    assert!(adjusted[1].is_nan());
+
+    assert!(holm_adjust(&[]).is_empty());
+}
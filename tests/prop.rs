@@ -0,0 +1,277 @@
+extern crate dent;
+extern crate quickcheck;
+
+use quickcheck::{quickcheck, TestResult};
+
+use dent::distribution::{Distribution, Gamma};
+use dent::plot::{self, PlotHeight, PlotWidth};
+use dent::rng::Rng;
+use dent::robust;
+use dent::smooth;
+use dent::summary::{percentile_of_unsorted, Percentiles, StreamingSummarizer, Summarizer, Summary};
+use dent::t_test::welch_t_test;
+
+
+// Arbitrary floats include non-finite values and magnitudes whose sums
+// overflow, which `Summarizer` rejects or cannot summarize meaningfully.
+// Instead, generate integers and scale them to get dense, finite samples.
+fn sample(xs: &[i32]) -> Vec<f64> {
+    xs.iter().map(|&x| x as f64 / 64.0).collect()
+}
+
+fn probability(p: u16) -> f64 {
+    p as f64 / u16::MAX as f64
+}
+
+#[test]
+fn summary_is_valid() {
+    fn prop(xs: Vec<i32>) -> TestResult {
+        if xs.is_empty() {
+            return TestResult::discard();
+        }
+
+        let data = sample(&xs);
+        let grid = Percentiles::evenly_spaced(20);
+        let summary = Summary::new_with_percentiles(&data, &grid).unwrap();
+
+        TestResult::from_bool(summary.debug_validate().is_ok())
+    }
+
+    quickcheck(prop as fn(Vec<i32>) -> TestResult);
+}
+
+#[test]
+fn percentile_is_monotone() {
+    fn prop(xs: Vec<i32>, p1: u16, p2: u16) -> TestResult {
+        if xs.is_empty() {
+            return TestResult::discard();
+        }
+
+        let s = Summarizer::new(&sample(&xs)).unwrap();
+        let (lo, hi) = (p1.min(p2), p1.max(p2));
+
+        let x_lo = s.percentile(probability(lo)).unwrap();
+        let x_hi = s.percentile(probability(hi)).unwrap();
+
+        TestResult::from_bool(s.min() <= x_lo && x_lo <= x_hi && x_hi <= s.max())
+    }
+
+    quickcheck(prop as fn(Vec<i32>, u16, u16) -> TestResult);
+}
+
+#[test]
+fn cdf_at_inverts_percentile() {
+    fn prop(xs: Vec<i32>, x: i32) -> TestResult {
+        if xs.is_empty() {
+            return TestResult::discard();
+        }
+
+        let s = Summarizer::new(&sample(&xs)).unwrap();
+        let x = (x as f64 / 64.0).max(s.min()).min(s.max());
+
+        let p = s.cdf_at(x).unwrap();
+        let y = s.percentile(p).unwrap();
+
+        // Interpolation rounds to within some ulps of the largest magnitude.
+        let scale = s.min().abs().max(s.max().abs()).max(1.0);
+
+        TestResult::from_bool((0.0..=1.0).contains(&p) && (x - y).abs() <= 1e-12 * scale)
+    }
+
+    quickcheck(prop as fn(Vec<i32>, i32) -> TestResult);
+}
+
+#[test]
+fn streaming_summary_is_valid() {
+    fn prop(xs: Vec<i32>) -> TestResult {
+        if xs.is_empty() {
+            return TestResult::discard();
+        }
+
+        let data = sample(&xs);
+        let s = Summarizer::new(&data).unwrap();
+        let mut streaming = StreamingSummarizer::new();
+
+        for &x in &data {
+            streaming.push(x);
+        }
+
+        let summary = streaming.summary().unwrap();
+        let scale = s.min().abs().max(s.max().abs()).max(1.0);
+        let close = |a: f64, b: f64| (a - b).abs() <= 1e-9 * scale;
+
+        // The moments are exact, though the quartiles are estimated.
+        TestResult::from_bool(
+            summary.debug_validate().is_ok()
+                && (summary.min(), summary.max()) == (s.min(), s.max())
+                && close(summary.mean(), s.mean())
+                && (data.len() < 2 || close(summary.standard_deviation(), s.standard_deviation())),
+        )
+    }
+
+    quickcheck(prop as fn(Vec<i32>) -> TestResult);
+}
+
+#[test]
+fn value_counts_match_naive() {
+    fn prop(xs: Vec<i32>) -> TestResult {
+        if xs.is_empty() {
+            return TestResult::discard();
+        }
+
+        let counts = Summarizer::new(&sample(&xs)).unwrap().value_counts();
+
+        let mut distinct = xs.clone();
+        distinct.sort();
+        distinct.dedup();
+
+        TestResult::from_bool(
+            counts.zeros == xs.iter().filter(|&&x| x == 0).count()
+                && counts.negatives == xs.iter().filter(|&&x| x < 0).count()
+                && counts.distinct == distinct.len()
+                && counts.duplicates == xs.len() - distinct.len()
+        )
+    }
+
+    quickcheck(prop as fn(Vec<i32>) -> TestResult);
+}
+
+#[test]
+fn t_test_is_antisymmetric() {
+    fn prop(xs: Vec<i32>, ys: Vec<i32>) -> TestResult {
+        let s1 = match Summary::new(&sample(&xs)) {
+            Ok(s) => s,
+            Err(_) => return TestResult::discard(),
+        };
+        let s2 = match Summary::new(&sample(&ys)) {
+            Ok(s) => s,
+            Err(_) => return TestResult::discard(),
+        };
+
+        let (t12, t21) = match (welch_t_test(&s1, &s2), welch_t_test(&s2, &s1)) {
+            (Ok(t12), Ok(t21)) => (t12, t21),
+            _ => return TestResult::discard(),
+        };
+
+        if !t12.t.is_finite() {
+            return TestResult::discard();
+        }
+
+        TestResult::from_bool(t12.t == -t21.t && t12.p == t21.p && t12.df == t21.df)
+    }
+
+    quickcheck(prop as fn(Vec<i32>, Vec<i32>) -> TestResult);
+}
+
+#[test]
+fn plot_width_is_not_exceeded() {
+    fn prop(xs: Vec<i32>, ys: Vec<i32>, width: u8, outliers: bool) -> TestResult {
+        let width = 16 + width as usize;
+
+        let s1 = match Summary::new(&sample(&xs)) {
+            Ok(s) => s,
+            Err(_) => return TestResult::discard(),
+        };
+        let s2 = match Summary::new(&sample(&ys)) {
+            Ok(s) => s,
+            Err(_) => return TestResult::discard(),
+        };
+
+        let plot_width = PlotWidth::new(width).unwrap();
+        let rendered = match plot::comparison_plot(&[&s1, &s2], plot_width, false, true, outliers) {
+            Ok(r) => r,
+            // Degenerate samples may be unplottable, but must not overflow.
+            Err(_) => return TestResult::passed(),
+        };
+
+        TestResult::from_bool(
+            rendered.width() <= width &&
+                rendered.as_str().lines().all(|l| l.chars().count() <= width)
+        )
+    }
+
+    quickcheck(prop as fn(Vec<i32>, Vec<i32>, u8, bool) -> TestResult);
+}
+
+#[test]
+fn scatter_plot_fits_its_bounds() {
+    fn prop(xs: Vec<i32>, ys: Vec<i32>, width: u8, span: u16) -> TestResult {
+        let width = 16 + width as usize;
+        let height = 8;
+
+        let data: Vec<(f64, f64)> = sample(&xs).into_iter().zip(sample(&ys)).collect();
+        let span = probability(span).max(0.01);
+
+        let curve = match smooth::loess(&data, span) {
+            Ok(c) => c,
+            Err(_) => return TestResult::discard(),
+        };
+
+        let plot_width = PlotWidth::new(width).unwrap();
+        let plot_height = PlotHeight::new(height).unwrap();
+        let rendered = match plot::scatter_plot(&data, Some(&curve), plot_width, plot_height, false) {
+            Ok(r) => r,
+            Err(_) => return TestResult::discard(),
+        };
+
+        TestResult::from_bool(rendered.height() <= height + 3 && rendered.width() <= width)
+    }
+
+    quickcheck(prop as fn(Vec<i32>, Vec<i32>, u8, u16) -> TestResult);
+}
+
+#[test]
+fn subsample_indices_are_distinct_and_in_range() {
+    fn prop(seed: u64, len: u16, n: u16) -> bool {
+        let (len, n) = (len as usize, n as usize);
+        let indices = Rng::new(seed).subsample_indices(len, n);
+
+        indices.len() == n.min(len) &&
+            indices.windows(2).all(|w| w[0] < w[1]) &&
+            indices.iter().all(|&i| i < len)
+    }
+
+    quickcheck(prop as fn(u64, u16, u16) -> bool);
+}
+
+#[test]
+fn gamma_samples_are_finite_and_non_negative() {
+    fn prop(seed: u64, shape: u16, rate: u16) -> TestResult {
+        // Shapes from 0.01, to cover the boosted sampling of shapes below 1.
+        let shape = (shape as f64 + 1.0) / 100.0;
+        let rate = (rate as f64 + 1.0) / 100.0;
+
+        let gamma = match Gamma::new(shape, rate) {
+            Ok(g) => g,
+            Err(_) => return TestResult::failed(),
+        };
+
+        let samples = gamma.sample_n(&mut Rng::new(seed), 100);
+
+        TestResult::from_bool(samples.iter().all(|&x| x.is_finite() && x >= 0.0))
+    }
+
+    quickcheck(prop as fn(u64, u16, u16) -> TestResult);
+}
+
+#[test]
+fn hodges_lehmann_shift_matches_naive() {
+    fn prop(xs: Vec<i32>, ys: Vec<i32>) -> TestResult {
+        if xs.is_empty() || ys.is_empty() {
+            return TestResult::discard();
+        }
+
+        let x = Summarizer::new(&sample(&xs)).unwrap();
+        let y = Summarizer::new(&sample(&ys)).unwrap();
+
+        let mut differences: Vec<f64> = y.as_slice()
+            .iter()
+            .flat_map(|&b| x.as_slice().iter().map(move |&a| b - a))
+            .collect();
+        let naive = percentile_of_unsorted(&mut differences, 0.5).unwrap();
+
+        TestResult::from_bool(robust::hodges_lehmann_shift(&x, &y) == naive)
+    }
+
+    quickcheck(prop as fn(Vec<i32>, Vec<i32>) -> TestResult);
+}
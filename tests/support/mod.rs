@@ -2,11 +2,27 @@
 #[macro_use] pub mod kat;
 
 pub mod exe {
+    use std::env;
     use std::fs::File;
     use std::process::{Command, Output};
 
+    /// A config file which does not exist, so that tests see no config.
+    const NO_CONFIG: &str = "tests/support/fixture/no_such_config.toml";
+
+    /// A command to run dent, isolated from the config file and `DENT_*`
+    /// environment of whoever runs the tests.
     fn exe_cmd() -> Command {
-        Command::new("./target/debug/dent")
+        let mut cmd = Command::new("./target/debug/dent");
+
+        for (key, _) in env::vars_os() {
+            if key.to_string_lossy().starts_with("DENT_") {
+                cmd.env_remove(key);
+            }
+        }
+
+        cmd.env("DENT_CONFIG", NO_CONFIG);
+
+        cmd
     }
 
     pub fn run_with_stdin(f: File, args: &[&str]) -> Output {
@@ -25,6 +41,15 @@ pub mod exe {
             .output()
             .expect("Unable to run command in test")
     }
+
+    pub fn run_with_env(env: &[(&str, &str)], args: &[&str]) -> Output {
+        let mut cmd = exe_cmd();
+
+        cmd.args(args)
+            .envs(env.iter().cloned())
+            .output()
+            .expect("Unable to run command in test")
+    }
 }
 
 pub mod fs {